@@ -6,6 +6,8 @@ use serde::{Serialize, Deserialize};
 use zip::ZipArchive;
 use goblin::elf::Elf;
 use goblin::elf::program_header::PT_LOAD;
+use crate::commands::android_manifest::{read_manifest, AndroidManifestInfo};
+use crate::commands::ipa_metadata::{read_ipa, IpaInfo};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -30,13 +32,78 @@ pub struct AppSizeReport {
     pub file_type: String,
     pub supports_16k_page_size: Option<bool>,
     pub large_files: Vec<LargeFile>,
+    /// Combined size of the React Native JS bundles (plain or Hermes bytecode)
+    #[serde(default)]
+    pub js_bundle_size: u64,
+    /// Decoded AndroidManifest.xml of an APK or AAB
+    pub manifest: Option<AndroidManifestInfo>,
+    /// Why the manifest of an APK or AAB could not be decoded
+    pub manifest_error: Option<String>,
+    /// Info.plist, signing profile and Mach-O binaries of an IPA
+    pub ipa: Option<IpaInfo>,
+    /// Why the contents of an IPA could not be read
+    pub ipa_error: Option<String>,
+}
+
+pub const CODE: &str = "Code (DEX/JS)";
+pub const RESOURCES: &str = "Resources";
+pub const ASSETS: &str = "Assets";
+pub const NATIVE_LIBRARIES: &str = "Native Libraries";
+pub const OTHERS: &str = "Others";
+
+/// The breakdown's categories, in the order the report lists them
+pub const CATEGORIES: [&str; 5] = [CODE, RESOURCES, ASSETS, NATIVE_LIBRARIES, OTHERS];
+
+/// The breakdown category of the archive entry `name` in an APK, AAB or IPA
+pub fn size_category(extension: &str, name: &str) -> &'static str {
+    if extension == "apk" || extension == "aab" {
+        if name.ends_with(".dex") {
+            CODE
+        } else if name.starts_with("res/") || name == "resources.arsc" {
+            RESOURCES
+        } else if name.starts_with("assets/") {
+            ASSETS
+        } else if name.starts_with("lib/") || name.contains("/lib/") { // Check for /lib/ for AABs often in base/lib/
+            NATIVE_LIBRARIES
+        } else {
+            OTHERS
+        }
+    } else {
+        // Logic for iOS (IPA contains a Payload folder with .app bundle)
+        if name.contains(".app/Frameworks/") || name.ends_with(".dylib") {
+            NATIVE_LIBRARIES
+        } else if name.contains(".app/Assets.car") || name.ends_with(".nib") || name.ends_with(".plist") {
+            RESOURCES
+        } else if name.contains(".app/") && (name.ends_with(".jsbundle") || name.contains("bundle")) {
+            CODE
+        } else if name.contains(".app/") {
+            // Check if it's the main executable
+            let parts: Vec<&str> = name.split('/').collect();
+            if parts.len() > 2 && parts[1].ends_with(".app") && parts[2] == parts[1].trim_end_matches(".app") {
+                CODE
+            } else {
+                OTHERS
+            }
+        } else {
+            OTHERS
+        }
+    }
+}
+
+/// Whether the archive entry `name` is a React Native JS bundle
+pub fn is_js_bundle(name: &str) -> bool {
+    name.ends_with(".jsbundle") || name.ends_with(".bundle") || name.ends_with(".hbc")
 }
 
 #[command]
 pub async fn analyze_app_size(artifact_path: String) -> Result<AppSizeReport, String> {
-    let path = Path::new(&artifact_path);
+    analyze_artifact(Path::new(&artifact_path))
+}
+
+/// Break an APK, AAB or IPA down by category and decode its manifest or app bundle
+pub fn analyze_artifact(path: &Path) -> Result<AppSizeReport, String> {
     if !path.exists() {
-        return Err(format!("File not found: {}", artifact_path));
+        return Err(format!("File not found: {}", path.display()));
     }
 
     let file = File::open(path).map_err(|e| e.to_string())?;
@@ -47,17 +114,17 @@ pub async fn analyze_app_size(artifact_path: String) -> Result<AppSizeReport, St
     let mut breakdown: Vec<SizeBreakdown> = Vec::new();
     let large_files: Vec<LargeFile>;
     let mut supports_16k_page_size = if extension == "apk" || extension == "aab" { Some(true) } else { None };
+    let mut manifest = None;
+    let mut manifest_error = None;
+    let mut ipa = None;
+    let mut ipa_error = None;
+    let mut js_bundle_size = 0;
 
     match extension {
         "apk" | "aab" | "ipa" => {
             let mut archive = ZipArchive::new(file).map_err(|e| e.to_string())?;
 
-            let mut code_size = 0;
-            let mut resource_size = 0;
-            let mut asset_size = 0;
-            let mut native_lib_size = 0;
-            let mut other_size = 0;
-
+            let mut category_sizes = [0u64; CATEGORIES.len()];
             let mut all_files: Vec<(String, u64)> = Vec::new();
 
             for i in 0..archive.len() {
@@ -66,99 +133,60 @@ pub async fn analyze_app_size(artifact_path: String) -> Result<AppSizeReport, St
                 let name = file.name().to_string(); // Clone name to avoid borrow issues
 
                 all_files.push((name.clone(), size));
+                if is_js_bundle(&name) {
+                    js_bundle_size += size;
+                }
+
+                let category = size_category(extension, &name);
+                if let Some(index) = CATEGORIES.iter().position(|c| *c == category) {
+                    category_sizes[index] += size;
+                }
 
-                if extension == "apk" || extension == "aab" {
-                    if name.ends_with(".dex") {
-                        code_size += size;
-                    } else if name.starts_with("res/") || name == "resources.arsc" {
-                        resource_size += size;
-                    } else if name.starts_with("assets/") {
-                        asset_size += size;
-                    } else if name.starts_with("lib/") || name.contains("/lib/") { // Check for /lib/ for AABs often in base/lib/
-                        native_lib_size += size;
-
-                        // Check 16KB support if we still think it supports it
-                        if supports_16k_page_size == Some(true) && name.ends_with(".so") {
-                            // Only check ARM64 libraries as 16KB page size is primarily strict on ARM64
-                            // Typically lib/arm64-v8a/
-                            if name.contains("arm64-v8a") || name.contains("x86_64") {
-                                let mut buffer = Vec::new();
-                                if file.read_to_end(&mut buffer).is_ok() {
-                                    if let Ok(elf) = Elf::parse(&buffer) {
-                                        let is_16k_compatible = elf.program_headers.iter()
-                                            .filter(|ph| ph.p_type == PT_LOAD)
-                                            .all(|ph| ph.p_align >= 0x4000);
-
-                                        if !is_16k_compatible {
-                                            supports_16k_page_size = Some(false);
-                                        }
-                                    }
+                // Check 16KB support if we still think it supports it
+                if category == NATIVE_LIBRARIES && supports_16k_page_size == Some(true) && name.ends_with(".so") {
+                    // Only check ARM64 libraries as 16KB page size is primarily strict on ARM64
+                    // Typically lib/arm64-v8a/
+                    if name.contains("arm64-v8a") || name.contains("x86_64") {
+                        let mut buffer = Vec::new();
+                        if file.read_to_end(&mut buffer).is_ok() {
+                            if let Ok(elf) = Elf::parse(&buffer) {
+                                let is_16k_compatible = elf.program_headers.iter()
+                                    .filter(|ph| ph.p_type == PT_LOAD)
+                                    .all(|ph| ph.p_align >= 0x4000);
+
+                                if !is_16k_compatible {
+                                    supports_16k_page_size = Some(false);
                                 }
                             }
                         }
-                    } else {
-                        other_size += size;
-                    }
-                } else if extension == "ipa" {
-                    // Logic for iOS (IPA contains a Payload folder with .app bundle)
-                    if name.contains(".app/Frameworks/") || name.ends_with(".dylib") {
-                        native_lib_size += size;
-                    } else if name.contains(".app/Assets.car") || name.ends_with(".nib") || name.ends_with(".plist") {
-                        resource_size += size;
-                    } else if name.contains(".app/") && (name.ends_with(".jsbundle") || name.contains("bundle")) {
-                        code_size += size;
-                    } else if name.contains(".app/") {
-                        // Check if it's the main executable
-                        let parts: Vec<&str> = name.split('/').collect();
-                        if parts.len() > 2 && parts[1].ends_with(".app") && parts[2] == parts[1].trim_end_matches(".app") {
-                             code_size += size;
-                        } else {
-                             other_size += size;
-                        }
-                    } else {
-                        other_size += size;
                     }
                 }
             }
 
+            if extension == "apk" || extension == "aab" {
+                match read_manifest(&mut archive, extension) {
+                    Ok(info) => manifest = Some(info),
+                    Err(e) => manifest_error = Some(e),
+                }
+            } else {
+                match read_ipa(&mut archive, chrono::Utc::now()) {
+                    Ok(info) => ipa = Some(info),
+                    Err(e) => ipa_error = Some(e),
+                }
+            }
+
             // Process largest files
             all_files.sort_by(|a, b| b.1.cmp(&a.1));
             large_files = all_files.into_iter().take(20).map(|(path, size)| LargeFile { path, size }).collect();
 
-            if code_size > 0 {
-                breakdown.push(SizeBreakdown {
-                    name: "Code (DEX/JS)".to_string(),
-                    size: code_size,
-                    percentage: (code_size as f64 / total_size as f64) * 100.0,
-                });
-            }
-            if resource_size > 0 {
-                breakdown.push(SizeBreakdown {
-                    name: "Resources".to_string(),
-                    size: resource_size,
-                    percentage: (resource_size as f64 / total_size as f64) * 100.0,
-                });
-            }
-            if asset_size > 0 {
-                breakdown.push(SizeBreakdown {
-                    name: "Assets".to_string(),
-                    size: asset_size,
-                    percentage: (asset_size as f64 / total_size as f64) * 100.0,
-                });
-            }
-            if native_lib_size > 0 {
-                breakdown.push(SizeBreakdown {
-                    name: "Native Libraries".to_string(),
-                    size: native_lib_size,
-                    percentage: (native_lib_size as f64 / total_size as f64) * 100.0,
-                });
-            }
-            if other_size > 0 {
-                breakdown.push(SizeBreakdown {
-                    name: "Others".to_string(),
-                    size: other_size,
-                    percentage: (other_size as f64 / total_size as f64) * 100.0,
-                });
+            for (name, size) in CATEGORIES.iter().zip(category_sizes) {
+                if size > 0 {
+                    breakdown.push(SizeBreakdown {
+                        name: name.to_string(),
+                        size,
+                        percentage: (size as f64 / total_size as f64) * 100.0,
+                    });
+                }
             }
         },
         _ => return Err(format!("Unsupported file extension: {}", extension)),
@@ -170,5 +198,10 @@ pub async fn analyze_app_size(artifact_path: String) -> Result<AppSizeReport, St
         file_type: extension.to_uppercase(),
         supports_16k_page_size,
         large_files,
+        js_bundle_size,
+        manifest,
+        manifest_error,
+        ipa,
+        ipa_error,
     })
 }
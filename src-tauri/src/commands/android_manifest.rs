@@ -0,0 +1,429 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// Resource IDs of the `android:` attributes the report reads, for APKs whose attribute
+/// names were stripped by an obfuscator
+const ANDROID_ATTRIBUTES: [(u32, &str); 10] = [
+    (0x0101_0003, "name"),
+    (0x0101_000f, "debuggable"),
+    (0x0101_0010, "exported"),
+    (0x0101_020c, "minSdkVersion"),
+    (0x0101_021b, "versionCode"),
+    (0x0101_021c, "versionName"),
+    (0x0101_0270, "targetSdkVersion"),
+    (0x0101_0280, "allowBackup"),
+    (0x0101_0572, "compileSdkVersion"),
+    (0x0101_0573, "compileSdkVersionCodename"),
+];
+
+/// Components another app can start, bind to or query
+const COMPONENTS: [&str; 5] = ["activity", "activity-alias", "service", "receiver", "provider"];
+
+/// A decoded manifest element. Attributes are keyed by local name (`versionCode`, `package`)
+/// and their values are rendered as text.
+#[derive(Debug, Default)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: BTreeMap<String, String>,
+    pub children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedComponent {
+    /// activity, activity-alias, service, receiver or provider
+    pub kind: String,
+    pub name: String,
+    /// Exported because it has an intent filter and no `android:exported`, which Android 12
+    /// refuses to install
+    pub implicit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AndroidManifestInfo {
+    pub package: String,
+    pub version_code: Option<u64>,
+    pub version_name: Option<String>,
+    pub min_sdk: Option<u32>,
+    pub target_sdk: Option<u32>,
+    pub compile_sdk: Option<u32>,
+    pub permissions: Vec<String>,
+    pub exported_components: Vec<ExportedComponent>,
+    pub debuggable: bool,
+    /// Android's default when the attribute is missing is true
+    pub allow_backup: bool,
+}
+
+/// Decode the manifest of an APK (binary XML) or AAB (aapt2 protobuf, in `base/manifest/`)
+pub fn read_manifest<R: Read + Seek>(archive: &mut ZipArchive<R>, extension: &str) -> Result<AndroidManifestInfo, String> {
+    let entry_name = if extension == "aab" { "base/manifest/AndroidManifest.xml" } else { "AndroidManifest.xml" };
+    let mut entry = archive.by_name(entry_name).map_err(|_| format!("{} not found", entry_name))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read {}: {}", entry_name, e))?;
+
+    let root = if extension == "aab" { parse_proto_xml(&bytes)? } else { parse_binary_xml(&bytes)? };
+    manifest_info(&root)
+}
+
+/// Pick the report's fields out of a decoded `<manifest>`
+pub fn manifest_info(root: &XmlElement) -> Result<AndroidManifestInfo, String> {
+    if root.name != "manifest" {
+        return Err(format!("Expected a <manifest> root element, found <{}>", root.name));
+    }
+    let uses_sdk = root.children_named("uses-sdk").next();
+    let sdk = |name: &str| uses_sdk.and_then(|e| e.attribute(name)).and_then(|v| v.parse().ok());
+    let application = root.children_named("application").next();
+    let flag = |name: &str| application.and_then(|e| e.attribute(name)).map(|v| v == "true");
+
+    let permissions = root
+        .children
+        .iter()
+        .filter(|c| c.name == "uses-permission" || c.name == "uses-permission-sdk-23")
+        .filter_map(|c| c.attribute("name").map(str::to_string))
+        .collect();
+
+    let mut exported_components = Vec::new();
+    for component in application.into_iter().flat_map(|a| a.children.iter()) {
+        if !COMPONENTS.contains(&component.name.as_str()) {
+            continue;
+        }
+        let (exported, implicit) = match component.attribute("exported") {
+            Some(value) => (value == "true", false),
+            None => {
+                let has_filter = component.children_named("intent-filter").next().is_some();
+                (has_filter, has_filter)
+            }
+        };
+        if exported {
+            exported_components.push(ExportedComponent {
+                kind: component.name.clone(),
+                name: component.attribute("name").unwrap_or_default().to_string(),
+                implicit,
+            });
+        }
+    }
+
+    Ok(AndroidManifestInfo {
+        package: root.attribute("package").unwrap_or_default().to_string(),
+        version_code: root.attribute("versionCode").and_then(|v| v.parse().ok()),
+        version_name: root.attribute("versionName").map(str::to_string),
+        min_sdk: sdk("minSdkVersion"),
+        target_sdk: sdk("targetSdkVersion"),
+        compile_sdk: root
+            .attribute("compileSdkVersion")
+            .or_else(|| root.attribute("platformBuildVersionCode"))
+            .and_then(|v| v.parse().ok()),
+        permissions,
+        exported_components,
+        debuggable: flag("debuggable").unwrap_or(false),
+        allow_backup: flag("allowBackup").unwrap_or(true),
+    })
+}
+
+// Binary XML (AXML), as compiled into APKs. See ResourceTypes.h in the Android framework.
+
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_XML_TYPE: u16 = 0x0003;
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+const RES_XML_END_ELEMENT_TYPE: u16 = 0x0103;
+const RES_XML_RESOURCE_MAP_TYPE: u16 = 0x0180;
+const UTF8_FLAG: u32 = 1 << 8;
+const NO_INDEX: u32 = 0xffff_ffff;
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, String> {
+    bytes
+        .get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| format!("Binary XML truncated at offset {}", offset))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| format!("Binary XML truncated at offset {}", offset))
+}
+
+/// A string length in a UTF-8 pool: one byte, or two when the high bit is set
+fn utf8_length(bytes: &[u8], at: &mut usize) -> Result<usize, String> {
+    let first = *bytes.get(*at).ok_or("Binary XML string pool truncated")? as usize;
+    *at += 1;
+    if first & 0x80 == 0 {
+        return Ok(first);
+    }
+    let second = *bytes.get(*at).ok_or("Binary XML string pool truncated")? as usize;
+    *at += 1;
+    Ok(((first & 0x7f) << 8) | second)
+}
+
+/// Decode a ResStringPool chunk starting at `chunk`
+fn parse_string_pool(bytes: &[u8], chunk: usize) -> Result<Vec<String>, String> {
+    let count = u32_at(bytes, chunk + 8)? as usize;
+    let flags = u32_at(bytes, chunk + 16)?;
+    let strings_start = chunk + u32_at(bytes, chunk + 20)? as usize;
+    let offsets_start = chunk + u16_at(bytes, chunk + 2)? as usize;
+
+    let mut strings = Vec::with_capacity(count);
+    for i in 0..count {
+        let mut at = strings_start + u32_at(bytes, offsets_start + i * 4)? as usize;
+        let string = if flags & UTF8_FLAG != 0 {
+            // The length in UTF-16 units, then in bytes
+            utf8_length(bytes, &mut at)?;
+            let length = utf8_length(bytes, &mut at)?;
+            let raw = bytes.get(at..at + length).ok_or("Binary XML string pool truncated")?;
+            String::from_utf8_lossy(raw).into_owned()
+        } else {
+            let mut length = u16_at(bytes, at)? as usize;
+            at += 2;
+            if length & 0x8000 != 0 {
+                length = ((length & 0x7fff) << 16) | u16_at(bytes, at)? as usize;
+                at += 2;
+            }
+            let units = (0..length).map(|j| u16_at(bytes, at + j * 2)).collect::<Result<Vec<_>, _>>()?;
+            String::from_utf16_lossy(&units)
+        };
+        strings.push(string);
+    }
+    Ok(strings)
+}
+
+/// Render a Res_value the way aapt2 dump does
+fn format_value(strings: &[String], data_type: u8, data: u32) -> String {
+    match data_type {
+        0x03 => strings.get(data as usize).cloned().unwrap_or_default(),
+        0x01 => format!("@0x{:08x}", data),
+        0x02 => format!("?0x{:08x}", data),
+        0x10 => (data as i32).to_string(),
+        0x11 => format!("0x{:x}", data),
+        0x12 => (data != 0).to_string(),
+        0x04 => f32::from_bits(data).to_string(),
+        _ => format!("0x{:08x}", data),
+    }
+}
+
+/// Decode the binary XML of an APK's AndroidManifest.xml into its root element
+pub fn parse_binary_xml(bytes: &[u8]) -> Result<XmlElement, String> {
+    if u16_at(bytes, 0)? != RES_XML_TYPE {
+        return Err("Not a binary XML file".to_string());
+    }
+    let end = (u32_at(bytes, 4)? as usize).min(bytes.len());
+    let mut offset = u16_at(bytes, 2)? as usize;
+
+    let mut strings = Vec::new();
+    let mut resource_ids = Vec::new();
+    let mut stack: Vec<XmlElement> = Vec::new();
+    let mut root = None;
+
+    while offset + 8 <= end {
+        let chunk_type = u16_at(bytes, offset)?;
+        let chunk_size = u32_at(bytes, offset + 4)? as usize;
+        if chunk_size < 8 {
+            return Err(format!("Invalid binary XML chunk at offset {}", offset));
+        }
+
+        match chunk_type {
+            RES_STRING_POOL_TYPE => strings = parse_string_pool(bytes, offset)?,
+            RES_XML_RESOURCE_MAP_TYPE => {
+                let header_size = u16_at(bytes, offset + 2)? as usize;
+                resource_ids = (offset + header_size..offset + chunk_size)
+                    .step_by(4)
+                    .map(|at| u32_at(bytes, at))
+                    .collect::<Result<_, _>>()?;
+            }
+            RES_XML_START_ELEMENT_TYPE => {
+                // ResXMLTree_node (16 bytes), then ResXMLTree_attrExt
+                let ext = offset + 16;
+                let name = strings.get(u32_at(bytes, ext + 4)? as usize).cloned().unwrap_or_default();
+                let attribute_start = u16_at(bytes, ext + 8)? as usize;
+                let attribute_size = u16_at(bytes, ext + 10)? as usize;
+                let attribute_count = u16_at(bytes, ext + 12)? as usize;
+
+                let mut element = XmlElement { name, ..Default::default() };
+                for i in 0..attribute_count {
+                    let at = ext + attribute_start + i * attribute_size;
+                    let name_index = u32_at(bytes, at + 4)?;
+                    let raw_value = u32_at(bytes, at + 8)?;
+                    let data_type = *bytes.get(at + 15).ok_or("Binary XML attribute truncated")?;
+                    let data = u32_at(bytes, at + 16)?;
+
+                    let mut attribute_name = strings.get(name_index as usize).cloned().unwrap_or_default();
+                    if let Some(&(_, known)) = resource_ids
+                        .get(name_index as usize)
+                        .and_then(|id| ANDROID_ATTRIBUTES.iter().find(|(known_id, _)| known_id == id))
+                    {
+                        attribute_name = known.to_string();
+                    }
+                    let value = if raw_value != NO_INDEX {
+                        strings.get(raw_value as usize).cloned().unwrap_or_default()
+                    } else {
+                        format_value(&strings, data_type, data)
+                    };
+                    element.attributes.insert(attribute_name, value);
+                }
+                stack.push(element);
+            }
+            RES_XML_END_ELEMENT_TYPE => {
+                let element = stack.pop().ok_or("Unbalanced binary XML end element")?;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            }
+            _ => {}
+        }
+        offset += chunk_size;
+    }
+
+    root.ok_or_else(|| "Binary XML has no root element".to_string())
+}
+
+// Protobuf XML, as stored in App Bundles. See Resources.proto in aapt2: XmlNode, XmlElement,
+// XmlAttribute, Item and Primitive.
+
+enum ProtoValue<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+    /// fixed32/fixed64, which none of the fields read here use
+    Fixed,
+}
+
+/// The (field number, value) pairs of one protobuf message
+fn proto_fields(mut bytes: &[u8]) -> Result<Vec<(u64, ProtoValue<'_>)>, String> {
+    fn varint(bytes: &mut &[u8]) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = bytes.split_first().ok_or("Protobuf varint truncated")?;
+            *bytes = rest;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Protobuf varint too long".to_string())
+    }
+    fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], String> {
+        if bytes.len() < n {
+            return Err("Protobuf field truncated".to_string());
+        }
+        let (taken, rest) = bytes.split_at(n);
+        *bytes = rest;
+        Ok(taken)
+    }
+
+    let mut fields = Vec::new();
+    while !bytes.is_empty() {
+        let key = varint(&mut bytes)?;
+        let value = match key & 7 {
+            0 => ProtoValue::Varint(varint(&mut bytes)?),
+            1 => take(&mut bytes, 8).map(|_| ProtoValue::Fixed)?,
+            2 => {
+                let length = varint(&mut bytes)? as usize;
+                ProtoValue::Bytes(take(&mut bytes, length)?)
+            }
+            5 => take(&mut bytes, 4).map(|_| ProtoValue::Fixed)?,
+            wire_type => return Err(format!("Unsupported protobuf wire type {}", wire_type)),
+        };
+        fields.push((key >> 3, value));
+    }
+    Ok(fields)
+}
+
+fn proto_string(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Render a compiled `Item`, for attributes without a textual value
+fn proto_item(bytes: &[u8]) -> Result<Option<String>, String> {
+    for (field, value) in proto_fields(bytes)? {
+        match (field, value) {
+            // Reference { id = 1 }
+            (1, ProtoValue::Bytes(reference)) => {
+                for (field, value) in proto_fields(reference)? {
+                    if let (1, ProtoValue::Varint(id)) = (field, value) {
+                        return Ok(Some(format!("@0x{:08x}", id)));
+                    }
+                }
+            }
+            // String { value = 1 } and RawString { value = 1 }
+            (2 | 3, ProtoValue::Bytes(string)) => {
+                for (field, value) in proto_fields(string)? {
+                    if let (1, ProtoValue::Bytes(text)) = (field, value) {
+                        return Ok(Some(proto_string(text)));
+                    }
+                }
+            }
+            // Primitive: int_decimal = 6, int_hexadecimal = 7, boolean = 8
+            (7, ProtoValue::Bytes(primitive)) => {
+                for (field, value) in proto_fields(primitive)? {
+                    match (field, value) {
+                        (6, ProtoValue::Varint(n)) => return Ok(Some((n as i32).to_string())),
+                        (7, ProtoValue::Varint(n)) => return Ok(Some(format!("0x{:x}", n as u32))),
+                        (8, ProtoValue::Varint(b)) => return Ok(Some((b != 0).to_string())),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+fn proto_element(bytes: &[u8]) -> Result<XmlElement, String> {
+    let mut element = XmlElement::default();
+    for (field, value) in proto_fields(bytes)? {
+        match (field, value) {
+            (3, ProtoValue::Bytes(name)) => element.name = proto_string(name),
+            (4, ProtoValue::Bytes(attribute)) => {
+                let (mut name, mut text, mut resource_id, mut compiled) = (String::new(), String::new(), 0, None);
+                for (field, value) in proto_fields(attribute)? {
+                    match (field, value) {
+                        (2, ProtoValue::Bytes(b)) => name = proto_string(b),
+                        (3, ProtoValue::Bytes(b)) => text = proto_string(b),
+                        (5, ProtoValue::Varint(id)) => resource_id = id as u32,
+                        (6, ProtoValue::Bytes(item)) => compiled = proto_item(item)?,
+                        _ => {}
+                    }
+                }
+                if let Some((_, known)) = ANDROID_ATTRIBUTES.iter().find(|(id, _)| *id == resource_id) {
+                    name = known.to_string();
+                }
+                let value = if text.is_empty() { compiled.unwrap_or_default() } else { text };
+                element.attributes.insert(name, value);
+            }
+            // XmlNode { element = 1, text = 2 }
+            (5, ProtoValue::Bytes(node)) => {
+                for (field, value) in proto_fields(node)? {
+                    if let (1, ProtoValue::Bytes(child)) = (field, value) {
+                        element.children.push(proto_element(child)?);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(element)
+}
+
+/// Decode the protobuf XmlNode of an App Bundle's manifest into its root element
+pub fn parse_proto_xml(bytes: &[u8]) -> Result<XmlElement, String> {
+    for (field, value) in proto_fields(bytes)? {
+        if let (1, ProtoValue::Bytes(element)) = (field, value) {
+            return proto_element(element);
+        }
+    }
+    Err("Protobuf manifest has no root element".to_string())
+}
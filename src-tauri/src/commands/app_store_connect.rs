@@ -0,0 +1,433 @@
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::SeekFrom;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Production endpoint of the App Store Connect API
+pub const APP_STORE_CONNECT_URL: &str = "https://api.appstoreconnect.apple.com";
+
+/// Apple rejects tokens that live longer than 20 minutes
+const TOKEN_LIFETIME_SECS: i64 = 20 * 60;
+
+/// TestFlight limits "What to Test" to 4000 characters
+const WHAT_TO_TEST_LIMIT: usize = 4000;
+
+/// An App Store Connect API key: the `.p8` private key and the ids it was issued with
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub key_id: String,
+    pub issuer_id: String,
+    /// PEM contents of the `.p8` file
+    pub private_key: String,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+impl ApiKey {
+    /// The `.p8` key saved by `create_credential` for an iOS credential
+    pub fn load(credential_id: &str, key_id: &str, issuer_id: &str) -> Result<Self, String> {
+        let entry = Entry::new("app-builder-credentials", &format!("ios-api-key-{}", credential_id))
+            .map_err(|e| e.to_string())?;
+        let private_key = entry
+            .get_password()
+            .map_err(|e| format!("API key (.p8) not found in Keychain: {}", e))?;
+        Ok(ApiKey { key_id: key_id.to_string(), issuer_id: issuer_id.to_string(), private_key })
+    }
+
+    /// A signed ES256 token for the `Authorization` header
+    pub fn token(&self) -> Result<String, String> {
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims { iss: &self.issuer_id, aud: "appstoreconnect-v1", iat: now, exp: now + TOKEN_LIFETIME_SECS };
+
+        let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::ES256);
+        header.kid = Some(self.key_id.clone());
+        let encoding_key = jsonwebtoken::EncodingKey::from_ec_pem(self.private_key.as_bytes())
+            .map_err(|e| format!("Invalid App Store Connect API key: {}", e))?;
+        jsonwebtoken::encode(&header, &claims, &encoding_key).map_err(|e| e.to_string())
+    }
+}
+
+/// A build as App Store Connect sees it once the upload has been received
+#[derive(Debug, Clone, PartialEq)]
+pub struct Build {
+    pub id: String,
+    /// `PROCESSING`, `FAILED`, `INVALID` or `VALID`
+    pub processing_state: String,
+}
+
+/// An IPA sent with `upload_build`, before App Store Connect has processed it
+#[derive(Debug, Clone)]
+pub struct BuildUpload {
+    pub id: String,
+    pub app_id: String,
+    pub version: String,
+    pub build_number: String,
+}
+
+/// One part of the IPA to send, as laid out by App Store Connect
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadOperation {
+    method: String,
+    url: String,
+    offset: u64,
+    length: u64,
+    #[serde(default)]
+    request_headers: Vec<HttpHeader>,
+}
+
+#[derive(Deserialize)]
+struct HttpHeader {
+    name: String,
+    value: String,
+}
+
+/// A client of the App Store Connect API, authenticated with an API key
+pub struct AppStoreConnectClient {
+    http: reqwest::Client,
+    base_url: String,
+    key: ApiKey,
+}
+
+/// Turn a non-success response into an error carrying Apple's message
+async fn check(response: reqwest::Response, action: &str) -> Result<reqwest::Response, String> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    // {"errors": [{"status": "409", "code": "ENTITY_ERROR", "title": "...", "detail": "..."}]}
+    let message = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|v| {
+            let messages: Vec<String> = v
+                .get("errors")?
+                .as_array()?
+                .iter()
+                .filter_map(|e| e.get("detail").or_else(|| e.get("title")).and_then(|m| m.as_str()))
+                .map(|m| m.to_string())
+                .collect();
+            Some(messages.join("; ")).filter(|m| !m.is_empty())
+        })
+        .unwrap_or(body);
+    Err(format!("{} failed ({}): {}", action, status, message))
+}
+
+/// The `id` of a `{"data": {...}}` document
+fn data_id(document: &Value) -> Result<String, String> {
+    document["data"]["id"]
+        .as_str()
+        .map(|id| id.to_string())
+        .ok_or_else(|| "App Store Connect returned no id".to_string())
+}
+
+impl AppStoreConnectClient {
+    pub fn new(key: ApiKey, base_url: &str) -> Self {
+        AppStoreConnectClient { http: reqwest::Client::new(), base_url: base_url.trim_end_matches('/').to_string(), key }
+    }
+
+    /// Send a request with a fresh token and parse the JSON document it returns
+    /// (`Value::Null` for the empty 204 responses)
+    async fn send(&self, request: reqwest::RequestBuilder, action: &str) -> Result<Value, String> {
+        let response = request
+            .bearer_auth(self.key.token()?)
+            .send()
+            .await
+            .map_err(|e| format!("{} failed: {}", action, e))?;
+        let body = check(response, action).await?.text().await.map_err(|e| e.to_string())?;
+        if body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&body).map_err(|e| format!("{}: unexpected response: {}", action, e))
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)], action: &str) -> Result<Value, String> {
+        let request = self.http.get(format!("{}{}", self.base_url, path)).query(query);
+        self.send(request, action).await
+    }
+
+    async fn post(&self, path: &str, body: Value, action: &str) -> Result<Value, String> {
+        let request = self.http.post(format!("{}{}", self.base_url, path)).json(&body);
+        self.send(request, action).await
+    }
+
+    async fn patch(&self, path: &str, body: Value, action: &str) -> Result<Value, String> {
+        let request = self.http.patch(format!("{}{}", self.base_url, path)).json(&body);
+        self.send(request, action).await
+    }
+
+    /// The App Store Connect id of the app with `bundle_id`
+    pub async fn find_app(&self, bundle_id: &str) -> Result<String, String> {
+        let apps = self
+            .get("/v1/apps", &[("filter[bundleId]", bundle_id), ("fields[apps]", "bundleId")], "Looking up the app")
+            .await?;
+        apps["data"]
+            .as_array()
+            .and_then(|apps| apps.iter().find(|a| a["attributes"]["bundleId"] == bundle_id))
+            .and_then(|a| a["id"].as_str())
+            .map(|id| id.to_string())
+            .ok_or_else(|| format!("No app with bundle id {} in App Store Connect", bundle_id))
+    }
+
+    /// Upload an IPA with the Build Upload API, calling `on_progress(sent, total)` as parts go out.
+    /// The returned upload is followed with `wait_for_processing`.
+    pub async fn upload_build(
+        &self,
+        app_id: &str,
+        ipa_path: &Path,
+        version: &str,
+        build_number: &str,
+        mut on_progress: impl FnMut(u64, u64),
+    ) -> Result<BuildUpload, String> {
+        // Read part by part as the operations ask for them, the IPA can be several hundred MB
+        let read_error = |e: std::io::Error| format!("Failed to read {}: {}", ipa_path.display(), e);
+        let mut ipa = tokio::fs::File::open(ipa_path).await.map_err(read_error)?;
+        let total = ipa.metadata().await.map_err(read_error)?.len();
+        let file_name = ipa_path.file_name().and_then(|n| n.to_str()).unwrap_or("App.ipa");
+
+        let upload = self
+            .post(
+                "/v1/buildUploads",
+                json!({
+                    "data": {
+                        "type": "buildUploads",
+                        "attributes": {
+                            "cfBundleShortVersionString": version,
+                            "cfBundleVersion": build_number,
+                            "platform": "IOS"
+                        },
+                        "relationships": { "app": { "data": { "type": "apps", "id": app_id } } }
+                    }
+                }),
+                "Creating the build upload",
+            )
+            .await?;
+        let upload_id = data_id(&upload)?;
+
+        let file = self
+            .post(
+                "/v1/buildUploadFiles",
+                json!({
+                    "data": {
+                        "type": "buildUploadFiles",
+                        "attributes": {
+                            "assetType": "ASSET",
+                            "fileName": file_name,
+                            "fileSize": total,
+                            "uti": "com.apple.ipa"
+                        },
+                        "relationships": { "buildUpload": { "data": { "type": "buildUploads", "id": upload_id } } }
+                    }
+                }),
+                "Reserving the upload",
+            )
+            .await?;
+        let file_id = data_id(&file)?;
+        let operations: Vec<UploadOperation> =
+            serde_json::from_value(file["data"]["attributes"]["uploadOperations"].clone())
+                .map_err(|e| format!("Reserving the upload: unexpected upload operations: {}", e))?;
+
+        let mut sent = 0;
+        on_progress(sent, total);
+        for operation in operations {
+            let (start, end) = (operation.offset, operation.offset + operation.length);
+            if end > total {
+                return Err(format!("Upload part {}..{} is outside the {} byte IPA", start, end, total));
+            }
+            let mut part = vec![0; operation.length as usize];
+            ipa.seek(SeekFrom::Start(start)).await.map_err(read_error)?;
+            ipa.read_exact(&mut part).await.map_err(read_error)?;
+            let method = reqwest::Method::from_bytes(operation.method.as_bytes()).map_err(|e| e.to_string())?;
+            // The operation URLs are pre-signed, they take no token
+            let mut request = self.http.request(method, &operation.url).body(part);
+            for header in &operation.request_headers {
+                request = request.header(&header.name, &header.value);
+            }
+            let response = request.send().await.map_err(|e| format!("Uploading the IPA failed: {}", e))?;
+            check(response, "Uploading the IPA").await?;
+
+            sent += operation.length;
+            on_progress(sent, total);
+        }
+
+        self.patch(
+            &format!("/v1/buildUploadFiles/{}", file_id),
+            json!({ "data": { "type": "buildUploadFiles", "id": file_id, "attributes": { "uploaded": true } } }),
+            "Completing the upload",
+        )
+        .await?;
+        Ok(BuildUpload {
+            id: upload_id,
+            app_id: app_id.to_string(),
+            version: version.to_string(),
+            build_number: build_number.to_string(),
+        })
+    }
+
+    /// The state of a build upload and the errors Apple reported for it
+    async fn upload_state(&self, upload_id: &str) -> Result<(String, Vec<String>), String> {
+        let upload = self
+            .get(&format!("/v1/buildUploads/{}", upload_id), &[], "Checking the build upload")
+            .await?;
+        // {"state": "FAILED", "errors": [{"code": "...", "description": "..."}]}
+        let state = &upload["data"]["attributes"]["state"];
+        let name = state["state"].as_str().or(state.as_str()).unwrap_or("UNKNOWN").to_string();
+        let errors = state["errors"]
+            .as_array()
+            .map(|errors| {
+                errors
+                    .iter()
+                    .filter_map(|e| e["description"].as_str().or(e["code"].as_str()))
+                    .map(|e| e.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok((name, errors))
+    }
+
+    /// The build with `version` and `build_number`, once App Store Connect has created it
+    pub async fn find_build(&self, app_id: &str, version: &str, build_number: &str) -> Result<Option<Build>, String> {
+        let builds = self
+            .get(
+                "/v1/builds",
+                &[
+                    ("filter[app]", app_id),
+                    ("filter[version]", build_number),
+                    ("filter[preReleaseVersion.version]", version),
+                    ("fields[builds]", "processingState"),
+                ],
+                "Looking up the build",
+            )
+            .await?;
+        Ok(builds["data"].as_array().and_then(|b| b.first()).and_then(|build| {
+            Some(Build {
+                id: build["id"].as_str()?.to_string(),
+                processing_state: build["attributes"]["processingState"].as_str().unwrap_or("PROCESSING").to_string(),
+            })
+        }))
+    }
+
+    /// Poll until the uploaded build is processed. `on_state` is called whenever the state
+    /// changes. Returns `Ok(None)` when `timeout` runs out first.
+    pub async fn wait_for_processing(
+        &self,
+        upload: &BuildUpload,
+        interval: Duration,
+        timeout: Duration,
+        mut on_state: impl FnMut(&str),
+    ) -> Result<Option<Build>, String> {
+        let started = Instant::now();
+        let mut last_state = String::new();
+        loop {
+            // The upload is checked until the build appears, then the build itself
+            let state = match self.find_build(&upload.app_id, &upload.version, &upload.build_number).await? {
+                Some(build) => {
+                    match build.processing_state.as_str() {
+                        "VALID" => return Ok(Some(build)),
+                        "FAILED" | "INVALID" => {
+                            return Err(format!("App Store Connect marked the build {}", build.processing_state))
+                        }
+                        _ => {}
+                    }
+                    build.processing_state
+                }
+                None => {
+                    let (state, errors) = self.upload_state(&upload.id).await?;
+                    if state == "FAILED" {
+                        return Err(format!("App Store Connect rejected the upload: {}", errors.join("; ")));
+                    }
+                    state
+                }
+            };
+            if state != last_state {
+                on_state(&state);
+                last_state = state;
+            }
+
+            if started.elapsed() + interval > timeout {
+                return Ok(None);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Set the "What to Test" notes testers see for `locale`
+    pub async fn set_what_to_test(&self, build_id: &str, locale: &str, notes: &str) -> Result<(), String> {
+        let notes: String = notes.trim().chars().take(WHAT_TO_TEST_LIMIT).collect();
+        let localizations = self
+            .get(&format!("/v1/builds/{}/betaBuildLocalizations", build_id), &[], "Reading the test notes")
+            .await?;
+        let existing = localizations["data"]
+            .as_array()
+            .and_then(|l| l.iter().find(|l| l["attributes"]["locale"] == locale))
+            .and_then(|l| l["id"].as_str());
+
+        match existing {
+            Some(id) => {
+                self.patch(
+                    &format!("/v1/betaBuildLocalizations/{}", id),
+                    json!({ "data": { "type": "betaBuildLocalizations", "id": id, "attributes": { "whatsNew": notes } } }),
+                    "Updating the test notes",
+                )
+                .await?
+            }
+            None => {
+                self.post(
+                    "/v1/betaBuildLocalizations",
+                    json!({
+                        "data": {
+                            "type": "betaBuildLocalizations",
+                            "attributes": { "locale": locale, "whatsNew": notes },
+                            "relationships": { "build": { "data": { "type": "builds", "id": build_id } } }
+                        }
+                    }),
+                    "Adding the test notes",
+                )
+                .await?
+            }
+        };
+        Ok(())
+    }
+
+    /// Give the TestFlight groups named `group_names` access to the build.
+    /// Fails without changing anything when one of the groups does not exist.
+    pub async fn add_to_beta_groups(&self, app_id: &str, build_id: &str, group_names: &[String]) -> Result<(), String> {
+        let groups = self
+            .get(
+                "/v1/betaGroups",
+                &[("filter[app]", app_id), ("fields[betaGroups]", "name"), ("limit", "200")],
+                "Looking up the TestFlight groups",
+            )
+            .await?;
+        let groups = groups["data"].as_array().cloned().unwrap_or_default();
+
+        let mut ids = Vec::new();
+        let mut missing = Vec::new();
+        for name in group_names {
+            match groups.iter().find(|g| g["attributes"]["name"].as_str() == Some(name.as_str())) {
+                Some(group) => ids.push(json!({ "type": "betaGroups", "id": group["id"] })),
+                None => missing.push(name.as_str()),
+            }
+        }
+        if !missing.is_empty() {
+            return Err(format!("TestFlight group not found: {}", missing.join(", ")));
+        }
+
+        self.post(
+            &format!("/v1/builds/{}/relationships/betaGroups", build_id),
+            json!({ "data": ids }),
+            "Adding the build to TestFlight groups",
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,393 @@
+use crate::commands::analyzer::{size_category, CATEGORIES, NATIVE_LIBRARIES};
+use crate::commands::android_manifest::{read_manifest, AndroidManifestInfo};
+use crate::commands::ipa_metadata::{read_ipa, IpaInfo, MachOBinary};
+use crate::DbState;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::path::Path;
+use tauri::{command, State};
+use zip::ZipArchive;
+
+/// One side of a comparison
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSummary {
+    pub path: String,
+    pub file_type: String,
+    pub total_size: u64,
+    /// Version and build number of the build history entry, when compared by build
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    pub path: String,
+    /// added, removed or changed
+    pub change: String,
+    pub base_size: u64,
+    pub target_size: u64,
+    pub delta: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryDiff {
+    pub name: String,
+    pub base_size: u64,
+    pub target_size: u64,
+    pub delta: i64,
+    /// Largest changes first
+    pub files: Vec<FileChange>,
+}
+
+/// A manifest or Info.plist value that differs between the artifacts
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataChange {
+    pub field: String,
+    pub base: Option<String>,
+    pub target: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactDiff {
+    pub base: ArtifactSummary,
+    pub target: ArtifactSummary,
+    pub size_delta: i64,
+    pub categories: Vec<CategoryDiff>,
+    pub metadata_changes: Vec<MetadataChange>,
+    pub permissions_added: Vec<String>,
+    pub permissions_removed: Vec<String>,
+    /// Shared objects of APKs and AABs, Mach-O binaries of IPAs
+    pub native_libraries: Vec<FileChange>,
+}
+
+/// What a comparison needs from an artifact
+struct ArtifactContents {
+    extension: String,
+    total_size: u64,
+    /// Uncompressed size and CRC-32 per entry
+    files: BTreeMap<String, (u64, u32)>,
+    manifest: Option<AndroidManifestInfo>,
+    ipa: Option<IpaInfo>,
+}
+
+/// Compare two artifacts, given either as paths or as build history IDs
+#[command]
+pub async fn compare_artifacts(
+    state: State<'_, DbState>,
+    base_path: Option<String>,
+    target_path: Option<String>,
+    base_build_id: Option<String>,
+    target_build_id: Option<String>,
+) -> Result<ArtifactDiff, String> {
+    let (base_path, base_label) = resolve_artifact(&state, base_path, base_build_id)?;
+    let (target_path, target_label) = resolve_artifact(&state, target_path, target_build_id)?;
+
+    let mut diff = compare_artifact_files(Path::new(&base_path), Path::new(&target_path))?;
+    diff.base.label = base_label;
+    diff.target.label = target_label;
+    Ok(diff)
+}
+
+/// Write a comparison to `save_path` as `json` or `markdown`
+#[command]
+pub async fn export_artifact_diff(diff: ArtifactDiff, format: String, save_path: String) -> Result<String, String> {
+    let contents = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&diff).map_err(|e| e.to_string())?,
+        "markdown" => diff_markdown(&diff),
+        other => return Err(format!("Unsupported export format: {}", other)),
+    };
+    std::fs::write(&save_path, contents).map_err(|e| format!("Failed to write {}: {}", save_path, e))?;
+    Ok(save_path)
+}
+
+/// The artifact path and label of a path or a build history entry
+fn resolve_artifact(state: &State<'_, DbState>, path: Option<String>, build_id: Option<String>) -> Result<(String, Option<String>), String> {
+    if let Some(path) = path {
+        return Ok((path, None));
+    }
+    let build_id = build_id.ok_or("Pass an artifact path or a build ID for both sides")?;
+    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let (artifact_path, version, build_number): (Option<String>, String, i64) = conn
+        .query_row(
+            "SELECT artifact_path, version, build_number FROM build_history WHERE id = ?1",
+            params![build_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|_| format!("Build {} not found", build_id))?;
+    let artifact_path = artifact_path.ok_or_else(|| format!("Build {} has no artifact", build_id))?;
+    Ok((artifact_path, Some(format!("{} ({})", version, build_number))))
+}
+
+fn read_contents(path: &Path) -> Result<ArtifactContents, String> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_string();
+    if !matches!(extension.as_str(), "apk" | "aab" | "ipa") {
+        return Err(format!("Unsupported file extension: {}", extension));
+    }
+    let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let total_size = file.metadata().map_err(|e| e.to_string())?.len();
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut files = BTreeMap::new();
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        if !entry.is_dir() {
+            files.insert(entry.name().to_string(), (entry.size(), entry.crc32()));
+        }
+    }
+    // Metadata that can't be decoded is left out of the comparison rather than failing it
+    let manifest = if extension == "ipa" { None } else { read_manifest(&mut archive, &extension).ok() };
+    let ipa = if extension == "ipa" { read_ipa(&mut archive, chrono::Utc::now()).ok() } else { None };
+
+    Ok(ArtifactContents { extension, total_size, files, manifest, ipa })
+}
+
+/// Compare the contents, manifests or Info.plists and native binaries of two artifacts of the same type
+pub fn compare_artifact_files(base_path: &Path, target_path: &Path) -> Result<ArtifactDiff, String> {
+    let base = read_contents(base_path)?;
+    let target = read_contents(target_path)?;
+    if base.extension != target.extension {
+        return Err(format!(
+            "Can't compare an {} with an {}",
+            base.extension.to_uppercase(),
+            target.extension.to_uppercase()
+        ));
+    }
+    let extension = base.extension.as_str();
+
+    let mut categories: Vec<CategoryDiff> = CATEGORIES
+        .iter()
+        .map(|name| CategoryDiff { name: name.to_string(), base_size: 0, target_size: 0, delta: 0, files: Vec::new() })
+        .collect();
+    let mut changes = Vec::new();
+    let paths: BTreeSet<&String> = base.files.keys().chain(target.files.keys()).collect();
+    for path in paths {
+        let before = base.files.get(path);
+        let after = target.files.get(path);
+        let (base_size, target_size) = (before.map_or(0, |f| f.0), after.map_or(0, |f| f.0));
+
+        let category = size_category(extension, path);
+        let index = CATEGORIES.iter().position(|c| *c == category).unwrap_or(CATEGORIES.len() - 1);
+        categories[index].base_size += base_size;
+        categories[index].target_size += target_size;
+
+        let change = match (before, after) {
+            (None, Some(_)) => "added",
+            (Some(_), None) => "removed",
+            (Some(before), Some(after)) if before != after => "changed",
+            _ => continue,
+        };
+        let file_change = FileChange {
+            path: path.clone(),
+            change: change.to_string(),
+            base_size,
+            target_size,
+            delta: target_size as i64 - base_size as i64,
+        };
+        changes.push((index, file_change));
+    }
+    changes.sort_by_key(|(_, c)| (std::cmp::Reverse(c.delta.unsigned_abs()), c.path.clone()));
+
+    // Mach-O binaries are found by content, shared objects by name
+    let macho_paths: Vec<&str> = [&base.ipa, &target.ipa]
+        .into_iter()
+        .flatten()
+        .flat_map(|ipa| ipa.binaries.iter().map(|b| b.path.as_str()))
+        .collect();
+    let native_libraries = changes
+        .iter()
+        .filter(|(index, c)| {
+            if extension == "ipa" {
+                macho_paths.contains(&c.path.as_str())
+            } else {
+                CATEGORIES[*index] == NATIVE_LIBRARIES && c.path.ends_with(".so")
+            }
+        })
+        .map(|(_, c)| c.clone())
+        .collect();
+    for (index, change) in changes {
+        categories[index].files.push(change);
+    }
+    for category in &mut categories {
+        category.delta = category.target_size as i64 - category.base_size as i64;
+    }
+    categories.retain(|c| c.base_size > 0 || c.target_size > 0);
+
+    let mut metadata_changes = Vec::new();
+    let (mut permissions_added, mut permissions_removed) = (Vec::new(), Vec::new());
+    if let (Some(before), Some(after)) = (&base.manifest, &target.manifest) {
+        metadata_changes = manifest_changes(before, after);
+        permissions_added = after.permissions.iter().filter(|p| !before.permissions.contains(p)).cloned().collect();
+        permissions_removed = before.permissions.iter().filter(|p| !after.permissions.contains(p)).cloned().collect();
+    }
+    if let (Some(before), Some(after)) = (&base.ipa, &target.ipa) {
+        metadata_changes = ipa_changes(before, after);
+    }
+
+    Ok(ArtifactDiff {
+        base: ArtifactSummary {
+            path: base_path.to_string_lossy().to_string(),
+            file_type: base.extension.to_uppercase(),
+            total_size: base.total_size,
+            label: None,
+        },
+        target: ArtifactSummary {
+            path: target_path.to_string_lossy().to_string(),
+            file_type: target.extension.to_uppercase(),
+            total_size: target.total_size,
+            label: None,
+        },
+        size_delta: target.total_size as i64 - base.total_size as i64,
+        categories,
+        metadata_changes,
+        permissions_added,
+        permissions_removed,
+        native_libraries,
+    })
+}
+
+fn push_change(changes: &mut Vec<MetadataChange>, field: &str, base: Option<String>, target: Option<String>) {
+    if base != target {
+        changes.push(MetadataChange { field: field.to_string(), base, target });
+    }
+}
+
+fn manifest_changes(before: &AndroidManifestInfo, after: &AndroidManifestInfo) -> Vec<MetadataChange> {
+    let mut changes = Vec::new();
+    let text = |value: Option<u32>| value.map(|v| v.to_string());
+    let components = |info: &AndroidManifestInfo| {
+        let names: Vec<&str> = info.exported_components.iter().map(|c| c.name.as_str()).collect();
+        Some(names.join(", "))
+    };
+    push_change(&mut changes, "Package", Some(before.package.clone()), Some(after.package.clone()));
+    push_change(&mut changes, "Version code", before.version_code.map(|v| v.to_string()), after.version_code.map(|v| v.to_string()));
+    push_change(&mut changes, "Version name", before.version_name.clone(), after.version_name.clone());
+    push_change(&mut changes, "Min SDK", text(before.min_sdk), text(after.min_sdk));
+    push_change(&mut changes, "Target SDK", text(before.target_sdk), text(after.target_sdk));
+    push_change(&mut changes, "Compile SDK", text(before.compile_sdk), text(after.compile_sdk));
+    push_change(&mut changes, "Debuggable", Some(before.debuggable.to_string()), Some(after.debuggable.to_string()));
+    push_change(&mut changes, "Allow backup", Some(before.allow_backup.to_string()), Some(after.allow_backup.to_string()));
+    push_change(&mut changes, "Exported components", components(before), components(after));
+    changes
+}
+
+fn ipa_changes(before: &IpaInfo, after: &IpaInfo) -> Vec<MetadataChange> {
+    let mut changes = Vec::new();
+    push_change(&mut changes, "Bundle ID", Some(before.bundle_id.clone()), Some(after.bundle_id.clone()));
+    push_change(&mut changes, "Version", before.version.clone(), after.version.clone());
+    push_change(&mut changes, "Build number", before.build_number.clone(), after.build_number.clone());
+    push_change(&mut changes, "Minimum OS", before.minimum_os.clone(), after.minimum_os.clone());
+    push_change(
+        &mut changes,
+        "Signing profile",
+        before.profile.as_ref().map(|p| p.name.clone()),
+        after.profile.as_ref().map(|p| p.name.clone()),
+    );
+    for binary in &after.binaries {
+        let Some(previous) = before.binaries.iter().find(|b| b.path == binary.path) else {
+            continue;
+        };
+        let arches = |b: &MachOBinary| {
+            Some(b.slices.iter().map(|s| s.arch.as_str()).collect::<Vec<_>>().join(", "))
+        };
+        let name = binary.path.rsplit('/').next().unwrap_or_default();
+        push_change(&mut changes, &format!("{} architectures", name), arches(previous), arches(binary));
+        push_change(
+            &mut changes,
+            &format!("{} linked libraries", name),
+            Some(previous.linked_libraries.join(", ")),
+            Some(binary.linked_libraries.join(", ")),
+        );
+    }
+    changes
+}
+
+pub fn format_size(bytes: u64) -> String {
+    let units = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", size, units[unit])
+    }
+}
+
+pub fn format_delta(delta: i64) -> String {
+    let sign = if delta > 0 { "+" } else if delta < 0 { "-" } else { "±" };
+    format!("{}{}", sign, format_size(delta.unsigned_abs()))
+}
+
+/// Render a comparison as a Markdown report
+pub fn diff_markdown(diff: &ArtifactDiff) -> String {
+    let side = |summary: &ArtifactSummary| {
+        let name = Path::new(&summary.path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        match &summary.label {
+            Some(label) => format!("{} `{}`", label, name),
+            None => format!("`{}`", name),
+        }
+    };
+
+    let mut out = format!("# {} comparison\n\n", diff.target.file_type);
+    out.push_str(&format!(
+        "{} → {}: {} → {} ({})\n",
+        side(&diff.base),
+        side(&diff.target),
+        format_size(diff.base.total_size),
+        format_size(diff.target.total_size),
+        format_delta(diff.size_delta)
+    ));
+
+    out.push_str("\n## Size by category\n\n| Category | Before | After | Change |\n| --- | ---: | ---: | ---: |\n");
+    for category in &diff.categories {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            category.name,
+            format_size(category.base_size),
+            format_size(category.target_size),
+            format_delta(category.delta)
+        ));
+    }
+
+    if !diff.metadata_changes.is_empty() || !diff.permissions_added.is_empty() || !diff.permissions_removed.is_empty() {
+        out.push_str(if diff.target.file_type == "IPA" { "\n## Info.plist\n\n" } else { "\n## Manifest\n\n" });
+        for change in &diff.metadata_changes {
+            out.push_str(&format!(
+                "- {}: {} → {}\n",
+                change.field,
+                change.base.as_deref().unwrap_or("—"),
+                change.target.as_deref().unwrap_or("—")
+            ));
+        }
+        for permission in &diff.permissions_added {
+            out.push_str(&format!("- Permission added: `{}`\n", permission));
+        }
+        for permission in &diff.permissions_removed {
+            out.push_str(&format!("- Permission removed: `{}`\n", permission));
+        }
+    }
+
+    if !diff.native_libraries.is_empty() {
+        out.push_str("\n## Native libraries\n\n");
+        for change in &diff.native_libraries {
+            out.push_str(&format!("- {} `{}` ({})\n", change.change, change.path, format_delta(change.delta)));
+        }
+    }
+
+    for category in diff.categories.iter().filter(|c| !c.files.is_empty()) {
+        out.push_str(&format!("\n## {}\n\n| File | Change | Size |\n| --- | --- | ---: |\n", category.name));
+        for change in &category.files {
+            out.push_str(&format!("| `{}` | {} | {} |\n", change.path, change.change, format_delta(change.delta)));
+        }
+    }
+    out
+}
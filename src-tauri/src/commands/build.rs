@@ -1,15 +1,35 @@
-use tauri::{command, Window, Emitter, State};
-use std::process::{Command, Stdio};
-use std::io::{BufRead, BufReader, Write};
-use std::sync::Arc;
-use std::fs::File;
-#[cfg(unix)]
-use std::os::unix::process::CommandExt;
-use crate::models::project::Project;
-use crate::BuildProcessState;
+use tauri::{command, AppHandle, Manager, State, Window};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use crate::models::project::{PrebuildSettings, Project, SizeBudgets, VersionBumpSettings};
+use crate::{BuildProcessState, DbState};
 use crate::commands::notification::send_all_notifications;
-
-#[derive(serde::Deserialize)]
+use crate::commands::diagnostics::{save_build_diagnostics, DiagnosticsSink};
+use crate::commands::events::{BuildEventBus, BuildEventSink, BuildStatus, WindowSink};
+use crate::commands::google_auth::ServiceAccountKey;
+use crate::commands::google_play::{self, PlayClient, PlayRelease};
+use crate::commands::app_store_connect::{self, ApiKey, AppStoreConnectClient};
+use crate::commands::firebase_distribution::{self, split_list, AppDistributionClient};
+use crate::commands::export_options::write_export_options;
+use crate::commands::provisioning::{load_installed_profiles, match_profile, ProvisioningCheck};
+use crate::commands::signing::{injected_signing_args, load_signing_passwords, signer_certificate, signing_env};
+use crate::commands::git::{capture_provenance, create_tag, release_tag_name};
+use crate::commands::release_notes::{self, last_built_commit};
+use crate::commands::pipeline::{
+    run_command, run_command_with_env, BuildContext, BuildPipeline, IosBuildState, StepError, StepName,
+    StepOutcome, StepResult, StreamMode,
+};
+use crate::commands::timings::{save_build_timing, timing_from_reports};
+use crate::commands::expo::{prebuild_command, prebuild_workspace, prepare_workspace, update_expo_versions};
+use crate::commands::profiles::{android_output_path, apply_profile, find_profile, gradle_task};
+use crate::commands::project::{is_expo_project, write_project_versions};
+use crate::commands::versioning::{apply_bump, load_project_versions, save_project_versions, VersionSnapshot};
+use crate::commands::analyzer::{analyze_artifact, AppSizeReport};
+use crate::commands::artifact_diff::{format_delta, format_size};
+use crate::commands::size_budget::check_budgets;
+use crate::commands::size_history::{previous_build_size, save_build_size};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildOptions {
     #[serde(rename = "uploadToAppStore")]
@@ -19,6 +39,13 @@ pub struct BuildOptions {
     pub android_format: Option<String>,
     #[serde(rename = "sendToAppDistribution")]
     pub send_to_app_distribution: Option<bool>,
+    pub upload_to_play_store: Option<bool>,
+    /// Overrides the project's Google Play track for this build
+    pub play_track: Option<String>,
+    /// Id of the build_history row this build will be recorded under
+    pub build_id: Option<String>,
+    /// Name of the project's build profile to build
+    pub profile: Option<String>,
 }
 
 #[command]
@@ -28,784 +55,1184 @@ pub async fn build_project(
     platform: String,
     options: Option<BuildOptions>,
     process_state: State<'_, BuildProcessState>,
+) -> Result<(), String> {
+    run_build(window.app_handle(), project, platform, options, process_state.inner(), Vec::new()).await
+}
+
+/// Runs a build to completion. Every event goes to the window (`build-event` channel), the
+/// build log file and the extra `sinks`. Shared by the `build_project` command and the build queue.
+pub async fn run_build(
+    app: &AppHandle,
+    mut project: Project,
+    platform: String,
+    options: Option<BuildOptions>,
+    process_state: &BuildProcessState,
+    sinks: Vec<Box<dyn BuildEventSink>>,
 ) -> Result<(), String> {
     println!("Building project {} for platform {}", project.name, platform);
+    let started_at = chrono::Utc::now().timestamp_millis() as u64;
 
-    match platform.as_str() {
-        "android" => {
-            let android_dir = std::path::Path::new(&project.path).join("android");
-
-            // Create logs directory for Android
-            let logs_dir = android_dir.join("build/logs");
-            std::fs::create_dir_all(&logs_dir)
-                .map_err(|e| format!("Failed to create logs directory: {}", e))?;
-
-            // Create log file with timestamp
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            let log_file_path = logs_dir.join(format!("{}.log", timestamp));
-            let mut log_file = File::create(&log_file_path)
-                .map_err(|e| format!("Failed to create log file: {}", e))?;
-
-            // Emit start message
-            let start_msg = format!("🚀 Starting Android build for project: {}", project.name);
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": start_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", start_msg).map_err(|e| e.to_string())?;
-
-            if !android_dir.exists() {
-                let err_msg = format!("❌ Android directory not found at: {:?}", android_dir);
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": err_msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", err_msg).map_err(|e| e.to_string())?;
-                window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap() })).map_err(|e| e.to_string())?;
-                window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "android" })).map_err(|e| e.to_string())?;
-                return Err(err_msg);
-            }
+    let options = options.unwrap_or_default();
+    let mut events = BuildEventBus::new(&project.id, &platform, options.build_id.clone());
+    events.subscribe(Box::new(WindowSink::new(app)));
+    for sink in sinks {
+        events.subscribe(sink);
+    }
+
+    if platform != "android" && platform != "ios" {
+        events.status(BuildStatus::Failed);
+        return Err(format!("Unsupported platform: {}", platform));
+    }
 
-            // Check if gradlew exists
-            let gradlew_path = android_dir.join("gradlew");
-            if !gradlew_path.exists() {
-                let err_msg = format!("❌ gradlew not found at: {:?}", gradlew_path);
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": err_msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", err_msg).map_err(|e| e.to_string())?;
-                window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap() })).map_err(|e| e.to_string())?;
-                window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "android" })).map_err(|e| e.to_string())?;
+    let profile = match options.profile.as_deref().map(|name| find_profile(&project, name)).transpose() {
+        Ok(profile) => profile,
+        Err(e) => {
+            events.error(&format!("❌ {}", e));
+            events.status(BuildStatus::Failed);
+            return Err(e);
+        }
+    };
+    if let Some(profile) = &profile {
+        if let Some(env_file) = &profile.env_file {
+            if !std::path::Path::new(&project.path).join(env_file).is_file() {
+                let err_msg = format!("❌ Env file {} of the {} profile not found", env_file, profile.name);
+                events.error(&err_msg);
+                events.status(BuildStatus::Failed);
                 return Err(err_msg);
             }
+        }
+        apply_profile(&mut project, profile);
+    }
 
-            let path_msg = format!("📁 Android directory: {:?}", android_dir);
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": path_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", path_msg).map_err(|e| e.to_string())?;
-
-            // Use /bin/sh -l -c to run in a login shell
-            // Add common paths for Node.js (Homebrew, NVM, etc.) and source shell profiles
-            let android_dir_str = android_dir.to_str().unwrap_or("");
-
-            // Build comprehensive shell command that sources profiles and adds common paths
-            let shell_command = format!(
-                r#"
-                # 1. Add standard paths explicitly (Homebrew, Local, System)
-                export PATH="/opt/homebrew/bin:/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin:$PATH"
-
-                # 2. Source shell profiles (This handles most version managers like fnm, asdf, volta if configured in shell)
-                # We suppress output to avoid polluting logs
-                [ -f "$HOME/.zshrc" ] && source "$HOME/.zshrc" >/dev/null 2>&1 || true
-                [ -f "$HOME/.bashrc" ] && source "$HOME/.bashrc" >/dev/null 2>&1 || true
-                [ -f "$HOME/.bash_profile" ] && source "$HOME/.bash_profile" >/dev/null 2>&1 || true
-
-                # 3. Explicitly look for NVM if likely not loaded yet
-                if [ -d "$HOME/.nvm" ]; then
-                    export NVM_DIR="$HOME/.nvm"
-                    [ -s "$NVM_DIR/nvm.sh" ] && source "$NVM_DIR/nvm.sh" >/dev/null 2>&1 || true
-                fi
-
-                # 4. Fallback: Try to find common version managers if node is still missing
-                if ! command -v node >/dev/null 2>&1; then
-                    # Check for Volta
-                    if [ -d "$HOME/.volta" ]; then
-                        export VOLTA_HOME="$HOME/.volta"
-                        export PATH="$VOLTA_HOME/bin:$PATH"
-                    fi
-                    # Check for FNM (Fast Node Manager)
-                    if [ -d "$HOME/Library/Application Support/fnm" ]; then
-                        export PATH="$HOME/Library/Application Support/fnm:$PATH"
-                        eval "`fnm env --use-on-cd 2>/dev/null`" || true
-                    fi
-                fi
-
-                # Log environment for debugging
-                echo "🔍 Node path: $(which node 2>/dev/null || echo 'not found')"
-                echo "🔍 Node version: $(node -v 2>/dev/null || echo 'unknown')"
-                echo "🔍 Java path: $(which java 2>/dev/null || echo 'not found')"
-
-                cd '{}' && {} 2>&1
-                "#,
-                android_dir_str,
-                {
-                    let format = options.as_ref().and_then(|o| o.android_format.as_deref()).unwrap_or("aab");
-                    let base_cmd = project.android.build_command.as_deref().unwrap_or(
-                        if format == "apk" { "./gradlew assembleRelease" } else { "./gradlew bundleRelease" }
-                    );
-
-                    if format == "apk" {
-                        base_cmd.replace("bundle", "assemble")
-                    } else {
-                        base_cmd.replace("assemble", "bundle")
-                    }
-                }
-            );
+    let platform_dir = std::path::Path::new(&project.path).join(&platform);
+    let prebuild = project.prebuild_settings().is_some();
+    if prebuild && !is_expo_project(std::path::Path::new(&project.path)) {
+        let err_msg = format!("❌ Prebuild is enabled, but no Expo config was found in {}", project.path);
+        events.error(&err_msg);
+        events.status(BuildStatus::Failed);
+        return Err(err_msg);
+    }
+    if !prebuild && !platform_dir.exists() {
+        let err_msg = format!("❌ {} directory not found at: {:?}", if platform == "ios" { "iOS" } else { "Android" }, platform_dir);
+        events.error(&err_msg);
+        events.status(BuildStatus::Failed);
+        return Err(err_msg);
+    }
 
-            let cmd_info = format!("🔧 Running Android build with enhanced environment...");
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": cmd_info })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", cmd_info).map_err(|e| e.to_string())?;
-
-            let mut child = Command::new("/bin/sh")
-                .args(&["-c", &shell_command])
-                .current_dir(&android_dir)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .process_group(0) // own process group so killpg kills gradle + all children
-                .spawn()
-                .map_err(|e| {
-                    let err_msg = format!("❌ Failed to start build command: {}", e);
-                    let _ = window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": err_msg }));
-                    let _ = writeln!(log_file, "{}", err_msg);
-                    let _ = window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap_or_default() }));
-                    let _ = window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "android" }));
-                    err_msg
-                })?;
-
-            // Take stdout/stderr BEFORE storing so cancel_build_process can still kill the child
-            let child_stdout = child.stdout.take();
-            let child_stderr = child.stderr.take();
-
-            // Store process for cancellation (child stays as Some so kill() works)
-            let process_id = format!("{}_{}", project.id, platform);
-            {
-                let mut processes = process_state.0.lock().unwrap();
-                processes.insert(process_id.clone(), Arc::new(std::sync::Mutex::new(Some(child))));
+    // The native folders of a prebuild project may not exist yet, or be wiped by `--clean`
+    let logs_dir = if prebuild {
+        app.path().app_data_dir().map_err(|e| e.to_string())?.join("logs").join(&project.id).join(&platform)
+    } else {
+        platform_dir.join("build/logs")
+    };
+    events.open_log_file(&logs_dir)?;
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    events.subscribe(Box::new(DiagnosticsSink::new(diagnostics.clone())));
+    let pipeline = BuildPipeline::for_platform(&platform, &options);
+
+    let mut ctx = BuildContext {
+        app,
+        process_state,
+        project,
+        platform,
+        options,
+        platform_dir,
+        profile,
+        events,
+        artifact_path: None,
+        git: None,
+        reports: Vec::new(),
+        step_count: 0,
+        diagnostics,
+        version_snapshot: None,
+        uploaded: false,
+        size_report: None,
+        ios: IosBuildState::default(),
+    };
+
+    let outcome = pipeline.run(&mut ctx).await;
+    if !matches!(outcome, Ok(BuildStatus::Success)) {
+        rollback_version_bump(&mut ctx)?;
+    }
+    pipeline.finish(&mut ctx, &outcome);
+
+    if let Some(build_id) = &ctx.options.build_id {
+        let finished_at = chrono::Utc::now().timestamp_millis() as u64;
+        let timing = timing_from_reports(started_at, finished_at, &ctx.reports);
+        let diagnostics = ctx.diagnostics.lock().unwrap().clone();
+        let db = app.state::<DbState>();
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        if let Err(e) = save_build_timing(&conn, build_id, &timing) {
+            println!("Failed to save timing for build {}: {}", build_id, e);
+        }
+        if let Err(e) = save_build_diagnostics(&conn, build_id, &diagnostics) {
+            println!("Failed to save diagnostics for build {}: {}", build_id, e);
+        }
+        if let (Ok(BuildStatus::Success), Some(report)) = (&outcome, &ctx.size_report) {
+            if let Err(e) = save_build_size(&conn, build_id, report) {
+                println!("Failed to save the size of build {}: {}", build_id, e);
             }
+        }
+    }
 
-            {
-                // Read stdout and write to log file
-                if let Some(stdout) = child_stdout {
-                    let reader = BufReader::new(stdout);
-                    for line in reader.lines() {
-                        if let Ok(line_content) = line {
-                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": line_content })).map_err(|e| e.to_string())?;
-                            writeln!(log_file, "{}", line_content).map_err(|e| e.to_string())?;
-                        }
-                    }
-                }
+    outcome.map(|_| ())
+}
 
-                // Read stderr and write to log file
-                if let Some(stderr) = child_stderr {
-                    let reader = BufReader::new(stderr);
-                    for line in reader.lines() {
-                        if let Ok(line_content) = line {
-                            let stderr_line = format!("⚠️ {}", line_content);
-                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": stderr_line })).map_err(|e| e.to_string())?;
-                            writeln!(log_file, "{}", stderr_line).map_err(|e| e.to_string())?;
-                        }
-                    }
-                }
+/// Dispatch a pipeline step to its platform implementation.
+pub async fn execute_step(step: StepName, ctx: &mut BuildContext<'_>) -> StepResult {
+    match (ctx.platform.as_str(), step) {
+        (_, StepName::Prepare) => prepare(ctx),
+        ("android", StepName::Archive) => android_archive(ctx),
+        ("android", StepName::Export) => android_export(ctx),
+        ("android", StepName::Sign) => android_sign(ctx),
+        ("android", StepName::Upload) => android_upload(ctx).await,
+        ("ios", StepName::Archive) => ios_archive(ctx),
+        ("ios", StepName::Sign) => ios_sign(ctx),
+        ("ios", StepName::Export) => ios_export(ctx),
+        ("ios", StepName::Upload) => ios_upload(ctx).await,
+        (_, StepName::Measure) => measure_artifact(ctx).await,
+        (_, StepName::Distribute) => distribute(ctx).await,
+        (_, StepName::Notify) => notify(ctx).await,
+        (platform, step) => Ok(StepOutcome::Skipped(format!("{} is not supported for {}", step.label(), platform))),
+    }
+}
 
-                // Wait for completion — detect if build was cancelled
-                let status_opt = {
-                    let mut processes = process_state.0.lock().unwrap();
-                    if let Some(mutex) = processes.remove(&process_id) {
-                        let mut guard = mutex.lock().unwrap();
-                        match guard.take() {
-                            Some(mut c) => Some(c.wait().map_err(|e| e.to_string())?),
-                            None => None, // killed by cancel_build_process
-                        }
-                    } else {
-                        None // already removed by cancel
-                    }
-                };
-
-                let status = match status_opt {
-                    Some(s) => s,
-                    None => {
-                        window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-                        window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "android" })).map_err(|e| e.to_string())?;
-                        return Ok(());
-                    }
-                };
-
-                // Emit log file path so frontend can save it
-                window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap() })).map_err(|e| e.to_string())?;
-
-                if status.success() {
-                    let format = options.as_ref().and_then(|o| o.android_format.as_deref()).unwrap_or("aab");
-                    let extension = if format == "apk" { "apk" } else { "aab" };
-
-                    // Direct path to the built artifact
-                    let artifact_file = if format == "apk" {
-                        android_dir.join("app/build/outputs/apk/release/app-release.apk")
-                    } else {
-                        android_dir.join("app/build/outputs/bundle/release/app-release.aab")
-                    };
-
-                    let mut artifact_path: Option<std::path::PathBuf> = None;
-
-                    if artifact_file.exists() {
-                        let ts = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
-                        let new_filename = format!(
-                            "{}_{}_{}_{}.{}",
-                            project.name.replace(" ", "_"),
-                            project.android.version,
-                            project.android.version_code,
-                            ts,
-                            extension
-                        );
-                        let dest_path = artifact_file.parent().unwrap().join(&new_filename);
-                        std::fs::rename(&artifact_file, &dest_path)
-                            .map_err(|e| format!("Failed to rename {} file: {}", extension.to_uppercase(), e))?;
-
-                        let rename_msg = format!("✅ {} renamed to: {}", extension.to_uppercase(), new_filename);
-                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": rename_msg })).map_err(|e| e.to_string())?;
-                        writeln!(log_file, "{}", rename_msg).map_err(|e| e.to_string())?;
-
-                        // Emit artifact path
-                        window.emit("build-artifact-path", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": dest_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-
-                        artifact_path = Some(dest_path);
-                    } else {
-                        // Warn that file was not found
-                        let warn_msg = format!("⚠️ No {} file found at: {:?}", extension.to_uppercase(), artifact_file);
-                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": warn_msg })).map_err(|e| e.to_string())?;
-                        writeln!(log_file, "{}", warn_msg).map_err(|e| e.to_string())?;
-                    }
-
-
-                    let success_msg = "✅ Android build completed successfully";
-                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": success_msg })).map_err(|e| e.to_string())?;
-                    writeln!(log_file, "{}", success_msg).map_err(|e| e.to_string())?;
-
-                    // Upload to Firebase App Distribution if enabled
-                    if let Some(true) = options.as_ref().and_then(|o| o.send_to_app_distribution) {
-                        if let (Some(artifact), Some(config)) = (&artifact_path, &project.android.config) {
-                            if let Some(firebase_app_id) = &config.firebase_app_id {
-                                let upload_msg = "📤 Uploading to Firebase App Distribution...";
-                                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": upload_msg })).map_err(|e| e.to_string())?;
-                                writeln!(log_file, "{}", upload_msg).map_err(|e| e.to_string())?;
-
-                                let artifact_str = artifact.to_str().unwrap_or_default();
-                                let groups = config.distribution_groups.as_deref().unwrap_or("");
-                                let release_notes = options.as_ref()
-                                    .and_then(|o| o.release_note.as_deref())
-                                    .unwrap_or("");
-
-                                // Build firebase command
-                                let firebase_cmd = format!(
-                                    r#"
-                                    [ -f "$HOME/.zshrc" ] && source "$HOME/.zshrc" >/dev/null 2>&1 || true
-                                    [ -f "$HOME/.bashrc" ] && source "$HOME/.bashrc" >/dev/null 2>&1 || true
-                                    export PATH="/opt/homebrew/bin:/usr/local/bin:$HOME/.npm-global/bin:$PATH"
-                                    firebase appdistribution:distribute '{}' --app '{}' {} {} 2>&1
-                                    "#,
-                                    artifact_str.replace("'", "'\\''"),
-                                    firebase_app_id.replace("'", "'\\''"),
-                                    if !groups.is_empty() { format!("--groups '{}'", groups.replace("'", "'\\''")) } else { String::new() },
-                                    if !release_notes.is_empty() { format!("--release-notes '{}'", release_notes.replace("'", "'\\''")) } else { String::new() }
-                                );
-                                let firebase_output = Command::new("/bin/sh")
-                                    .args(&["-c", &firebase_cmd])
-                                    .output();
-
-                                match firebase_output {
-                                    Ok(output) => {
-                                        let stdout = String::from_utf8_lossy(&output.stdout);
-                                        let stderr = String::from_utf8_lossy(&output.stderr);
-
-                                        // Log firebase output
-                                        for line in stdout.lines() {
-                                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": line })).map_err(|e| e.to_string())?;
-                                            writeln!(log_file, "{}", line).map_err(|e| e.to_string())?;
-                                        }
-
-                                        if output.status.success() {
-                                            let upload_success = "✅ Successfully uploaded to Firebase App Distribution!";
-                                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": upload_success })).map_err(|e| e.to_string())?;
-                                            writeln!(log_file, "{}", upload_success).map_err(|e| e.to_string())?;
-                                        } else {
-                                            let upload_fail = format!("❌ Firebase upload failed: {}", stderr);
-                                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": upload_fail })).map_err(|e| e.to_string())?;
-                                            writeln!(log_file, "{}", upload_fail).map_err(|e| e.to_string())?;
-                                        }
-                                    }
-                                    Err(e) => {
-                                        let err_msg = format!("❌ Failed to run firebase command: {}", e);
-                                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": err_msg })).map_err(|e| e.to_string())?;
-                                        writeln!(log_file, "{}", err_msg).map_err(|e| e.to_string())?;
-                                    }
-                                }
-                            } else {
-                                let warn = "⚠️ App Distribution enabled but Firebase App ID not configured";
-                                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": warn })).map_err(|e| e.to_string())?;
-                                writeln!(log_file, "{}", warn).map_err(|e| e.to_string())?;
-                            }
-                        } else {
-                            let warn = "⚠️ App Distribution enabled but artifact path or config not available";
-                            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": warn })).map_err(|e| e.to_string())?;
-                            writeln!(log_file, "{}", warn).map_err(|e| e.to_string())?;
-                        }
-                    }
-
-                    window.emit("build-status", serde_json::json!({ "status": "success", "projectId": project.id, "platform": "android" })).map_err(|e| e.to_string())?;
-                    return Ok(());
-                } else {
-                    let exit_code = status.code().map(|c| c.to_string()).unwrap_or("unknown".to_string());
-                    let error_msg = format!("❌ Build failed with exit code: {}", exit_code);
-                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": error_msg })).map_err(|e| e.to_string())?;
-                    writeln!(log_file, "{}", error_msg).map_err(|e| e.to_string())?;
-                    window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "android", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-                    window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "android" })).map_err(|e| e.to_string())?;
-                    return Err(error_msg);
+/// Escape a value for use inside single quotes in a /bin/sh command
+fn shell_quote(value: &str) -> String {
+    value.replace("'", "'\\''")
+}
+
+/// Record where the build comes from, then run the platform's own preparation
+fn prepare(ctx: &mut BuildContext) -> StepResult {
+    let release = ctx.project.release.clone().unwrap_or_default();
+    if let Some(profile) = &ctx.profile {
+        let msg = format!("🎛️ Build profile: {} ({})", profile.name, ctx.bundle_id());
+        ctx.events.log(&msg);
+    }
+    ctx.git = capture_provenance(std::path::Path::new(&ctx.project.path));
+
+    match &ctx.git {
+        Some(git) => {
+            let msg = format!(
+                "🔖 Commit {}{}{}",
+                &git.commit[..git.commit.len().min(10)],
+                git.branch.as_ref().map(|b| format!(" on {}", b)).unwrap_or_default(),
+                git.tag.as_ref().map(|t| format!(" ({})", t)).unwrap_or_default()
+            );
+            ctx.events.log(&msg);
+            ctx.events.provenance(git);
+
+            if git.dirty {
+                let files = git.uncommitted_files.iter().take(10).cloned().collect::<Vec<_>>().join(", ");
+                let msg = format!("⚠️ {} uncommitted file(s): {}", git.uncommitted_files.len(), files);
+                if release.require_clean_tree && is_release_build(ctx) {
+                    let err_msg = format!("❌ Release builds require a clean working tree. {}", msg.trim_start_matches("⚠️ "));
+                    ctx.events.error(&err_msg);
+                    return Err(StepError::Failed(err_msg));
                 }
+                ctx.events.warn(&msg);
             }
-        },
-        "ios" => {
-            let ios_dir = std::path::Path::new(&project.path).join("ios");
-            if !ios_dir.exists() {
-                return Err(format!("iOS directory not found at {:?}", ios_dir));
+        }
+        None if release.require_clean_tree && is_release_build(ctx) => {
+            let err_msg = "❌ Release builds require a clean working tree, but the project is not a git repository";
+            ctx.events.error(err_msg);
+            return Err(StepError::Failed(err_msg.to_string()));
+        }
+        None => {}
+    }
+
+    if let Some(settings) = &release.version_bump {
+        if let Err(e) = bump_versions(ctx, settings) {
+            let err_msg = format!("❌ Failed to bump version: {}", e);
+            ctx.events.error(&err_msg);
+            return Err(StepError::Failed(err_msg));
+        }
+    }
+
+    if release.auto_release_notes && ctx.options.release_note.as_deref().unwrap_or_default().trim().is_empty() {
+        generate_build_release_notes(ctx)?;
+    }
+
+    if let Some(settings) = ctx.project.prebuild_settings().cloned() {
+        expo_prebuild(ctx, &settings)?;
+    }
+
+    if ctx.platform == "ios" {
+        ios_prepare(ctx)
+    } else {
+        android_prepare(ctx)
+    }
+}
+
+/// Generate the platform's native folder with `expo prebuild`, in the project or in a copy of it.
+/// The Expo config gets the build's version first, as the native files are generated from it.
+fn expo_prebuild(ctx: &mut BuildContext, settings: &PrebuildSettings) -> Result<(), StepError> {
+    let project_dir = std::path::PathBuf::from(&ctx.project.path);
+    let dir = if settings.isolated {
+        let workspace = prebuild_workspace(&ctx.project.id, &ctx.platform);
+        if let Err(e) = prepare_workspace(&project_dir, &workspace) {
+            let err_msg = format!("❌ Failed to prepare the prebuild workspace: {}", e);
+            ctx.events.error(&err_msg);
+            return Err(StepError::Failed(err_msg));
+        }
+        ctx.events.log(&format!("📁 Prebuild workspace: {:?}", workspace));
+        workspace
+    } else {
+        project_dir
+    };
+
+    let (build_number, version_code) = if ctx.platform == "ios" {
+        (Some(ctx.project.ios.build_number), None)
+    } else {
+        (None, Some(ctx.project.android.version_code))
+    };
+    // The Expo config has a single version, only write it when both platforms agree
+    let version = (ctx.project.ios.version == ctx.project.android.version).then_some(ctx.project.ios.version.clone());
+    if version.is_none() {
+        ctx.events.warn(&format!(
+            "⚠️ The iOS ({}) and Android ({}) versions differ, the Expo config's version is left as is",
+            ctx.project.ios.version, ctx.project.android.version
+        ));
+    }
+    match update_expo_versions(&dir, version.as_deref(), build_number, version_code) {
+        Ok(update) => {
+            for change in &update.changes {
+                let file = change.file.strip_prefix(&dir).unwrap_or(&change.file);
+                ctx.events.log(&format!("  ✏️ {}:{}: {} → {}", file.display(), change.line, change.old, change.new));
             }
+            if !update.missing.is_empty() {
+                let msg = format!("⚠️ The Expo config has no literal {}, set it there to version the build", update.missing.join(", "));
+                ctx.events.warn(&msg);
+            }
+        }
+        Err(e) => {
+            let err_msg = format!("❌ Failed to update the Expo config: {}", e);
+            ctx.events.error(&err_msg);
+            return Err(StepError::Failed(err_msg));
+        }
+    }
 
-            // Create logs directory
-            let logs_dir = ios_dir.join("build/logs");
-            std::fs::create_dir_all(&logs_dir)
-                .map_err(|e| format!("Failed to create logs directory: {}", e))?;
-
-            // Create log file with timestamp
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            let log_file_path = logs_dir.join(format!("{}.log", timestamp));
-            let mut log_file = File::create(&log_file_path)
-                .map_err(|e| format!("Failed to create log file: {}", e))?;
-
-            // Uses scheme and configuration from project settings or falls back to defaults
-            let scheme = project.ios.config.as_ref().map(|c| c.scheme.as_str()).unwrap_or(&project.name);
-            let configuration = project.ios.config.as_ref().map(|c| c.configuration.as_str()).unwrap_or("Release");
-
-            // Find workspace or project file in ios directory
-            let mut workspace_path = None;
-            let mut project_path = None;
-
-            if let Ok(entries) = std::fs::read_dir(&ios_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if let Some(ext) = path.extension() {
-                        if ext == "xcworkspace" {
-                            workspace_path = Some(path);
-                            break; // Prefer workspace over project
-                        } else if ext == "xcodeproj" && project_path.is_none() {
-                            project_path = Some(path);
-                        }
-                    }
-                }
+    ctx.events.log(&format!("🏗️ Running expo prebuild for {}...", ctx.platform));
+    let cmd = prebuild_command(&ctx.platform, settings.clean);
+    let output = run_command(ctx, &cmd, &dir, StreamMode::All)?;
+    if !output.status.success() {
+        log_recent_output(ctx, "❌ expo prebuild failed. Recent logs:", &output.recent, "Check log file for full details.");
+        return Err(StepError::Failed("expo prebuild failed".to_string()));
+    }
+
+    ctx.platform_dir = dir.join(&ctx.platform);
+    Ok(())
+}
+
+/// Bump the version and build number as configured, in the native files and the stored project.
+/// The previous state is kept in `ctx.version_snapshot` so a failed build can undo it. The
+/// database stays locked from reading the versions to storing the new ones, so builds of the
+/// same project running side by side bump one after the other.
+fn bump_versions(ctx: &mut BuildContext, settings: &VersionBumpSettings) -> Result<(), String> {
+    let db = ctx.app.state::<DbState>();
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    load_project_versions(&conn, &mut ctx.project)?;
+    let mut project = ctx.project.clone();
+    let today = chrono::Local::now().date_naive();
+    if !apply_bump(&mut project, &ctx.platform, settings, today)? {
+        return Ok(());
+    }
+
+    let snapshot = VersionSnapshot::new(&ctx.project, &project);
+    let written = write_project_versions(&project).and_then(|changes| save_project_versions(&conn, &project).map(|_| changes));
+    let changes = match written {
+        Ok(changes) => changes,
+        Err(e) => {
+            if let Err(restore_err) = snapshot.restore(&conn) {
+                ctx.events.error(&format!("❌ Could not restore version files: {}", restore_err));
             }
+            return Err(e);
+        }
+    };
+    drop(conn);
+
+    let (old_version, old_build, new_version, new_build) = if ctx.platform == "ios" {
+        (&ctx.project.ios.version, ctx.project.ios.build_number, &project.ios.version, project.ios.build_number)
+    } else {
+        (&ctx.project.android.version, ctx.project.android.version_code, &project.android.version, project.android.version_code)
+    };
+    let msg = format!("🔢 Version bumped: {} ({}) → {} ({})", old_version, old_build, new_version, new_build);
+    ctx.events.log(&msg);
+    for change in &changes {
+        let file = change.file.strip_prefix(&ctx.project.path).unwrap_or(&change.file);
+        ctx.events.log(&format!("  ✏️ {}:{}: {} → {}", file.display(), change.line, change.old, change.new));
+    }
+    ctx.events.version(new_version, new_build);
 
-            // Build archive path
-            let build_dir = ios_dir.join("build");
-            let archive_path = build_dir.join(format!("{}.xcarchive", &project.name));
-            let archive_path_str = archive_path.to_str().ok_or("Invalid archive path")?;
-
-            // Step 1: Archive
-            let archive_msg = format!("📦 Starting iOS archive for scheme: {}", scheme);
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": archive_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", archive_msg).map_err(|e| e.to_string())?;
-
-            // Find workspace or project flag
-            let build_file_path = if let Some(ref ws_path) = workspace_path {
-                let msg = format!("🔍 Found workspace: {}", ws_path.file_name().unwrap().to_string_lossy());
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", msg).map_err(|e| e.to_string())?;
-                ws_path.to_str().ok_or("Invalid workspace path")?.to_string()
-            } else if let Some(ref proj_path) = project_path {
-                let msg = format!("🔍 Found project: {}", proj_path.file_name().unwrap().to_string_lossy());
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", msg).map_err(|e| e.to_string())?;
-                proj_path.to_str().ok_or("Invalid project path")?.to_string()
+    ctx.project = project;
+    ctx.version_snapshot = Some(snapshot);
+    Ok(())
+}
+
+/// Undo the version bump of a build that failed or was cancelled
+fn rollback_version_bump(ctx: &mut BuildContext) -> Result<(), String> {
+    let Some(snapshot) = ctx.version_snapshot.take() else {
+        return Ok(());
+    };
+    if ctx.uploaded {
+        ctx.events.warn("⚠️ Version bump kept: the build already reached the store, which won't accept its build number again");
+        return Ok(());
+    }
+
+    let restored = {
+        let db = ctx.app.state::<DbState>();
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        snapshot.restore(&conn)
+    };
+    match restored {
+        Ok(()) => {
+            let (version, build_number) = if ctx.platform == "ios" {
+                (snapshot.project().ios.version.clone(), snapshot.project().ios.build_number)
             } else {
-                return Err(format!("No .xcworkspace or .xcodeproj file found in {:?}", ios_dir));
+                (snapshot.project().android.version.clone(), snapshot.project().android.version_code)
             };
+            ctx.events.log(&format!("↩️ Version bump rolled back to {} ({})", version, build_number));
+            ctx.events.version(&version, build_number);
+        }
+        Err(e) => ctx.events.error(&format!("❌ Could not roll back the version bump: {}", e)),
+    }
+    Ok(())
+}
 
-            let build_type = if workspace_path.is_some() { "-workspace" } else { "-project" };
-
-            let archive_cmd = format!(
-                "xcodebuild {} '{}' -scheme '{}' -sdk iphoneos -configuration '{}' archive -archivePath '{}' 2>&1",
-                build_type,
-                build_file_path.replace("'", "'\\''"),
-                scheme.replace("'", "'\\''"),
-                configuration.replace("'", "'\\''"),
-                archive_path_str.replace("'", "'\\''")
-            );
+fn generate_build_release_notes(ctx: &mut BuildContext) -> Result<(), String> {
+    let last_commit = {
+        let db = ctx.app.state::<DbState>();
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        last_built_commit(&conn, &ctx.project.id, &ctx.platform, ctx.options.profile.as_deref())?
+    };
 
-            let mut archive_child = Command::new("/bin/sh")
-                .args(&["-c", &archive_cmd])
-                .current_dir(&ios_dir)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .process_group(0) // own process group so killpg kills xcodebuild + clang/swiftc
-                .spawn()
-                .map_err(|e| format!("Failed to start archive command: {}", e))?;
-
-            // Take stdout ownership to read logs (now includes stderr due to 2>&1)
-            let archive_stdout = archive_child.stdout.take().unwrap();
-            let archive_reader = BufReader::new(archive_stdout);
-
-            // Store process for cancellation logic.
-            // We use Arc and Mutex to share ownership.
-            let process_id = format!("{}_{}", project.id, platform);
-            {
-                let mut processes = process_state.0.lock().unwrap();
-                processes.insert(process_id.clone(), Arc::new(std::sync::Mutex::new(Some(archive_child))));
+    match release_notes::generate(&ctx.project, &ctx.platform, last_commit.as_deref()) {
+        Ok(notes) if !notes.is_empty() => {
+            ctx.events.log("📝 Release notes generated from git history:");
+            for line in notes.lines() {
+                ctx.events.log(&format!("  {}", line));
             }
+            ctx.events.release_notes(&notes);
+            ctx.options.release_note = Some(notes);
+        }
+        Ok(_) => ctx.events.warn("⚠️ No new commits since the last build, release notes left empty"),
+        Err(e) => ctx.events.warn(&format!("⚠️ Could not generate release notes: {}", e)),
+    }
+    Ok(())
+}
 
-            // Collect some recent logs to show in case of failure
-            let mut recent_logs = std::collections::VecDeque::with_capacity(20);
+/// Whether the build uses a release configuration (as opposed to e.g. a Debug iOS configuration)
+fn is_release_build(ctx: &BuildContext) -> bool {
+    if ctx.platform == "ios" {
+        let configuration = ctx.project.ios.config.as_ref().map(|c| c.configuration.as_str()).unwrap_or("Release");
+        !configuration.eq_ignore_ascii_case("debug")
+    } else {
+        let profile_command = ctx.profile.as_ref().and_then(|p| p.android_build_command.as_deref());
+        !profile_command.or(ctx.project.android.build_command.as_deref()).unwrap_or_default().contains("Debug")
+    }
+}
 
-            // Write all logs to file, but only stream errors/warnings to UI to avoid lag
-            for line in archive_reader.lines() {
-                if let Ok(line_content) = line {
-                    writeln!(log_file, "{}", line_content).map_err(|e| e.to_string())?;
+/// Tag the built commit once the build has been published, when the project asks for it
+fn tag_release(ctx: &mut BuildContext) {
+    if !ctx.project.release.as_ref().is_some_and(|r| r.tag_after_upload) {
+        return;
+    }
+    let Some(git) = ctx.git.clone() else {
+        return;
+    };
 
-                    let lower_line = line_content.to_lowercase();
-                    if lower_line.contains("error:") || lower_line.contains("warning:") {
-                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": line_content })).map_err(|e| e.to_string())?;
-                    }
+    let (version, build_number) = if ctx.platform == "ios" {
+        (ctx.project.ios.version.clone(), ctx.project.ios.build_number)
+    } else {
+        (ctx.project.android.version.clone(), ctx.project.android.version_code)
+    };
+    let tag = release_tag_name(&ctx.platform, ctx.options.profile.as_deref(), &version, build_number);
+    let message = format!("{} {} ({}) {}", ctx.project.name, version, build_number, ctx.platform);
 
-                    if recent_logs.len() >= 20 {
-                        recent_logs.pop_front();
-                    }
-                    recent_logs.push_back(line_content);
-                }
-            }
+    match create_tag(std::path::Path::new(&ctx.project.path), &tag, &git.commit, &message) {
+        Ok(true) => ctx.events.log(&format!("🏷️ Tagged {} as {}", &git.commit[..git.commit.len().min(10)], tag)),
+        Ok(false) => {}
+        Err(e) => ctx.events.warn(&format!("⚠️ Could not create tag {}: {}", tag, e)),
+    }
+}
 
-            // Retrieve child process to wait for exit status
-            let archive_status = {
-                 let mut processes = process_state.0.lock().unwrap();
-                 if let Some(mutex) = processes.remove(&process_id) {
-                     let mut guard = mutex.lock().unwrap();
-                     if let Some(mut child) = guard.take() {
-                         child.wait().map_err(|e| e.to_string())?
-                     } else {
-                         // Process was already taken (should not happen in normal flow)
-                         return Err("Archive process handle lost".into());
-                     }
-                 } else {
-                     // If process is not in map, it was removed by cancel_build_process
-                     window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-                     window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-                     return Ok(());
-                 }
-            };
+fn android_prepare(ctx: &mut BuildContext) -> StepResult {
+    let start_msg = format!("🚀 Starting Android build for project: {}", ctx.project.name);
+    ctx.events.log(&start_msg);
 
-            if !archive_status.success() {
-                let error_header = "❌ Archive failed. Recent logs:";
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": error_header })).map_err(|e| e.to_string())?;
+    // Check if gradlew exists
+    let gradlew_path = ctx.platform_dir.join("gradlew");
+    if !gradlew_path.exists() {
+        let err_msg = format!("❌ gradlew not found at: {:?}", gradlew_path);
+        ctx.events.error(&err_msg);
+        return Err(StepError::Failed(err_msg));
+    }
 
-                for log in recent_logs {
-                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": format!("  {}", log) })).map_err(|e| e.to_string())?;
-                }
+    let path_msg = format!("📁 Android directory: {:?}", ctx.platform_dir);
+    ctx.events.log(&path_msg);
+    Ok(StepOutcome::Done)
+}
 
-                let final_err = "Check log file for full details.";
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": final_err })).map_err(|e| e.to_string())?;
-                window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-                window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap() })).map_err(|e| e.to_string())?;
-                return Err("Archive failed".into());
-            }
+fn android_format(ctx: &BuildContext) -> &'static str {
+    if ctx.options.android_format.as_deref() == Some("apk") { "apk" } else { "aab" }
+}
 
-            let success_msg = "✅ Archive completed successfully";
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": success_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", success_msg).map_err(|e| e.to_string())?;
-
-            // Step 2: Export
-            let export_msg = "📤 Starting export...";
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": export_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", export_msg).map_err(|e| e.to_string())?;
-
-            // Create or find export options plist
-            let export_plist_path = ios_dir.join("ExportOptions.plist");
-
-            // If team_id is provided, generate ExportOptions.plist automatically
-            if let Some(team_id) = project.ios.config.as_ref().and_then(|c| c.team_id.as_ref()) {
-                let msg = format!("🔧 Generating ExportOptions.plist with Team ID: {}", team_id);
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", msg).map_err(|e| e.to_string())?;
-
-                // Use export_method from config, or default to "development"
-                let export_method = project.ios.config.as_ref()
-                    .and_then(|c| c.export_method.as_ref())
-                    .map(|s| s.as_str())
-                    .unwrap_or("development");
-
-                let method_msg = format!("📦 Export method: {}", export_method);
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": method_msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", method_msg).map_err(|e| e.to_string())?;
-
-                let plist_content = format!(
-                    r#"<?xml version="1.0" encoding="UTF-8"?>
-<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
-<plist version="1.0">
-<dict>
-    <key>method</key>
-    <string>{}</string>
-    <key>teamID</key>
-    <string>{}</string>
-    <key>uploadSymbols</key>
-    <true/>
-    <key>compileBitcode</key>
-    <false/>
-    <key>signingStyle</key>
-    <string>automatic</string>
-    <key>stripSwiftSymbols</key>
-    <true/>
-</dict>
-</plist>"#,
-                    export_method, team_id
-                );
-
-                std::fs::write(&export_plist_path, plist_content)
-                    .map_err(|e| format!("Failed to write ExportOptions.plist: {}", e))?;
-            } else {
-                // Look for existing export options plist
-                let msg = "🔍 Looking for existing ExportOptions.plist...";
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": msg })).map_err(|e| e.to_string())?;
-                writeln!(log_file, "{}", msg).map_err(|e| e.to_string())?;
-
-                let export_plist_names: Vec<String> = vec![
-                    format!("{}ExportOptions.plist", configuration),
-                    "ExportOptions.plist".to_string(),
-                    "DevelopmentExportOptions.plist".to_string(),
-                    "ReleaseExportOptions.plist".to_string(),
-                ];
-
-                let found_plist = export_plist_names.iter()
-                    .map(|name| ios_dir.join(name))
-                    .find(|path| path.exists());
-
-                if found_plist.is_none() {
-                    return Err("Export options plist not found and no Team ID configured. Please either:\n1. Add Team ID in project settings, or\n2. Create an ExportOptions.plist file in the ios directory.".into());
-                }
+fn android_archive(ctx: &mut BuildContext) -> StepResult {
+    let format = android_format(ctx);
+    let flavor = ctx.profile.as_ref().and_then(|p| p.android_flavor.as_deref());
+    let default_cmd = format!("./gradlew {}", gradle_task(format, flavor));
+    // A flavor's task replaces the project's command, a profile's own command replaces both
+    let base_cmd = match (ctx.profile.as_ref().and_then(|p| p.android_build_command.as_deref()), flavor) {
+        (Some(command), _) => command,
+        (None, Some(_)) => default_cmd.as_str(),
+        (None, None) => ctx.project.android.build_command.as_deref().unwrap_or(&default_cmd),
+    };
+    let mut gradle_cmd = if format == "apk" {
+        base_cmd.replace("bundle", "assemble")
+    } else {
+        base_cmd.replace("assemble", "bundle")
+    };
+
+    // Sign with the project's keystore: the passwords reach Gradle through the environment
+    let mut env = Vec::new();
+    if let Some(signing) = ctx.project.android.config.as_ref().and_then(|c| c.signing.clone()) {
+        if !std::path::Path::new(&signing.keystore_path).exists() {
+            let err_msg = format!("❌ Keystore not found at: {}", signing.keystore_path);
+            ctx.events.error(&err_msg);
+            return Err(StepError::Failed(err_msg));
+        }
+        let passwords = match load_signing_passwords(&signing.id) {
+            Ok(passwords) => passwords,
+            Err(e) => {
+                let err_msg = format!("❌ {}. Enter the keystore passwords again in the project's Release Signing settings.", e);
+                ctx.events.error(&err_msg);
+                return Err(StepError::Failed(err_msg));
             }
+        };
+        ctx.events.log(&format!("🔏 Signing with {} (alias {})", signing.keystore_path, signing.key_alias));
+        gradle_cmd = format!("{} {}", gradle_cmd, injected_signing_args(&signing));
+        env = signing_env(&passwords);
+    }
 
-            let export_path = build_dir.to_str().ok_or("Invalid export path")?;
+    // Use /bin/sh -c with common paths for Node.js (Homebrew, NVM, etc.) and sourced shell profiles
+    let shell_command = format!(
+        r#"
+        # 1. Add standard paths explicitly (Homebrew, Local, System)
+        export PATH="/opt/homebrew/bin:/usr/local/bin:/usr/bin:/bin:/usr/sbin:/sbin:$PATH"
+
+        # 2. Source shell profiles (This handles most version managers like fnm, asdf, volta if configured in shell)
+        # We suppress output to avoid polluting logs
+        [ -f "$HOME/.zshrc" ] && source "$HOME/.zshrc" >/dev/null 2>&1 || true
+        [ -f "$HOME/.bashrc" ] && source "$HOME/.bashrc" >/dev/null 2>&1 || true
+        [ -f "$HOME/.bash_profile" ] && source "$HOME/.bash_profile" >/dev/null 2>&1 || true
+
+        # 3. Explicitly look for NVM if likely not loaded yet
+        if [ -d "$HOME/.nvm" ]; then
+            export NVM_DIR="$HOME/.nvm"
+            [ -s "$NVM_DIR/nvm.sh" ] && source "$NVM_DIR/nvm.sh" >/dev/null 2>&1 || true
+        fi
+
+        # 4. Fallback: Try to find common version managers if node is still missing
+        if ! command -v node >/dev/null 2>&1; then
+            # Check for Volta
+            if [ -d "$HOME/.volta" ]; then
+                export VOLTA_HOME="$HOME/.volta"
+                export PATH="$VOLTA_HOME/bin:$PATH"
+            fi
+            # Check for FNM (Fast Node Manager)
+            if [ -d "$HOME/Library/Application Support/fnm" ]; then
+                export PATH="$HOME/Library/Application Support/fnm:$PATH"
+                eval "`fnm env --use-on-cd 2>/dev/null`" || true
+            fi
+        fi
+
+        # Log environment for debugging
+        echo "🔍 Node path: $(which node 2>/dev/null || echo 'not found')"
+        echo "🔍 Node version: $(node -v 2>/dev/null || echo 'unknown')"
+        echo "🔍 Java path: $(which java 2>/dev/null || echo 'not found')"
+
+        cd '{}' && {} 2>&1
+        "#,
+        ctx.platform_dir.to_str().unwrap_or(""),
+        gradle_cmd
+    );
+
+    ctx.events.log("🔧 Running Android build with enhanced environment...");
+
+    let dir = ctx.platform_dir.clone();
+    let output = run_command_with_env(ctx, &shell_command, &dir, StreamMode::All, &env)?;
+    if !output.status.success() {
+        let exit_code = output.status.code().map(|c| c.to_string()).unwrap_or("unknown".to_string());
+        let error_msg = format!("❌ Build failed with exit code: {}", exit_code);
+        ctx.events.error(&error_msg);
+        return Err(StepError::Failed(error_msg));
+    }
 
-            let export_cmd = format!(
-                "xcodebuild -exportArchive -archivePath '{}' -exportOptionsPlist '{}' -exportPath '{}' 2>&1",
-                archive_path_str.replace("'", "'\\''"),
-                export_plist_path.to_str().ok_or("Invalid plist path")?.replace("'", "'\\''"),
-                export_path.replace("'", "'\\''")
-            );
+    ctx.events.log("✅ Android build completed successfully");
+    Ok(StepOutcome::Done)
+}
 
-            let mut export_child = Command::new("/bin/sh")
-                .args(&["-c", &export_cmd])
-                .current_dir(&ios_dir)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .process_group(0)
-                .spawn()
-                .map_err(|e| format!("Failed to start export command: {}", e))?;
+fn android_export(ctx: &mut BuildContext) -> StepResult {
+    let format = android_format(ctx);
 
-            let export_stdout = export_child.stdout.take().unwrap();
-            let export_reader = BufReader::new(export_stdout);
+    // Direct path to the built artifact
+    let flavor = ctx.profile.as_ref().and_then(|p| p.android_flavor.as_deref());
+    let artifact_file = android_output_path(&ctx.platform_dir, format, flavor);
 
-             // Store process again for cancellation
-            {
-                let mut processes = process_state.0.lock().unwrap();
-                processes.insert(process_id.clone(), Arc::new(std::sync::Mutex::new(Some(export_child))));
-            }
+    if !artifact_file.exists() {
+        // Warn that file was not found
+        let warn_msg = format!("⚠️ No {} file found at: {:?}", format.to_uppercase(), artifact_file);
+        ctx.events.warn(&warn_msg);
+        return Ok(StepOutcome::Skipped(warn_msg));
+    }
 
-            let mut recent_export_logs = std::collections::VecDeque::with_capacity(20);
+    let ts = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let app_name = match &ctx.profile {
+        Some(profile) => format!("{}_{}", ctx.project.name, profile.name),
+        None => ctx.project.name.clone(),
+    };
+    let new_filename = format!(
+        "{}_{}_{}_{}.{}",
+        app_name.replace(" ", "_"),
+        ctx.project.android.version,
+        ctx.project.android.version_code,
+        ts,
+        format
+    );
+    let dest_path = artifact_file.parent().unwrap().join(&new_filename);
+    std::fs::rename(&artifact_file, &dest_path)
+        .map_err(|e| format!("Failed to rename {} file: {}", format.to_uppercase(), e))?;
+
+    let rename_msg = format!("✅ {} renamed to: {}", format.to_uppercase(), new_filename);
+    ctx.events.log(&rename_msg);
+
+    // Emit artifact path
+    ctx.events.artifact(&dest_path);
+    ctx.artifact_path = Some(dest_path);
+    Ok(StepOutcome::Done)
+}
 
-            // Write all logs to file, but only stream errors/warnings to UI
-            for line in export_reader.lines() {
-                if let Ok(line_content) = line {
-                    writeln!(log_file, "{}", line_content).map_err(|e| e.to_string())?;
+/// Report which key signed the exported artifact
+fn android_sign(ctx: &mut BuildContext) -> StepResult {
+    let Some(artifact) = ctx.artifact_path.clone() else {
+        return Ok(StepOutcome::Skipped("No artifact to check the signature of".to_string()));
+    };
+    match signer_certificate(&artifact) {
+        Ok(Some(signer)) => {
+            ctx.events.log(&format!("🔏 Signed ({}) by certificate SHA-256: {}", signer.scheme, signer.sha256));
+            Ok(StepOutcome::Done)
+        }
+        Ok(None) => {
+            let warn_msg = "⚠️ The artifact is not signed. Attach a keystore in the project's Release Signing settings or add a signingConfig to build.gradle";
+            ctx.events.warn(warn_msg);
+            Ok(StepOutcome::Skipped(warn_msg.to_string()))
+        }
+        // The fingerprint is informational, the artifact itself is fine
+        Err(e) => {
+            let warn_msg = format!("⚠️ Could not read the signing certificate: {}", e);
+            ctx.events.warn(&warn_msg);
+            Ok(StepOutcome::Skipped(warn_msg))
+        }
+    }
+}
 
-                    let lower_line = line_content.to_lowercase();
-                    if lower_line.contains("error:") || lower_line.contains("warning:") {
-                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": line_content })).map_err(|e| e.to_string())?;
-                    }
+/// Publish the AAB to a Google Play track with the project's service account
+async fn android_upload(ctx: &mut BuildContext<'_>) -> StepResult {
+    let Some(artifact) = ctx.artifact_path.clone() else {
+        let err_msg = "❌ Google Play upload requested but no artifact was built";
+        ctx.events.error(err_msg);
+        return Err(StepError::Failed(err_msg.to_string()));
+    };
+    if artifact.extension().is_none_or(|e| e != "aab") {
+        let err_msg = "❌ Google Play only accepts Android App Bundles, build with the AAB format to upload";
+        ctx.events.error(err_msg);
+        return Err(StepError::Failed(err_msg.to_string()));
+    }
+    let Some(credential_id) = ctx.project.credentials.android_id.clone() else {
+        let err_msg = "❌ Google Play upload requires an Android credential with a service account key";
+        ctx.events.error(err_msg);
+        return Err(StepError::Failed(err_msg.to_string()));
+    };
 
-                    if recent_export_logs.len() >= 20 {
-                        recent_export_logs.pop_front();
-                    }
-                    recent_export_logs.push_back(line_content);
-                }
-            }
+    let config = ctx.project.android.config.clone();
+    let track = ctx
+        .options
+        .play_track
+        .clone()
+        .or_else(|| config.as_ref().and_then(|c| c.play_track.clone()))
+        .unwrap_or_else(|| "internal".to_string());
+    let status = config
+        .as_ref()
+        .and_then(|c| c.play_release_status.clone())
+        .unwrap_or_else(|| "completed".to_string());
+    let locales: Vec<String> = config
+        .as_ref()
+        .and_then(|c| c.play_locales.clone())
+        .unwrap_or_else(|| "en-US".to_string())
+        .split(',')
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let release_notes = google_play::release_notes_for(&locales, ctx.options.release_note.as_deref().unwrap_or_default());
+
+    ctx.events.log(&format!("📤 Uploading to Google Play ({} track)...", track));
+    let package_name = ctx.bundle_id();
+    let release = PlayRelease {
+        package_name: &package_name,
+        bundle_path: &artifact,
+        track: &track,
+        status: &status,
+        release_notes,
+    };
+    let published = async {
+        let key = ServiceAccountKey::load(&credential_id)?;
+        let client = PlayClient::authenticate(&key, google_play::ANDROID_PUBLISHER_URL).await?;
+        client.publish(&release).await
+    }
+    .await;
+
+    match published {
+        Ok(version_code) => {
+            ctx.uploaded = true;
+            ctx.events.log(&format!("✅ Version code {} published to the {} track on Google Play!", version_code, track));
+            tag_release(ctx);
+            Ok(StepOutcome::Done)
+        }
+        Err(e) => {
+            let err_msg = format!("❌ Google Play upload failed: {}", e);
+            ctx.events.error(&err_msg);
+            Err(StepError::Failed(err_msg))
+        }
+    }
+}
 
-            let export_status = {
-                 let mut processes = process_state.0.lock().unwrap();
-                 if let Some(mutex) = processes.remove(&process_id) {
-                     let mut guard = mutex.lock().unwrap();
-                     if let Some(mut child) = guard.take() {
-                         child.wait().map_err(|e| e.to_string())?
-                     } else {
-                         return Err("Export process handle lost".into());
-                     }
-                 } else {
-                     window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-                     window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-                     return Ok(());
-                 }
-            };
+/// How often and how long `distribute` waits for Firebase to process the binary
+const FIREBASE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const FIREBASE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
 
-            if !export_status.success() {
-                let error_header = "❌ Export failed. Recent logs:";
-                window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": error_header })).map_err(|e| e.to_string())?;
+/// Send the APK, AAB or IPA to testers with Firebase App Distribution
+async fn distribute(ctx: &mut BuildContext<'_>) -> StepResult {
+    let (app_id, groups, testers) = match ctx.platform.as_str() {
+        "ios" => {
+            let config = ctx.project.ios.config.as_ref();
+            (
+                config.and_then(|c| c.firebase_app_id.clone()),
+                split_list(config.and_then(|c| c.distribution_groups.as_deref())),
+                split_list(config.and_then(|c| c.testers.as_deref())),
+            )
+        }
+        _ => {
+            let config = ctx.project.android.config.as_ref();
+            (
+                config.and_then(|c| c.firebase_app_id.clone()),
+                split_list(config.and_then(|c| c.distribution_groups.as_deref())),
+                split_list(config.and_then(|c| c.testers.as_deref())),
+            )
+        }
+    };
+    let Some(artifact) = ctx.artifact_path.clone() else {
+        let warn = "⚠️ App Distribution enabled but no artifact was built";
+        ctx.events.warn(warn);
+        return Ok(StepOutcome::Skipped(warn.to_string()));
+    };
+    let Some(app_id) = app_id else {
+        let warn = "⚠️ App Distribution enabled but Firebase App ID not configured";
+        ctx.events.warn(warn);
+        return Ok(StepOutcome::Skipped(warn.to_string()));
+    };
+    // The service account of the project's Android credential also serves Firebase
+    let Some(credential_id) = ctx.project.credentials.android_id.clone() else {
+        let err_msg = "❌ App Distribution requires a Google service account, select an Android credential for the project";
+        ctx.events.error(err_msg);
+        return Err(StepError::Failed(err_msg.to_string()));
+    };
 
-                for log in recent_export_logs {
-                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": format!("  {}", log) })).map_err(|e| e.to_string())?;
-                }
+    ctx.events.log("📤 Uploading to Firebase App Distribution...");
+    match distribute_with_firebase(ctx, &credential_id, &app_id, &artifact, &groups, &testers).await {
+        Ok(()) => {
+            ctx.events.log("✅ Successfully uploaded to Firebase App Distribution!");
+            tag_release(ctx);
+            Ok(StepOutcome::Done)
+        }
+        Err(e) => {
+            let upload_fail = format!("❌ Firebase upload failed: {}", e);
+            ctx.events.error(&upload_fail);
+            Err(StepError::Failed(upload_fail))
+        }
+    }
+}
 
-                window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-                window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap() })).map_err(|e| e.to_string())?;
-                return Err("Export failed".into());
-            }
+async fn distribute_with_firebase(
+    ctx: &mut BuildContext<'_>,
+    credential_id: &str,
+    app_id: &str,
+    artifact: &std::path::Path,
+    groups: &[String],
+    testers: &[String],
+) -> Result<(), String> {
+    let key = ServiceAccountKey::load(credential_id)?;
+    let client = AppDistributionClient::authenticate(&key, firebase_distribution::FIREBASE_APP_DISTRIBUTION_URL).await?;
 
-            let final_msg = "✅ Export completed successfully";
-            window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": final_msg })).map_err(|e| e.to_string())?;
-            writeln!(log_file, "{}", final_msg).map_err(|e| e.to_string())?;
-
-            // Step 3: Upload to App Store (Optional)
-            if let Some(true) = options.and_then(|o| o.upload_to_app_store) {
-                 if let (Some(api_key), Some(api_issuer)) = (
-                    project.ios.config.as_ref().and_then(|c| c.api_key.as_ref()),
-                    project.ios.config.as_ref().and_then(|c| c.api_issuer.as_ref())
-                 ) {
-                    let upload_msg = "🚀 Starting upload to App Store...";
-                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": upload_msg })).map_err(|e| e.to_string())?;
-                    writeln!(log_file, "{}", upload_msg).map_err(|e| e.to_string())?;
-
-                    // Find IPA file
-                    let mut ipa_path = None;
-                    if let Ok(entries) = std::fs::read_dir(&build_dir) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                             if path.extension().and_then(|s| s.to_str()) == Some("ipa") {
-                                ipa_path = Some(path);
-                                break;
-                            }
-                        }
-                    }
-
-                     if let Some(ipa_file) = ipa_path {
-                         let ipa_path_str = ipa_file.to_str().unwrap();
-                         let file_msg = format!("Found IPA: {}", ipa_path_str);
-                         window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": file_msg })).map_err(|e| e.to_string())?;
-                         writeln!(log_file, "{}", file_msg).map_err(|e| e.to_string())?;
-
-                         let upload_cmd = format!(
-                            "xcrun altool --upload-app --type ios --file '{}' --apiKey '{}' --apiIssuer '{}' 2>&1",
-                            ipa_path_str.replace("'", "'\\''"),
-                            api_key.replace("'", "'\\''"),
-                            api_issuer.replace("'", "'\\''")
-                         );
-
-                         let mut upload_child = Command::new("/bin/sh")
-                            .args(&["-c", &upload_cmd])
-                            .stdout(Stdio::piped())
-                            .stderr(Stdio::piped())
-                            .spawn()
-                            .map_err(|e| format!("Failed to start upload: {}", e))?;
-
-                        let upload_stdout = upload_child.stdout.take().unwrap();
-                        let upload_reader = BufReader::new(upload_stdout);
-                        let mut recent_upload_logs = std::collections::VecDeque::with_capacity(20);
-
-                        // Store process for cancellation
-                        // Re-use the same composite key for the upload phase
-                        {
-                            let mut processes = process_state.0.lock().unwrap();
-                            processes.insert(process_id.clone(), Arc::new(std::sync::Mutex::new(Some(upload_child))));
-                        }
-
-                        for line in upload_reader.lines() {
-                            if let Ok(line_content) = line {
-                                writeln!(log_file, "{}", line_content).map_err(|e| e.to_string())?;
-
-                                let lower_line = line_content.to_lowercase();
-                                if lower_line.contains("error:") || lower_line.contains("warning:") {
-                                    window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": line_content })).map_err(|e| e.to_string())?;
-                                }
-
-                                if recent_upload_logs.len() >= 20 {
-                                    recent_upload_logs.pop_front();
-                                }
-                                recent_upload_logs.push_back(line_content);
-                            }
-                        }
-
-                         let upload_status = {
-                             let mut processes = process_state.0.lock().unwrap();
-                             if let Some(mutex) = processes.remove(&process_id) {
-                                 let mut guard = mutex.lock().unwrap();
-                                 if let Some(mut child) = guard.take() {
-                                     child.wait().map_err(|e| e.to_string())?
-                                 } else {
-                                     return Err("Upload process handle lost".into());
-                                 }
-                             } else {
-                                 window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-                                 window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-                                 return Ok(());
-                             }
-                         };
-
-                         if !upload_status.success() {
-                             let err_header = "❌ App Store upload failed. Recent logs:";
-                             window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": err_header })).map_err(|e| e.to_string())?;
-
-                             for log in recent_upload_logs {
-                                 window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": format!("  {}", log) })).map_err(|e| e.to_string())?;
-                             }
-
-                             let footer = "Please check the log file for full details and Apple's specific error code.";
-                             window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": footer })).map_err(|e| e.to_string())?;
-                             window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
-
-                             // Send Slack notification on failure
-                             let slack_msg = format!("❌ *{}* (iOS) App Store upload failed!\nVersion: {}\nBuild: {}",
-                                 project.name, project.ios.version, project.ios.build_number);
-                             let _ = send_all_notifications(&project, &slack_msg).await;
-
-                             return Err("Upload failed".into());
-                         } else {
-                             let succ = "✅ App Store upload completed successfully! Your app is now being processed on App Store Connect.";
-                             window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": succ })).map_err(|e| e.to_string())?;
-                             writeln!(log_file, "{}", succ).map_err(|e| e.to_string())?;
-
-                             // Send Slack notification
-                             let slack_msg = format!("🚀 *{}* (iOS) uploaded to App Store successfully!\nVersion: {}\nBuild: {}",
-                                 project.name, project.ios.version, project.ios.build_number);
-                             let _ = send_all_notifications(&project, &slack_msg).await;
-                         }
-
-                     } else {
-                        let err = "❌ IPA file not found for upload";
-                        window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": err })).map_err(|e| e.to_string())?;
-                        writeln!(log_file, "{}", err).map_err(|e| e.to_string())?;
-                    }
-                 } else {
-                     let msg = "⚠️ Upload requested but API Key or Issuer missing in project settings.";
-                     window.emit("build-log", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": msg })).map_err(|e| e.to_string())?;
-                     writeln!(log_file, "{}", msg).map_err(|e| e.to_string())?;
-                 }
-            }
+    let operation = client.upload(app_id, artifact).await?;
+    ctx.events.log("  ⏳ Waiting for Firebase to process the binary...");
+    let Some(uploaded) = client.wait_for_release(&operation, FIREBASE_POLL_INTERVAL, FIREBASE_TIMEOUT).await? else {
+        return Err(format!("the binary was still processing after {} minutes", FIREBASE_TIMEOUT.as_secs() / 60));
+    };
+    let what = match uploaded.result.as_str() {
+        "RELEASE_UPDATED" => "updated",
+        "RELEASE_UNMODIFIED" => "was already uploaded",
+        _ => "created",
+    };
+    ctx.events.log(&format!(
+        "  📦 Release {} ({}) {}",
+        uploaded.release.display_version, uploaded.release.build_version, what
+    ));
+
+    let notes = ctx.options.release_note.clone().unwrap_or_default();
+    if !notes.trim().is_empty() {
+        client.set_release_notes(&uploaded.release.name, &notes).await?;
+        ctx.events.log("  📝 Release notes set");
+    }
 
-            // Emit log file path for frontend to save
-            window.emit("build-log-file", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": log_file_path.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
-
-            // Find IPA file for artifact path
-            let mut ipa_path = None;
-            if let Ok(entries) = std::fs::read_dir(&build_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                     if path.extension().and_then(|s| s.to_str()) == Some("ipa") {
-                        ipa_path = Some(path);
-                        break;
-                    }
+    if groups.is_empty() && testers.is_empty() {
+        ctx.events.warn("⚠️ No distribution groups or testers configured, the release was uploaded but not distributed");
+        return Ok(());
+    }
+    client.distribute(&uploaded.release.name, testers, groups).await?;
+    let mut recipients = Vec::new();
+    if !groups.is_empty() {
+        recipients.push(format!("groups {}", groups.join(", ")));
+    }
+    if !testers.is_empty() {
+        recipients.push(format!("{} tester(s)", testers.len()));
+    }
+    ctx.events.log(&format!("  👥 Distributed to {}", recipients.join(" and ")));
+    Ok(())
+}
+
+fn ios_prepare(ctx: &mut BuildContext) -> StepResult {
+    let ios_dir = ctx.platform_dir.clone();
+
+    // Uses scheme and configuration from project settings or falls back to defaults
+    ctx.ios.scheme = ctx.project.ios.config.as_ref().map(|c| c.scheme.clone()).unwrap_or(ctx.project.name.clone());
+    ctx.ios.configuration = ctx.project.ios.config.as_ref().map(|c| c.configuration.clone()).unwrap_or("Release".to_string());
+
+    // Find workspace or project file in ios directory
+    let mut workspace_path = None;
+    let mut project_path = None;
+
+    if let Ok(entries) = std::fs::read_dir(&ios_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if let Some(ext) = path.extension() {
+                if ext == "xcworkspace" {
+                    workspace_path = Some(path);
+                    break; // Prefer workspace over project
+                } else if ext == "xcodeproj" && project_path.is_none() {
+                    project_path = Some(path);
                 }
             }
-            if let Some(ipa) = ipa_path {
-                window.emit("build-artifact-path", serde_json::json!({ "projectId": project.id, "platform": "ios", "payload": ipa.to_str().unwrap_or_default() })).map_err(|e| e.to_string())?;
+        }
+    }
+
+    ctx.ios.xcode_target = if let Some(ws_path) = workspace_path {
+        let msg = format!("🔍 Found workspace: {}", ws_path.file_name().unwrap().to_string_lossy());
+        ctx.events.log(&msg);
+        Some(("-workspace", ws_path))
+    } else if let Some(proj_path) = project_path {
+        let msg = format!("🔍 Found project: {}", proj_path.file_name().unwrap().to_string_lossy());
+        ctx.events.log(&msg);
+        Some(("-project", proj_path))
+    } else {
+        return Err(StepError::Failed(format!("No .xcworkspace or .xcodeproj file found in {:?}", ios_dir)));
+    };
+
+    ctx.ios.archive_path = ios_dir.join("build").join(format!("{}.xcarchive", &ctx.project.name));
+    ctx.ios.export_options_path = ios_dir.join("ExportOptions.plist");
+    check_provisioning(ctx)?;
+    Ok(StepOutcome::Done)
+}
+
+/// Look for a provisioning profile matching the bundle ID and export method before archiving,
+/// since xcodebuild only reports a bad one at the end of a long export. Targets with a profile
+/// mapped in the export options are checked against that profile only.
+fn check_provisioning(ctx: &mut BuildContext) -> StepResult {
+    let config = ctx.project.ios.config.as_ref();
+    let export_method = config.and_then(|c| c.export_method.clone()).unwrap_or("development".to_string());
+    let team_id = config.and_then(|c| c.team_id.clone());
+    let mut targets: Vec<(String, Option<String>)> = config
+        .and_then(|c| c.export_options.as_ref())
+        .map(|o| o.provisioning_profiles.iter().map(|(bundle_id, profile)| (bundle_id.clone(), Some(profile.clone()))).collect())
+        .unwrap_or_default();
+    let app_bundle_id = ctx.bundle_id();
+    if !targets.iter().any(|(bundle_id, _)| *bundle_id == app_bundle_id) {
+        targets.insert(0, (app_bundle_id, None));
+    }
+
+    let profiles = load_installed_profiles(chrono::Utc::now());
+    for (bundle_id, mapped) in targets {
+        let candidates: Vec<_> = match &mapped {
+            Some(name) => profiles.iter().filter(|p| p.name == *name || p.uuid == *name).cloned().collect(),
+            None => profiles.clone(),
+        };
+        let check = match (&mapped, candidates.is_empty()) {
+            (Some(name), true) => ProvisioningCheck { profile: None, problems: vec![format!("\"{}\" is not installed", name)] },
+            _ => match_profile(&candidates, &bundle_id, &export_method, team_id.as_deref()),
+        };
+
+        if let Some(profile) = check.profile {
+            ctx.events.log(&format!("✅ Provisioning profile \"{}\" ({}) for {} expires {}", profile.name, profile.profile_type, bundle_id, profile.expires));
+        } else {
+            ctx.events.warn(&format!("⚠️ No usable provisioning profile for {} ({} export):", bundle_id, export_method));
+            for problem in &check.problems {
+                ctx.events.warn(&format!("   • {}", problem));
             }
+        }
+    }
+    Ok(StepOutcome::Done)
+}
 
-            window.emit("build-status", serde_json::json!({ "status": "success", "projectId": project.id, "platform": "ios" })).map_err(|e| e.to_string())?;
+/// Log the recent output of a failed xcodebuild command
+fn log_recent_output(ctx: &mut BuildContext, header: &str, recent: &std::collections::VecDeque<String>, footer: &str) {
+    ctx.events.error(header);
+    for log in recent {
+        ctx.events.log(&format!("  {}", log));
+    }
+    ctx.events.log(footer);
+}
 
-            return Ok(());
-        },
-        _ => {
-            window.emit("build-status", serde_json::json!({ "status": "failed", "projectId": project.id, "platform": &platform })).map_err(|e| e.to_string())?;
-            return Err(format!("Unsupported platform: {}", platform));
+fn ios_archive(ctx: &mut BuildContext) -> StepResult {
+    let (build_type, build_file_path) = ctx.ios.xcode_target.clone().ok_or("Xcode project not resolved")?;
+    let archive_path_str = ctx.ios.archive_path.to_str().ok_or("Invalid archive path")?.to_string();
+
+    let archive_msg = format!("📦 Starting iOS archive for scheme: {}", ctx.ios.scheme);
+    ctx.events.log(&archive_msg);
+
+    let archive_cmd = format!(
+        "xcodebuild {} '{}' -scheme '{}' -sdk iphoneos -configuration '{}' archive -archivePath '{}' 2>&1",
+        build_type,
+        shell_quote(build_file_path.to_str().ok_or("Invalid project path")?),
+        shell_quote(&ctx.ios.scheme),
+        shell_quote(&ctx.ios.configuration),
+        shell_quote(&archive_path_str)
+    );
+
+    // Write all logs to file, but only stream errors/warnings to UI to avoid lag
+    let dir = ctx.platform_dir.clone();
+    let output = run_command(ctx, &archive_cmd, &dir, StreamMode::Problems)?;
+    if !output.status.success() {
+        log_recent_output(ctx, "❌ Archive failed. Recent logs:", &output.recent, "Check log file for full details.");
+        return Err(StepError::Failed("Archive failed".to_string()));
+    }
+
+    ctx.events.log("✅ Archive completed successfully");
+    Ok(StepOutcome::Done)
+}
+
+/// Generate (or locate) the ExportOptions.plist that controls how the archive is signed on export
+fn ios_sign(ctx: &mut BuildContext) -> StepResult {
+    // If team_id is provided, generate ExportOptions.plist automatically
+    if let Some(team_id) = ctx.project.ios.config.as_ref().and_then(|c| c.team_id.clone()) {
+        let msg = format!("🔧 Generating ExportOptions.plist with Team ID: {}", team_id);
+        ctx.events.log(&msg);
+
+        // Use export_method from config, or default to "development"
+        let export_method = ctx.project.ios.config.as_ref()
+            .and_then(|c| c.export_method.clone())
+            .unwrap_or("development".to_string());
+
+        let method_msg = format!("📦 Export method: {}", export_method);
+        ctx.events.log(&method_msg);
+
+        let options = ctx.project.ios.config.as_ref().and_then(|c| c.export_options.clone()).unwrap_or_default();
+        for (bundle_id, profile) in &options.provisioning_profiles {
+            ctx.events.log(&format!("📜 {} → {}", bundle_id, profile));
+        }
+        if let Some(certificate) = &options.signing_certificate {
+            ctx.events.log(&format!("🔏 Signing certificate: {}", certificate));
+        }
+
+        write_export_options(&ctx.ios.export_options_path, &export_method, &team_id, &options)?;
+        return Ok(StepOutcome::Done);
+    }
+
+    // Look for existing export options plist
+    ctx.events.log("🔍 Looking for existing ExportOptions.plist...");
+
+    let export_plist_names: Vec<String> = vec![
+        format!("{}ExportOptions.plist", ctx.ios.configuration),
+        "ExportOptions.plist".to_string(),
+        "DevelopmentExportOptions.plist".to_string(),
+        "ReleaseExportOptions.plist".to_string(),
+    ];
+
+    let found_plist = export_plist_names.iter()
+        .map(|name| ctx.platform_dir.join(name))
+        .find(|path| path.exists());
+
+    match found_plist {
+        Some(path) => {
+            ctx.events.log(&format!("🔍 Using {}", path.file_name().unwrap_or_default().to_string_lossy()));
+            ctx.ios.export_options_path = path;
+            Ok(StepOutcome::Done)
         }
+        None => Err(StepError::Failed("Export options plist not found and no Team ID configured. Please either:\n1. Add Team ID in project settings, or\n2. Create an ExportOptions.plist file in the ios directory.".to_string())),
+    }
+}
+
+fn find_ipa(build_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    std::fs::read_dir(build_dir).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().and_then(|s| s.to_str()) == Some("ipa"))
+}
+
+fn ios_export(ctx: &mut BuildContext) -> StepResult {
+    ctx.events.log("📤 Starting export...");
+
+    let build_dir = ctx.platform_dir.join("build");
+    let export_cmd = format!(
+        "xcodebuild -exportArchive -archivePath '{}' -exportOptionsPlist '{}' -exportPath '{}' 2>&1",
+        shell_quote(ctx.ios.archive_path.to_str().ok_or("Invalid archive path")?),
+        shell_quote(ctx.ios.export_options_path.to_str().ok_or("Invalid plist path")?),
+        shell_quote(build_dir.to_str().ok_or("Invalid export path")?)
+    );
+
+    let dir = ctx.platform_dir.clone();
+    let output = run_command(ctx, &export_cmd, &dir, StreamMode::Problems)?;
+    if !output.status.success() {
+        log_recent_output(ctx, "❌ Export failed. Recent logs:", &output.recent, "Check log file for full details.");
+        return Err(StepError::Failed("Export failed".to_string()));
+    }
+
+    ctx.events.log("✅ Export completed successfully");
+
+    // Find IPA file for artifact path
+    if let Some(ipa) = find_ipa(&build_dir) {
+        ctx.events.artifact(&ipa);
+        ctx.artifact_path = Some(ipa);
+    }
+    Ok(StepOutcome::Done)
+}
+
+/// Analyze the exported artifact and compare it to the stored size of the previous successful
+/// build and to the project's size budgets. The report is saved with the build once it succeeds.
+async fn measure_artifact(ctx: &mut BuildContext<'_>) -> StepResult {
+    let Some(artifact) = ctx.artifact_path.clone() else {
+        return Ok(StepOutcome::Skipped("No artifact to measure".to_string()));
+    };
+    let report = match analyze_artifact(&artifact) {
+        Ok(report) => report,
+        Err(e) => {
+            let warn_msg = format!("⚠️ Could not measure the artifact: {}", e);
+            ctx.events.warn(&warn_msg);
+            return Ok(StepOutcome::Skipped(warn_msg));
+        }
+    };
+
+    let previous = {
+        let db = ctx.app.state::<DbState>();
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        previous_build_size(&conn, &ctx.project.id, &ctx.platform, ctx.options.profile.as_deref(), &report.file_type)?
+    };
+    let growth = previous
+        .as_ref()
+        .map(|p| format!(" ({} since the previous build)", format_delta(report.total_size as i64 - p.total_size as i64)))
+        .unwrap_or_default();
+    ctx.events.log(&format!("📏 Artifact size: {}{}", format_size(report.total_size), growth));
+
+    let result = match ctx.project.size_budgets.clone() {
+        Some(budgets) => check_size_budgets(ctx, &report, previous.as_ref(), &budgets).await,
+        None => Ok(StepOutcome::Done),
+    };
+    ctx.size_report = Some(report);
+    result
+}
+
+/// Check the artifact's report against the budgets. Exceeded budgets are sent to the notification
+/// channels and warned about, or fail the build when the budgets say so.
+async fn check_size_budgets(
+    ctx: &mut BuildContext<'_>,
+    report: &AppSizeReport,
+    previous: Option<&AppSizeReport>,
+    budgets: &SizeBudgets,
+) -> StepResult {
+    let violations = check_budgets(report, budgets, previous);
+    if violations.is_empty() {
+        ctx.events.log("✅ Within the size budgets");
+        return Ok(StepOutcome::Done);
+    }
+    for violation in &violations {
+        if budgets.fail_build {
+            ctx.events.error(&format!("❌ {}", violation));
+        } else {
+            ctx.events.warn(&format!("⚠️ {}", violation));
+        }
+    }
+
+    let (platform, version, build) = if ctx.platform == "android" {
+        ("Android", ctx.project.android.version.clone(), ctx.project.android.version_code)
+    } else {
+        ("iOS", ctx.project.ios.version.clone(), ctx.project.ios.build_number)
+    };
+    let message = format!(
+        "{} *{}* ({}) is over its size budget\nVersion: {}\nBuild: {}\n{}",
+        if budgets.fail_build { "❌" } else { "⚠️" },
+        ctx.project.name,
+        platform,
+        version,
+        build,
+        violations.iter().map(|v| format!("• {}", v)).collect::<Vec<_>>().join("\n")
+    );
+    if let Err(e) = send_all_notifications(&ctx.project, &message).await {
+        ctx.events.warn(&format!("⚠️ Failed to send the size budget notification: {}", e));
     }
+
+    if budgets.fail_build {
+        return Err(StepError::Failed("Size budget exceeded".to_string()));
+    }
+    Ok(StepOutcome::Done)
+}
+
+/// How often and how long `ios_upload` checks on App Store Connect's processing
+const PROCESSING_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+const PROCESSING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(45 * 60);
+const WHAT_TO_TEST_LOCALE: &str = "en-US";
+
+/// Upload the IPA through the App Store Connect API, wait for Apple to process it, then set
+/// the TestFlight "What to Test" notes and add it to the project's beta groups
+async fn ios_upload(ctx: &mut BuildContext<'_>) -> StepResult {
+    let (Some(api_key), Some(api_issuer)) = (
+        ctx.project.ios.config.as_ref().and_then(|c| c.api_key.clone()),
+        ctx.project.ios.config.as_ref().and_then(|c| c.api_issuer.clone()),
+    ) else {
+        let msg = "⚠️ Upload requested but API Key or Issuer missing in project settings.";
+        ctx.events.warn(msg);
+        return Ok(StepOutcome::Skipped(msg.to_string()));
+    };
+    let Some(credential_id) = ctx.project.credentials.ios_id.clone() else {
+        let err_msg = "❌ App Store upload requires an iOS credential with its API key (.p8)";
+        ctx.events.error(err_msg);
+        return Err(StepError::Failed(err_msg.to_string()));
+    };
+
+    ctx.events.log("🚀 Starting upload to App Store Connect...");
+
+    let Some(ipa_file) = ctx.artifact_path.clone() else {
+        let err = "❌ IPA file not found for upload";
+        ctx.events.error(err);
+        return Err(StepError::Failed(err.to_string()));
+    };
+    ctx.events.log(&format!("Found IPA: {}", ipa_file.display()));
+
+    if let Err(e) = upload_to_app_store_connect(ctx, &credential_id, &api_key, &api_issuer, &ipa_file).await {
+        let err_msg = format!("❌ App Store upload failed: {}", e);
+        ctx.events.error(&err_msg);
+
+        // Send notification on failure
+        let message = format!("❌ *{}* (iOS) App Store upload failed!\nVersion: {}\nBuild: {}",
+            ctx.project.name, ctx.project.ios.version, ctx.project.ios.build_number);
+        let _ = send_all_notifications(&ctx.project, &message).await;
+
+        return Err(StepError::Failed(err_msg));
+    }
+
+    tag_release(ctx);
+    Ok(StepOutcome::Done)
+}
+
+async fn upload_to_app_store_connect(
+    ctx: &mut BuildContext<'_>,
+    credential_id: &str,
+    api_key: &str,
+    api_issuer: &str,
+    ipa_file: &std::path::Path,
+) -> Result<(), String> {
+    let key = ApiKey::load(credential_id, api_key, api_issuer)?;
+    let client = AppStoreConnectClient::new(key, app_store_connect::APP_STORE_CONNECT_URL);
+    let version = ctx.project.ios.version.clone();
+    let build_number = ctx.project.ios.build_number.to_string();
+
+    let app_id = client.find_app(&ctx.bundle_id()).await?;
+
+    // The upload takes the first half of the step's share of the progress bar, processing the rest
+    let (start, middle) = (ctx.step_fraction(0.0), ctx.step_fraction(0.5));
+    let events = &mut ctx.events;
+    let mut last_percent = None;
+    let upload = client
+        .upload_build(&app_id, ipa_file, &version, &build_number, |sent, total| {
+            let percent = (sent * 100).checked_div(total).unwrap_or(100);
+            if last_percent == Some(percent) {
+                return;
+            }
+            last_percent = Some(percent);
+            let fraction = start + (middle - start) * percent as f64 / 100.0;
+            events.progress(fraction, &format!("Uploading to App Store Connect ({}%)", percent));
+        })
+        .await?;
+    ctx.uploaded = true;
+    ctx.events.log("✅ IPA uploaded, waiting for App Store Connect to process the build...");
+    ctx.events.progress(middle, "Processing on App Store Connect");
+
+    let events = &mut ctx.events;
+    let build = client
+        .wait_for_processing(&upload, PROCESSING_POLL_INTERVAL, PROCESSING_TIMEOUT, |state| {
+            events.log(&format!("  ⏳ Processing state: {}", state));
+        })
+        .await?;
+    let Some(build) = build else {
+        ctx.events.warn(&format!(
+            "⚠️ The build is still processing after {} minutes, TestFlight notes and groups were not set. Check App Store Connect.",
+            PROCESSING_TIMEOUT.as_secs() / 60
+        ));
+        return Ok(());
+    };
+    ctx.events.log(&format!("✅ Build {} ({}) processed and ready for TestFlight", version, build_number));
+
+    let notes = ctx.options.release_note.clone().unwrap_or_default();
+    if !notes.trim().is_empty() {
+        client.set_what_to_test(&build.id, WHAT_TO_TEST_LOCALE, &notes).await?;
+        ctx.events.log("📝 TestFlight \"What to Test\" notes set");
+    }
+
+    let groups: Vec<String> = ctx
+        .project
+        .ios
+        .config
+        .as_ref()
+        .and_then(|c| c.beta_groups.clone())
+        .unwrap_or_default()
+        .split(',')
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty())
+        .collect();
+    if !groups.is_empty() {
+        client.add_to_beta_groups(&app_id, &build.id, &groups).await?;
+        ctx.events.log(&format!("👥 Added to TestFlight groups: {}", groups.join(", ")));
+    }
+    Ok(())
+}
+
+/// Notify the configured channels when the build was published somewhere
+async fn notify(ctx: &mut BuildContext<'_>) -> StepResult {
+    let message = if ctx.step_succeeded(StepName::Upload) && ctx.platform == "android" {
+        format!("🚀 *{}* (Android) published to Google Play!\nVersion: {}\nBuild: {}",
+            ctx.project.name, ctx.project.android.version, ctx.project.android.version_code)
+    } else if ctx.step_succeeded(StepName::Upload) {
+        format!("🚀 *{}* (iOS) uploaded to App Store successfully!\nVersion: {}\nBuild: {}",
+            ctx.project.name, ctx.project.ios.version, ctx.project.ios.build_number)
+    } else if ctx.step_succeeded(StepName::Distribute) && ctx.platform == "ios" {
+        format!("🚀 *{}* (iOS) distributed via Firebase App Distribution!\nVersion: {}\nBuild: {}",
+            ctx.project.name, ctx.project.ios.version, ctx.project.ios.build_number)
+    } else if ctx.step_succeeded(StepName::Distribute) {
+        format!("🚀 *{}* (Android) distributed via Firebase App Distribution!\nVersion: {}\nBuild: {}",
+            ctx.project.name, ctx.project.android.version, ctx.project.android.version_code)
+    } else {
+        return Ok(StepOutcome::Skipped("Nothing was published".to_string()));
+    };
+
+    send_all_notifications(&ctx.project, &message).await?;
+    Ok(StepOutcome::Done)
 }
 
 #[command]
@@ -814,6 +1241,11 @@ pub async fn cancel_build_process(
     platform: String,
     process_state: State<'_, BuildProcessState>,
 ) -> Result<(), String> {
+    kill_build_process(process_state.inner(), &project_id, &platform)
+}
+
+/// Kill the running build process for a project/platform pair, if any.
+pub fn kill_build_process(process_state: &BuildProcessState, project_id: &str, platform: &str) -> Result<(), String> {
     let composite_key = format!("{}_{}", project_id, platform);
     let process_arc = {
         let mut processes = process_state.0.lock().unwrap();
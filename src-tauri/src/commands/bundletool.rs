@@ -0,0 +1,377 @@
+use crate::commands::signing::load_signing_passwords;
+use crate::models::project::AndroidSigningConfig;
+use crate::DbState;
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::OnceLock;
+use tauri::{command, State};
+
+/// The bundletool command line (program, then its leading arguments), found on first use
+static BUNDLETOOL: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Dimensions `get-size total` breaks the download size down by when no device is given
+const SIZE_DIMENSIONS: &str = "SDK,ABI,SCREEN_DENSITY";
+
+/// APKs built from an App Bundle
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApkSet {
+    pub apks_path: String,
+    /// The single APK of a universal build, extracted next to the bundle
+    pub universal_apk: Option<String>,
+    /// Key alias the APKs were signed with, or "debug keystore"
+    pub signed_with: String,
+    pub size: u64,
+}
+
+/// Download size range, in bytes, for one combination of dimension values
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSize {
+    /// e.g. {"ABI": "arm64-v8a", "SCREEN_DENSITY": "XXHDPI", "SDK": "24-"}; empty for a single device
+    pub dimensions: BTreeMap<String, String>,
+    pub min: u64,
+    pub max: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadSizeReport {
+    pub device_id: Option<String>,
+    pub sizes: Vec<DownloadSize>,
+}
+
+/// The keystore arguments of `build-apks`. bundletool reads the passwords from files, so
+/// they stay out of the process list.
+pub struct ApkSigning {
+    pub keystore_path: String,
+    pub key_alias: String,
+    pub store_password_file: PathBuf,
+    pub key_password_file: PathBuf,
+}
+
+/// A password written for bundletool, removed once the command has run
+struct PasswordFile(PathBuf);
+
+impl PasswordFile {
+    fn create(name: &str, password: &str) -> Result<Self, String> {
+        let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+        let path = std::env::temp_dir().join(format!("app-builder-{}-{}-{}", name, std::process::id(), nanos));
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path).map_err(|e| format!("Failed to write password file: {}", e))?;
+        std::io::Write::write_all(&mut file, password.as_bytes()).map_err(|e| format!("Failed to write password file: {}", e))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for PasswordFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[command]
+pub async fn build_apks(
+    state: State<'_, DbState>,
+    artifact_path: String,
+    project_id: Option<String>,
+    mode: String,
+    device_id: Option<String>,
+) -> Result<ApkSet, String> {
+    let signing = project_signing(&state, project_id.as_deref())?;
+    let aab = bundle_path(&artifact_path)?;
+    blocking(move || build_apk_set(&aab, &mode, device_id.as_deref(), signing.as_ref())).await
+}
+
+/// Download sizes of the bundle from Google Play, per device configuration or for one device
+#[command]
+pub async fn estimate_download_sizes(
+    state: State<'_, DbState>,
+    artifact_path: String,
+    project_id: Option<String>,
+    device_id: Option<String>,
+) -> Result<DownloadSizeReport, String> {
+    let signing = project_signing(&state, project_id.as_deref())?;
+    let aab = bundle_path(&artifact_path)?;
+    blocking(move || {
+        let apks = default_apk_set(&aab, signing.as_ref())?;
+
+        let mut args = vec!["get-size".to_string(), "total".to_string(), format!("--apks={}", apks.display())];
+        let spec = device_id.as_deref().map(|id| device_spec(&aab, id)).transpose()?;
+        match &spec {
+            Some(spec) => args.push(format!("--device-spec={}", spec.display())),
+            None => args.push(format!("--dimensions={}", SIZE_DIMENSIONS)),
+        }
+        let output = run_bundletool(&args);
+        if let Some(spec) = &spec {
+            let _ = std::fs::remove_file(spec);
+        }
+
+        Ok(DownloadSizeReport { device_id, sizes: parse_size_csv(&output?)? })
+    })
+    .await
+}
+
+/// Install the APKs a device needs from the bundle, onto a running emulator or device
+#[command]
+pub async fn install_apks(
+    state: State<'_, DbState>,
+    artifact_path: String,
+    project_id: Option<String>,
+    device_id: String,
+) -> Result<String, String> {
+    let signing = project_signing(&state, project_id.as_deref())?;
+    let aab = bundle_path(&artifact_path)?;
+    blocking(move || {
+        let apks = default_apk_set(&aab, signing.as_ref())?;
+
+        let mut args = vec!["install-apks".to_string(), format!("--apks={}", apks.display()), format!("--device-id={}", device_id)];
+        args.extend(adb_arg());
+        run_bundletool(&args).map_err(|e| format!("{}. Boot the emulator before installing.", e))?;
+        Ok(format!("Installed {} on {}", aab.file_name().unwrap_or_default().to_string_lossy(), device_id))
+    })
+    .await
+}
+
+/// Run bundletool work off the async runtime, a large bundle takes minutes
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> Result<T, String> + Send + 'static) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(work).await.map_err(|e| e.to_string())?
+}
+
+fn bundle_path(artifact_path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(artifact_path);
+    if path.extension().is_none_or(|e| e != "aab") {
+        return Err("bundletool works on Android App Bundles (.aab)".to_string());
+    }
+    if !path.exists() {
+        return Err(format!("Bundle not found: {}", artifact_path));
+    }
+    Ok(path)
+}
+
+/// The keystore of the project the bundle was built for, if it has one
+fn project_signing(state: &State<'_, DbState>, project_id: Option<&str>) -> Result<Option<AndroidSigningConfig>, String> {
+    let Some(project_id) = project_id else {
+        return Ok(None);
+    };
+    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    let signing: Option<String> = conn
+        .query_row("SELECT android_signing FROM projects WHERE id = ?1", params![project_id], |row| row.get(0))
+        .unwrap_or(None);
+    Ok(signing.and_then(|s| serde_json::from_str(&s).ok()))
+}
+
+/// The full APK set, rebuilt when the bundle is newer than it. Each keystore gets its own set.
+fn default_apk_set(aab: &Path, signing: Option<&AndroidSigningConfig>) -> Result<PathBuf, String> {
+    let apks = apks_path(aab, "default", None, signing.map(|s| s.key_alias.as_str()));
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    if apks.exists() && modified(&apks) >= modified(aab) {
+        return Ok(apks);
+    }
+    build_apk_set(aab, "default", None, signing)?;
+    Ok(apks)
+}
+
+fn build_apk_set(aab: &Path, mode: &str, device_id: Option<&str>, signing: Option<&AndroidSigningConfig>) -> Result<ApkSet, String> {
+    let output = apks_path(aab, mode, device_id, signing.map(|s| s.key_alias.as_str()));
+
+    // Keep the password files alive until bundletool has read them
+    let mut password_files = Vec::new();
+    let apk_signing = match signing {
+        Some(config) => {
+            let passwords = load_signing_passwords(&config.id)
+                .map_err(|e| format!("{}. Enter the keystore passwords again in the project's Release Signing settings.", e))?;
+            let store = PasswordFile::create("store-pass", &passwords.store_password)?;
+            let key = PasswordFile::create("key-pass", &passwords.key_password)?;
+            let apk_signing = ApkSigning {
+                keystore_path: config.keystore_path.clone(),
+                key_alias: config.key_alias.clone(),
+                store_password_file: store.0.clone(),
+                key_password_file: key.0.clone(),
+            };
+            password_files.extend([store, key]);
+            Some(apk_signing)
+        }
+        None => None,
+    };
+
+    let mut args = build_apks_args(aab, &output, mode, device_id, apk_signing.as_ref())?;
+    if device_id.is_some() {
+        args.extend(adb_arg());
+    }
+    run_bundletool(&args)?;
+    drop(password_files);
+
+    let universal_apk = if mode == "universal" {
+        let path = aab.with_extension("universal.apk");
+        extract_universal_apk(&output, &path)?;
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(ApkSet {
+        apks_path: output.to_string_lossy().to_string(),
+        universal_apk,
+        signed_with: signing.map(|s| s.key_alias.clone()).unwrap_or("debug keystore".to_string()),
+        size: std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Where the APK set of a mode is written: next to the bundle, e.g. `app-release.universal.apks`,
+/// or `app-release.universal.key-upload.apks` when signed with the `upload` key instead of the
+/// debug keystore
+pub fn apks_path(aab: &Path, mode: &str, device_id: Option<&str>, key_alias: Option<&str>) -> PathBuf {
+    let mut extension = match (mode, device_id) {
+        ("default", _) => String::new(),
+        ("device", Some(id)) => format!("{}.", id.replace([':', '/'], "_")),
+        (mode, _) => format!("{}.", mode),
+    };
+    if let Some(alias) = key_alias {
+        extension.push_str(&format!("key-{}.", alias.replace([':', '/', '\\'], "_")));
+    }
+    aab.with_extension(format!("{}apks", extension))
+}
+
+/// `build-apks` arguments for a mode: "universal" for one APK holding every split,
+/// "default" for the split APKs of every device configuration, or "device" for only the
+/// splits a connected device needs. Without a keystore bundletool signs with the debug one.
+pub fn build_apks_args(
+    aab: &Path,
+    output: &Path,
+    mode: &str,
+    device_id: Option<&str>,
+    signing: Option<&ApkSigning>,
+) -> Result<Vec<String>, String> {
+    let mut args = vec![
+        "build-apks".to_string(),
+        format!("--bundle={}", aab.display()),
+        format!("--output={}", output.display()),
+        "--overwrite".to_string(),
+    ];
+    match mode {
+        "universal" => args.push("--mode=universal".to_string()),
+        "default" => {}
+        "device" => {
+            let device_id = device_id.ok_or("Pick a running device to build its APKs")?;
+            args.push("--connected-device".to_string());
+            args.push(format!("--device-id={}", device_id));
+        }
+        mode => return Err(format!("Unknown APK mode: {}", mode)),
+    }
+    if let Some(signing) = signing {
+        args.push(format!("--ks={}", signing.keystore_path));
+        args.push(format!("--ks-key-alias={}", signing.key_alias));
+        args.push(format!("--ks-pass=file:{}", signing.store_password_file.display()));
+        args.push(format!("--key-pass=file:{}", signing.key_password_file.display()));
+    }
+    Ok(args)
+}
+
+/// Copy `universal.apk` out of a universal APK set
+pub fn extract_universal_apk(apks: &Path, destination: &Path) -> Result<(), String> {
+    let file = std::fs::File::open(apks).map_err(|e| format!("Failed to open {}: {}", apks.display(), e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Invalid APK set: {}", e))?;
+    let mut entry = archive.by_name("universal.apk").map_err(|_| "The APK set has no universal.apk".to_string())?;
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+    std::fs::write(destination, bytes).map_err(|e| format!("Failed to write {}: {}", destination.display(), e))
+}
+
+/// Parse the CSV `get-size total` prints: the dimension columns, then MIN and MAX
+pub fn parse_size_csv(output: &str) -> Result<Vec<DownloadSize>, String> {
+    let mut lines = output.lines().map(str::trim).filter(|l| !l.is_empty());
+    let header: Vec<&str> = lines.next().ok_or("bundletool printed no sizes")?.split(',').collect();
+    if header.len() < 2 || header[header.len() - 2..] != ["MIN", "MAX"] {
+        return Err(format!("Unexpected get-size output: {}", header.join(",")));
+    }
+    let dimensions = &header[..header.len() - 2];
+
+    lines
+        .map(|line| {
+            let values: Vec<&str> = line.split(',').collect();
+            if values.len() != header.len() {
+                return Err(format!("Unexpected get-size row: {}", line));
+            }
+            let size = |value: &str| value.parse::<u64>().map_err(|_| format!("Unexpected size: {}", value));
+            Ok(DownloadSize {
+                dimensions: dimensions.iter().zip(&values).map(|(d, v)| (d.to_string(), v.to_string())).collect(),
+                min: size(values[header.len() - 2])?,
+                max: size(values[header.len() - 1])?,
+            })
+        })
+        .collect()
+}
+
+/// Write the spec of a connected device next to the bundle, for `get-size`
+fn device_spec(aab: &Path, device_id: &str) -> Result<PathBuf, String> {
+    let spec = aab.with_extension(format!("{}.json", device_id.replace([':', '/'], "_")));
+    let mut args = vec!["get-device-spec".to_string(), format!("--device-id={}", device_id), format!("--output={}", spec.display()), "--overwrite".to_string()];
+    args.extend(adb_arg());
+    run_bundletool(&args).map_err(|e| format!("{}. Boot the emulator to size the download for it.", e))?;
+    Ok(spec)
+}
+
+/// bundletool looks for adb on the PATH, which apps launched from the Finder don't inherit
+fn adb_arg() -> Option<String> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    std::env::var("ANDROID_HOME")
+        .ok()
+        .map(|sdk| PathBuf::from(sdk).join("platform-tools/adb"))
+        .into_iter()
+        .chain([PathBuf::from(&home).join("Library/Android/sdk/platform-tools/adb")])
+        .find(|adb| adb.exists())
+        .map(|adb| format!("--adb={}", adb.display()))
+}
+
+/// `bundletool` from Homebrew, or `bundletool.jar` from `BUNDLETOOL_JAR` or ~/.android run with java.
+/// Looked up once; while it is missing every call looks again, so installing it needs no restart.
+fn bundletool() -> Result<Command, String> {
+    let command_line = match BUNDLETOOL.get() {
+        Some(command_line) => command_line,
+        None => {
+            let _ = BUNDLETOOL.set(find_bundletool()?);
+            BUNDLETOOL.get().unwrap()
+        }
+    };
+    let mut command = Command::new(&command_line[0]);
+    command.args(&command_line[1..]);
+    Ok(command)
+}
+
+fn find_bundletool() -> Result<Vec<String>, String> {
+    for candidate in ["bundletool", "/opt/homebrew/bin/bundletool", "/usr/local/bin/bundletool"] {
+        if Command::new(candidate).arg("version").output().is_ok_and(|o| o.status.success()) {
+            return Ok(vec![candidate.to_string()]);
+        }
+    }
+    let home = std::env::var("HOME").unwrap_or_default();
+    let jar = std::env::var("BUNDLETOOL_JAR")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain([PathBuf::from(&home).join(".android/bundletool.jar")])
+        .find(|jar| jar.exists())
+        .ok_or("bundletool not found. Install it with `brew install bundletool` or save bundletool.jar to ~/.android")?;
+    Ok(vec!["java".to_string(), "-jar".to_string(), jar.to_string_lossy().to_string()])
+}
+
+fn run_bundletool(args: &[String]) -> Result<String, String> {
+    let output = bundletool()?
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run bundletool: {}", e))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("unknown error");
+        Err(format!("bundletool {} failed: {}", args.first().map(String::as_str).unwrap_or_default(), message.trim()))
+    }
+}
@@ -0,0 +1,363 @@
+use crate::commands::events::{BuildEvent, BuildEventEnvelope, BuildEventSink};
+use crate::models::project::BuildDiagnostic;
+use regex::{Captures, Regex};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+/// xcodebuild can print thousands of deprecation warnings, keep the first ones only
+const MAX_DIAGNOSTICS: usize = 200;
+
+type Remediation = fn(&Captures) -> Option<String>;
+
+struct Rule {
+    regex: Regex,
+    category: &'static str,
+    /// `None` reads the severity from the `severity` capture group
+    severity: Option<&'static str>,
+    remediation: Remediation,
+}
+
+/// Recognises common Gradle, Kotlin, Java, CocoaPods, clang/swiftc, codesign and Metro
+/// error patterns in build output.
+pub struct DiagnosticParser {
+    rules: Vec<Rule>,
+    /// Gradle prints the actual problem on the line after `* What went wrong:`
+    after_what_went_wrong: bool,
+    seen: HashSet<(String, Option<String>, Option<u32>, String)>,
+}
+
+fn rule(pattern: &str, category: &'static str, severity: Option<&'static str>, remediation: Remediation) -> Rule {
+    Rule {
+        regex: Regex::new(pattern).expect("invalid diagnostic pattern"),
+        category,
+        severity,
+        remediation,
+    }
+}
+
+fn compiler_remediation(caps: &Captures) -> Option<String> {
+    let message = caps.name("message")?.as_str();
+    if message.starts_with("No such module") || message.contains("file not found") {
+        Some("Run `pod install` in the ios directory and build the .xcworkspace rather than the .xcodeproj.".to_string())
+    } else {
+        None
+    }
+}
+
+fn cocoapods_remediation(caps: &Captures) -> Option<String> {
+    let message = caps.name("message")?.as_str();
+    if message.contains("could not find compatible versions") || message.contains("Unable to find a specification") {
+        Some("Run `pod install --repo-update` in the ios directory, or relax the version constraint in the Podfile.".to_string())
+    } else if message.contains("not in sync with the Podfile.lock") {
+        Some("Run `pod install` in the ios directory.".to_string())
+    } else {
+        None
+    }
+}
+
+impl Default for DiagnosticParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiagnosticParser {
+    pub fn new() -> Self {
+        let rules = vec![
+            // Kotlin 1.x/2.x: `e: file:///path/Foo.kt:12:5 Unresolved reference: bar`
+            rule(
+                r"^(?P<severity>e|w): (?:file://)?(?P<file>[^:]+\.kts?):(?P<line>\d+):(?P<column>\d+) (?P<message>.+)$",
+                "kotlin",
+                None,
+                |_| None,
+            ),
+            // Older Kotlin: `e: /path/Foo.kt: (12, 5): Unresolved reference: bar`
+            rule(
+                r"^(?P<severity>e|w): (?P<file>[^:]+\.kts?): \((?P<line>\d+), (?P<column>\d+)\): (?P<message>.+)$",
+                "kotlin",
+                None,
+                |_| None,
+            ),
+            rule(
+                r"^(?P<file>/[^:]+\.java):(?P<line>\d+): (?P<severity>error|warning): (?P<message>.+)$",
+                "java",
+                None,
+                |_| None,
+            ),
+            rule(
+                r"^(?P<file>/[^:]+\.(?:swift|m|mm|c|cc|cpp|h|hpp)):(?P<line>\d+):(?P<column>\d+): (?P<severity>fatal error|error|warning): (?P<message>.+)$",
+                "compiler",
+                None,
+                compiler_remediation,
+            ),
+            rule(
+                r"Unable to resolve module (?P<module>\S+) from (?P<file>[^:\s]+)",
+                "metro",
+                Some("error"),
+                |caps| {
+                    let module = &caps["module"];
+                    if module.starts_with('.') || module.starts_with('/') {
+                        Some("Fix the import path (file names are case-sensitive), then restart Metro with `--reset-cache`.".to_string())
+                    } else {
+                        Some(format!("Install the missing package (`npm install {}`), then restart Metro with `--reset-cache`.", module))
+                    }
+                },
+            ),
+            rule(
+                r"^(?:error )?SyntaxError: (?P<file>/[^:]+): (?P<message>.+?) \((?P<line>\d+):(?P<column>\d+)\)",
+                "metro",
+                Some("error"),
+                |_| None,
+            ),
+            rule(
+                r"No profiles? for '(?P<bundle>[^']+)' (?:was|were) found",
+                "codesign",
+                Some("error"),
+                |caps| Some(format!(
+                    "Create or download a provisioning profile for {} in the Apple Developer portal, or enable automatic signing.",
+                    &caps["bundle"]
+                )),
+            ),
+            rule(
+                r#"No signing certificate "(?P<certificate>[^"]+)" found"#,
+                "codesign",
+                Some("error"),
+                |caps| Some(format!(
+                    "Install the \"{}\" certificate together with its private key in the login keychain.",
+                    &caps["certificate"]
+                )),
+            ),
+            rule(
+                r#"Provisioning profile "(?P<profile>[^"]+)" doesn't include signing certificate"#,
+                "codesign",
+                Some("error"),
+                |caps| Some(format!(
+                    "Regenerate \"{}\" with the certificate installed on this Mac, or install the certificate the profile was created with.",
+                    &caps["profile"]
+                )),
+            ),
+            rule(
+                r"errSecInternalComponent",
+                "codesign",
+                Some("error"),
+                |_| Some("Unlock the login keychain (`security unlock-keychain`) — this happens when building over SSH or from a locked session.".to_string()),
+            ),
+            rule(
+                r"^(?:error: )?Code ?Signing Error: (?P<message>.+)$",
+                "codesign",
+                Some("error"),
+                |_| Some("Check the Team ID, export method and provisioning profiles in the project's iOS settings.".to_string()),
+            ),
+            rule(
+                r"^\[!\] (?P<message>.+)$",
+                "cocoapods",
+                Some("error"),
+                cocoapods_remediation,
+            ),
+            rule(
+                r"(?P<message>The sandbox is not in sync with the Podfile\.lock.*)$",
+                "cocoapods",
+                Some("error"),
+                cocoapods_remediation,
+            ),
+            rule(
+                r"^xcodebuild: error: (?P<message>.+)$",
+                "xcodebuild",
+                Some("error"),
+                |_| Some("Check the scheme and configuration in the project's iOS settings (`xcodebuild -list` shows the available schemes).".to_string()),
+            ),
+            rule(
+                r"Execution failed for task '(?P<task>[^']+)'",
+                "gradle",
+                Some("error"),
+                |caps| Some(format!("Run `./gradlew {} --stacktrace` in the android directory for the full error.", &caps["task"])),
+            ),
+            rule(
+                r"Could not resolve all (?:files|dependencies|artifacts) for configuration '(?P<configuration>[^']+)'",
+                "gradle",
+                Some("error"),
+                |_| Some("Check the repositories in build.gradle and your network/proxy settings, then retry with `--refresh-dependencies`.".to_string()),
+            ),
+            rule(
+                r"Could not find (?P<dependency>[\w.\-]+:[\w.\-]+:[\w.\-+]*\w)",
+                "gradle",
+                Some("error"),
+                |caps| Some(format!("{} is not in any configured repository: fix the version or add the repository that hosts it.", &caps["dependency"])),
+            ),
+            rule(
+                r"SDK location not found",
+                "gradle",
+                Some("error"),
+                |_| Some("Set ANDROID_HOME, or add `sdk.dir=/path/to/Android/sdk` to android/local.properties.".to_string()),
+            ),
+            rule(
+                r"OutOfMemoryError: (?:Java heap space|Metaspace)|JVM heap space is exhausted",
+                "gradle",
+                Some("error"),
+                |_| Some("Increase `org.gradle.jvmargs` in android/gradle.properties, e.g. `-Xmx4g -XX:MaxMetaspaceSize=1g`.".to_string()),
+            ),
+            rule(
+                r"Unsupported class file major version (?P<version>\d+)",
+                "gradle",
+                Some("error"),
+                |_| Some("The JDK is too new for this Gradle version: point JAVA_HOME to JDK 17 or upgrade the Gradle wrapper.".to_string()),
+            ),
+            rule(
+                r"Keystore file '(?P<file>[^']+)' not found",
+                "codesign",
+                Some("error"),
+                |_| Some("Check `storeFile` in the release signingConfig, or generate a keystore from the Keystore tab.".to_string()),
+            ),
+        ];
+
+        DiagnosticParser {
+            rules,
+            after_what_went_wrong: false,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Parse one line of build output
+    pub fn parse_line(&mut self, raw_line: &str, step: Option<&str>) -> Option<BuildDiagnostic> {
+        let line = raw_line
+            .trim_start_matches("⚠️")
+            .trim()
+            .trim_start_matches("> ")
+            .trim();
+        if line.is_empty() {
+            return None;
+        }
+
+        if line == "* What went wrong:" {
+            self.after_what_went_wrong = true;
+            return None;
+        }
+        let after_what_went_wrong = std::mem::take(&mut self.after_what_went_wrong);
+
+        let diagnostic = self
+            .rules
+            .iter()
+            .find_map(|rule| rule.regex.captures(line).map(|caps| (rule, caps)))
+            .map(|(rule, caps)| {
+                let severity = match rule.severity {
+                    Some(severity) => severity.to_string(),
+                    None => match caps.name("severity").map(|m| m.as_str()) {
+                        Some("w") | Some("warning") => "warning".to_string(),
+                        _ => "error".to_string(),
+                    },
+                };
+                BuildDiagnostic {
+                    step: step.map(|s| s.to_string()),
+                    severity,
+                    category: rule.category.to_string(),
+                    file: caps.name("file").map(|m| m.as_str().to_string()),
+                    line: caps.name("line").and_then(|m| m.as_str().parse().ok()),
+                    column: caps.name("column").and_then(|m| m.as_str().parse().ok()),
+                    message: caps.name("message").map(|m| m.as_str()).unwrap_or(line).to_string(),
+                    remediation: (rule.remediation)(&caps),
+                }
+            })
+            .or_else(|| {
+                after_what_went_wrong.then(|| BuildDiagnostic {
+                    step: step.map(|s| s.to_string()),
+                    severity: "error".to_string(),
+                    category: "gradle".to_string(),
+                    file: None,
+                    line: None,
+                    column: None,
+                    message: line.to_string(),
+                    remediation: None,
+                })
+            })?;
+
+        let key = (
+            diagnostic.category.clone(),
+            diagnostic.file.clone(),
+            diagnostic.line,
+            diagnostic.message.clone(),
+        );
+        if self.seen.len() >= MAX_DIAGNOSTICS || !self.seen.insert(key) {
+            return None;
+        }
+        Some(diagnostic)
+    }
+}
+
+/// Runs every log line produced by a pipeline step through a `DiagnosticParser`.
+/// Lines logged outside a step (e.g. the failure summary) are ignored.
+pub struct DiagnosticsSink {
+    parser: DiagnosticParser,
+    diagnostics: Arc<Mutex<Vec<BuildDiagnostic>>>,
+}
+
+impl DiagnosticsSink {
+    pub fn new(diagnostics: Arc<Mutex<Vec<BuildDiagnostic>>>) -> Self {
+        DiagnosticsSink { parser: DiagnosticParser::new(), diagnostics }
+    }
+}
+
+impl BuildEventSink for DiagnosticsSink {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String> {
+        if let BuildEvent::Log { step: Some(step), message, .. } = &envelope.event {
+            if let Some(diagnostic) = self.parser.parse_line(message, Some(step.as_str())) {
+                self.diagnostics.lock().unwrap().push(diagnostic);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Replace the stored diagnostics of a build
+pub fn save_build_diagnostics(conn: &Connection, build_id: &str, diagnostics: &[BuildDiagnostic]) -> Result<(), String> {
+    // All or nothing, a failed insert must not leave the build with part of its diagnostics
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM build_diagnostics WHERE build_id = ?1", params![build_id])
+        .map_err(|e| e.to_string())?;
+
+    for diagnostic in diagnostics {
+        tx.execute(
+            "INSERT INTO build_diagnostics (build_id, step, severity, category, file, line_number, column_number, message, remediation)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                build_id,
+                diagnostic.step,
+                diagnostic.severity,
+                diagnostic.category,
+                diagnostic.file,
+                diagnostic.line,
+                diagnostic.column,
+                diagnostic.message,
+                diagnostic.remediation,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+pub fn load_build_diagnostics(conn: &Connection, build_id: &str) -> Result<Vec<BuildDiagnostic>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT step, severity, category, file, line_number, column_number, message, remediation
+             FROM build_diagnostics WHERE build_id = ?1 ORDER BY id",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![build_id], |row| {
+            Ok(BuildDiagnostic {
+                step: row.get(0)?,
+                severity: row.get(1)?,
+                category: row.get(2)?,
+                file: row.get(3)?,
+                line: row.get(4)?,
+                column: row.get(5)?,
+                message: row.get(6)?,
+                remediation: row.get(7)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
@@ -0,0 +1,303 @@
+use crate::commands::pipeline::{StepName, StepReport};
+use crate::models::project::GitProvenance;
+use serde::Serialize;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+/// Channel carrying every `BuildEventEnvelope` to the frontend
+pub const BUILD_EVENT_CHANNEL: &str = "build-event";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Written to the log file only (e.g. the bulk of xcodebuild output)
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    /// Classify a raw line of tool output
+    pub fn classify(line: &str, default: LogLevel) -> LogLevel {
+        let lower_line = line.to_lowercase();
+        if lower_line.contains("error:") {
+            LogLevel::Error
+        } else if lower_line.contains("warning:") {
+            LogLevel::Warning
+        } else {
+            default
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildStatus {
+    Success,
+    Failed,
+    Cancelled,
+}
+
+impl BuildStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildStatus::Success => "success",
+            BuildStatus::Failed => "failed",
+            BuildStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum BuildEvent {
+    Log {
+        level: LogLevel,
+        step: Option<StepName>,
+        message: String,
+    },
+    Step(StepReport),
+    Progress {
+        step: Option<StepName>,
+        /// 0.0 – 1.0
+        fraction: f64,
+        message: String,
+    },
+    Artifact {
+        path: String,
+    },
+    /// Source control state the build was made from
+    Provenance(GitProvenance),
+    /// Version and build number the build was bumped to
+    Version {
+        version: String,
+        #[serde(rename = "buildNumber")]
+        build_number: u32,
+    },
+    /// Release notes generated for this build
+    ReleaseNotes {
+        notes: String,
+    },
+    LogFile {
+        path: String,
+    },
+    Status {
+        status: BuildStatus,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildEventEnvelope {
+    pub project_id: String,
+    pub platform: String,
+    pub build_id: Option<String>,
+    /// Monotonic per build, lets subscribers detect dropped or reordered events
+    pub sequence: u64,
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub event: BuildEvent,
+}
+
+/// Receives every event of a build. Sinks must not block for long: they run inline
+/// with the build output reader. An error is reported once and does not stop the build or
+/// the other sinks.
+pub trait BuildEventSink: Send {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String>;
+}
+
+/// Streams events to the frontend on the `build-event` channel.
+/// Debug-level log lines are not sent, to keep the UI responsive.
+pub struct WindowSink {
+    app: AppHandle,
+}
+
+impl WindowSink {
+    pub fn new(app: &AppHandle) -> Self {
+        WindowSink { app: app.clone() }
+    }
+}
+
+impl BuildEventSink for WindowSink {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String> {
+        if let BuildEvent::Log { level: LogLevel::Debug, .. } = envelope.event {
+            return Ok(());
+        }
+        self.app.emit(BUILD_EVENT_CHANNEL, envelope).map_err(|e| e.to_string())
+    }
+}
+
+/// Writes log lines to the build log file
+pub struct LogFileSink {
+    file: File,
+}
+
+impl LogFileSink {
+    pub fn new(file: File) -> Self {
+        LogFileSink { file }
+    }
+}
+
+impl BuildEventSink for LogFileSink {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String> {
+        if let BuildEvent::Log { message, .. } = &envelope.event {
+            writeln!(self.file, "{}", message).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Fans every build event out to the registered sinks, tagging it with the build identity,
+/// the current step and a sequence number.
+pub struct BuildEventBus {
+    project_id: String,
+    platform: String,
+    build_id: Option<String>,
+    sinks: Vec<Box<dyn BuildEventSink>>,
+    sequence: u64,
+    current_step: Option<StepName>,
+    log_path: Option<PathBuf>,
+    /// Lines written to the log file so far
+    lines: usize,
+    /// Distinct errors returned by sinks
+    sink_errors: Vec<String>,
+}
+
+impl BuildEventBus {
+    pub fn new(project_id: &str, platform: &str, build_id: Option<String>) -> Self {
+        BuildEventBus {
+            project_id: project_id.to_string(),
+            platform: platform.to_string(),
+            build_id,
+            sinks: Vec::new(),
+            sequence: 0,
+            current_step: None,
+            log_path: None,
+            lines: 0,
+            sink_errors: Vec::new(),
+        }
+    }
+
+    pub fn subscribe(&mut self, sink: Box<dyn BuildEventSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Create a timestamped log file in `logs_dir` and start writing log lines to it
+    pub fn open_log_file(&mut self, logs_dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(logs_dir)
+            .map_err(|e| format!("Failed to create logs directory: {}", e))?;
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let path = logs_dir.join(format!("{}.log", timestamp));
+        let file = File::create(&path)
+            .map_err(|e| format!("Failed to create log file: {}", e))?;
+
+        self.subscribe(Box::new(LogFileSink::new(file)));
+        self.log_path = Some(path);
+        Ok(())
+    }
+
+    pub fn log_path(&self) -> Option<&Path> {
+        self.log_path.as_deref()
+    }
+
+    /// Number of lines written to the log file so far
+    pub fn line_count(&self) -> usize {
+        self.lines
+    }
+
+    /// Errors the sinks returned so far, each reported once
+    pub fn sink_errors(&self) -> &[String] {
+        &self.sink_errors
+    }
+
+    pub fn set_step(&mut self, step: Option<StepName>) {
+        self.current_step = step;
+    }
+
+    pub fn emit(&mut self, event: BuildEvent) {
+        if let BuildEvent::Log { message, .. } = &event {
+            if self.log_path.is_some() {
+                self.lines += message.lines().count().max(1);
+            }
+        }
+
+        self.sequence += 1;
+        let envelope = BuildEventEnvelope {
+            project_id: self.project_id.clone(),
+            platform: self.platform.clone(),
+            build_id: self.build_id.clone(),
+            sequence: self.sequence,
+            timestamp: chrono::Utc::now().timestamp_millis() as u64,
+            event,
+        };
+
+        for sink in self.sinks.iter_mut() {
+            if let Err(e) = sink.handle(&envelope) {
+                if !self.sink_errors.contains(&e) {
+                    println!("Build event sink failed: {}", e);
+                    self.sink_errors.push(e);
+                }
+            }
+        }
+    }
+
+    pub fn log_at(&mut self, level: LogLevel, message: &str) {
+        let step = self.current_step;
+        self.emit(BuildEvent::Log { level, step, message: message.to_string() })
+    }
+
+    /// Write a line to the log file and stream it to the UI
+    pub fn log(&mut self, message: &str) {
+        self.log_at(LogLevel::Info, message)
+    }
+
+    pub fn warn(&mut self, message: &str) {
+        self.log_at(LogLevel::Warning, message)
+    }
+
+    pub fn error(&mut self, message: &str) {
+        self.log_at(LogLevel::Error, message)
+    }
+
+    pub fn progress(&mut self, fraction: f64, message: &str) {
+        let step = self.current_step;
+        self.emit(BuildEvent::Progress { step, fraction: fraction.clamp(0.0, 1.0), message: message.to_string() })
+    }
+
+    pub fn artifact(&mut self, artifact: &Path) {
+        self.emit(BuildEvent::Artifact { path: artifact.to_string_lossy().to_string() })
+    }
+
+    pub fn provenance(&mut self, git: &GitProvenance) {
+        self.emit(BuildEvent::Provenance(git.clone()))
+    }
+
+    pub fn version(&mut self, version: &str, build_number: u32) {
+        self.emit(BuildEvent::Version { version: version.to_string(), build_number })
+    }
+
+    pub fn release_notes(&mut self, notes: &str) {
+        self.emit(BuildEvent::ReleaseNotes { notes: notes.to_string() })
+    }
+
+    pub fn log_file(&mut self) {
+        if let Some(path) = self.log_path.clone() {
+            self.emit(BuildEvent::LogFile { path: path.to_string_lossy().to_string() })
+        }
+    }
+
+    pub fn step(&mut self, report: &StepReport) {
+        self.emit(BuildEvent::Step(report.clone()))
+    }
+
+    pub fn status(&mut self, status: BuildStatus) {
+        self.emit(BuildEvent::Status { status })
+    }
+}
@@ -0,0 +1,151 @@
+use crate::commands::versioning::FileChange;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Top-level entries not copied into a prebuild workspace: generated, or linked instead
+const WORKSPACE_EXCLUDES: [&str; 5] = ["node_modules", ".git", ".expo", "ios", "android"];
+
+/// The lines `update_expo_versions` changed, and the fields it could not set
+#[derive(Debug, Default)]
+pub struct ExpoVersionUpdate {
+    pub changes: Vec<FileChange>,
+    /// Fields the config does not declare as literals, e.g. "ios.buildNumber"
+    pub missing: Vec<String>,
+}
+
+/// `expo prebuild` for one platform. `CI=1` keeps it from prompting.
+pub fn prebuild_command(platform: &str, clean: bool) -> String {
+    format!("CI=1 npx expo prebuild --platform {}{} 2>&1", platform, if clean { " --clean" } else { "" })
+}
+
+/// Where an isolated prebuild of a project generates its native folders. Recreated for every
+/// build, so its artifacts only last until the next isolated build of the platform.
+pub fn prebuild_workspace(project_id: &str, platform: &str) -> PathBuf {
+    std::env::temp_dir().join("app-builder-prebuild").join(project_id).join(platform)
+}
+
+/// Copy the project into `workspace`, without its native folders, and link its node_modules
+pub fn prepare_workspace(project_dir: &Path, workspace: &Path) -> Result<(), String> {
+    if workspace.exists() {
+        fs::remove_dir_all(workspace).map_err(|e| format!("Failed to clear {}: {}", workspace.display(), e))?;
+    }
+    fs::create_dir_all(workspace).map_err(|e| format!("Failed to create {}: {}", workspace.display(), e))?;
+
+    let entries = fs::read_dir(project_dir).map_err(|e| format!("Failed to read {}: {}", project_dir.display(), e))?;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if WORKSPACE_EXCLUDES.iter().any(|excluded| name == *excluded) {
+            continue;
+        }
+        copy_entry(&entry.path(), &workspace.join(&name))?;
+    }
+
+    let node_modules = project_dir.join("node_modules");
+    if node_modules.exists() {
+        symlink(&node_modules, &workspace.join("node_modules"))?;
+    }
+    Ok(())
+}
+
+fn copy_entry(from: &Path, to: &Path) -> Result<(), String> {
+    let metadata = fs::symlink_metadata(from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    if metadata.file_type().is_symlink() {
+        let target = fs::read_link(from).map_err(|e| e.to_string())?;
+        symlink(&target, to)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+        for entry in fs::read_dir(from).map_err(|e| e.to_string())?.flatten() {
+            copy_entry(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ()).map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| format!("Failed to link {}: {}", link.display(), e))
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> Result<(), String> {
+    std::os::windows::fs::symlink_dir(target, link).map_err(|e| format!("Failed to link {}: {}", link.display(), e))
+}
+
+/// The file holding the Expo config: app.json when it has an `expo` key, else the dynamic config
+pub fn expo_config_file(project_dir: &Path) -> Option<PathBuf> {
+    let app_json = project_dir.join("app.json");
+    let has_expo_key = fs::read_to_string(&app_json)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|json| json.get("expo").is_some());
+    if has_expo_key {
+        return Some(app_json);
+    }
+    ["app.config.ts", "app.config.js"].into_iter().map(|name| project_dir.join(name)).find(|path| path.exists())
+}
+
+/// Set `version`, `ios.buildNumber` and `android.versionCode` in the Expo config, which
+/// prebuild writes into the generated native projects. Edits the lines in place to keep the
+/// file's formatting; in app.config.js/ts only literal values can be updated.
+pub fn update_expo_versions(
+    project_dir: &Path,
+    version: Option<&str>,
+    build_number: Option<u32>,
+    version_code: Option<u32>,
+) -> Result<ExpoVersionUpdate, String> {
+    let file = expo_config_file(project_dir).ok_or("No app.json or app.config.js/ts found")?;
+    let content = fs::read_to_string(&file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    // Each line without its ending, kept to write the file back with the same line endings
+    let mut lines: Vec<(String, &str)> = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let text = line.trim_end_matches(['\r', '\n']);
+            (text.to_string(), &line[text.len()..])
+        })
+        .collect();
+
+    let fields = [
+        ("version", "version", version.map(|v| (v.to_string(), true))),
+        ("buildNumber", "ios.buildNumber", build_number.map(|n| (n.to_string(), true))),
+        ("versionCode", "android.versionCode", version_code.map(|n| (n.to_string(), false))),
+    ];
+    let mut update = ExpoVersionUpdate::default();
+    for (key, name, value) in fields {
+        let Some((value, quoted)) = value else {
+            continue;
+        };
+        // `"version": "1.0.0",` in JSON, `version: '1.0.0',` or `ios: { buildNumber: '1' }` in
+        // JS/TS; the first one is the app's
+        let pattern = Regex::new(&format!(r#"^((?:.*[\s{{,])?"?{}"?\s*:\s*)(["'`]?)([^"'`,\s}}]*)["'`]?(.*)$"#, key)).unwrap();
+        let Some((index, caps)) = lines.iter().enumerate().find_map(|(i, (line, _))| pattern.captures(line).map(|caps| (i, caps))) else {
+            update.missing.push(name.to_string());
+            continue;
+        };
+        // A non-literal value such as `process.env.VERSION` or `pkg.version` is left alone
+        let is_literal = if quoted { !caps[2].is_empty() } else { caps[3].chars().all(|c| c.is_ascii_digit()) && !caps[3].is_empty() };
+        if !is_literal {
+            update.missing.push(name.to_string());
+            continue;
+        }
+        let quote = if quoted { &caps[2] } else { "" };
+        let new = format!("{}{}{}{}{}", &caps[1], quote, value, quote, &caps[4]);
+        if lines[index].0 != new {
+            update.changes.push(FileChange {
+                file: file.clone(),
+                line: index + 1,
+                old: lines[index].0.trim().to_string(),
+                new: new.trim().to_string(),
+            });
+            lines[index].0 = new;
+        }
+    }
+
+    if !update.changes.is_empty() {
+        let new_content: String = lines.iter().map(|(line, ending)| format!("{}{}", line, ending)).collect();
+        fs::write(&file, new_content).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+    }
+    Ok(update)
+}
@@ -0,0 +1,42 @@
+use crate::models::project::IosExportOptions;
+use plist::{Dictionary, Value};
+use std::path::Path;
+
+/// The ExportOptions.plist `xcodebuild -exportArchive` is given. Mapping any profiles switches
+/// to manual signing, which multi-target apps need when automatic signing can't pick profiles
+/// for their extensions.
+pub fn export_options_plist(export_method: &str, team_id: &str, options: &IosExportOptions) -> Dictionary {
+    let mut plist = Dictionary::new();
+    plist.insert("method".to_string(), Value::String(export_method.to_string()));
+    plist.insert("teamID".to_string(), Value::String(team_id.to_string()));
+    plist.insert("uploadSymbols".to_string(), Value::Boolean(options.upload_symbols));
+    plist.insert("compileBitcode".to_string(), Value::Boolean(options.compile_bitcode));
+    plist.insert("stripSwiftSymbols".to_string(), Value::Boolean(true));
+
+    let signing_style = if options.provisioning_profiles.is_empty() { "automatic" } else { "manual" };
+    plist.insert("signingStyle".to_string(), Value::String(signing_style.to_string()));
+    if !options.provisioning_profiles.is_empty() {
+        let profiles = options
+            .provisioning_profiles
+            .iter()
+            .map(|(bundle_id, profile)| (bundle_id.clone(), Value::String(profile.clone())))
+            .collect::<Dictionary>();
+        plist.insert("provisioningProfiles".to_string(), Value::Dictionary(profiles));
+    }
+    if let Some(certificate) = options.signing_certificate.as_ref().filter(|c| !c.is_empty()) {
+        plist.insert("signingCertificate".to_string(), Value::String(certificate.clone()));
+    }
+    if let Some(thinning) = options.thinning.as_ref().filter(|t| !t.is_empty()) {
+        plist.insert("thinning".to_string(), Value::String(thinning.clone()));
+    }
+    if let Some(manage) = options.manage_app_version_and_build_number {
+        plist.insert("manageAppVersionAndBuildNumber".to_string(), Value::Boolean(manage));
+    }
+    plist
+}
+
+pub fn write_export_options(path: &Path, export_method: &str, team_id: &str, options: &IosExportOptions) -> Result<(), String> {
+    Value::Dictionary(export_options_plist(export_method, team_id, options))
+        .to_file_xml(path)
+        .map_err(|e| format!("Failed to write ExportOptions.plist: {}", e))
+}
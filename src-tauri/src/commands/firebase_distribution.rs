@@ -0,0 +1,163 @@
+use crate::commands::google_auth::{check, ServiceAccountKey};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Production endpoint of the Firebase App Distribution API
+pub const FIREBASE_APP_DISTRIBUTION_URL: &str = "https://firebaseappdistribution.googleapis.com";
+
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+
+/// Firebase rejects release notes longer than this
+const RELEASE_NOTES_LIMIT: usize = 16000;
+
+/// A release created (or found, for a binary uploaded before) by `upload`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Release {
+    /// `projects/{number}/apps/{app_id}/releases/{release_id}`
+    pub name: String,
+    #[serde(default)]
+    pub display_version: String,
+    #[serde(default)]
+    pub build_version: String,
+}
+
+/// What `upload` did with the binary
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadResult {
+    /// `RELEASE_CREATED`, `RELEASE_UPDATED` or `RELEASE_UNMODIFIED`
+    pub result: String,
+    pub release: Release,
+}
+
+/// The project number of a Firebase app id, `1:<project number>:<platform>:<hash>`
+pub fn project_number(app_id: &str) -> Result<&str, String> {
+    let parts: Vec<&str> = app_id.split(':').collect();
+    match parts.as_slice() {
+        [_, number, "android" | "ios", _] if number.chars().all(|c| c.is_ascii_digit()) => Ok(number),
+        _ => Err(format!("Invalid Firebase App ID {}, expected 1:<project number>:android|ios:<hash>", app_id)),
+    }
+}
+
+/// A client of the Firebase App Distribution API (v1), authenticated with a service account
+pub struct AppDistributionClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: String,
+}
+
+impl AppDistributionClient {
+    /// Authenticate with the service account for the Cloud Platform scope
+    pub async fn authenticate(key: &ServiceAccountKey, base_url: &str) -> Result<Self, String> {
+        let http = reqwest::Client::new();
+        let access_token = key.access_token(&http, SCOPE).await?;
+        Ok(AppDistributionClient { http, base_url: base_url.trim_end_matches('/').to_string(), access_token })
+    }
+
+    /// Upload an APK, AAB or IPA. Returns the name of the long-running operation that
+    /// processes it, to follow with `wait_for_release`.
+    pub async fn upload(&self, app_id: &str, artifact: &Path) -> Result<String, String> {
+        let bytes = tokio::fs::read(artifact).await.map_err(|e| format!("Failed to read {}: {}", artifact.display(), e))?;
+        let file_name = artifact.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let url = format!(
+            "{}/upload/v1/projects/{}/apps/{}/releases:upload",
+            self.base_url,
+            project_number(app_id)?,
+            app_id
+        );
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(&self.access_token)
+            .header("X-Goog-Upload-Protocol", "raw")
+            .header("X-Goog-Upload-File-Name", file_name)
+            .header("Content-Type", "application/octet-stream")
+            .body(bytes)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let operation: Value = check(response, "Uploading the binary").await?.json().await.map_err(|e| e.to_string())?;
+        operation["name"]
+            .as_str()
+            .map(|name| name.to_string())
+            .ok_or_else(|| "Uploading the binary: no operation was returned".to_string())
+    }
+
+    /// Poll the upload operation until Firebase has processed the binary.
+    /// Returns `Ok(None)` when `timeout` runs out first.
+    pub async fn wait_for_release(
+        &self,
+        operation: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<Option<UploadResult>, String> {
+        let started = Instant::now();
+        loop {
+            let response = self
+                .http
+                .get(format!("{}/v1/{}", self.base_url, operation))
+                .bearer_auth(&self.access_token)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            let operation: Value = check(response, "Checking the upload").await?.json().await.map_err(|e| e.to_string())?;
+
+            if operation["done"].as_bool() == Some(true) {
+                if let Some(message) = operation["error"]["message"].as_str() {
+                    return Err(format!("Firebase could not process the binary: {}", message));
+                }
+                let release: Release = serde_json::from_value(operation["response"]["release"].clone())
+                    .map_err(|e| format!("Checking the upload: unexpected release: {}", e))?;
+                let result = operation["response"]["result"].as_str().unwrap_or("RELEASE_CREATED").to_string();
+                return Ok(Some(UploadResult { result, release }));
+            }
+
+            if started.elapsed() + interval > timeout {
+                return Ok(None);
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Replace the release notes testers see
+    pub async fn set_release_notes(&self, release: &str, notes: &str) -> Result<(), String> {
+        let notes: String = notes.trim().chars().take(RELEASE_NOTES_LIMIT).collect();
+        let response = self
+            .http
+            .patch(format!("{}/v1/{}", self.base_url, release))
+            .query(&[("updateMask", "release_notes.text")])
+            .bearer_auth(&self.access_token)
+            .json(&json!({ "name": release, "releaseNotes": { "text": notes } }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        check(response, "Setting the release notes").await?;
+        Ok(())
+    }
+
+    /// Give testers, by email, and tester groups, by alias, access to the release
+    pub async fn distribute(&self, release: &str, tester_emails: &[String], group_aliases: &[String]) -> Result<(), String> {
+        let response = self
+            .http
+            .post(format!("{}/v1/{}:distribute", self.base_url, release))
+            .bearer_auth(&self.access_token)
+            .json(&json!({ "testerEmails": tester_emails, "groupAliases": group_aliases }))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        check(response, "Distributing the release").await?;
+        Ok(())
+    }
+}
+
+/// The entries of a comma-separated setting such as `distribution_groups`
+pub fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
@@ -0,0 +1,71 @@
+use crate::models::project::GitProvenance;
+use std::path::Path;
+use std::process::Command;
+
+/// Run a git command in `dir`, returning trimmed stdout or git's error output
+pub fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    // Only trim the end: `status --porcelain` lines start with a meaningful space
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// Capture the commit, branch, tag and working tree state of the project.
+/// Returns `None` when the project is not inside a git repository (or git is not installed).
+pub fn capture_provenance(project_path: &Path) -> Option<GitProvenance> {
+    let commit = git(project_path, &["rev-parse", "HEAD"]).ok()?;
+
+    let branch = git(project_path, &["rev-parse", "--abbrev-ref", "HEAD"])
+        .ok()
+        .filter(|b| b != "HEAD"); // detached HEAD
+    let tag = git(project_path, &["describe", "--tags", "--exact-match", "HEAD"]).ok();
+
+    // Limited to the project directory, so unrelated changes elsewhere in a monorepo don't count
+    let uncommitted_files: Vec<String> = git(project_path, &["status", "--porcelain", "--", "."])
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| line.len() > 3)
+        .filter_map(|line| line[3..].rsplit(" -> ").next()) // renames: `R  old -> new`
+        .map(|path| path.trim_matches('"').to_string())
+        .collect();
+
+    Some(GitProvenance {
+        commit,
+        branch,
+        tag,
+        dirty: !uncommitted_files.is_empty(),
+        uncommitted_files,
+    })
+}
+
+/// Create an annotated tag on `commit`. Returns `false` when the tag already points at
+/// `commit` (a build publishing to several places tags once per upload), and fails if it
+/// points at another commit.
+pub fn create_tag(project_path: &Path, name: &str, commit: &str, message: &str) -> Result<bool, String> {
+    let existing = git(project_path, &["rev-parse", "--verify", "--quiet", &format!("refs/tags/{}^{{commit}}", name)]);
+    if let Ok(tagged) = existing {
+        let commit = git(project_path, &["rev-parse", "--verify", &format!("{}^{{commit}}", commit)])?;
+        if tagged == commit {
+            return Ok(false);
+        }
+        return Err(format!("tag {} already exists on commit {}", name, &tagged[..tagged.len().min(10)]));
+    }
+    git(project_path, &["tag", "-a", name, commit, "-m", message]).map(|_| true)
+}
+
+/// Tag name for a published build, e.g. `ios/1.4.0+57`, or `ios-staging/1.4.0+57` for a
+/// build profile
+pub fn release_tag_name(platform: &str, profile: Option<&str>, version: &str, build_number: u32) -> String {
+    match profile {
+        Some(profile) => format!("{}-{}/{}+{}", platform, profile, version, build_number),
+        None => format!("{}/{}+{}", platform, version, build_number),
+    }
+}
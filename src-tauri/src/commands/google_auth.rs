@@ -0,0 +1,93 @@
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// The parts of a Google Cloud service account JSON key the clients use
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    pub client_email: String,
+    pub private_key: String,
+    pub private_key_id: Option<String>,
+    pub token_uri: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+impl ServiceAccountKey {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid service account JSON: {}", e))
+    }
+
+    /// The key saved by `create_credential` for an Android credential
+    pub fn load(credential_id: &str) -> Result<Self, String> {
+        let entry = Entry::new("app-builder-credentials", &format!("android-service-account-{}", credential_id))
+            .map_err(|e| e.to_string())?;
+        let json = entry
+            .get_password()
+            .map_err(|e| format!("Service account key not found in Keychain: {}", e))?;
+        Self::from_json(&json)
+    }
+
+    /// Exchange a signed JWT for an access token to `scope` at the key's token endpoint
+    pub async fn access_token(&self, http: &reqwest::Client, scope: &str) -> Result<String, String> {
+        let token_uri = self.token_uri.as_deref().unwrap_or(DEFAULT_TOKEN_URI);
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims { iss: &self.client_email, scope, aud: token_uri, iat: now, exp: now + 3600 };
+
+        let mut header = jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256);
+        header.kid = self.private_key_id.clone();
+        let encoding_key = jsonwebtoken::EncodingKey::from_rsa_pem(self.private_key.as_bytes())
+            .map_err(|e| format!("Invalid service account private key: {}", e))?;
+        let assertion = jsonwebtoken::encode(&header, &claims, &encoding_key).map_err(|e| e.to_string())?;
+
+        let response = http
+            .post(token_uri)
+            .form(&[("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"), ("assertion", assertion.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach {}: {}", token_uri, e))?;
+        let token: TokenResponse = check(response, "Service account authentication")
+            .await?
+            .json()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(token.access_token)
+    }
+}
+
+/// Turn a non-success response from a Google API into an error carrying Google's message
+pub async fn check(response: reqwest::Response, action: &str) -> Result<reqwest::Response, String> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|v| {
+            let error = v.get("error")?;
+            // OAuth errors: {"error": "invalid_grant", "error_description": "..."}
+            // API errors: {"error": {"code": 403, "message": "..."}}
+            error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .or_else(|| v.get("error_description").and_then(|m| m.as_str()))
+                .or_else(|| error.as_str())
+                .map(|m| m.to_string())
+        })
+        .unwrap_or(body);
+    Err(format!("{} failed ({}): {}", action, status, message))
+}
@@ -0,0 +1,215 @@
+use crate::commands::google_auth::{check, ServiceAccountKey};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Production endpoint of the Google Play Developer API
+pub const ANDROID_PUBLISHER_URL: &str = "https://androidpublisher.googleapis.com";
+
+const SCOPE: &str = "https://www.googleapis.com/auth/androidpublisher";
+
+pub const TRACKS: &[&str] = &["internal", "alpha", "beta", "production"];
+
+#[derive(Deserialize)]
+struct Edit {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Bundle {
+    version_code: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalizedText {
+    pub language: String,
+    pub text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackRelease<'a> {
+    version_codes: Vec<String>,
+    status: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    release_notes: Vec<LocalizedText>,
+}
+
+#[derive(Serialize)]
+struct Track<'a> {
+    track: &'a str,
+    releases: Vec<TrackRelease<'a>>,
+}
+
+/// What to publish with `PlayClient::publish`
+pub struct PlayRelease<'a> {
+    pub package_name: &'a str,
+    pub bundle_path: &'a Path,
+    /// `internal`, `alpha`, `beta` or `production`
+    pub track: &'a str,
+    /// `completed`, or `draft` for apps that were never published
+    pub status: &'a str,
+    pub release_notes: Vec<LocalizedText>,
+}
+
+/// A client of the Android Publisher API (v3), authenticated with a service account
+pub struct PlayClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: String,
+}
+
+impl PlayClient {
+    /// Authenticate with the service account for the Android Publisher scope
+    pub async fn authenticate(key: &ServiceAccountKey, base_url: &str) -> Result<Self, String> {
+        let http = reqwest::Client::new();
+        let access_token = key.access_token(&http, SCOPE).await?;
+        Ok(PlayClient { http, base_url: base_url.trim_end_matches('/').to_string(), access_token })
+    }
+
+    fn edits_url(&self, package_name: &str) -> String {
+        format!("{}/androidpublisher/v3/applications/{}/edits", self.base_url, package_name)
+    }
+
+    /// Open an edit: every change is staged in it until `commit_edit`
+    pub async fn insert_edit(&self, package_name: &str) -> Result<String, String> {
+        let response = self
+            .http
+            .post(self.edits_url(package_name))
+            .bearer_auth(&self.access_token)
+            .json(&serde_json::json!({}))
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let edit: Edit = check(response, "Creating the edit").await?.json().await.map_err(|e| e.to_string())?;
+        Ok(edit.id)
+    }
+
+    /// Upload an Android App Bundle, returning its version code
+    pub async fn upload_bundle(&self, package_name: &str, edit_id: &str, bundle_path: &Path) -> Result<u32, String> {
+        let bytes = tokio::fs::read(bundle_path).await.map_err(|e| format!("Failed to read {}: {}", bundle_path.display(), e))?;
+        let url = format!(
+            "{}/upload/androidpublisher/v3/applications/{}/edits/{}/bundles?uploadType=media",
+            self.base_url, package_name, edit_id
+        );
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(&self.access_token)
+            .header("Content-Type", "application/octet-stream")
+            .body(bytes)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        let bundle: Bundle = check(response, "Uploading the bundle").await?.json().await.map_err(|e| e.to_string())?;
+        Ok(bundle.version_code)
+    }
+
+    /// Make `version_code` the release of `track`
+    pub async fn update_track(
+        &self,
+        package_name: &str,
+        edit_id: &str,
+        track: &str,
+        version_code: u32,
+        status: &str,
+        release_notes: Vec<LocalizedText>,
+    ) -> Result<(), String> {
+        let body = Track {
+            track,
+            releases: vec![TrackRelease { version_codes: vec![version_code.to_string()], status, release_notes }],
+        };
+        let response = self
+            .http
+            .put(format!("{}/{}/tracks/{}", self.edits_url(package_name), edit_id, track))
+            .bearer_auth(&self.access_token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        check(response, &format!("Assigning the release to the {} track", track)).await?;
+        Ok(())
+    }
+
+    pub async fn commit_edit(&self, package_name: &str, edit_id: &str) -> Result<(), String> {
+        let response = self
+            .http
+            .post(format!("{}/{}:commit", self.edits_url(package_name), edit_id))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        check(response, "Committing the edit").await?;
+        Ok(())
+    }
+
+    pub async fn delete_edit(&self, package_name: &str, edit_id: &str) -> Result<(), String> {
+        let response = self
+            .http
+            .delete(format!("{}/{}", self.edits_url(package_name), edit_id))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        check(response, "Deleting the edit").await?;
+        Ok(())
+    }
+
+    /// Upload the bundle, assign it to the track and commit, in one edit.
+    /// The edit is deleted when a step fails, so nothing is left half-published.
+    /// Returns the version code Google Play assigned.
+    pub async fn publish(&self, release: &PlayRelease<'_>) -> Result<u32, String> {
+        if !TRACKS.contains(&release.track) {
+            return Err(format!("Unknown track {}, expected one of {}", release.track, TRACKS.join(", ")));
+        }
+
+        let edit_id = self.insert_edit(release.package_name).await?;
+        let result = async {
+            let version_code = self.upload_bundle(release.package_name, &edit_id, release.bundle_path).await?;
+            self.update_track(
+                release.package_name,
+                &edit_id,
+                release.track,
+                version_code,
+                release.status,
+                release.release_notes.clone(),
+            )
+            .await?;
+            self.commit_edit(release.package_name, &edit_id).await?;
+            Ok::<u32, String>(version_code)
+        }
+        .await;
+
+        if result.is_err() {
+            let _ = self.delete_edit(release.package_name, &edit_id).await;
+        }
+        result
+    }
+}
+
+/// Release notes for each locale. Notes written the way the Play Console takes them,
+/// `<en-US>…</en-US><fr-FR>…</fr-FR>`, are split per locale; plain notes go to every locale
+/// in `locales`. Play limits each text to 500 characters.
+pub fn release_notes_for(locales: &[String], notes: &str) -> Vec<LocalizedText> {
+    let truncate = |text: &str| text.trim().chars().take(500).collect::<String>();
+
+    let tagged = regex::Regex::new(r"(?s)<([a-z]{2,3}(?:-[A-Za-z0-9]+)*)>(.*?)</([a-z]{2,3}(?:-[A-Za-z0-9]+)*)>").unwrap();
+    let localized: Vec<LocalizedText> = tagged
+        .captures_iter(notes)
+        .filter(|caps| caps[1] == caps[3])
+        .map(|caps| LocalizedText { language: caps[1].to_string(), text: truncate(&caps[2]) })
+        .filter(|note| !note.text.is_empty())
+        .collect();
+    if !localized.is_empty() {
+        return localized;
+    }
+
+    let text = truncate(notes);
+    if text.is_empty() {
+        return Vec::new();
+    }
+    locales
+        .iter()
+        .map(|language| LocalizedText { language: language.clone(), text: text.clone() })
+        .collect()
+}
@@ -0,0 +1,262 @@
+use crate::commands::versioning::FileChange;
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// A `libs.versions.<accessor>.get()` version catalog reference
+static CATALOG_REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"libs\.versions\.([A-Za-z0-9_.]+?)\.get\(\)").unwrap());
+
+/// A quoted property name, e.g. in `findProperty("VERSION_CODE")`
+static QUOTED_NAME: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"["']([A-Za-z0-9_.]+)["']"#).unwrap());
+
+static IDENTIFIER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap());
+
+/// A `name = value` entry of the version catalog, with a quoted or numeric value
+static CATALOG_ENTRY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^(\s*"?([A-Za-z0-9_.\-]+)"?\s*=\s*)("[^"]*"|'[^']*'|\d+)(.*)$"#).unwrap());
+
+/// Words that can appear in a version expression without naming a property,
+/// e.g. `(project.findProperty("VERSION_CODE") as String).toInt()`
+const EXPRESSION_WORDS: &[&str] = &[
+    "project", "rootProject", "ext", "extra", "findProperty", "property", "properties", "toInteger",
+    "toInt", "as", "String", "Integer", "Int", "get", "libs", "versions",
+];
+
+/// Where a version value is defined: a line split around the value, quotes excluded
+struct Location {
+    file: PathBuf,
+    /// 0-based
+    line: usize,
+    prefix: String,
+    value: String,
+    quote: Option<char>,
+    suffix: String,
+    /// The line as it is now
+    text: String,
+}
+
+impl Location {
+    fn render(&self, value: &str) -> String {
+        let mut prefix = self.prefix.clone();
+        // `versionCode 12` is Groovy only; the Kotlin DSL needs an assignment
+        let is_kts = self.file.extension().is_some_and(|e| e == "kts");
+        if is_kts && !prefix.contains('=') {
+            prefix = format!("{} = ", prefix.trim_end());
+        }
+        match self.quote {
+            // Kotlin strings are always double-quoted
+            Some('\'') if is_kts => format!("{}\"{}\"{}", prefix, value, self.suffix),
+            Some(q) => format!("{}{}{}{}{}", prefix, q, value, q, self.suffix),
+            None => format!("{}{}{}", prefix, value, self.suffix),
+        }
+    }
+}
+
+/// `android/app/build.gradle`, or its Kotlin DSL variant
+pub fn app_build_file(android_dir: &Path) -> Option<PathBuf> {
+    ["app/build.gradle", "app/build.gradle.kts"]
+        .iter()
+        .map(|f| android_dir.join(f))
+        .find(|p| p.exists())
+}
+
+fn read_lines(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|c| c.lines().map(|l| l.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Split `expr` into its literal value and quote, if it is a literal
+fn literal(expr: &str) -> Option<(String, Option<char>)> {
+    let expr = expr.trim();
+    for q in ['"', '\''] {
+        if expr.len() >= 2 && expr.starts_with(q) && expr.ends_with(q) {
+            let inner = &expr[1..expr.len() - 1];
+            if !inner.contains('$') && !inner.contains(q) {
+                return Some((inner.to_string(), Some(q)));
+            }
+        }
+    }
+    if !expr.is_empty() && expr.chars().all(|c| c.is_ascii_digit()) {
+        return Some((expr.to_string(), None));
+    }
+    None
+}
+
+/// Lines of the `defaultConfig { … }` block, or every line when there is none
+fn default_config_range(lines: &[String]) -> std::ops::Range<usize> {
+    let Some(start) = lines.iter().position(|l| l.trim_start().starts_with("defaultConfig") && l.contains('{')) else {
+        return 0..lines.len();
+    };
+    let mut depth = 0i32;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        depth += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        if depth <= 0 {
+            return start..i + 1;
+        }
+    }
+    start..lines.len()
+}
+
+/// Matches `<key> = <value>` (or Groovy `<key> <value>`) for a key matching `key_pattern`
+fn assignment_pattern(key_pattern: &str) -> Regex {
+    Regex::new(&format!(r"^(\s*{}(?:\s*[=:]\s*|\s+))(.+?)(\s*(?://.*)?)$", key_pattern)).unwrap()
+}
+
+/// Find an `assignment_pattern` line in `range` of `file`'s lines
+fn find_assignment(lines: &[String], range: std::ops::Range<usize>, re: &Regex) -> Option<(usize, String, String, String)> {
+    range.into_iter().find_map(|i| {
+        let caps = re.captures(&lines[i])?;
+        Some((i, caps[1].to_string(), caps[2].to_string(), caps[3].to_string()))
+    })
+}
+
+/// A literal assignment of `key` in one of `files`
+fn find_literal(files: &[PathBuf], key_pattern: &str) -> Option<Location> {
+    let re = assignment_pattern(key_pattern);
+    files.iter().find_map(|file| {
+        let lines = read_lines(file);
+        let (line, prefix, expr, suffix) = find_assignment(&lines, 0..lines.len(), &re)?;
+        let (value, quote) = literal(&expr).or_else(|| Some((expr.trim().to_string(), None)).filter(|_| is_properties(file)))?;
+        Some(Location { file: file.clone(), text: lines[line].clone(), line, prefix, value, quote, suffix })
+    })
+}
+
+fn is_properties(file: &Path) -> bool {
+    file.extension().is_some_and(|e| e == "properties")
+}
+
+/// An entry of the `[versions]` table of the version catalog. Gradle's accessors treat
+/// `-`, `_` and `.` alike, so `libs.versions.app.versionCode` matches `app-versionCode`.
+fn find_catalog_version(android_dir: &Path, accessor: &str) -> Option<Location> {
+    let file = android_dir.join("gradle/libs.versions.toml");
+    let normalize = |s: &str| s.replace(['-', '_'], ".");
+
+    let mut in_versions = false;
+    for (i, line) in read_lines(&file).iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_versions = trimmed == "[versions]";
+            continue;
+        }
+        if !in_versions {
+            continue;
+        }
+        if let Some(caps) = CATALOG_ENTRY.captures(line) {
+            if normalize(&caps[2]) == normalize(accessor) {
+                let (value, quote) = literal(&caps[3])?;
+                return Some(Location {
+                    file: file.clone(),
+                    text: line.clone(),
+                    line: i,
+                    prefix: caps[1].to_string(),
+                    value,
+                    quote,
+                    suffix: caps[4].to_string(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Follow a version expression to where its value is defined: a version catalog entry,
+/// a `gradle.properties` property or an `ext` value of the root build file
+fn resolve_reference(android_dir: &Path, expr: &str) -> Option<Location> {
+    if let Some(caps) = CATALOG_REFERENCE.captures(expr) {
+        return find_catalog_version(android_dir, &caps[1]);
+    }
+
+    // Computed values (`"${major}.${minor}"`, `major * 10000 + minor`) have no single place to write to
+    if QUOTED_NAME.replace_all(expr, "").contains(['$', '+', '-', '*', '/']) {
+        return None;
+    }
+    let mut names: Vec<&str> = QUOTED_NAME.captures_iter(expr).filter_map(|c| c.get(1)).map(|m| m.as_str()).collect();
+    names.extend(IDENTIFIER.find_iter(expr).map(|m| m.as_str()).filter(|w| !EXPRESSION_WORDS.contains(w)));
+    names.dedup();
+
+    let files = [
+        android_dir.join("gradle.properties"),
+        android_dir.join("app/gradle.properties"),
+        android_dir.join("build.gradle"),
+        android_dir.join("build.gradle.kts"),
+    ];
+    names.into_iter().find_map(|name| {
+        let pattern = format!(r"(?:ext\.)?{}", regex::escape(name));
+        find_literal(&files, &pattern)
+    })
+}
+
+/// Where `key` (`versionCode` / `versionName`) of the app module gets its value
+fn locate(android_dir: &Path, key: &str) -> Result<Option<Location>, String> {
+    let Some(build_file) = app_build_file(android_dir) else {
+        return Ok(None);
+    };
+    let lines = read_lines(&build_file);
+    let range = default_config_range(&lines);
+    let Some((line, prefix, expr, suffix)) = find_assignment(&lines, range, &assignment_pattern(key)) else {
+        return Ok(None);
+    };
+
+    if let Some((value, quote)) = literal(&expr) {
+        let text = lines[line].clone();
+        return Ok(Some(Location { file: build_file, line, prefix, value, quote, suffix, text }));
+    }
+    match resolve_reference(android_dir, &expr) {
+        Some(location) => Ok(Some(location)),
+        None => Err(format!(
+            "{}:{}: cannot update {} = {}, it is not a literal, a gradle.properties property or a version catalog entry",
+            build_file.display(),
+            line + 1,
+            key,
+            expr.trim()
+        )),
+    }
+}
+
+/// The versionName and versionCode of the app module, following properties and the version catalog
+pub fn read_android_versions(android_dir: &Path) -> (Option<String>, Option<u32>) {
+    let version_name = locate(android_dir, "versionName").ok().flatten().map(|l| l.value);
+    let version_code = locate(android_dir, "versionCode").ok().flatten().and_then(|l| l.value.parse().ok());
+    (version_name, version_code)
+}
+
+fn write_line(location: &Location, new_line: &str) -> Result<(), String> {
+    let content = fs::read_to_string(&location.file)
+        .map_err(|e| format!("Failed to read {}: {}", location.file.display(), e))?;
+    let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let Some(old) = lines.get(location.line) else {
+        return Err(format!("{} changed while it was being updated", location.file.display()));
+    };
+    let ending = &old[old.trim_end_matches(['\r', '\n']).len()..];
+    let replaced = format!("{}{}", new_line, ending);
+    lines[location.line] = &replaced;
+
+    fs::write(&location.file, lines.concat())
+        .map_err(|e| format!("Failed to write {}: {}", location.file.display(), e))
+}
+
+/// Set the app module's versionName and versionCode wherever they are defined: the app's
+/// `build.gradle` / `build.gradle.kts`, `gradle.properties`, the version catalog or the
+/// root build file. Returns the lines that changed.
+pub fn update_android_versions(android_dir: &Path, version_name: &str, version_code: u32) -> Result<Vec<FileChange>, String> {
+    let mut changes = Vec::new();
+    for (key, value) in [("versionCode", version_code.to_string()), ("versionName", version_name.to_string())] {
+        let Some(location) = locate(android_dir, key)? else {
+            continue;
+        };
+        let new = location.render(&value);
+        if location.text == new {
+            continue;
+        }
+        write_line(&location, &new)?;
+        changes.push(FileChange {
+            file: location.file.clone(),
+            line: location.line + 1,
+            old: location.text.trim().to_string(),
+            new: new.trim().to_string(),
+        });
+    }
+    Ok(changes)
+}
@@ -1,8 +1,10 @@
-use crate::models::project::BuildHistory;
+use crate::commands::diagnostics::load_build_diagnostics;
+use crate::commands::timings::load_build_timing;
+use crate::models::project::{BuildHistory, GitProvenance};
 use crate::DbState;
 use serde::Serialize;
 use tauri::{command, State};
-use rusqlite::params;
+use rusqlite::{params, Connection};
 
 #[derive(Serialize)]
 pub struct PaginatedBuildHistory {
@@ -10,12 +12,11 @@ pub struct PaginatedBuildHistory {
     pub total: i64,
 }
 
-#[command]
-pub async fn save_build_history(state: State<'_, DbState>, history: BuildHistory) -> Result<(), String> {
-    let conn = state.0.lock().map_err(|e| e.to_string())?;
+pub fn insert_build_history(conn: &Connection, history: &BuildHistory) -> Result<(), String> {
     conn.execute(
-        "INSERT INTO build_history (id, project_id, platform, version, build_number, status, timestamp, logs, release_note, format, artifact_path, log_file_path)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        "INSERT INTO build_history (id, project_id, platform, version, build_number, status, timestamp, logs, release_note, format, artifact_path, log_file_path,
+            git_commit, git_branch, git_tag, git_dirty, git_uncommitted_files, profile)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
         params![
             history.id,
             history.project_id,
@@ -29,6 +30,12 @@ pub async fn save_build_history(state: State<'_, DbState>, history: BuildHistory
             history.format,
             history.artifact_path,
             history.log_file_path,
+            history.git.as_ref().map(|g| &g.commit),
+            history.git.as_ref().and_then(|g| g.branch.as_ref()),
+            history.git.as_ref().and_then(|g| g.tag.as_ref()),
+            history.git.as_ref().map(|g| g.dirty),
+            history.git.as_ref().map(|g| serde_json::to_string(&g.uncommitted_files).unwrap_or_default()),
+            history.profile,
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -36,6 +43,29 @@ pub async fn save_build_history(state: State<'_, DbState>, history: BuildHistory
     Ok(())
 }
 
+/// Read the `git_*` columns starting at `first`
+fn git_from_row(row: &rusqlite::Row, first: usize) -> rusqlite::Result<Option<GitProvenance>> {
+    let Some(commit) = row.get::<_, Option<String>>(first)? else {
+        return Ok(None);
+    };
+    let uncommitted_files: Option<String> = row.get(first + 4)?;
+    Ok(Some(GitProvenance {
+        commit,
+        branch: row.get(first + 1)?,
+        tag: row.get(first + 2)?,
+        dirty: row.get::<_, Option<bool>>(first + 3)?.unwrap_or(false),
+        uncommitted_files: uncommitted_files
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default(),
+    }))
+}
+
+#[command]
+pub async fn save_build_history(state: State<'_, DbState>, history: BuildHistory) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    insert_build_history(&conn, &history)
+}
+
 #[command]
 pub async fn list_build_history(
     state: State<'_, DbState>,
@@ -60,7 +90,8 @@ pub async fn list_build_history(
 
         let mut stmt = conn
             .prepare(
-                "SELECT h.id, h.project_id, h.platform, h.version, h.build_number, h.status, h.timestamp, h.logs, h.release_note, h.format, h.artifact_path, h.log_file_path
+                "SELECT h.id, h.project_id, h.platform, h.version, h.build_number, h.status, h.timestamp, h.logs, h.release_note, h.format, h.artifact_path, h.log_file_path,
+                        h.git_commit, h.git_branch, h.git_tag, h.git_dirty, h.git_uncommitted_files, h.profile
                  FROM build_history h
                  INNER JOIN projects p ON h.project_id = p.id
                  WHERE h.project_id = ?1
@@ -84,6 +115,10 @@ pub async fn list_build_history(
                     format: row.get(9)?,
                     artifact_path: row.get(10)?,
                     log_file_path: row.get(11)?,
+                    profile: row.get(17)?,
+                    git: git_from_row(row, 12)?,
+                    diagnostics: Vec::new(),
+                    timing: None,
                 })
             })
             .map_err(|e| e.to_string())?;
@@ -102,7 +137,8 @@ pub async fn list_build_history(
 
         let mut stmt = conn
             .prepare(
-                "SELECT h.id, h.project_id, h.platform, h.version, h.build_number, h.status, h.timestamp, h.logs, h.release_note, h.format, h.artifact_path, h.log_file_path
+                "SELECT h.id, h.project_id, h.platform, h.version, h.build_number, h.status, h.timestamp, h.logs, h.release_note, h.format, h.artifact_path, h.log_file_path,
+                        h.git_commit, h.git_branch, h.git_tag, h.git_dirty, h.git_uncommitted_files, h.profile
                  FROM build_history h
                  INNER JOIN projects p ON h.project_id = p.id
                  ORDER BY h.timestamp DESC
@@ -125,6 +161,10 @@ pub async fn list_build_history(
                     format: row.get(9)?,
                     artifact_path: row.get(10)?,
                     log_file_path: row.get(11)?,
+                    profile: row.get(17)?,
+                    git: git_from_row(row, 12)?,
+                    diagnostics: Vec::new(),
+                    timing: None,
                 })
             })
             .map_err(|e| e.to_string())?;
@@ -134,5 +174,10 @@ pub async fn list_build_history(
         }
     }
 
+    for item in items.iter_mut() {
+        item.diagnostics = load_build_diagnostics(&conn, &item.id)?;
+        item.timing = load_build_timing(&conn, &item.id)?;
+    }
+
     Ok(PaginatedBuildHistory { items, total })
 }
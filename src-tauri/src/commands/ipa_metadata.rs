@@ -0,0 +1,269 @@
+use crate::commands::provisioning::parse_profile;
+use chrono::{DateTime, Utc};
+use goblin::mach::constants::cputype::{get_arch_name_from_types, CPU_TYPE_I386, CPU_TYPE_X86_64};
+use goblin::mach::load_command::{
+    CommandVariant, PLATFORM_IOS, PLATFORM_IOSSIMULATOR, PLATFORM_MACCATALYST, PLATFORM_MACOS, PLATFORM_TVOS, PLATFORM_TVOSSIMULATOR,
+    PLATFORM_VISIONOS, PLATFORM_VISIONOSSIMULATOR, PLATFORM_WATCHOS, PLATFORM_WATCHOSSIMULATOR,
+};
+use goblin::mach::{Mach, MachO, SingleArch};
+use plist::Value;
+use serde::{Deserialize, Serialize};
+use std::io::{Cursor, Read, Seek};
+use zip::ZipArchive;
+
+/// Capability bits (such as arm64e pointer authentication) in the high byte of a CPU subtype
+const CPU_SUBTYPE_MASK: u32 = 0xff00_0000;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentSize {
+    pub name: String,
+    pub file_size: u64,
+    pub vm_size: u64,
+}
+
+/// One architecture of a Mach-O binary
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachOSlice {
+    pub arch: String,
+    /// ios, iossimulator, ... from LC_BUILD_VERSION, or from LC_VERSION_MIN_* on older binaries
+    pub platform: Option<String>,
+    pub minimum_os: Option<String>,
+    /// `cryptid` of LC_ENCRYPTION_INFO; binaries downloaded from the App Store are encrypted
+    pub encrypted: Option<bool>,
+    pub segments: Vec<SegmentSize>,
+    /// Built for a simulator, which App Store Connect rejects
+    pub simulator: bool,
+    /// Has STABS debug entries or a __DWARF segment, i.e. was not stripped
+    pub debug_symbols: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MachOBinary {
+    pub path: String,
+    /// app, extension, framework or dylib
+    pub kind: String,
+    pub size: u64,
+    pub slices: Vec<MachOSlice>,
+    /// Libraries and frameworks the binary loads, across all slices
+    pub linked_libraries: Vec<String>,
+}
+
+/// The embedded.mobileprovision an IPA was signed with
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmbeddedProfile {
+    pub name: String,
+    pub team_id: String,
+    pub profile_type: String,
+    pub expires: String,
+    pub expired: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpaInfo {
+    pub bundle_id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub build_number: Option<String>,
+    pub minimum_os: Option<String>,
+    pub supported_platforms: Vec<String>,
+    pub profile: Option<EmbeddedProfile>,
+    pub binaries: Vec<MachOBinary>,
+    /// dSYM bundles shipped inside the app, which only add size
+    pub dsym_paths: Vec<String>,
+}
+
+/// Read Info.plist, embedded.mobileprovision and every Mach-O binary of the app in an IPA
+pub fn read_ipa<R: Read + Seek>(archive: &mut ZipArchive<R>, now: DateTime<Utc>) -> Result<IpaInfo, String> {
+    let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    let info_plist = names
+        .iter()
+        .find(|n| n.starts_with("Payload/") && n.ends_with(".app/Info.plist") && n.matches('/').count() == 2)
+        .ok_or("Payload/*.app/Info.plist not found")?;
+    let app_dir = info_plist.trim_end_matches("Info.plist").to_string();
+
+    let plist = Value::from_reader(Cursor::new(read_entry(archive, info_plist)?)).map_err(|e| format!("Invalid Info.plist: {}", e))?;
+    let dict = plist.as_dictionary().ok_or("Invalid Info.plist: not a dictionary")?;
+    let string = |key: &str| dict.get(key).and_then(|v| v.as_string()).map(str::to_string);
+    let executable = string("CFBundleExecutable").unwrap_or_default();
+
+    // A profile that can't be decoded doesn't stop the rest of the report
+    let profile_name = format!("{}embedded.mobileprovision", app_dir);
+    let profile = if names.contains(&profile_name) {
+        parse_profile(&read_entry(archive, &profile_name)?, &[], now).ok().map(|profile| EmbeddedProfile {
+            name: profile.name,
+            team_id: profile.team_id,
+            profile_type: profile.profile_type,
+            expires: profile.expires,
+            expired: profile.expired,
+        })
+    } else {
+        None
+    };
+
+    let mut binaries = Vec::new();
+    let mut dsym_paths = Vec::new();
+    for name in names.iter().filter(|n| n.starts_with(&app_dir) && !n.ends_with('/')) {
+        let relative = &name[app_dir.len()..];
+        if let Some(end) = relative.find(".dSYM/") {
+            let dsym = format!("{}{}", app_dir, &relative[..end + 5]);
+            if !dsym_paths.contains(&dsym) {
+                dsym_paths.push(dsym);
+            }
+            continue;
+        }
+        let file_name = relative.rsplit('/').next().unwrap_or_default();
+        if relative.contains("_CodeSignature/") || (file_name.contains('.') && !file_name.ends_with(".dylib")) {
+            continue;
+        }
+
+        let mut entry = archive.by_name(name).map_err(|e| e.to_string())?;
+        let size = entry.size();
+        let mut magic = [0u8; 4];
+        if entry.read_exact(&mut magic).is_err() || !is_macho_magic(magic) {
+            continue;
+        }
+        let mut bytes = magic.to_vec();
+        entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+
+        let kind = if relative == executable {
+            "app"
+        } else if relative.contains(".appex/") {
+            "extension"
+        } else if relative.contains(".framework/") {
+            "framework"
+        } else {
+            "dylib"
+        };
+        let (slices, linked_libraries) = parse_macho(&bytes).map_err(|e| format!("Failed to parse {}: {}", name, e))?;
+        binaries.push(MachOBinary { path: name.clone(), kind: kind.to_string(), size, slices, linked_libraries });
+    }
+    // The app's executable first, then the largest binaries
+    binaries.sort_by_key(|b| (b.kind != "app", std::cmp::Reverse(b.size)));
+
+    Ok(IpaInfo {
+        bundle_id: string("CFBundleIdentifier").unwrap_or_default(),
+        name: string("CFBundleDisplayName").or_else(|| string("CFBundleName")),
+        version: string("CFBundleShortVersionString"),
+        build_number: string("CFBundleVersion"),
+        minimum_os: string("MinimumOSVersion"),
+        supported_platforms: dict
+            .get("CFBundleSupportedPlatforms")
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_string()).map(str::to_string).collect())
+            .unwrap_or_default(),
+        profile,
+        binaries,
+        dsym_paths,
+    })
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Result<Vec<u8>, String> {
+    let mut entry = archive.by_name(name).map_err(|e| format!("{} not found: {}", name, e))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    Ok(bytes)
+}
+
+/// 32/64-bit Mach-O in either byte order, or a fat binary
+fn is_macho_magic(magic: [u8; 4]) -> bool {
+    matches!(u32::from_be_bytes(magic), 0xfeed_face | 0xfeed_facf | 0xcefa_edfe | 0xcffa_edfe | 0xcafe_babe)
+}
+
+/// The slices of a thin or fat Mach-O and the libraries they link
+pub fn parse_macho(bytes: &[u8]) -> Result<(Vec<MachOSlice>, Vec<String>), String> {
+    let mut slices = Vec::new();
+    let mut libraries: Vec<String> = Vec::new();
+    let mut add = |macho: &MachO| {
+        for lib in macho.libs.iter().filter(|l| **l != "self") {
+            if !libraries.iter().any(|known| known == lib) {
+                libraries.push(lib.to_string());
+            }
+        }
+        slices.push(macho_slice(macho));
+    };
+
+    match Mach::parse(bytes).map_err(|e| e.to_string())? {
+        Mach::Binary(macho) => add(&macho),
+        Mach::Fat(fat) => {
+            for arch in &fat {
+                // Static archives can't be loaded, so they don't belong in an app
+                if let SingleArch::MachO(macho) = arch.map_err(|e| e.to_string())? {
+                    add(&macho);
+                }
+            }
+        }
+    }
+    Ok((slices, libraries))
+}
+
+fn macho_slice(macho: &MachO) -> MachOSlice {
+    let cputype = macho.header.cputype();
+    let cpusubtype = macho.header.cpusubtype();
+    let arch = get_arch_name_from_types(cputype, cpusubtype & !CPU_SUBTYPE_MASK)
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("cpu {}/{}", cputype, cpusubtype));
+
+    let mut platform = None;
+    let mut minimum_os = None;
+    let mut encrypted = None;
+    for command in &macho.load_commands {
+        match &command.command {
+            CommandVariant::BuildVersion(build) => {
+                platform = Some(platform_name(build.platform));
+                minimum_os = Some(format_version(build.minos));
+            }
+            CommandVariant::VersionMinIphoneos(min) if platform.is_none() => {
+                // Before LC_BUILD_VERSION, simulator slices were told apart by architecture
+                let simulator = cputype == CPU_TYPE_X86_64 || cputype == CPU_TYPE_I386;
+                platform = Some(if simulator { "iossimulator" } else { "ios" }.to_string());
+                minimum_os = Some(format_version(min.version));
+            }
+            CommandVariant::EncryptionInfo32(info) => encrypted = Some(info.cryptid != 0),
+            CommandVariant::EncryptionInfo64(info) => encrypted = Some(info.cryptid != 0),
+            _ => {}
+        }
+    }
+
+    let segments: Vec<SegmentSize> = macho
+        .segments
+        .iter()
+        .map(|s| SegmentSize { name: s.name().unwrap_or_default().to_string(), file_size: s.filesize, vm_size: s.vmsize })
+        .collect();
+    let debug_symbols = segments.iter().any(|s| s.name == "__DWARF")
+        || macho.symbols().any(|symbol| symbol.is_ok_and(|(_, nlist)| nlist.is_stab()));
+    let simulator = platform.as_deref().is_some_and(|p| p.ends_with("simulator"));
+
+    MachOSlice { arch, platform, minimum_os, encrypted, segments, simulator, debug_symbols }
+}
+
+fn platform_name(platform: u32) -> String {
+    let name = match platform {
+        PLATFORM_MACOS => "macos",
+        PLATFORM_IOS => "ios",
+        PLATFORM_TVOS => "tvos",
+        PLATFORM_WATCHOS => "watchos",
+        PLATFORM_MACCATALYST => "maccatalyst",
+        PLATFORM_IOSSIMULATOR => "iossimulator",
+        PLATFORM_TVOSSIMULATOR => "tvossimulator",
+        PLATFORM_WATCHOSSIMULATOR => "watchossimulator",
+        PLATFORM_VISIONOS => "visionos",
+        PLATFORM_VISIONOSSIMULATOR => "visionossimulator",
+        other => return format!("platform {}", other),
+    };
+    name.to_string()
+}
+
+/// X.Y.Z encoded in nibbles xxxx.yy.zz, without a zero patch version
+fn format_version(version: u32) -> String {
+    let (major, minor, patch) = (version >> 16, (version >> 8) & 0xff, version & 0xff);
+    if patch == 0 {
+        format!("{}.{}", major, minor)
+    } else {
+        format!("{}.{}.{}", major, minor, patch)
+    }
+}
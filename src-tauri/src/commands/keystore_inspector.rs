@@ -0,0 +1,316 @@
+use crate::commands::signing::fingerprint;
+use chrono::{DateTime, Utc};
+use p12_keystore::{KeyStore, KeyStoreEntry};
+use serde::Serialize;
+use sha1::{Digest, Sha1};
+use tauri::command;
+use x509_parser::objects::{oid2abbrev, oid_registry};
+use x509_parser::prelude::{FromDer, X509Certificate, X509Name};
+use x509_parser::public_key::PublicKey;
+
+/// How close to expiry a certificate has to be for `inspect_keystore` to warn, by default
+pub const DEFAULT_EXPIRY_WARNING_DAYS: i64 = 90;
+
+const JKS_MAGIC: u32 = 0xfeed_feed;
+const JCEKS_MAGIC: u32 = 0xcece_cece;
+/// Salt of the JKS integrity digest, fixed by the JDK
+const JKS_DIGEST_SALT: &[u8] = b"Mighty Aphrodite";
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreInfo {
+    /// "JKS", "JCEKS" or "PKCS12"
+    pub format: String,
+    pub entries: Vec<KeystoreEntryInfo>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreEntryInfo {
+    pub alias: String,
+    /// "privateKey" or "trustedCertificate"
+    pub kind: String,
+    /// JKS and JCEKS stores record when an entry was added
+    pub created: Option<String>,
+    /// The certificate chain, signing certificate first
+    pub certificates: Vec<CertificateInfo>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial_number: String,
+    pub valid_from: String,
+    pub valid_until: String,
+    /// Negative once the certificate has expired
+    pub days_until_expiry: i64,
+    /// e.g. "SHA256withRSA", as keytool names it
+    pub signature_algorithm: String,
+    /// e.g. "RSA 2048-bit"
+    pub public_key: String,
+    pub sha1: String,
+    pub sha256: String,
+}
+
+/// List the entries of a JKS, JCEKS or PKCS12 keystore with their certificates, warning
+/// about certificates that expire within `expiry_warning_days`
+#[command]
+pub async fn inspect_keystore(path: String, password: String, expiry_warning_days: Option<i64>) -> Result<KeystoreInfo, String> {
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    inspect(&bytes, &password, Utc::now(), expiry_warning_days.unwrap_or(DEFAULT_EXPIRY_WARNING_DAYS))
+}
+
+pub fn inspect(bytes: &[u8], password: &str, now: DateTime<Utc>, expiry_warning_days: i64) -> Result<KeystoreInfo, String> {
+    let mut warnings = Vec::new();
+    let magic = bytes.get(..4).map(|m| u32::from_be_bytes(m.try_into().unwrap()));
+    let (format, mut raw_entries) = match magic {
+        Some(JKS_MAGIC) => ("JKS", read_java_keystore(bytes, password, &mut warnings)?),
+        Some(JCEKS_MAGIC) => ("JCEKS", read_java_keystore(bytes, password, &mut warnings)?),
+        _ => ("PKCS12", read_pkcs12(bytes, password)?),
+    };
+    raw_entries.sort_by(|a, b| a.alias.cmp(&b.alias));
+
+    let mut entries = Vec::new();
+    for entry in raw_entries {
+        let certificates = entry
+            .certificates
+            .iter()
+            .map(|der| certificate_info(der, now))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Entry {}: {}", entry.alias, e))?;
+        if let Some(leaf) = certificates.first() {
+            if leaf.days_until_expiry < 0 {
+                warnings.push(format!("The certificate of {} expired on {}", entry.alias, leaf.valid_until));
+            } else if leaf.days_until_expiry <= expiry_warning_days {
+                warnings.push(format!(
+                    "The certificate of {} expires on {}, in {} days",
+                    entry.alias, leaf.valid_until, leaf.days_until_expiry
+                ));
+            }
+        }
+        entries.push(KeystoreEntryInfo {
+            alias: entry.alias,
+            kind: entry.kind.to_string(),
+            created: entry.created.map(|c| c.to_rfc3339()),
+            certificates,
+        });
+    }
+
+    Ok(KeystoreInfo { format: format.to_string(), entries, warnings })
+}
+
+/// An entry before its certificates are parsed
+struct RawEntry {
+    alias: String,
+    kind: &'static str,
+    created: Option<DateTime<Utc>>,
+    certificates: Vec<Vec<u8>>,
+}
+
+fn read_pkcs12(bytes: &[u8], password: &str) -> Result<Vec<RawEntry>, String> {
+    let store = KeyStore::from_pkcs12(bytes, password).map_err(|e| match e {
+        p12_keystore::error::Error::MacError(_) => "Keystore password is incorrect".to_string(),
+        p12_keystore::error::Error::DerError(_) => "Not a JKS or PKCS12 keystore".to_string(),
+        e => format!("Failed to read the PKCS12 keystore: {}", e),
+    })?;
+    Ok(store
+        .entries()
+        .map(|(alias, entry)| match entry {
+            KeyStoreEntry::PrivateKeyChain(chain) => RawEntry {
+                alias: alias.clone(),
+                kind: "privateKey",
+                created: None,
+                certificates: chain.chain().iter().map(|c| c.as_der().to_vec()).collect(),
+            },
+            KeyStoreEntry::Certificate(cert) => RawEntry {
+                alias: alias.clone(),
+                kind: "trustedCertificate",
+                created: None,
+                certificates: vec![cert.as_der().to_vec()],
+            },
+        })
+        .collect())
+}
+
+/// Reads the big-endian structures `java.io.DataOutputStream` writes
+struct JavaReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> JavaReader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        let end = self.position.checked_add(count).filter(|&end| end <= self.bytes.len());
+        let end = end.ok_or("The keystore is truncated")?;
+        let slice = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// `writeUTF`: a length and modified UTF-8, which only differs from UTF-8 for NUL
+    /// and characters outside the BMP
+    fn utf(&mut self) -> Result<String, String> {
+        let length = self.u16()? as usize;
+        Ok(String::from_utf8_lossy(self.take(length)?).into_owned())
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], String> {
+        let length = self.u32()? as usize;
+        self.take(length)
+    }
+
+    fn certificate(&mut self, version: u32) -> Result<Vec<u8>, String> {
+        if version == 2 {
+            let kind = self.utf()?;
+            if kind != "X.509" {
+                return Err(format!("Unsupported certificate type {}", kind));
+            }
+        }
+        Ok(self.bytes()?.to_vec())
+    }
+
+    fn timestamp(&mut self) -> Result<Option<DateTime<Utc>>, String> {
+        Ok(DateTime::from_timestamp_millis(self.u64()? as i64))
+    }
+}
+
+/// Check the SHA-1 digest of the password, salt and contents that ends a JKS or JCEKS store
+fn verify_jks_digest<'a>(bytes: &'a [u8], password: &str) -> Result<&'a [u8], String> {
+    let split = bytes.len().checked_sub(20).ok_or("The keystore is truncated")?;
+    let (contents, digest) = bytes.split_at(split);
+    let mut hasher = Sha1::new();
+    for unit in password.encode_utf16() {
+        hasher.update(unit.to_be_bytes());
+    }
+    hasher.update(JKS_DIGEST_SALT);
+    hasher.update(contents);
+    if hasher.finalize().as_slice() != digest {
+        return Err("Keystore password is incorrect".to_string());
+    }
+    Ok(contents)
+}
+
+fn read_java_keystore(bytes: &[u8], password: &str, warnings: &mut Vec<String>) -> Result<Vec<RawEntry>, String> {
+    let contents = verify_jks_digest(bytes, password)?;
+    let mut reader = JavaReader { bytes: contents, position: 4 };
+    let version = reader.u32()?;
+    if version != 1 && version != 2 {
+        return Err(format!("Unsupported keystore version {}", version));
+    }
+
+    let count = reader.u32()?;
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        match reader.u32()? {
+            // Private key: the encrypted key, then its certificate chain
+            1 => {
+                let alias = reader.utf()?;
+                let created = reader.timestamp()?;
+                reader.bytes()?;
+                let chain_length = reader.u32()?;
+                let certificates = (0..chain_length)
+                    .map(|_| reader.certificate(version))
+                    .collect::<Result<Vec<_>, _>>()?;
+                entries.push(RawEntry { alias, kind: "privateKey", created, certificates });
+            }
+            2 => {
+                let alias = reader.utf()?;
+                let created = reader.timestamp()?;
+                let certificates = vec![reader.certificate(version)?];
+                entries.push(RawEntry { alias, kind: "trustedCertificate", created, certificates });
+            }
+            // JCEKS secret keys are serialized Java objects, which can't be skipped
+            // without decoding them; they come after the entries keytool usually writes
+            3 => {
+                let alias = reader.utf()?;
+                warnings.push(format!("{} is a secret key entry, entries from there on are not listed", alias));
+                break;
+            }
+            tag => return Err(format!("Unknown keystore entry type {}", tag)),
+        }
+    }
+    Ok(entries)
+}
+
+fn colon_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+/// keytool's name for the signature algorithms certificates are commonly signed with
+fn signature_algorithm_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.113549.1.1.5" => "SHA1withRSA",
+        "1.2.840.113549.1.1.11" => "SHA256withRSA",
+        "1.2.840.113549.1.1.12" => "SHA384withRSA",
+        "1.2.840.113549.1.1.13" => "SHA512withRSA",
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS",
+        "1.2.840.10045.4.1" => "SHA1withECDSA",
+        "1.2.840.10045.4.3.2" => "SHA256withECDSA",
+        "1.2.840.10045.4.3.3" => "SHA384withECDSA",
+        "1.2.840.10045.4.3.4" => "SHA512withECDSA",
+        "1.2.840.10040.4.3" => "SHA1withDSA",
+        "2.16.840.1.101.3.4.3.2" => "SHA256withDSA",
+        "1.3.101.112" => "Ed25519",
+        other => other,
+    }
+    .to_string()
+}
+
+/// A distinguished name the way keytool prints it, most specific attribute first
+fn distinguished_name(name: &X509Name) -> String {
+    let rdns: Vec<_> = name.iter().collect();
+    rdns.into_iter()
+        .rev()
+        .flat_map(|rdn| rdn.iter())
+        .map(|attr| {
+            let key = oid2abbrev(attr.attr_type(), oid_registry())
+                .map(|abbrev| abbrev.to_string())
+                .unwrap_or_else(|_| attr.attr_type().to_id_string());
+            format!("{}={}", key, attr.as_str().unwrap_or("?"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Subject, validity, algorithms and fingerprints of a DER certificate
+pub fn certificate_info(der: &[u8], now: DateTime<Utc>) -> Result<CertificateInfo, String> {
+    let (_, cert) = X509Certificate::from_der(der).map_err(|e| format!("Invalid certificate: {}", e))?;
+    let validity = cert.validity();
+    let date = |timestamp: i64| {
+        DateTime::from_timestamp(timestamp, 0).map(|d| d.to_rfc3339()).unwrap_or_default()
+    };
+    let public_key = match cert.public_key().parsed() {
+        Ok(key @ PublicKey::RSA(_)) => format!("RSA {}-bit", key.key_size()),
+        Ok(key @ PublicKey::EC(_)) => format!("EC {}-bit", key.key_size()),
+        Ok(key @ PublicKey::DSA(_)) => format!("DSA {}-bit", key.key_size()),
+        _ => cert.public_key().algorithm.algorithm.to_id_string(),
+    };
+
+    Ok(CertificateInfo {
+        subject: distinguished_name(cert.subject()),
+        issuer: distinguished_name(cert.issuer()),
+        serial_number: cert.raw_serial_as_string(),
+        valid_from: date(validity.not_before.timestamp()),
+        valid_until: date(validity.not_after.timestamp()),
+        days_until_expiry: (validity.not_after.timestamp() - now.timestamp()).div_euclid(86400),
+        signature_algorithm: signature_algorithm_name(&cert.signature_algorithm.algorithm.to_id_string()),
+        public_key,
+        sha1: colon_hex(&Sha1::digest(der)),
+        sha256: fingerprint(der),
+    })
+}
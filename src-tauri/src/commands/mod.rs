@@ -8,6 +8,32 @@ pub mod doctor;
 pub mod emulator;
 pub mod notification;
 pub mod keystore;
+pub mod keystore_inspector;
 pub mod maintenance;
 pub mod dependencies;
 pub mod analyzer;
+pub mod queue;
+pub mod pipeline;
+pub mod events;
+pub mod diagnostics;
+pub mod timings;
+pub mod git;
+pub mod release_notes;
+pub mod versioning;
+pub mod pbxproj;
+pub mod gradle;
+pub mod google_auth;
+pub mod google_play;
+pub mod app_store_connect;
+pub mod firebase_distribution;
+pub mod signing;
+pub mod provisioning;
+pub mod export_options;
+pub mod bundletool;
+pub mod expo;
+pub mod profiles;
+pub mod android_manifest;
+pub mod ipa_metadata;
+pub mod artifact_diff;
+pub mod size_budget;
+pub mod size_history;
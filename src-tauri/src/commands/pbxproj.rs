@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// A `$(NAME)`, `${NAME}` or `$(NAME:modifier)` build setting reference
+static SETTING_REFERENCE: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"\$[({]([A-Za-z0-9_]+)(?::[^)}]*)?[)}]").unwrap());
+
+/// A value of the old-style plist format used by `project.pbxproj`, with the byte ranges
+/// needed to edit the file in place without reformatting it
+enum Node {
+    Str { value: String, span: Range<usize> },
+    Array(Vec<Node>),
+    Dict { entries: Vec<(String, Node)>, span: Range<usize> },
+}
+
+impl Node {
+    fn get(&self, key: &str) -> Option<&Node> {
+        match self {
+            Node::Dict { entries, .. } => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::Str { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    fn strings(&self) -> Vec<&str> {
+        match self {
+            Node::Array(items) => items.iter().filter_map(|i| i.as_str()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+enum Token {
+    Punct(u8),
+    Str(String, Range<usize>),
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_trivia(&mut self) {
+        let bytes = self.src.as_bytes();
+        loop {
+            while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            let rest = &self.src[self.pos..];
+            if rest.starts_with("/*") {
+                self.pos = rest.find("*/").map(|i| self.pos + i + 2).unwrap_or(self.src.len());
+            } else if rest.starts_with("//") {
+                self.pos = rest.find('\n').map(|i| self.pos + i + 1).unwrap_or(self.src.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Token, String> {
+        self.skip_trivia();
+        let start = self.pos;
+        let bytes = self.src.as_bytes();
+        let Some(&c) = bytes.get(start) else {
+            return Err("Unexpected end of project.pbxproj".to_string());
+        };
+
+        match c {
+            b'{' | b'}' | b'(' | b')' | b'=' | b';' | b',' => {
+                self.pos += 1;
+                Ok(Token::Punct(c))
+            }
+            b'"' => {
+                let mut value = String::new();
+                let mut chars = self.src[start + 1..].char_indices();
+                loop {
+                    match chars.next() {
+                        Some((i, '"')) => {
+                            self.pos = start + 1 + i + 1;
+                            break;
+                        }
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, 'n')) => value.push('\n'),
+                            Some((_, 't')) => value.push('\t'),
+                            Some((_, ch)) => value.push(ch),
+                            None => return Err("Unterminated string in project.pbxproj".to_string()),
+                        },
+                        Some((_, ch)) => value.push(ch),
+                        None => return Err("Unterminated string in project.pbxproj".to_string()),
+                    }
+                }
+                Ok(Token::Str(value, start..self.pos))
+            }
+            _ => {
+                while self.pos < bytes.len()
+                    && !bytes[self.pos].is_ascii_whitespace()
+                    && !b"{}()=;,\"".contains(&bytes[self.pos])
+                    && !self.src[self.pos..].starts_with("/*")
+                {
+                    self.pos += 1;
+                }
+                Ok(Token::Str(self.src[start..self.pos].to_string(), start..self.pos))
+            }
+        }
+    }
+
+    fn unexpected(&self, c: u8) -> String {
+        format!("Unexpected '{}' at byte {} of project.pbxproj", c as char, self.pos - 1)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.next_token()? {
+            Token::Punct(c) if c == expected => Ok(()),
+            Token::Punct(c) => Err(self.unexpected(c)),
+            Token::Str(value, _) => Err(format!("Expected '{}' before \"{}\" in project.pbxproj", expected as char, value)),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Node, String> {
+        match self.next_token()? {
+            Token::Punct(b'{') => self.parse_dict(self.pos - 1),
+            Token::Punct(b'(') => self.parse_array(),
+            Token::Punct(c) => Err(self.unexpected(c)),
+            Token::Str(value, span) => Ok(Node::Str { value, span }),
+        }
+    }
+
+    fn parse_dict(&mut self, start: usize) -> Result<Node, String> {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.next_token()? {
+                Token::Punct(b'}') => return Ok(Node::Dict { entries, span: start..self.pos }),
+                Token::Punct(c) => return Err(self.unexpected(c)),
+                Token::Str(key, _) => key,
+            };
+            self.expect(b'=')?;
+            let value = self.parse_value()?;
+            self.expect(b';')?;
+            entries.push((key, value));
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Node, String> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia();
+            if self.src[self.pos..].starts_with(')') {
+                self.pos += 1;
+                return Ok(Node::Array(items));
+            }
+            items.push(self.parse_value()?);
+            self.skip_trivia();
+            if self.src[self.pos..].starts_with(',') {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
+/// Quote a value unless it only has characters Xcode leaves bare
+fn format_value(value: &str) -> String {
+    let bare = !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "_$./".contains(c));
+    if bare {
+        value.to_string()
+    } else {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// A path setting such as `INFOPLIST_FILE` relative to the directory of the `.xcodeproj`,
+/// without its `$(SRCROOT)/` or `$(PROJECT_DIR)/` prefix
+pub fn source_relative(path: &str) -> &str {
+    ["$(SRCROOT)/", "${SRCROOT}/", "$(PROJECT_DIR)/", "${PROJECT_DIR}/"]
+        .iter()
+        .find_map(|prefix| path.strip_prefix(prefix))
+        .unwrap_or(path)
+}
+
+/// Apps and app extensions (notification service, widgets, watch apps…), which all carry a
+/// version. Test bundles and frameworks don't.
+fn is_versioned_product(product_type: &str) -> bool {
+    product_type.contains(".application") || product_type.contains("extension")
+}
+
+pub struct BuildConfiguration {
+    /// e.g. `Debug`, `Release`
+    pub name: String,
+    /// Project-level settings overridden by the target's own
+    pub settings: HashMap<String, String>,
+}
+
+impl BuildConfiguration {
+    /// Expand `$(NAME)` / `${NAME}` references from the configuration's settings.
+    /// Returns `None` when a reference cannot be resolved.
+    pub fn resolve(&self, value: &str) -> Option<String> {
+        let mut value = value.to_string();
+        // Settings may reference each other; bounded to survive cycles
+        for _ in 0..8 {
+            if !SETTING_REFERENCE.is_match(&value) {
+                return Some(value);
+            }
+            let mut unresolved = false;
+            value = SETTING_REFERENCE
+                .replace_all(&value, |caps: &regex::Captures| match self.settings.get(&caps[1]) {
+                    Some(setting) => setting.clone(),
+                    None => {
+                        unresolved = true;
+                        caps[0].to_string()
+                    }
+                })
+                .to_string();
+            if unresolved {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// A build setting with its references expanded
+    pub fn setting(&self, key: &str) -> Option<String> {
+        self.settings.get(key).and_then(|v| self.resolve(v))
+    }
+}
+
+pub struct Target {
+    pub name: String,
+    /// e.g. `com.apple.product-type.application`, `com.apple.product-type.app-extension`
+    pub product_type: String,
+    pub configurations: Vec<BuildConfiguration>,
+}
+
+impl Target {
+    pub fn is_versioned(&self) -> bool {
+        is_versioned_product(&self.product_type)
+    }
+
+    /// The named configuration, or the first one when the target doesn't have it
+    pub fn configuration(&self, name: &str) -> Option<&BuildConfiguration> {
+        self.configurations.iter().find(|c| c.name == name).or_else(|| self.configurations.first())
+    }
+}
+
+/// The `project.pbxproj` of an Xcode project, read and edited in place
+pub struct XcodeProject {
+    path: PathBuf,
+    content: String,
+    root: Node,
+}
+
+impl XcodeProject {
+    /// The `project.pbxproj` of the app's `.xcodeproj` in `ios_dir`
+    pub fn find(ios_dir: &Path) -> Option<PathBuf> {
+        let mut projects: Vec<PathBuf> = fs::read_dir(ios_dir)
+            .ok()?
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "xcodeproj"))
+            .filter(|p| p.file_name().is_some_and(|n| n != "Pods.xcodeproj"))
+            .collect();
+        projects.sort();
+        projects.into_iter().map(|p| p.join("project.pbxproj")).find(|p| p.exists())
+    }
+
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let root = Parser { src: &content, pos: 0 }.parse_value()?;
+        Ok(XcodeProject { path: path.to_path_buf(), content, root })
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, &self.content).map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+
+    /// The directory `$(SRCROOT)` points to: the one containing the `.xcodeproj`
+    fn source_root(&self) -> PathBuf {
+        self.path
+            .parent()
+            .and_then(|p| p.parent())
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+
+    fn object(&self, id: &str) -> Option<&Node> {
+        self.root.get("objects")?.get(id)
+    }
+
+    fn objects_of(&self, isa: &str) -> Vec<&Node> {
+        match self.root.get("objects") {
+            Some(Node::Dict { entries, .. }) => entries
+                .iter()
+                .map(|(_, object)| object)
+                .filter(|object| object.get("isa").and_then(Node::as_str) == Some(isa))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// `(name, buildSettings)` of each configuration in a configuration list
+    fn configuration_list(&self, list_id: Option<&str>) -> Vec<(&str, &Node)> {
+        let Some(list) = list_id.and_then(|id| self.object(id)) else {
+            return Vec::new();
+        };
+        list.get("buildConfigurations")
+            .map(Node::strings)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|id| self.object(id))
+            .filter_map(|config| {
+                let name = config.get("name").and_then(Node::as_str)?;
+                let settings = config.get("buildSettings")?;
+                Some((name, settings))
+            })
+            .collect()
+    }
+
+    fn native_targets(&self) -> Vec<&Node> {
+        self.objects_of("PBXNativeTarget")
+    }
+
+    pub fn targets(&self) -> Vec<Target> {
+        let project_list = self
+            .objects_of("PBXProject")
+            .first()
+            .and_then(|p| p.get("buildConfigurationList"))
+            .and_then(Node::as_str);
+        let project_settings = self.configuration_list(project_list);
+
+        self.native_targets()
+            .into_iter()
+            .map(|target| {
+                let list = target.get("buildConfigurationList").and_then(Node::as_str);
+                let configurations = self
+                    .configuration_list(list)
+                    .into_iter()
+                    .map(|(name, settings)| {
+                        let mut merged = HashMap::new();
+                        let inherited = project_settings.iter().find(|(n, _)| *n == name).map(|(_, s)| *s);
+                        for node in inherited.into_iter().chain([settings]) {
+                            if let Node::Dict { entries, .. } = node {
+                                for (key, value) in entries {
+                                    if let Some(value) = value.as_str() {
+                                        merged.insert(key.clone(), value.to_string());
+                                    }
+                                }
+                            }
+                        }
+                        BuildConfiguration { name: name.to_string(), settings: merged }
+                    })
+                    .collect();
+
+                Target {
+                    name: target.get("name").and_then(Node::as_str).unwrap_or_default().to_string(),
+                    product_type: target.get("productType").and_then(Node::as_str).unwrap_or_default().to_string(),
+                    configurations,
+                }
+            })
+            .collect()
+    }
+
+    /// The main application target
+    pub fn app_target(&self) -> Option<Target> {
+        self.targets()
+            .into_iter()
+            .find(|t| t.product_type == "com.apple.product-type.application")
+    }
+
+    /// Info.plist files of the versioned targets, from their `INFOPLIST_FILE` setting
+    pub fn info_plists(&self) -> Vec<PathBuf> {
+        let root = self.source_root();
+        let mut plists: Vec<PathBuf> = Vec::new();
+        for target in self.targets().iter().filter(|t| t.is_versioned()) {
+            for configuration in &target.configurations {
+                let Some(file) = configuration.settings.get("INFOPLIST_FILE") else {
+                    continue;
+                };
+                let path = root.join(source_relative(file));
+                if path.exists() && !plists.contains(&path) {
+                    plists.push(path);
+                }
+            }
+        }
+        plists
+    }
+
+    /// Set `MARKETING_VERSION` and `CURRENT_PROJECT_VERSION` in every configuration of every
+    /// versioned target, so the app and its extensions share one version.
+    /// Returns the `Target (Configuration)` pairs that changed.
+    pub fn set_versions(&mut self, version: &str, build_number: &str) -> Result<Vec<String>, String> {
+        let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+        let mut changed = Vec::new();
+
+        for target in self.native_targets() {
+            let product_type = target.get("productType").and_then(Node::as_str).unwrap_or_default();
+            if !is_versioned_product(product_type) {
+                continue;
+            }
+            let target_name = target.get("name").and_then(Node::as_str).unwrap_or_default();
+            let list = target.get("buildConfigurationList").and_then(Node::as_str);
+
+            for (config_name, settings) in self.configuration_list(list) {
+                let Node::Dict { span, .. } = settings else {
+                    continue;
+                };
+                let mut config_changed = false;
+                for (key, value) in [("MARKETING_VERSION", version), ("CURRENT_PROJECT_VERSION", build_number)] {
+                    match settings.get(key) {
+                        Some(Node::Str { value: current, span }) => {
+                            if current != value {
+                                edits.push((span.clone(), format_value(value)));
+                                config_changed = true;
+                            }
+                        }
+                        Some(_) => return Err(format!("{} of {} ({}) is not a string", key, target_name, config_name)),
+                        None => {
+                            edits.push((self.insertion_point(span), self.setting_line(span, key, value)));
+                            config_changed = true;
+                        }
+                    }
+                }
+                if config_changed {
+                    changed.push(format!("{} ({})", target_name, config_name));
+                }
+            }
+        }
+
+        // Apply back to front so earlier ranges stay valid
+        edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start));
+        let mut content = self.content.clone();
+        for (range, text) in edits {
+            content.replace_range(range, &text);
+        }
+        self.root = Parser { src: &content, pos: 0 }.parse_value()?;
+        self.content = content;
+        Ok(changed)
+    }
+
+    /// Where a new setting goes in a `buildSettings` dictionary: at the start of the line
+    /// holding its closing brace
+    fn insertion_point(&self, dict: &Range<usize>) -> Range<usize> {
+        let brace = dict.end - 1;
+        let line_start = self.content[..brace].rfind('\n').map(|i| i + 1).unwrap_or(brace);
+        if self.content[line_start..brace].trim().is_empty() {
+            line_start..line_start
+        } else {
+            brace..brace
+        }
+    }
+
+    fn setting_line(&self, dict: &Range<usize>, key: &str, value: &str) -> String {
+        let brace = dict.end - 1;
+        let line_start = self.content[..brace].rfind('\n').map(|i| i + 1).unwrap_or(brace);
+        let indent = &self.content[line_start..brace];
+        if indent.trim().is_empty() {
+            format!("{}\t{} = {};\n", indent, key, format_value(value))
+        } else {
+            format!("{} = {}; ", key, format_value(value))
+        }
+    }
+}
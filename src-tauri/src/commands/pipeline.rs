@@ -0,0 +1,503 @@
+use crate::commands::analyzer::AppSizeReport;
+use crate::commands::build::{self, BuildOptions};
+use crate::commands::events::{BuildEventBus, BuildStatus, LogLevel};
+use crate::commands::profiles::{profile_bundle_id, profile_env};
+use crate::commands::queue;
+use crate::commands::versioning::VersionSnapshot;
+use crate::models::project::{BuildDiagnostic, BuildProfile, GitProvenance, Project};
+use crate::BuildProcessState;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use tauri::AppHandle;
+
+/// The named steps a build is made of. Each platform uses the subset that applies to it,
+/// in the order returned by `BuildPipeline::for_platform`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepName {
+    Prepare,
+    Archive,
+    Export,
+    Sign,
+    Measure,
+    Upload,
+    Distribute,
+    Notify,
+}
+
+impl StepName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StepName::Prepare => "prepare",
+            StepName::Archive => "archive",
+            StepName::Export => "export",
+            StepName::Sign => "sign",
+            StepName::Measure => "measure",
+            StepName::Upload => "upload",
+            StepName::Distribute => "distribute",
+            StepName::Notify => "notify",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StepName::Prepare => "Prepare",
+            StepName::Archive => "Archive",
+            StepName::Export => "Export",
+            StepName::Sign => "Sign",
+            StepName::Measure => "Size check",
+            StepName::Upload => "Upload",
+            StepName::Distribute => "Distribute",
+            StepName::Notify => "Notify",
+        }
+    }
+
+    /// A failure in a non-fatal step is reported but does not fail the build
+    /// (e.g. the artifact was built but Firebase rejected the upload).
+    fn is_fatal(&self) -> bool {
+        !matches!(self, StepName::Distribute | StepName::Notify)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Running,
+    Success,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepReport {
+    pub name: StepName,
+    pub status: StepStatus,
+    pub started_at: Option<u64>,
+    pub duration_ms: Option<u64>,
+    /// First line of the build log written by this step (0-based)
+    pub log_start: usize,
+    /// One past the last line of the build log written by this step
+    pub log_end: usize,
+    pub message: Option<String>,
+}
+
+pub enum StepOutcome {
+    Done,
+    Skipped(String),
+}
+
+pub enum StepError {
+    Failed(String),
+    Cancelled,
+}
+
+impl From<String> for StepError {
+    fn from(e: String) -> Self {
+        StepError::Failed(e)
+    }
+}
+
+impl From<&str> for StepError {
+    fn from(e: &str) -> Self {
+        StepError::Failed(e.to_string())
+    }
+}
+
+pub type StepResult = Result<StepOutcome, StepError>;
+
+/// Everything a step needs, threaded through the pipeline.
+pub struct BuildContext<'a> {
+    pub app: &'a AppHandle,
+    pub process_state: &'a BuildProcessState,
+    pub project: Project,
+    pub platform: String,
+    pub options: BuildOptions,
+    /// `<project>/android` or `<project>/ios`, or the folder `expo prebuild` generated in an
+    /// isolated workspace
+    pub platform_dir: PathBuf,
+    /// The build profile picked in the options, already applied to `project`
+    pub profile: Option<BuildProfile>,
+    pub events: BuildEventBus,
+    /// Set by the step that produces the final APK/AAB/IPA
+    pub artifact_path: Option<PathBuf>,
+    /// Captured in the prepare step, `None` outside a git repository
+    pub git: Option<GitProvenance>,
+    /// Reports of the steps that already ran
+    pub reports: Vec<StepReport>,
+    /// Number of steps in the pipeline, set by `BuildPipeline::run`
+    pub step_count: usize,
+    /// Filled by the `DiagnosticsSink` subscribed to `events`
+    pub diagnostics: Arc<Mutex<Vec<BuildDiagnostic>>>,
+    /// Set when the prepare step bumped the version, to undo it if the build does not succeed
+    pub version_snapshot: Option<VersionSnapshot>,
+    /// Set once the artifact reached App Store Connect or Google Play. The store keeps its build
+    /// number, so the version bump is no longer rolled back.
+    pub uploaded: bool,
+    /// Size report of the exported artifact, saved with the build once it succeeds
+    pub size_report: Option<AppSizeReport>,
+    pub ios: IosBuildState,
+}
+
+/// iOS paths resolved in the prepare step and used by archive/sign/export.
+#[derive(Default)]
+pub struct IosBuildState {
+    pub scheme: String,
+    pub configuration: String,
+    /// ("-workspace" | "-project", path)
+    pub xcode_target: Option<(&'static str, PathBuf)>,
+    pub archive_path: PathBuf,
+    pub export_options_path: PathBuf,
+}
+
+impl<'a> BuildContext<'a> {
+    /// Bundle ID (iOS) or package name (Android) of the app being built
+    pub fn bundle_id(&self) -> String {
+        let bundle_id = if self.platform == "ios" { &self.project.ios.bundle_id } else { &self.project.android.bundle_id };
+        profile_bundle_id(bundle_id, self.profile.as_ref())
+    }
+
+    pub fn process_key(&self) -> String {
+        format!("{}_{}", self.project.id, self.platform)
+    }
+
+    pub fn step_succeeded(&self, step: StepName) -> bool {
+        self.reports
+            .iter()
+            .any(|r| r.name == step && r.status == StepStatus::Success)
+    }
+
+    /// Overall build progress once the running step is `within` (0.0 – 1.0) done
+    pub fn step_fraction(&self, within: f64) -> f64 {
+        (self.reports.len() as f64 + within.clamp(0.0, 1.0)) / self.step_count.max(1) as f64
+    }
+}
+
+/// How lines of a command's output are logged (all of them are written to the log file)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StreamMode {
+    All,
+    /// Only lines containing `error:` or `warning:` reach the UI, the rest are logged at debug
+    /// level — xcodebuild is too chatty to stream
+    Problems,
+}
+
+pub struct CommandOutput {
+    pub status: ExitStatus,
+    /// Last lines of output, shown when the command fails
+    pub recent: VecDeque<String>,
+}
+
+/// Run a shell command in its own process group, registered in `BuildProcessState`
+/// so `cancel_build_process` can kill it. Returns `StepError::Cancelled` if it was killed.
+pub fn run_command(
+    ctx: &mut BuildContext,
+    shell_command: &str,
+    dir: &Path,
+    mode: StreamMode,
+) -> Result<CommandOutput, StepError> {
+    run_command_with_env(ctx, shell_command, dir, mode, &[])
+}
+
+/// `run_command` with extra environment variables, for values that must not appear in
+/// the command line (and so in the process list), such as signing passwords
+pub fn run_command_with_env(
+    ctx: &mut BuildContext,
+    shell_command: &str,
+    dir: &Path,
+    mode: StreamMode,
+    env: &[(String, String)],
+) -> Result<CommandOutput, StepError> {
+    let mut child = Command::new("/bin/sh")
+        .args(["-c", shell_command])
+        .current_dir(dir)
+        .envs(profile_env(ctx.profile.as_ref()))
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .process_group(0) // own process group so killpg kills gradle/xcodebuild + all children
+        .spawn()
+        .map_err(|e| format!("Failed to start command: {}", e))?;
+
+    // Take stdout/stderr BEFORE storing so cancel_build_process can still kill the child
+    let child_stdout = child.stdout.take();
+    let child_stderr = child.stderr.take();
+
+    let process_id = ctx.process_key();
+    {
+        let mut processes = ctx.process_state.0.lock().unwrap();
+        processes.insert(process_id.clone(), Arc::new(Mutex::new(Some(child))));
+    }
+    // The queued job may have been cancelled before the process could be killed
+    if queue::queued_build_cancelled(ctx.app, &ctx.project.id, &ctx.platform) {
+        let _ = build::kill_build_process(ctx.process_state, &ctx.project.id, &ctx.platform);
+    }
+
+    let mut recent = VecDeque::with_capacity(20);
+    let default_level = match mode {
+        StreamMode::All => LogLevel::Info,
+        StreamMode::Problems => LogLevel::Debug,
+    };
+    let mut handle_line = |events: &mut BuildEventBus, line: String| -> Result<(), String> {
+        events.log_at(LogLevel::classify(&line, default_level), &line);
+        if recent.len() >= 20 {
+            recent.pop_front();
+        }
+        recent.push_back(line);
+        Ok(())
+    };
+
+    // Drain both pipes at once: a command filling the stderr pipe while stdout is still
+    // being read would otherwise block forever
+    let (sender, lines) = mpsc::channel();
+    if let Some(stdout) = child_stdout {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    if let Some(stderr) = child_stderr {
+        let sender = sender.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                if sender.send(format!("⚠️ {}", line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(sender);
+    for line in lines {
+        handle_line(&mut ctx.events, line)?;
+    }
+
+    // Wait for completion — detect if the process was cancelled
+    let status = {
+        let mut processes = ctx.process_state.0.lock().unwrap();
+        match processes.remove(&process_id) {
+            Some(mutex) => {
+                let mut guard = mutex.lock().unwrap();
+                match guard.take() {
+                    Some(mut c) => c.wait().map_err(|e| e.to_string())?,
+                    None => return Err(StepError::Cancelled), // killed by cancel_build_process
+                }
+            }
+            None => return Err(StepError::Cancelled), // already removed by cancel
+        }
+    };
+
+    Ok(CommandOutput { status, recent })
+}
+
+/// An ordered list of named steps. Steps can be skipped up front (reported as `skipped`)
+/// or skip themselves at run time by returning `StepOutcome::Skipped`.
+pub struct BuildPipeline {
+    steps: Vec<StepName>,
+    skipped: Vec<(StepName, String)>,
+}
+
+impl BuildPipeline {
+    pub fn new(steps: Vec<StepName>) -> Self {
+        BuildPipeline { steps, skipped: Vec::new() }
+    }
+
+    pub fn for_platform(platform: &str, options: &BuildOptions) -> Self {
+        let mut pipeline = match platform {
+            "android" => BuildPipeline::new(vec![
+                StepName::Prepare,
+                StepName::Archive,
+                StepName::Export,
+                StepName::Sign,
+                StepName::Measure,
+                StepName::Upload,
+                StepName::Distribute,
+                StepName::Notify,
+            ]),
+            _ => BuildPipeline::new(vec![
+                StepName::Prepare,
+                StepName::Archive,
+                StepName::Sign,
+                StepName::Export,
+                StepName::Measure,
+                StepName::Upload,
+                StepName::Distribute,
+                StepName::Notify,
+            ]),
+        };
+
+        if platform == "android" {
+            if options.upload_to_play_store != Some(true) {
+                pipeline.skip(StepName::Upload, "Google Play upload not requested");
+            }
+        } else if options.upload_to_app_store != Some(true) {
+            pipeline.skip(StepName::Upload, "App Store upload not requested");
+        }
+        if options.send_to_app_distribution != Some(true) {
+            pipeline.skip(StepName::Distribute, "App Distribution not requested");
+        }
+        pipeline
+    }
+
+    pub fn steps(&self) -> &[StepName] {
+        &self.steps
+    }
+
+    pub fn skip(&mut self, step: StepName, reason: &str) {
+        self.skipped.push((step, reason.to_string()));
+    }
+
+    /// Run every step in order. Returns the `Success` or `Cancelled` status, `Err` when a
+    /// fatal step fails. The final status is emitted by `finish`.
+    pub async fn run(&self, ctx: &mut BuildContext<'_>) -> Result<BuildStatus, String> {
+        let mut failure: Option<String> = None;
+        let mut cancelled = false;
+
+        let total = self.steps.len();
+        ctx.step_count = total;
+        for (index, step) in self.steps.iter().enumerate() {
+            let log_start = ctx.events.line_count();
+
+            if failure.is_some() || cancelled {
+                self.finish_step(ctx, *step, StepStatus::Skipped, None, None, log_start, None);
+                continue;
+            }
+
+            if let Some((_, reason)) = self.skipped.iter().find(|(s, _)| s == step) {
+                self.finish_step(ctx, *step, StepStatus::Skipped, None, None, log_start, Some(reason.clone()));
+                continue;
+            }
+
+            let started_at = chrono::Utc::now().timestamp_millis() as u64;
+            let timer = Instant::now();
+            ctx.events.set_step(Some(*step));
+            ctx.events.progress(index as f64 / total as f64, step.label());
+            ctx.events.step(&StepReport {
+                name: *step,
+                status: StepStatus::Running,
+                started_at: Some(started_at),
+                duration_ms: None,
+                log_start,
+                log_end: log_start,
+                message: None,
+            });
+
+            let result = if queue::queued_build_cancelled(ctx.app, &ctx.project.id, &ctx.platform) {
+                Err(StepError::Cancelled)
+            } else {
+                build::execute_step(*step, ctx).await
+            };
+            let duration = Some(timer.elapsed().as_millis() as u64);
+
+            match result {
+                Ok(StepOutcome::Done) => {
+                    self.finish_step(ctx, *step, StepStatus::Success, Some(started_at), duration, log_start, None);
+                }
+                Ok(StepOutcome::Skipped(reason)) => {
+                    self.finish_step(ctx, *step, StepStatus::Skipped, Some(started_at), duration, log_start, Some(reason));
+                }
+                Err(StepError::Cancelled) => {
+                    ctx.events.error("❌ Build cancelled by user");
+                    self.finish_step(ctx, *step, StepStatus::Failed, Some(started_at), duration, log_start, Some("Cancelled".to_string()));
+                    cancelled = true;
+                }
+                Err(StepError::Failed(message)) => {
+                    self.finish_step(ctx, *step, StepStatus::Failed, Some(started_at), duration, log_start, Some(message.clone()));
+                    if step.is_fatal() {
+                        failure = Some(message);
+                    }
+                }
+            }
+        }
+
+        ctx.events.set_step(None);
+        if cancelled {
+            Ok(BuildStatus::Cancelled)
+        } else if let Some(message) = failure {
+            Err(message)
+        } else {
+            Ok(BuildStatus::Success)
+        }
+    }
+
+    /// Report the outcome of `run`: the diagnostics summary of a failed build, the log file
+    /// and the final status. Kept separate so the caller can clean up before the status goes out.
+    pub fn finish(&self, ctx: &mut BuildContext<'_>, outcome: &Result<BuildStatus, String>) {
+        if outcome.is_err() {
+            log_diagnostics_summary(ctx);
+        }
+        ctx.events.log_file();
+        match outcome {
+            Ok(BuildStatus::Success) => {
+                ctx.events.progress(1.0, "Done");
+                ctx.events.status(BuildStatus::Success)
+            }
+            Ok(status) => ctx.events.status(*status),
+            Err(_) => ctx.events.status(BuildStatus::Failed),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn finish_step(
+        &self,
+        ctx: &mut BuildContext<'_>,
+        name: StepName,
+        status: StepStatus,
+        started_at: Option<u64>,
+        duration_ms: Option<u64>,
+        log_start: usize,
+        message: Option<String>,
+    ) {
+        let report = StepReport {
+            name,
+            status,
+            started_at,
+            duration_ms,
+            log_start,
+            log_end: ctx.events.line_count(),
+            message,
+        };
+        ctx.events.step(&report);
+        ctx.reports.push(report);
+    }
+}
+
+/// Log the errors found in the build output, so the cause of a failure shows up
+/// at the end of the log instead of somewhere in the middle of it.
+fn log_diagnostics_summary(ctx: &mut BuildContext<'_>) {
+    let errors: Vec<BuildDiagnostic> = ctx
+        .diagnostics
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|d| d.severity == "error")
+        .cloned()
+        .collect();
+    if errors.is_empty() {
+        return;
+    }
+
+    ctx.events.error(&format!("🩺 {} problem(s) found:", errors.len()));
+    for diagnostic in errors.iter().take(5) {
+        let location = match (&diagnostic.file, diagnostic.line) {
+            (Some(file), Some(line)) => format!(" ({}:{})", file, line),
+            (Some(file), None) => format!(" ({})", file),
+            _ => String::new(),
+        };
+        ctx.events.error(&format!("  [{}] {}{}", diagnostic.category, diagnostic.message, location));
+        if let Some(remediation) = &diagnostic.remediation {
+            ctx.events.log(&format!("    💡 {}", remediation));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use crate::models::project::{AndroidConfig, BuildProfile, IosConfig, Project};
+use std::path::{Path, PathBuf};
+
+/// The profile named `name`
+pub fn find_profile(project: &Project, name: &str) -> Result<BuildProfile, String> {
+    project
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| format!("Project {} has no build profile named {}", project.name, name))
+}
+
+/// Apply a profile's scheme, configuration, Firebase apps, credentials and notification targets
+/// to the project a build runs with. Bundle IDs, the Gradle task and the env file are not part of
+/// the project's stored files, so the build reads them from the profile instead.
+pub fn apply_profile(project: &mut Project, profile: &BuildProfile) {
+    if let Some(scheme) = &profile.ios_scheme {
+        ios_config(project).scheme = scheme.clone();
+    }
+    if let Some(configuration) = &profile.ios_configuration {
+        ios_config(project).configuration = configuration.clone();
+    }
+    if let Some(app_id) = &profile.ios_firebase_app_id {
+        ios_config(project).firebase_app_id = Some(app_id.clone());
+    }
+
+    if let Some(app_id) = &profile.android_firebase_app_id {
+        let config = project.android.config.get_or_insert(AndroidConfig {
+            firebase_app_id: None,
+            distribution_groups: None,
+            testers: None,
+            play_track: None,
+            play_locales: None,
+            play_release_status: None,
+            signing: None,
+        });
+        config.firebase_app_id = Some(app_id.clone());
+    }
+
+    if profile.ios_credential_id.is_some() {
+        project.credentials.ios_id = profile.ios_credential_id.clone();
+    }
+    if profile.android_credential_id.is_some() {
+        project.credentials.android_id = profile.android_credential_id.clone();
+    }
+    if profile.notifications.is_some() {
+        project.notifications = profile.notifications.clone();
+    }
+}
+
+/// The project's iOS config, created with the default scheme and configuration if missing
+fn ios_config(project: &mut Project) -> &mut IosConfig {
+    let scheme = project.name.clone();
+    project.ios.config.get_or_insert_with(|| IosConfig {
+        scheme,
+        configuration: "Release".to_string(),
+        team_id: None,
+        export_method: None,
+        api_key: None,
+        api_issuer: None,
+        beta_groups: None,
+        firebase_app_id: None,
+        distribution_groups: None,
+        testers: None,
+        export_options: None,
+    })
+}
+
+/// `bundle_id` with the profile's suffix
+pub fn profile_bundle_id(bundle_id: &str, profile: Option<&BuildProfile>) -> String {
+    let suffix = profile.and_then(|p| p.bundle_id_suffix.as_deref()).unwrap_or_default();
+    format!("{}{}", bundle_id, suffix)
+}
+
+/// Environment every command of a build with this profile runs with. react-native-config
+/// resolves `ENVFILE` against the project root, so the path stays relative.
+pub fn profile_env(profile: Option<&BuildProfile>) -> Vec<(String, String)> {
+    profile
+        .and_then(|p| p.env_file.clone())
+        .map(|env_file| vec![("ENVFILE".to_string(), env_file)])
+        .unwrap_or_default()
+}
+
+/// Gradle task building a release of `flavor`, e.g. `bundleStagingRelease`
+pub fn gradle_task(format: &str, flavor: Option<&str>) -> String {
+    let prefix = if format == "apk" { "assemble" } else { "bundle" };
+    let mut flavor = flavor.unwrap_or_default().to_string();
+    if let Some(first) = flavor.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    format!("{}{}Release", prefix, flavor)
+}
+
+/// Where Gradle writes the release APK or AAB of `flavor`
+pub fn android_output_path(android_dir: &Path, format: &str, flavor: Option<&str>) -> PathBuf {
+    let outputs = android_dir.join("app/build/outputs");
+    match (format, flavor) {
+        ("apk", Some(flavor)) => outputs.join(format!("apk/{}/release/app-{}-release.apk", flavor, flavor)),
+        ("apk", None) => outputs.join("apk/release/app-release.apk"),
+        (_, Some(flavor)) => outputs.join(format!("bundle/{}Release/app-{}-release.aab", flavor, flavor)),
+        (_, None) => outputs.join("bundle/release/app-release.aab"),
+    }
+}
@@ -1,6 +1,12 @@
 use crate::models::project::{Project, IosPlatform, AndroidPlatform};
 use crate::DbState;
-use tauri::{command, Emitter, State};
+use crate::commands::gradle::{read_android_versions, update_android_versions};
+use crate::commands::expo::update_expo_versions;
+use crate::commands::pbxproj::{source_relative, XcodeProject};
+use crate::commands::queue::remove_project_jobs;
+use crate::commands::signing::forget_signing_passwords;
+use crate::commands::versioning::FileChange;
+use tauri::{command, AppHandle, Emitter, State};
 use rusqlite::params;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -55,61 +61,66 @@ fn update_ios_info_plist(project_path: &str, version: &str, build_number: &str)
         return Ok(());
     }
 
-    // Find .xcodeproj to get the project name
-    let entries = fs::read_dir(&ios_dir).map_err(|e| e.to_string())?;
-    let mut project_name = None;
-
-    for entry in entries {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let file_name = entry.file_name();
-        let file_name_str = file_name.to_string_lossy();
-        if file_name_str.ends_with(".xcodeproj") {
-            project_name = Some(file_name_str.trim_end_matches(".xcodeproj").to_string());
-            break;
+    // Modern templates keep the numbers in the MARKETING_VERSION / CURRENT_PROJECT_VERSION
+    // build settings and reference them from Info.plist
+    let mut plists = Vec::new();
+    let pbxproj_path = XcodeProject::find(&ios_dir);
+    if let Some(pbxproj_path) = &pbxproj_path {
+        let mut xcode_project = XcodeProject::open(pbxproj_path)
+            .map_err(|e| format!("Failed to read {}: {}", pbxproj_path.display(), e))?;
+        if !xcode_project.set_versions(version, build_number)?.is_empty() {
+            xcode_project.save()?;
         }
+        plists = xcode_project.info_plists();
     }
 
-    if let Some(name) = project_name {
-        let plist_path = ios_dir.join(&name).join("Info.plist");
-        if plist_path.exists() {
-            let mut value = Value::from_file(&plist_path).map_err(|e| e.to_string())?;
-
-            if let Some(dict) = value.as_dictionary_mut() {
-                dict.insert(
-                    "CFBundleShortVersionString".to_string(),
-                    Value::String(version.to_string()),
-                );
-                dict.insert(
-                    "CFBundleVersion".to_string(),
-                    Value::String(build_number.to_string()),
-                );
+    if plists.is_empty() {
+        // Find .xcodeproj to get the project name
+        let entries = fs::read_dir(&ios_dir).map_err(|e| e.to_string())?;
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let file_name = entry.file_name();
+            let file_name_str = file_name.to_string_lossy();
+            if file_name_str.ends_with(".xcodeproj") {
+                let plist_path = ios_dir.join(file_name_str.trim_end_matches(".xcodeproj")).join("Info.plist");
+                if plist_path.exists() {
+                    plists.push(plist_path);
+                }
+                break;
             }
-
-            value.to_file_xml(&plist_path).map_err(|e| e.to_string())?;
         }
     }
 
+    for plist_path in plists {
+        update_info_plist_versions(&plist_path, version, build_number, pbxproj_path.is_none())?;
+    }
+
     Ok(())
 }
 
-fn update_android_gradle(project_path: &str, version_name: &str, version_code: &str) -> Result<(), String> {
-    let gradle_path = Path::new(project_path).join("android/app/build.gradle");
-    if !gradle_path.exists() {
-        return Ok(());
+/// Write literal versions to an Info.plist. Values referencing build settings such as
+/// `$(MARKETING_VERSION)` are left alone, the pbxproj holds their numbers.
+fn update_info_plist_versions(plist_path: &Path, version: &str, build_number: &str, insert_missing: bool) -> Result<(), String> {
+    let mut value = Value::from_file(plist_path).map_err(|e| e.to_string())?;
+    let mut changed = false;
+
+    if let Some(dict) = value.as_dictionary_mut() {
+        for (key, new_value) in [("CFBundleShortVersionString", version), ("CFBundleVersion", build_number)] {
+            let current = dict.get(key).and_then(|v| v.as_string());
+            let writable = match current {
+                Some(current) => !current.contains("$(") && !current.contains("${") && current != new_value,
+                None => insert_missing,
+            };
+            if writable {
+                dict.insert(key.to_string(), Value::String(new_value.to_string()));
+                changed = true;
+            }
+        }
     }
 
-    let content = fs::read_to_string(&gradle_path).map_err(|e| e.to_string())?;
-
-    // Update version code
-    let version_code_regex = Regex::new(r"versionCode\s*=?\s*(\d+)").map_err(|e| e.to_string())?;
-    let content = version_code_regex.replace(&content, format!("versionCode {}", version_code));
-
-    // Update version name
-    let version_name_regex = Regex::new(r#"versionName\s*=?\s*["']([^"']+)["']"#).map_err(|e| e.to_string())?;
-    let content = version_name_regex.replace(&content, format!("versionName \"{}\"", version_name));
-
-    fs::write(&gradle_path, content.to_string()).map_err(|e| e.to_string())?;
-
+    if changed {
+        value.to_file_xml(plist_path).map_err(|e| e.to_string())?;
+    }
     Ok(())
 }
 
@@ -227,6 +238,52 @@ fn update_build_json(
 
 
 
+/// Write the project's versions and build numbers to Info.plist, build.gradle and
+/// `.app-builder/build.json`. Returns the Gradle or app config lines that changed. Prebuild projects get
+/// theirs in the Expo config instead, since their native folders are regenerated.
+pub(crate) fn write_project_versions(project: &Project) -> Result<Vec<FileChange>, String> {
+    let changes = if project.prebuild_settings().is_some() {
+        // app.json has a single version, only write it when both platforms agree
+        let version = (project.ios.version == project.android.version).then_some(project.ios.version.as_str());
+        update_expo_versions(
+            Path::new(&project.path),
+            version,
+            Some(project.ios.build_number),
+            Some(project.android.version_code),
+        )?
+        .changes
+    } else {
+        // Update Info.plist
+        update_ios_info_plist(
+            &project.path,
+            &project.ios.version,
+            &project.ios.build_number.to_string()
+        )?;
+
+        // Update Android build.gradle(.kts), gradle.properties or libs.versions.toml
+        update_android_versions(
+            &Path::new(&project.path).join("android"),
+            &project.android.version,
+            project.android.version_code,
+        )?
+    };
+
+    // Update build.json
+    update_build_json(
+        &project.path,
+        &project.name,
+        &project.ios.bundle_id,
+        &project.ios.version,
+        project.ios.build_number,
+        &project.android.bundle_id,
+        &project.android.version,
+        project.android.version_code,
+        project.android.build_command.as_deref(),
+    )?;
+
+    Ok(changes)
+}
+
 pub fn is_expo_project(project_path: &std::path::Path) -> bool {
     // Check for app.json or app.config.js (Expo config files)
     let app_json_path = project_path.join("app.json");
@@ -332,53 +389,37 @@ pub async fn read_native_project_info(project_path: String) -> Result<AppJsonInf
                         .join(file_name_str.as_ref())
                         .join("project.pbxproj");
 
-                    if pbxproj_path.exists() {
-                        if let Ok(content) = fs::read_to_string(&pbxproj_path) {
-                            // Extract PRODUCT_BUNDLE_IDENTIFIER — the real bundle ID
-                            // Matches: PRODUCT_BUNDLE_IDENTIFIER = com.example.app;
-                            if info.ios_bundle_id.is_none() {
-                                if let Ok(re) = Regex::new(r"PRODUCT_BUNDLE_IDENTIFIER\s*=\s*([A-Za-z0-9._\-]+)\s*;") {
-                                    if let Some(cap) = re.captures(&content) {
-                                        if let Some(bundle_id) = cap.get(1) {
-                                            let bid = bundle_id.as_str().to_string();
-                                            // Skip placeholder values
-                                            if !bid.contains("$(") && !bid.is_empty() {
-                                                info.ios_bundle_id = Some(bid);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
+                    // Build settings of the app target's Release configuration, which Info.plist
+                    // usually references as $(PRODUCT_BUNDLE_IDENTIFIER), $(MARKETING_VERSION)…
+                    let app_target = XcodeProject::open(&pbxproj_path).ok().and_then(|p| p.app_target());
+                    let configuration = app_target.as_ref().and_then(|t| t.configuration("Release"));
 
                     // Fallback: read Info.plist for version/build info
                     let project_name = file_name_str.trim_end_matches(".xcodeproj");
-                    let plist_path = ios_dir.join(project_name).join("Info.plist");
-                    if plist_path.exists() {
-                        if let Ok(value) = Value::from_file(&plist_path) {
-                            if let Some(dict) = value.as_dictionary() {
-                                // Only use bundle ID from plist if it's a real value (not a variable)
-                                if info.ios_bundle_id.is_none() {
-                                    if let Some(Value::String(bundle_id)) = dict.get("CFBundleIdentifier") {
-                                        if !bundle_id.contains("$(") {
-                                            info.ios_bundle_id = Some(bundle_id.clone());
-                                        }
-                                    }
-                                }
-                                if let Some(Value::String(version)) = dict.get("CFBundleShortVersionString") {
-                                    if !version.contains("$(") {
-                                        info.ios_version = Some(version.clone());
-                                    }
-                                }
-                                if let Some(Value::String(build_number)) = dict.get("CFBundleVersion") {
-                                    if !build_number.contains("$(") {
-                                        info.ios_build_number = Some(build_number.clone());
-                                    }
-                                }
-                            }
-                        }
-                    }
+                    let plist_path = configuration
+                        .and_then(|c| c.settings.get("INFOPLIST_FILE"))
+                        .map(|file| ios_dir.join(source_relative(file)))
+                        .filter(|p| p.exists())
+                        .unwrap_or_else(|| ios_dir.join(project_name).join("Info.plist"));
+                    let plist = Value::from_file(&plist_path).ok();
+                    let plist_value = |key: &str| -> Option<String> {
+                        plist.as_ref()?.as_dictionary()?.get(key)?.as_string().map(|v| v.to_string())
+                    };
+
+                    // Resolve a plist value through the build settings; without a plist, use the setting directly
+                    let resolve = |plist_key: &str, setting: &str| -> Option<String> {
+                        let value = plist_value(plist_key).unwrap_or_else(|| format!("$({})", setting));
+                        let resolved = match configuration {
+                            Some(configuration) => configuration.resolve(&value)?,
+                            None => value,
+                        };
+                        // Skip placeholder values
+                        Some(resolved).filter(|v| !v.contains("$(") && !v.contains("${") && !v.is_empty())
+                    };
+
+                    info.ios_bundle_id = resolve("CFBundleIdentifier", "PRODUCT_BUNDLE_IDENTIFIER");
+                    info.ios_version = resolve("CFBundleShortVersionString", "MARKETING_VERSION");
+                    info.ios_build_number = resolve("CFBundleVersion", "CURRENT_PROJECT_VERSION");
                     break;
                 }
             }
@@ -425,6 +466,15 @@ pub async fn read_native_project_info(project_path: String) -> Result<AppJsonInf
         }
     }
 
+    // Versions defined in gradle.properties or the version catalog
+    let (version_name, version_code) = read_android_versions(&Path::new(&project_path).join("android"));
+    if version_name.is_some() {
+        info.android_version = version_name;
+    }
+    if version_code.is_some() {
+        info.android_version_code = version_code;
+    }
+
     Ok(info)
 }
 
@@ -432,7 +482,7 @@ pub async fn read_native_project_info(project_path: String) -> Result<AppJsonInf
 pub async fn list_projects(state: State<'_, DbState>) -> Result<Vec<Project>, String> {
     let conn = state.0.lock().map_err(|e| e.to_string())?;
     let mut stmt = conn
-        .prepare("SELECT id, name, path, bundle_id_ios, bundle_id_android, version_ios, version_android, build_number_ios, build_number_android, ios_scheme, ios_configuration, ios_team_id, ios_export_method, ios_api_key, ios_api_issuer, ios_credential_id, android_credential_id, slack_notifications, android_build_command, android_firebase_app_id, android_distribution_groups FROM projects")
+        .prepare("SELECT id, name, path, bundle_id_ios, bundle_id_android, version_ios, version_android, build_number_ios, build_number_android, ios_scheme, ios_configuration, ios_team_id, ios_export_method, ios_api_key, ios_api_issuer, ios_credential_id, android_credential_id, slack_notifications, android_build_command, android_firebase_app_id, android_distribution_groups, release_settings, android_play_track, android_play_locales, android_play_release_status, ios_beta_groups, android_testers, ios_firebase_app_id, ios_distribution_groups, ios_testers, android_signing, ios_export_options, prebuild_settings, build_profiles, size_budgets FROM projects")
         .map_err(|e| e.to_string())?;
 
     let project_iter = stmt
@@ -446,10 +496,31 @@ pub async fn list_projects(state: State<'_, DbState>) -> Result<Vec<Project>, St
             let slack_notifications_json: Option<String> = row.get(17)?;
             let android_firebase_app_id: Option<String> = row.get(19)?;
             let android_distribution_groups: Option<String> = row.get(20)?;
+            let release_settings_json: Option<String> = row.get(21)?;
+            let android_play_track: Option<String> = row.get(22)?;
+            let android_play_locales: Option<String> = row.get(23)?;
+            let android_play_release_status: Option<String> = row.get(24)?;
+            let ios_beta_groups: Option<String> = row.get(25)?;
+            let android_testers: Option<String> = row.get(26)?;
+            let ios_firebase_app_id: Option<String> = row.get(27)?;
+            let ios_distribution_groups: Option<String> = row.get(28)?;
+            let ios_testers: Option<String> = row.get(29)?;
+            let android_signing_json: Option<String> = row.get(30)?;
+            let ios_export_options_json: Option<String> = row.get(31)?;
+            let prebuild_json: Option<String> = row.get(32)?;
+            let profiles_json: Option<String> = row.get(33)?;
+            let size_budgets_json: Option<String> = row.get(34)?;
 
             let notifications = slack_notifications_json.and_then(|s| {
                 serde_json::from_str(&s).ok()
             });
+            let release = release_settings_json.and_then(|s| serde_json::from_str(&s).ok());
+            let prebuild = prebuild_json.and_then(|s| serde_json::from_str(&s).ok());
+            let profiles = profiles_json.and_then(|s| serde_json::from_str(&s).ok()).unwrap_or_default();
+            let size_budgets = size_budgets_json.and_then(|s| serde_json::from_str(&s).ok());
+            let android_signing: Option<crate::models::project::AndroidSigningConfig> =
+                android_signing_json.and_then(|s| serde_json::from_str(&s).ok());
+            let ios_export_options = ios_export_options_json.and_then(|s| serde_json::from_str(&s).ok());
 
             let ios_config = if let (Some(scheme), Some(configuration)) = (ios_scheme.clone(), ios_configuration.clone()) {
                 Some(crate::models::project::IosConfig {
@@ -459,15 +530,32 @@ pub async fn list_projects(state: State<'_, DbState>) -> Result<Vec<Project>, St
                     export_method: ios_export_method,
                     api_key: ios_api_key,
                     api_issuer: ios_api_issuer,
+                    beta_groups: ios_beta_groups,
+                    firebase_app_id: ios_firebase_app_id,
+                    distribution_groups: ios_distribution_groups,
+                    testers: ios_testers,
+                    export_options: ios_export_options,
                 })
             } else {
                 None
             };
 
-            let android_config = if android_firebase_app_id.is_some() || android_distribution_groups.is_some() {
+            let android_config = if android_firebase_app_id.is_some()
+                || android_distribution_groups.is_some()
+                || android_testers.is_some()
+                || android_play_track.is_some()
+                || android_play_locales.is_some()
+                || android_play_release_status.is_some()
+                || android_signing.is_some()
+            {
                 Some(crate::models::project::AndroidConfig {
                     firebase_app_id: android_firebase_app_id,
                     distribution_groups: android_distribution_groups,
+                    testers: android_testers,
+                    play_track: android_play_track,
+                    play_locales: android_play_locales,
+                    play_release_status: android_play_release_status,
+                    signing: android_signing,
                 })
             } else {
                 None
@@ -495,6 +583,10 @@ pub async fn list_projects(state: State<'_, DbState>) -> Result<Vec<Project>, St
                     android_id: row.get(16)?,
                 },
                 notifications,
+                release,
+                prebuild,
+                profiles,
+                size_budgets,
             };
             println!("Loaded project: {} with credentials: {:?}", p.name, p.credentials);
             Ok(p)
@@ -509,8 +601,9 @@ pub async fn list_projects(state: State<'_, DbState>) -> Result<Vec<Project>, St
     Ok(projects)
 }
 
+/// Returns the lines of the build files or app config that were updated with the new versions
 #[command]
-pub async fn save_project(state: State<'_, DbState>, project: Project) -> Result<(), String> {
+pub async fn save_project(state: State<'_, DbState>, project: Project) -> Result<Vec<FileChange>, String> {
     // Explicitly reject Flutter projects
     if Path::new(&project.path).join("pubspec.yaml").exists() {
         return Err("Flutter projects are not supported. Please select a React Native project.".to_string());
@@ -519,6 +612,9 @@ pub async fn save_project(state: State<'_, DbState>, project: Project) -> Result
     println!("Saving project: {} (ID: {})", project.name, project.id);
     println!("Project credentials: {:?}", project.credentials);
 
+    // NULL when the project has no release settings
+    let release_settings = project.release.as_ref().map(serde_json::to_string).transpose().map_err(|e| e.to_string())?;
+
     let conn = state.0.lock().map_err(|e| e.to_string())?;
     conn.execute(
         "INSERT OR REPLACE INTO projects (
@@ -529,9 +625,14 @@ pub async fn save_project(state: State<'_, DbState>, project: Project) -> Result
             ios_scheme, ios_configuration, ios_team_id, ios_export_method,
             ios_api_key, ios_api_issuer, ios_credential_id, android_credential_id,
             slack_notifications, android_build_command,
-            android_firebase_app_id, android_distribution_groups
+            android_firebase_app_id, android_distribution_groups,
+            release_settings,
+            android_play_track, android_play_locales, android_play_release_status,
+            ios_beta_groups, android_testers,
+            ios_firebase_app_id, ios_distribution_groups, ios_testers,
+            android_signing, ios_export_options, prebuild_settings, build_profiles, size_budgets
         )
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, ?35)",
         params![
             project.id,
             project.name,
@@ -554,45 +655,60 @@ pub async fn save_project(state: State<'_, DbState>, project: Project) -> Result
             project.android.build_command.as_ref(),
             project.android.config.as_ref().and_then(|c| c.firebase_app_id.as_ref()),
             project.android.config.as_ref().and_then(|c| c.distribution_groups.as_ref()),
+            release_settings,
+            project.android.config.as_ref().and_then(|c| c.play_track.as_ref()),
+            project.android.config.as_ref().and_then(|c| c.play_locales.as_ref()),
+            project.android.config.as_ref().and_then(|c| c.play_release_status.as_ref()),
+            project.ios.config.as_ref().and_then(|c| c.beta_groups.as_ref()),
+            project.android.config.as_ref().and_then(|c| c.testers.as_ref()),
+            project.ios.config.as_ref().and_then(|c| c.firebase_app_id.as_ref()),
+            project.ios.config.as_ref().and_then(|c| c.distribution_groups.as_ref()),
+            project.ios.config.as_ref().and_then(|c| c.testers.as_ref()),
+            project
+                .android
+                .config
+                .as_ref()
+                .and_then(|c| c.signing.as_ref())
+                .and_then(|s| serde_json::to_string(s).ok()),
+            project
+                .ios
+                .config
+                .as_ref()
+                .and_then(|c| c.export_options.as_ref())
+                .and_then(|o| serde_json::to_string(o).ok()),
+            project.prebuild.as_ref().and_then(|p| serde_json::to_string(p).ok()),
+            serde_json::to_string(&project.profiles).ok(),
+            project.size_budgets.as_ref().and_then(|b| serde_json::to_string(b).ok()),
         ],
     )
     .map_err(|e| e.to_string())?;
 
-    // Update Info.plist
-    update_ios_info_plist(
-        &project.path,
-        &project.ios.version,
-        &project.ios.build_number.to_string()
-    )?;
-
-    // Update Android build.gradle
-    update_android_gradle(
-        &project.path,
-        &project.android.version,
-        &project.android.version_code.to_string()
-    )?;
-
-    // Update build.json
-    update_build_json(
-        &project.path,
-        &project.name,
-        &project.ios.bundle_id,
-        &project.ios.version,
-        project.ios.build_number,
-        &project.android.bundle_id,
-        &project.android.version,
-        project.android.version_code,
-        project.android.build_command.as_deref(),
-    )?;
-
-    Ok(())
+    write_project_versions(&project)
 }
 
 #[command]
-pub async fn delete_project(state: State<'_, DbState>, id: String) -> Result<(), String> {
+pub async fn delete_project(app_handle: AppHandle, state: State<'_, DbState>, id: String) -> Result<(), String> {
     let conn = state.0.lock().map_err(|e| e.to_string())?;
 
+    // Queued builds would otherwise still run from their snapshot of the project
+    remove_project_jobs(&app_handle, &conn, &id)?;
+
+    // Forget the keystore passwords of the project's signing config
+    let signing: Option<String> = conn
+        .query_row("SELECT android_signing FROM projects WHERE id = ?1", params![id], |row| row.get(0))
+        .unwrap_or(None);
+    if let Some(config) = signing.and_then(|s| serde_json::from_str::<crate::models::project::AndroidSigningConfig>(&s).ok()) {
+        forget_signing_passwords(&config.id)?;
+    }
+
     // Delete build history first
+    for table in ["build_diagnostics", "build_timings", "build_phase_timings", "build_sizes", "build_size_categories"] {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE build_id IN (SELECT id FROM build_history WHERE project_id = ?1)", table),
+            params![id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
     conn.execute("DELETE FROM build_history WHERE project_id = ?1", params![id])
         .map_err(|e| e.to_string())?;
 
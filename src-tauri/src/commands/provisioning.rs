@@ -0,0 +1,331 @@
+use crate::commands::keystore_inspector::{certificate_info, CertificateInfo};
+use chrono::{DateTime, Utc};
+use plist::Value;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::command;
+
+/// Where Xcode 16+ and earlier versions keep downloaded provisioning profiles, under the home directory
+const PROFILE_DIRS: [&str; 2] = ["Library/Developer/Xcode/UserData/Provisioning Profiles", "Library/MobileDevice/Provisioning Profiles"];
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileCertificate {
+    #[serde(flatten)]
+    pub certificate: CertificateInfo,
+    /// Whether the keychain holds the private key for it, so codesign can use it
+    pub installed: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisioningProfile {
+    pub path: String,
+    pub name: String,
+    pub uuid: String,
+    pub team_id: String,
+    pub team_name: String,
+    pub app_id_name: String,
+    /// The application identifier without the team prefix; may end with a `*` wildcard
+    pub bundle_id: String,
+    /// The export method the profile allows: development, ad-hoc, app-store or enterprise
+    pub profile_type: String,
+    pub platforms: Vec<String>,
+    pub created: String,
+    pub expires: String,
+    pub expired: bool,
+    pub device_count: usize,
+    pub entitlements: serde_json::Value,
+    /// The certificates builds signed with this profile may use
+    pub certificates: Vec<ProfileCertificate>,
+}
+
+/// Which profile an iOS build would be signed with, or why none fits
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvisioningCheck {
+    pub profile: Option<ProvisioningProfile>,
+    pub problems: Vec<String>,
+}
+
+/// A code signing identity in the keychain, as `security find-identity` lists it
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningIdentity {
+    /// SHA-1 of the certificate, uppercase hex without separators
+    pub sha1: String,
+    pub name: String,
+}
+
+#[command]
+pub async fn list_provisioning_profiles() -> Result<Vec<ProvisioningProfile>, String> {
+    Ok(load_installed_profiles(Utc::now()))
+}
+
+#[command]
+pub async fn check_provisioning_profiles(bundle_id: String, export_method: Option<String>, team_id: Option<String>) -> Result<ProvisioningCheck, String> {
+    let profiles = load_installed_profiles(Utc::now());
+    Ok(match_profile(&profiles, &bundle_id, export_method.as_deref().unwrap_or("development"), team_id.as_deref()))
+}
+
+/// Every readable profile in the provisioning profile directories. Unreadable files are skipped.
+pub fn load_installed_profiles(now: DateTime<Utc>) -> Vec<ProvisioningProfile> {
+    let Some(home) = std::env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    let identities = installed_signing_identities();
+    let mut profiles = Vec::new();
+    for dir in PROFILE_DIRS {
+        let Ok(entries) = std::fs::read_dir(home.join(dir)) else {
+            continue;
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().is_some_and(|e| e == "mobileprovision" || e == "provisionprofile") {
+                match read_profile(&path, &identities, now) {
+                    Ok(profile) => profiles.push(profile),
+                    Err(e) => println!("Skipping {}: {}", path.display(), e),
+                }
+            }
+        }
+    }
+    profiles
+}
+
+/// The code signing identities `security find-identity -v -p codesigning` reports. Empty
+/// when the tool is unavailable.
+pub fn installed_signing_identities() -> Vec<SigningIdentity> {
+    let Ok(output) = Command::new("security").args(["find-identity", "-v", "-p", "codesigning"]).output() else {
+        return Vec::new();
+    };
+    parse_signing_identities(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Lines look like `  1) 0123…CDEF "Apple Distribution: Example Inc (AB12CD34EF)"`
+pub fn parse_signing_identities(output: &str) -> Vec<SigningIdentity> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.trim().split_once(") ")?;
+            let (sha1, name) = rest.split_once(' ')?;
+            let name = name.trim().strip_prefix('"')?.strip_suffix('"')?;
+            (sha1.len() == 40 && sha1.chars().all(|c| c.is_ascii_hexdigit()))
+                .then(|| SigningIdentity { sha1: sha1.to_ascii_uppercase(), name: name.to_string() })
+        })
+        .collect()
+}
+
+pub fn read_profile(path: &Path, identities: &[SigningIdentity], now: DateTime<Utc>) -> Result<ProvisioningProfile, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut profile = parse_profile(&bytes, identities, now)?;
+    profile.path = path.to_string_lossy().to_string();
+    Ok(profile)
+}
+
+/// Decode a `.mobileprovision`: a plist signed by Apple in a CMS envelope
+pub fn parse_profile(bytes: &[u8], identities: &[SigningIdentity], now: DateTime<Utc>) -> Result<ProvisioningProfile, String> {
+    let content = cms_content(bytes).ok_or("Not a provisioning profile: no signed content")?;
+    let plist = Value::from_reader(std::io::Cursor::new(content)).map_err(|e| format!("Invalid profile plist: {}", e))?;
+    let dict = plist.as_dictionary().ok_or("Invalid profile plist: not a dictionary")?;
+
+    let string = |key: &str| dict.get(key).and_then(|v| v.as_string()).unwrap_or_default().to_string();
+    let date = |key: &str| -> Option<DateTime<Utc>> {
+        dict.get(key).and_then(|v| v.as_date()).map(|d| DateTime::<Utc>::from(std::time::SystemTime::from(d)))
+    };
+    let strings = |key: &str| -> Vec<String> {
+        dict.get(key)
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_string()).map(|s| s.to_string()).collect())
+            .unwrap_or_default()
+    };
+
+    let entitlements = dict.get("Entitlements").and_then(|v| v.as_dictionary());
+    let entitlement_string = |key: &str| entitlements.and_then(|e| e.get(key)).and_then(|v| v.as_string()).unwrap_or_default();
+    let team_id = strings("TeamIdentifier").into_iter().next().unwrap_or_default();
+    let application_identifier = entitlement_string("application-identifier");
+    let bundle_id = application_identifier
+        .strip_prefix(&format!("{}.", team_id))
+        .or_else(|| application_identifier.split_once('.').map(|(_, id)| id))
+        .unwrap_or(application_identifier)
+        .to_string();
+
+    let devices = dict.get("ProvisionedDevices").and_then(|v| v.as_array());
+    let get_task_allow = entitlements.and_then(|e| e.get("get-task-allow")).and_then(|v| v.as_boolean()) == Some(true);
+    let profile_type = if dict.get("ProvisionsAllDevices").and_then(|v| v.as_boolean()) == Some(true) {
+        "enterprise"
+    } else if devices.is_some() && get_task_allow {
+        "development"
+    } else if devices.is_some() {
+        "ad-hoc"
+    } else {
+        "app-store"
+    };
+
+    let certificates = dict
+        .get("DeveloperCertificates")
+        .and_then(|v| v.as_array())
+        .map(|certs| {
+            certs
+                .iter()
+                .filter_map(|c| c.as_data())
+                .filter_map(|der| certificate_info(der, now).ok())
+                .map(|certificate| {
+                    let sha1 = certificate.sha1.replace(':', "");
+                    let installed = identities.iter().any(|i| i.sha1 == sha1);
+                    ProfileCertificate { certificate, installed }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let expires = date("ExpirationDate");
+    Ok(ProvisioningProfile {
+        path: String::new(),
+        name: string("Name"),
+        uuid: string("UUID"),
+        team_id,
+        team_name: string("TeamName"),
+        app_id_name: string("AppIDName"),
+        bundle_id,
+        profile_type: profile_type.to_string(),
+        platforms: strings("Platform"),
+        created: date("CreationDate").map(|d| d.to_rfc3339()).unwrap_or_default(),
+        expires: expires.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        expired: expires.is_none_or(|d| d <= now),
+        device_count: devices.map(|d| d.len()).unwrap_or(0),
+        entitlements: entitlements
+            .and_then(|e| serde_json::to_value(e).ok())
+            .unwrap_or(serde_json::Value::Null),
+        certificates,
+    })
+}
+
+/// The profile type an export method needs. Xcode 15.3 renamed the methods.
+fn profile_type_for(export_method: &str) -> &str {
+    match export_method {
+        "app-store-connect" => "app-store",
+        "release-testing" => "ad-hoc",
+        "debugging" => "development",
+        method => method,
+    }
+}
+
+fn bundle_id_matches(pattern: &str, bundle_id: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => bundle_id.starts_with(prefix),
+        None => pattern == bundle_id,
+    }
+}
+
+/// Pick the profile to sign `bundle_id` with for `export_method`: an explicit App ID over a
+/// wildcard, then the one that expires last. When none fits, say what is wrong with each.
+pub fn match_profile(profiles: &[ProvisioningProfile], bundle_id: &str, export_method: &str, team_id: Option<&str>) -> ProvisioningCheck {
+    let wanted_type = profile_type_for(export_method);
+    let mut candidates: Vec<&ProvisioningProfile> = profiles
+        .iter()
+        .filter(|p| bundle_id_matches(&p.bundle_id, bundle_id))
+        .filter(|p| p.platforms.is_empty() || p.platforms.iter().any(|platform| platform == "iOS"))
+        .collect();
+    candidates.sort_by(|a, b| a.bundle_id.ends_with('*').cmp(&b.bundle_id.ends_with('*')).then(b.expires.cmp(&a.expires)));
+
+    if candidates.is_empty() {
+        return ProvisioningCheck {
+            profile: None,
+            problems: vec![format!("No provisioning profile covers {}", bundle_id)],
+        };
+    }
+
+    let mut problems = Vec::new();
+    for profile in candidates {
+        let mut issues = Vec::new();
+        if profile.expired {
+            issues.push(format!("expired on {}", profile.expires));
+        }
+        if profile.profile_type != wanted_type {
+            issues.push(format!("is a {} profile, {} export needs {}", profile.profile_type, export_method, wanted_type));
+        }
+        if let Some(team) = team_id.filter(|t| !t.is_empty() && *t != profile.team_id) {
+            issues.push(format!("belongs to team {}, not {}", profile.team_id, team));
+        }
+        if !profile.certificates.iter().any(|c| c.installed && c.certificate.days_until_expiry >= 0) {
+            issues.push("none of its certificates is installed in the keychain".to_string());
+        }
+        if issues.is_empty() {
+            return ProvisioningCheck { profile: Some(profile.clone()), problems: Vec::new() };
+        }
+        problems.push(format!("\"{}\" {}", profile.name, issues.join(", ")));
+    }
+    ProvisioningCheck { profile: None, problems }
+}
+
+/// A BER element: its tag, its contents (without the end-of-contents marker of an
+/// indefinite length) and the length of the whole encoding
+fn ber_element(bytes: &[u8]) -> Option<(u8, &[u8], usize)> {
+    let tag = *bytes.first()?;
+    let first = *bytes.get(1)? as usize;
+    if first == 0x80 {
+        // Indefinite length: children until 00 00
+        let mut offset = 2;
+        while bytes.get(offset..offset + 2)? != [0, 0] {
+            let (_, _, length) = ber_element(&bytes[offset..])?;
+            offset += length;
+        }
+        return Some((tag, &bytes[2..offset], offset + 2));
+    }
+    let (length, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count > 4 {
+            return None;
+        }
+        let length = bytes.get(2..2 + count)?.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (length, 2 + count)
+    };
+    let end = header.checked_add(length)?;
+    Some((tag, bytes.get(header..end)?, end))
+}
+
+fn ber_children(mut bytes: &[u8]) -> Option<Vec<(u8, &[u8])>> {
+    let mut children = Vec::new();
+    while !bytes.is_empty() {
+        let (tag, contents, length) = ber_element(bytes)?;
+        children.push((tag, contents));
+        bytes = &bytes[length..];
+    }
+    Some(children)
+}
+
+/// An OCTET STRING's value, joining the segments of a constructed one
+fn octet_string(tag: u8, contents: &[u8]) -> Option<Vec<u8>> {
+    match tag {
+        0x04 => Some(contents.to_vec()),
+        0x24 => ber_children(contents)?
+            .into_iter()
+            .map(|(tag, contents)| octet_string(tag, contents))
+            .collect::<Option<Vec<_>>>()
+            .map(|segments| segments.concat()),
+        _ => None,
+    }
+}
+
+/// ContentInfo{ signedData, [0]{ SignedData{ version, digestAlgorithms, EncapsulatedContentInfo{ data, [0]{ OCTET STRING } } } } }
+fn cms_content(bytes: &[u8]) -> Option<Vec<u8>> {
+    let (0x30, content_info, _) = ber_element(bytes)? else {
+        return None;
+    };
+    let content_info = ber_children(content_info)?;
+    let (0xa0, explicit) = *content_info.get(1)? else {
+        return None;
+    };
+    let (0x30, signed_data, _) = ber_element(explicit)? else {
+        return None;
+    };
+    let (0x30, encapsulated) = *ber_children(signed_data)?.get(2)? else {
+        return None;
+    };
+    let (0xa0, explicit) = *ber_children(encapsulated)?.get(1)? else {
+        return None;
+    };
+    let (tag, contents, _) = ber_element(explicit)?;
+    octet_string(tag, contents)
+}
@@ -0,0 +1,536 @@
+use crate::commands::build::{kill_build_process, run_build, BuildOptions};
+use crate::commands::events::{BuildEvent, BuildEventEnvelope, BuildEventSink, BuildStatus, LogLevel};
+use crate::commands::history::insert_build_history;
+use crate::models::project::{BuildHistory, GitProvenance, Project};
+use crate::models::queue::{BuildQueueJob, BuildQueueSettings};
+use crate::{BuildProcessState, BuildQueueState, DbState};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::{Arc, Mutex};
+use tauri::{command, AppHandle, Emitter, Manager, State};
+
+const JOB_COLUMNS: &str = "id, project_id, platform, project, options, status, position, enqueued_at, started_at, finished_at, error";
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+fn new_job_id() -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("q{:x}", nanos)
+}
+
+fn job_from_row(row: &rusqlite::Row) -> rusqlite::Result<BuildQueueJob> {
+    let project_json: String = row.get(3)?;
+    let options_json: Option<String> = row.get(4)?;
+    let project: Project = serde_json::from_str(&project_json).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+
+    Ok(BuildQueueJob {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        platform: row.get(2)?,
+        project,
+        options: options_json.and_then(|o| serde_json::from_str(&o).ok()),
+        status: row.get(5)?,
+        position: row.get(6)?,
+        enqueued_at: row.get(7)?,
+        started_at: row.get(8)?,
+        finished_at: row.get(9)?,
+        error: row.get(10)?,
+    })
+}
+
+fn load_jobs(conn: &Connection, status: Option<&str>) -> Result<Vec<BuildQueueJob>, String> {
+    let jobs = if let Some(status) = status {
+        let mut stmt = conn
+            .prepare(&format!("SELECT {} FROM build_queue WHERE status = ?1 ORDER BY position ASC, enqueued_at ASC", JOB_COLUMNS))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map(params![status], job_from_row).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?
+    } else {
+        let mut stmt = conn
+            .prepare(&format!("SELECT {} FROM build_queue ORDER BY position ASC, enqueued_at ASC", JOB_COLUMNS))
+            .map_err(|e| e.to_string())?;
+        let rows = stmt.query_map([], job_from_row).map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())?
+    };
+    Ok(jobs)
+}
+
+fn load_job(conn: &Connection, job_id: &str) -> Result<Option<BuildQueueJob>, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM build_queue WHERE id = ?1", JOB_COLUMNS),
+        params![job_id],
+        job_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub fn load_queue_settings(conn: &Connection) -> Result<BuildQueueSettings, String> {
+    let settings = conn
+        .query_row(
+            "SELECT max_concurrent, max_ios, max_android FROM build_queue_settings WHERE id = 1",
+            [],
+            |row| {
+                Ok(BuildQueueSettings {
+                    max_concurrent: row.get(0)?,
+                    max_ios: row.get(1)?,
+                    max_android: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    Ok(settings.unwrap_or_default())
+}
+
+fn set_job_status(conn: &Connection, job_id: &str, status: &str, error: Option<&str>) -> Result<(), String> {
+    let now = now_millis();
+    match status {
+        "running" => conn.execute(
+            "UPDATE build_queue SET status = ?1, started_at = ?2, error = NULL WHERE id = ?3",
+            params![status, now, job_id],
+        ),
+        "pending" => conn.execute(
+            "UPDATE build_queue SET status = ?1, started_at = NULL, finished_at = NULL, error = NULL WHERE id = ?2",
+            params![status, job_id],
+        ),
+        _ => conn.execute(
+            "UPDATE build_queue SET status = ?1, finished_at = ?2, error = ?3 WHERE id = ?4",
+            params![status, now, error, job_id],
+        ),
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn emit_queue_event(app: &AppHandle, job: &BuildQueueJob, status: &str) {
+    let _ = app.emit(
+        "build-queue",
+        serde_json::json!({
+            "jobId": job.id,
+            "projectId": job.project_id,
+            "platform": job.platform,
+            "status": status,
+            "position": job.position,
+        }),
+    );
+}
+
+/// Re-queue jobs that were interrupted by an app exit and start dispatching.
+/// Called once from `setup`.
+pub fn restore_build_queue(app: &AppHandle) -> Result<(), String> {
+    {
+        let db = app.state::<DbState>();
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE build_queue SET status = 'pending', started_at = NULL WHERE status = 'running'",
+            [],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    dispatch_build_queue(app)
+}
+
+/// Start as many pending jobs as the concurrency limits allow.
+pub fn dispatch_build_queue(app: &AppHandle) -> Result<(), String> {
+    let db = app.state::<DbState>();
+    let queue_state = app.state::<BuildQueueState>();
+    let process_state = app.state::<BuildProcessState>();
+
+    let to_start = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let settings = load_queue_settings(&conn)?;
+        let pending = load_jobs(&conn, Some("pending"))?;
+        let mut running = queue_state.0.lock().map_err(|e| e.to_string())?;
+        let active_processes = process_state.0.lock().map_err(|e| e.to_string())?;
+
+        let mut started = Vec::new();
+        for job in pending {
+            if running.len() as u32 >= settings.max_concurrent {
+                break;
+            }
+
+            let platform_running = running.values().filter(|j| j.platform == job.platform).count() as u32;
+            if platform_running >= settings.limit_for(&job.platform) {
+                continue;
+            }
+
+            // Only one build per project/platform can run at a time (queued or started directly)
+            let same_target = running
+                .values()
+                .any(|j| j.project_id == job.project_id && j.platform == job.platform);
+            let process_key = format!("{}_{}", job.project_id, job.platform);
+            if same_target || active_processes.contains_key(&process_key) {
+                continue;
+            }
+
+            set_job_status(&conn, &job.id, "running", None)?;
+            running.insert(job.id.clone(), job.clone());
+            started.push(job);
+        }
+        started
+    };
+
+    for job in to_start {
+        emit_queue_event(app, &job, "running");
+        let app_handle = app.clone();
+        tauri::async_runtime::spawn(async move {
+            run_queued_job(app_handle, job).await;
+        });
+    }
+
+    Ok(())
+}
+
+/// Records the parts of a queued build's events that end up in its history row.
+#[derive(Clone, Default)]
+struct JobCapture(Arc<Mutex<CapturedBuild>>);
+
+#[derive(Default)]
+struct CapturedBuild {
+    status: Option<BuildStatus>,
+    log_file_path: Option<String>,
+    artifact_path: Option<String>,
+    git: Option<GitProvenance>,
+    release_notes: Option<String>,
+    /// Version and build number after a bump (or its rollback)
+    version: Option<(String, u32)>,
+    /// The log as the build window shows it: every line but the debug ones
+    logs: String,
+}
+
+impl BuildEventSink for JobCapture {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String> {
+        let mut captured = self.0.lock().unwrap();
+        match &envelope.event {
+            BuildEvent::Status { status } => captured.status = Some(*status),
+            BuildEvent::LogFile { path } => captured.log_file_path = Some(path.clone()),
+            BuildEvent::Artifact { path } => captured.artifact_path = Some(path.clone()),
+            BuildEvent::Provenance(git) => captured.git = Some(git.clone()),
+            BuildEvent::ReleaseNotes { notes } => captured.release_notes = Some(notes.clone()),
+            BuildEvent::Version { version, build_number } => captured.version = Some((version.clone(), *build_number)),
+            BuildEvent::Log { level, message, .. } if *level != LogLevel::Debug => {
+                captured.logs.push_str(message);
+                captured.logs.push('\n');
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+async fn run_queued_job(app: AppHandle, job: BuildQueueJob) {
+    let mut options: BuildOptions = job
+        .options
+        .clone()
+        .and_then(|o| serde_json::from_value(o).ok())
+        .unwrap_or_default();
+    let build_id = options.build_id.clone().unwrap_or_else(|| job.id.clone());
+    options.build_id = Some(build_id.clone());
+
+    let capture = JobCapture::default();
+    let result = if queued_build_cancelled(&app, &job.project_id, &job.platform) {
+        Ok(())
+    } else {
+        let process_state = app.state::<BuildProcessState>();
+        let sinks: Vec<Box<dyn BuildEventSink>> = vec![Box::new(capture.clone())];
+        run_build(&app, job.project.clone(), job.platform.clone(), Some(options.clone()), process_state.inner(), sinks).await
+    };
+
+    let captured = std::mem::take(&mut *capture.0.lock().unwrap());
+    let logs = format!("Starting queued {} build for {}...\n{}", job.platform, job.project.name, captured.logs);
+    let (status, error) = match &result {
+        Ok(()) => match captured.status {
+            Some(BuildStatus::Success) => ("success", None),
+            _ => ("cancelled", None),
+        },
+        Err(e) => ("failed", Some(e.clone())),
+    };
+
+    {
+        let db = app.state::<DbState>();
+        let queue_state = app.state::<BuildQueueState>();
+        if let Ok(conn) = db.0.lock() {
+            // The row is gone when the project was deleted while the job ran: nothing to record
+            if let Some(current) = load_job(&conn, &job.id).ok().flatten() {
+                // A cancel request may already have marked the job as cancelled
+                let final_status = if current.status == "cancelled" { "cancelled" } else { status };
+                let _ = set_job_status(&conn, &job.id, final_status, error.as_deref());
+
+                let (version, build_number) = match captured.version.clone() {
+                    Some(version) => version,
+                    None if job.platform == "ios" => (job.project.ios.version.clone(), job.project.ios.build_number),
+                    None => (job.project.android.version.clone(), job.project.android.version_code),
+                };
+                let history = BuildHistory {
+                    id: build_id,
+                    project_id: job.project_id.clone(),
+                    platform: job.platform.clone(),
+                    version,
+                    build_number,
+                    status: if final_status == "success" { "success".to_string() } else { "failed".to_string() },
+                    timestamp: now_millis(),
+                    logs: match &error {
+                        Some(e) => format!("{}Error: {}\n", logs, e),
+                        None => logs,
+                    },
+                    release_note: captured
+                        .release_notes
+                        .or_else(|| options.release_note.clone())
+                        .unwrap_or_default(),
+                    format: if job.platform == "android" {
+                        Some(options.android_format.clone().unwrap_or_else(|| "aab".to_string()))
+                    } else {
+                        None
+                    },
+                    artifact_path: captured.artifact_path,
+                    log_file_path: captured.log_file_path,
+                    profile: options.profile.clone(),
+                    git: captured.git,
+                    diagnostics: Vec::new(),
+                    timing: None,
+                };
+                if let Err(e) = insert_build_history(&conn, &history) {
+                    println!("Failed to save history for queued job {}: {}", job.id, e);
+                }
+                emit_queue_event(&app, &job, final_status);
+            }
+        }
+        queue_state.0.lock().unwrap().remove(&job.id);
+    }
+
+    if let Err(e) = dispatch_build_queue(&app) {
+        println!("Failed to dispatch build queue: {}", e);
+    }
+}
+
+#[command]
+pub async fn enqueue_build(
+    app_handle: AppHandle,
+    db: State<'_, DbState>,
+    project: Project,
+    platform: String,
+    options: Option<BuildOptions>,
+) -> Result<BuildQueueJob, String> {
+    if platform != "ios" && platform != "android" {
+        return Err(format!("Unsupported platform: {}", platform));
+    }
+
+    let job = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let position: i64 = conn
+            .query_row("SELECT COALESCE(MAX(position), 0) + 1 FROM build_queue", [], |row| row.get(0))
+            .map_err(|e| e.to_string())?;
+
+        let job = BuildQueueJob {
+            id: new_job_id(),
+            project_id: project.id.clone(),
+            platform,
+            options: options.map(|o| serde_json::to_value(o).unwrap_or_default()),
+            project,
+            status: "pending".to_string(),
+            position,
+            enqueued_at: now_millis(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+        };
+
+        conn.execute(
+            "INSERT INTO build_queue (id, project_id, platform, project, options, status, position, enqueued_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                job.id,
+                job.project_id,
+                job.platform,
+                serde_json::to_string(&job.project).map_err(|e| e.to_string())?,
+                job.options.as_ref().map(|o| o.to_string()),
+                job.status,
+                job.position,
+                job.enqueued_at,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        job
+    };
+
+    emit_queue_event(&app_handle, &job, "pending");
+    dispatch_build_queue(&app_handle)?;
+    Ok(job)
+}
+
+#[command]
+pub async fn list_build_queue(
+    db: State<'_, DbState>,
+    include_finished: Option<bool>,
+) -> Result<Vec<BuildQueueJob>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let jobs = load_jobs(&conn, None)?;
+    if include_finished.unwrap_or(false) {
+        return Ok(jobs);
+    }
+    Ok(jobs
+        .into_iter()
+        .filter(|j| j.status == "pending" || j.status == "running")
+        .collect())
+}
+
+#[command]
+pub async fn cancel_queued_build(
+    app_handle: AppHandle,
+    db: State<'_, DbState>,
+    job_id: String,
+) -> Result<(), String> {
+    let job = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        let job = load_job(&conn, &job_id)?.ok_or_else(|| format!("Queued build not found: {}", job_id))?;
+        if job.status != "pending" && job.status != "running" {
+            return Err(format!("Build is already {}", job.status));
+        }
+        set_job_status(&conn, &job.id, "cancelled", None)?;
+        job
+    };
+
+    if job.status == "running" {
+        // run_queued_job records the history row and frees the slot once the build stops
+        stop_running_job(&app_handle, &job.id);
+    } else {
+        emit_queue_event(&app_handle, &job, "cancelled");
+    }
+    Ok(())
+}
+
+/// Flag a running job as cancelled and kill its build process. The process may not be
+/// registered yet, the build then stops at its next step or command (`queued_build_cancelled`).
+fn stop_running_job(app: &AppHandle, job_id: &str) {
+    let job = {
+        let queue_state = app.state::<BuildQueueState>();
+        let mut running = queue_state.0.lock().unwrap();
+        let Some(job) = running.get_mut(job_id) else { return };
+        job.status = "cancelled".to_string();
+        job.clone()
+    };
+    let process_state = app.state::<BuildProcessState>();
+    let _ = kill_build_process(process_state.inner(), &job.project_id, &job.platform);
+}
+
+/// Whether the queued job building this project/platform was cancelled, checked by the build
+/// before each step and right after it starts a command.
+pub fn queued_build_cancelled(app: &AppHandle, project_id: &str, platform: &str) -> bool {
+    let Some(queue_state) = app.try_state::<BuildQueueState>() else { return false };
+    let running = queue_state.0.lock().unwrap();
+    running
+        .values()
+        .any(|j| j.project_id == project_id && j.platform == platform && j.status == "cancelled")
+}
+
+/// Remove a deleted project's jobs from the queue. Running jobs are stopped, `run_queued_job`
+/// then finds their row gone and records no history.
+pub fn remove_project_jobs(app: &AppHandle, conn: &Connection, project_id: &str) -> Result<(), String> {
+    let running: Vec<String> = {
+        let queue_state = app.state::<BuildQueueState>();
+        let running = queue_state.0.lock().map_err(|e| e.to_string())?;
+        running.values().filter(|j| j.project_id == project_id).map(|j| j.id.clone()).collect()
+    };
+    for job_id in running {
+        stop_running_job(app, &job_id);
+    }
+    conn.execute("DELETE FROM build_queue WHERE project_id = ?1", params![project_id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Move a pending job to a new 0-based index among the pending jobs.
+#[command]
+pub async fn move_queued_build(
+    app_handle: AppHandle,
+    db: State<'_, DbState>,
+    job_id: String,
+    index: usize,
+) -> Result<Vec<BuildQueueJob>, String> {
+    let jobs = {
+        let mut conn = db.0.lock().map_err(|e| e.to_string())?;
+        let mut pending = load_jobs(&conn, Some("pending"))?;
+        let from = pending
+            .iter()
+            .position(|j| j.id == job_id)
+            .ok_or_else(|| "Only pending builds can be reordered".to_string())?;
+
+        let job = pending.remove(from);
+        let to = index.min(pending.len());
+        pending.insert(to, job);
+
+        // Pending jobs keep their slots after any running/finished ones
+        let base: i64 = conn
+            .query_row(
+                "SELECT COALESCE(MAX(position), 0) FROM build_queue WHERE status != 'pending'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+
+        let tx = conn.transaction().map_err(|e| e.to_string())?;
+        for (i, job) in pending.iter_mut().enumerate() {
+            job.position = base + 1 + i as i64;
+            tx.execute(
+                "UPDATE build_queue SET position = ?1 WHERE id = ?2",
+                params![job.position, job.id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+        pending
+    };
+
+    for job in &jobs {
+        emit_queue_event(&app_handle, job, "pending");
+    }
+    Ok(jobs)
+}
+
+/// Remove finished (success/failed/cancelled) jobs from the queue table.
+#[command]
+pub async fn clear_finished_builds(db: State<'_, DbState>) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM build_queue WHERE status NOT IN ('pending', 'running')",
+        [],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[command]
+pub async fn get_build_queue_settings(db: State<'_, DbState>) -> Result<BuildQueueSettings, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    load_queue_settings(&conn)
+}
+
+#[command]
+pub async fn update_build_queue_settings(
+    app_handle: AppHandle,
+    db: State<'_, DbState>,
+    settings: BuildQueueSettings,
+) -> Result<(), String> {
+    if settings.max_concurrent == 0 || settings.max_ios == 0 || settings.max_android == 0 {
+        return Err("Concurrency limits must be at least 1".to_string());
+    }
+
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO build_queue_settings (id, max_concurrent, max_ios, max_android) VALUES (1, ?1, ?2, ?3)",
+            params![settings.max_concurrent, settings.max_ios, settings.max_android],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    // Raised limits may free up slots for pending jobs
+    dispatch_build_queue(&app_handle)
+}
@@ -0,0 +1,189 @@
+use crate::commands::git::git;
+use crate::models::project::Project;
+use crate::DbState;
+use regex::Regex;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::Path;
+use std::sync::LazyLock;
+use tauri::{command, State};
+
+/// Used when the project has no template configured
+pub const DEFAULT_TEMPLATE: &str = "{{changes}}";
+
+/// Without a previous build or tag, only look this far back
+const MAX_COMMITS: usize = 50;
+
+/// `type(scope)!: description`
+static CONVENTIONAL_COMMIT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<kind>[a-zA-Z]+)(?:\((?P<scope>[^)]+)\))?(?P<breaking>!)?: (?P<description>.+)$").unwrap()
+});
+
+/// A `{{name}}` placeholder of a release notes template
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{([a-z]+)\}\}").unwrap());
+
+static BLANK_LINES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+pub struct Commit {
+    pub kind: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+/// Conventional-commit groups, in the order they are rendered
+const GROUPS: &[(&str, &str, &[&str])] = &[
+    ("features", "✨ Features", &["feat"]),
+    ("fixes", "🐛 Bug Fixes", &["fix"]),
+    ("performance", "⚡ Performance", &["perf"]),
+    ("chores", "🧹 Chores", &["chore", "refactor", "build", "ci", "docs", "style", "test"]),
+];
+
+impl Commit {
+    pub fn parse(subject: &str) -> Commit {
+        match CONVENTIONAL_COMMIT.captures(subject) {
+            Some(caps) => Commit {
+                kind: Some(caps["kind"].to_lowercase()),
+                scope: caps.name("scope").map(|m| m.as_str().to_string()),
+                breaking: caps.name("breaking").is_some(),
+                description: caps["description"].to_string(),
+            },
+            None => Commit {
+                kind: None,
+                scope: None,
+                breaking: false,
+                description: subject.to_string(),
+            },
+        }
+    }
+
+    fn group(&self) -> &'static str {
+        GROUPS
+            .iter()
+            .find(|(_, _, kinds)| self.kind.as_deref().is_some_and(|k| kinds.contains(&k)))
+            .map(|(key, _, _)| *key)
+            .unwrap_or("other")
+    }
+
+    fn bullet(&self) -> String {
+        let mut line = match &self.scope {
+            Some(scope) => format!("- {}: {}", scope, self.description),
+            None => format!("- {}", self.description),
+        };
+        if self.breaking {
+            line.push_str(" (breaking)");
+        }
+        line
+    }
+}
+
+/// The commit the previous successful build of this project/platform/profile was made from
+pub fn last_built_commit(conn: &Connection, project_id: &str, platform: &str, profile: Option<&str>) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT git_commit FROM build_history
+         WHERE project_id = ?1 AND platform = ?2 AND profile IS ?3 AND status = 'success' AND git_commit IS NOT NULL
+         ORDER BY timestamp DESC LIMIT 1",
+        params![project_id, platform, profile],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Where the notes start: the last successful build's commit if it is still an ancestor of HEAD,
+/// otherwise the most recent tag
+pub fn find_base(project_path: &Path, last_built_commit: Option<&str>) -> Option<String> {
+    if let Some(commit) = last_built_commit {
+        if git(project_path, &["merge-base", "--is-ancestor", commit, "HEAD"]).is_ok() {
+            return Some(commit.to_string());
+        }
+    }
+    git(project_path, &["describe", "--tags", "--abbrev=0", "HEAD"]).ok()
+}
+
+/// Commits touching the project directory since `base` (newest first), merges excluded
+pub fn commits_since(project_path: &Path, base: Option<&str>) -> Result<Vec<Commit>, String> {
+    let range = base.map(|b| format!("{}..HEAD", b)).unwrap_or_else(|| "HEAD".to_string());
+    let max_count = format!("--max-count={}", if base.is_some() { 500 } else { MAX_COMMITS });
+    let log = git(project_path, &["log", "--no-merges", &max_count, "--format=%s", &range, "--", "."])?;
+
+    Ok(log.lines().filter(|l| !l.trim().is_empty()).map(Commit::parse).collect())
+}
+
+/// Render commits with a template. Placeholders: `{{version}}`, `{{build}}`, `{{platform}}`,
+/// `{{changes}}` (every non-empty group with its heading), `{{features}}`, `{{fixes}}`,
+/// `{{performance}}`, `{{chores}}`, `{{other}}` (one group with its heading) and `{{commits}}`
+/// (a flat list).
+pub fn render(template: &str, commits: &[Commit], platform: &str, version: &str, build: u32) -> String {
+    let section = |key: &str, title: &str| -> String {
+        let lines: Vec<String> = commits.iter().filter(|c| c.group() == key).map(|c| c.bullet()).collect();
+        if lines.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n{}\n\n", title, lines.join("\n"))
+        }
+    };
+
+    let mut sections: Vec<(&str, String)> = GROUPS.iter().map(|(key, title, _)| (*key, section(key, title))).collect();
+    sections.push(("other", section("other", "📝 Other Changes")));
+    let changes: String = sections.iter().map(|(_, s)| s.as_str()).collect();
+
+    let platform = if platform == "ios" { "iOS" } else { "Android" };
+    let build = build.to_string();
+    let commits = commits.iter().map(|c| c.bullet()).collect::<Vec<_>>().join("\n");
+    // One pass over the template, so a `{{version}}` written in a commit subject stays as is.
+    // Unknown placeholders are kept too.
+    let notes = PLACEHOLDER.replace_all(template, |caps: &regex::Captures| {
+        let value = match &caps[1] {
+            "version" => version,
+            "build" => &build,
+            "platform" => platform,
+            "changes" => &changes,
+            "commits" => &commits,
+            key => match sections.iter().find(|(k, _)| *k == key) {
+                Some((_, text)) => text,
+                None => &caps[0],
+            },
+        };
+        value.to_string()
+    });
+
+    BLANK_LINES.replace_all(notes.trim(), "\n\n").to_string()
+}
+
+/// Generate the release notes of the next build of `project` for `platform`.
+/// `last_built_commit` comes from `last_built_commit`, queried by the caller.
+pub fn generate(project: &Project, platform: &str, last_built_commit: Option<&str>) -> Result<String, String> {
+    let project_path = Path::new(&project.path);
+    let base = find_base(project_path, last_built_commit);
+    let commits = commits_since(project_path, base.as_deref())?;
+    if commits.is_empty() {
+        return Ok(String::new());
+    }
+
+    let template = project
+        .release
+        .as_ref()
+        .and_then(|r| r.release_notes_template.clone())
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    let (version, build) = if platform == "ios" {
+        (&project.ios.version, project.ios.build_number)
+    } else {
+        (&project.android.version, project.android.version_code)
+    };
+    Ok(render(&template, &commits, platform, version, build))
+}
+
+#[command]
+pub async fn generate_release_notes(
+    state: State<'_, DbState>,
+    project: Project,
+    platform: String,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let last_commit = {
+        let conn = state.0.lock().map_err(|e| e.to_string())?;
+        last_built_commit(&conn, &project.id, &platform, profile.as_deref())?
+    };
+    generate(&project, &platform, last_commit.as_deref())
+}
@@ -0,0 +1,234 @@
+use crate::models::project::AndroidSigningConfig;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+use tauri::command;
+
+/// Environment variables the Gradle command reads the passwords from, so they never
+/// appear in the command line the build log and process list show
+pub const STORE_PASSWORD_ENV: &str = "APP_BUILDER_STORE_PASSWORD";
+pub const KEY_PASSWORD_ENV: &str = "APP_BUILDER_KEY_PASSWORD";
+
+/// APK Signature Scheme v3 and v2 blocks, in the order they are looked for
+const SIGNATURE_SCHEMES: [(u32, &str); 2] = [(0xf053_68c0, "APK Signature Scheme v3"), (0x7109_871a, "APK Signature Scheme v2")];
+const APK_SIG_BLOCK_MAGIC: &[u8; 16] = b"APK Sig Block 42";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningPasswords {
+    pub store_password: String,
+    pub key_password: String,
+}
+
+/// The certificate an artifact was signed with
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignerCertificate {
+    /// "APK Signature Scheme v3", "APK Signature Scheme v2" or "JAR signing"
+    pub scheme: &'static str,
+    /// SHA-256 of the certificate, as keytool prints it
+    pub sha256: String,
+}
+
+fn passwords_entry(signing_id: &str) -> Result<Entry, String> {
+    Entry::new("app-builder-credentials", &format!("android-signing-{}", signing_id)).map_err(|e| e.to_string())
+}
+
+/// Keep the keystore passwords of a project's signing config in the keychain.
+/// The key password defaults to the store password, as `generate_keystore` uses one for both.
+#[command]
+pub async fn save_signing_passwords(signing_id: String, store_password: String, key_password: Option<String>) -> Result<(), String> {
+    let passwords = SigningPasswords {
+        key_password: key_password.filter(|p| !p.is_empty()).unwrap_or_else(|| store_password.clone()),
+        store_password,
+    };
+    let json = serde_json::to_string(&passwords).map_err(|e| e.to_string())?;
+    passwords_entry(&signing_id)?.set_password(&json).map_err(|e| e.to_string())
+}
+
+#[command]
+pub async fn has_signing_passwords(signing_id: String) -> Result<bool, String> {
+    Ok(passwords_entry(&signing_id)?.get_password().is_ok())
+}
+
+#[command]
+pub async fn delete_signing_passwords(signing_id: String) -> Result<(), String> {
+    forget_signing_passwords(&signing_id)
+}
+
+pub fn forget_signing_passwords(signing_id: &str) -> Result<(), String> {
+    let _ = passwords_entry(signing_id)?.delete_credential(); // Ignore error if doesn't exist
+    Ok(())
+}
+
+pub fn load_signing_passwords(signing_id: &str) -> Result<SigningPasswords, String> {
+    let json = passwords_entry(signing_id)?
+        .get_password()
+        .map_err(|e| format!("Keystore passwords not found in Keychain: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid keystore passwords in Keychain: {}", e))
+}
+
+/// Gradle arguments that make the Android Gradle plugin sign with the keystore, overriding
+/// the `signingConfig` of `build.gradle`. The passwords are referenced through
+/// `signing_env`, to be expanded by the shell running Gradle.
+pub fn injected_signing_args(config: &AndroidSigningConfig) -> String {
+    format!(
+        "-Pandroid.injected.signing.store.file='{}' -Pandroid.injected.signing.store.password=\"${}\" \
+         -Pandroid.injected.signing.key.alias='{}' -Pandroid.injected.signing.key.password=\"${}\"",
+        config.keystore_path.replace('\'', r"'\''"),
+        STORE_PASSWORD_ENV,
+        config.key_alias.replace('\'', r"'\''"),
+        KEY_PASSWORD_ENV
+    )
+}
+
+pub fn signing_env(passwords: &SigningPasswords) -> Vec<(String, String)> {
+    vec![
+        (STORE_PASSWORD_ENV.to_string(), passwords.store_password.clone()),
+        (KEY_PASSWORD_ENV.to_string(), passwords.key_password.clone()),
+    ]
+}
+
+/// SHA-256 fingerprint of a DER certificate, colon-separated like `keytool -list`
+pub fn fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der).iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+/// The certificate that signed an APK or AAB, from its v3 or v2 signing block, or
+/// else from the JAR signature in `META-INF` (the only scheme bundles use).
+/// Returns `Ok(None)` for an unsigned artifact.
+pub fn signer_certificate(artifact: &Path) -> Result<Option<SignerCertificate>, String> {
+    let bytes = std::fs::read(artifact).map_err(|e| format!("Failed to read {}: {}", artifact.display(), e))?;
+
+    if let Some(block) = apk_signing_block(&bytes) {
+        for (id, scheme) in SIGNATURE_SCHEMES {
+            if let Some(cert) = find_pair(block, id).and_then(first_signer_certificate) {
+                return Ok(Some(SignerCertificate { scheme, sha256: fingerprint(cert) }));
+            }
+        }
+    }
+
+    let mut archive =
+        zip::ZipArchive::new(std::io::Cursor::new(&bytes)).map_err(|e| format!("{} is not a zip archive: {}", artifact.display(), e))?;
+    let signature_file = archive.file_names().find(|name| is_jar_signature(name)).map(|name| name.to_string());
+    let Some(name) = signature_file else {
+        return Ok(None);
+    };
+    let mut pkcs7 = Vec::new();
+    archive
+        .by_name(&name)
+        .and_then(|mut file| file.read_to_end(&mut pkcs7).map_err(Into::into))
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    let cert = pkcs7_first_certificate(&pkcs7).ok_or_else(|| format!("{} holds no certificate", name))?;
+    Ok(Some(SignerCertificate { scheme: "JAR signing", sha256: fingerprint(cert) }))
+}
+
+fn is_jar_signature(name: &str) -> bool {
+    let Some(file) = name.strip_prefix("META-INF/") else {
+        return false;
+    };
+    let upper = file.to_ascii_uppercase();
+    !file.contains('/') && (upper.ends_with(".RSA") || upper.ends_with(".EC") || upper.ends_with(".DSA"))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+/// The ID-value pairs of the APK Signing Block, which sits right before the central directory
+fn apk_signing_block(bytes: &[u8]) -> Option<&[u8]> {
+    // End of central directory record: 22 bytes plus a comment of up to 64 KiB
+    let search_from = bytes.len().saturating_sub(22 + u16::MAX as usize);
+    let eocd = (search_from..=bytes.len().checked_sub(22)?)
+        .rev()
+        .find(|&i| bytes[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])?;
+    let central_directory = u32_at(bytes, eocd + 16)? as usize;
+
+    let footer = central_directory.checked_sub(24)?;
+    if bytes.get(footer + 8..central_directory)? != APK_SIG_BLOCK_MAGIC {
+        return None;
+    }
+    let size = u64_at(bytes, footer)? as usize;
+    let start = central_directory.checked_sub(size.checked_add(8)?)?;
+    if u64_at(bytes, start)? as usize != size {
+        return None;
+    }
+    bytes.get(start + 8..footer)
+}
+
+fn find_pair(mut pairs: &[u8], id: u32) -> Option<&[u8]> {
+    while pairs.len() >= 12 {
+        let length = u64_at(pairs, 0)? as usize;
+        let pair = pairs.get(8..8usize.checked_add(length)?)?;
+        if u32_at(pair, 0)? == id {
+            return pair.get(4..);
+        }
+        pairs = &pairs[8 + length..];
+    }
+    None
+}
+
+/// Split off a value prefixed with its u32 length
+fn length_prefixed(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let length = u32_at(bytes, 0)? as usize;
+    Some((bytes.get(4..4usize.checked_add(length)?)?, &bytes[4 + length..]))
+}
+
+/// v2 and v3 blocks: signers[ signer{ signed data{ digests, certificates[ cert ], ... }, ... } ]
+fn first_signer_certificate(block: &[u8]) -> Option<&[u8]> {
+    let (signers, _) = length_prefixed(block)?;
+    let (signer, _) = length_prefixed(signers)?;
+    let (signed_data, _) = length_prefixed(signer)?;
+    let (_digests, rest) = length_prefixed(signed_data)?;
+    let (certificates, _) = length_prefixed(rest)?;
+    let (certificate, _) = length_prefixed(certificates)?;
+    Some(certificate)
+}
+
+/// A DER element: its tag, its contents, and the whole encoding
+fn der_element(bytes: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *bytes.first()?;
+    let first = *bytes.get(1)? as usize;
+    let (length, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let length = bytes.get(2..2 + count)?.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (length, 2 + count)
+    };
+    let end = header.checked_add(length)?;
+    Some((tag, bytes.get(header..end)?, &bytes[..end]))
+}
+
+/// ContentInfo{ contentType, [0]{ SignedData{ version, digestAlgorithms, contentInfo, [0] certificates, ... } } }
+fn pkcs7_first_certificate(pkcs7: &[u8]) -> Option<&[u8]> {
+    let (0x30, content_info, _) = der_element(pkcs7)? else {
+        return None;
+    };
+    let (_, _, content_type) = der_element(content_info)?;
+    let (0xa0, explicit, _) = der_element(&content_info[content_type.len()..])? else {
+        return None;
+    };
+    let (0x30, mut signed_data, _) = der_element(explicit)? else {
+        return None;
+    };
+    while !signed_data.is_empty() {
+        let (tag, contents, whole) = der_element(signed_data)?;
+        if tag == 0xa0 {
+            let (0x30, _, certificate) = der_element(contents)? else {
+                return None;
+            };
+            return Some(certificate);
+        }
+        signed_data = &signed_data[whole.len()..];
+    }
+    None
+}
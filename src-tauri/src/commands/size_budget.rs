@@ -0,0 +1,38 @@
+use crate::commands::analyzer::{AppSizeReport, ASSETS, NATIVE_LIBRARIES};
+use crate::commands::artifact_diff::format_size;
+use crate::models::project::SizeBudgets;
+
+/// Size of one category of the report's breakdown, 0 when the artifact has none
+fn category_size(report: &AppSizeReport, category: &str) -> u64 {
+    report.breakdown.iter().find(|b| b.name == category).map_or(0, |b| b.size)
+}
+
+/// The budgets `report` goes over, one message each. The growth limit is checked against
+/// `previous`, the report of the last successful build, when there is one.
+pub fn check_budgets(report: &AppSizeReport, budgets: &SizeBudgets, previous: Option<&AppSizeReport>) -> Vec<String> {
+    let checks = [
+        ("Total size", report.total_size, budgets.total),
+        ("Native libraries", category_size(report, NATIVE_LIBRARIES), budgets.native_libraries),
+        ("JS bundle", report.js_bundle_size, budgets.js_bundle),
+        ("Assets", category_size(report, ASSETS), budgets.assets),
+    ];
+    let mut violations: Vec<String> = checks
+        .into_iter()
+        .filter_map(|(name, size, budget)| {
+            let budget = budget.filter(|budget| size > *budget)?;
+            Some(format!("{} is {}, over the {} budget", name, format_size(size), format_size(budget)))
+        })
+        .collect();
+
+    if let (Some(limit), Some(previous)) = (budgets.max_increase, previous) {
+        let increase = report.total_size.saturating_sub(previous.total_size);
+        if increase > limit {
+            violations.push(format!(
+                "Total size grew by {} since the previous build, over the {} limit",
+                format_size(increase),
+                format_size(limit)
+            ));
+        }
+    }
+    violations
+}
@@ -0,0 +1,206 @@
+use crate::commands::analyzer::{AppSizeReport, CATEGORIES};
+use crate::DbState;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::{command, State};
+
+/// The stored size of one successful build
+#[derive(Debug, Clone)]
+pub struct BuildSize {
+    pub build_id: String,
+    pub platform: String,
+    pub version: String,
+    pub build_number: u32,
+    pub profile: Option<String>,
+    pub timestamp: u64,
+    pub file_type: String,
+    pub total_size: u64,
+    pub js_bundle_size: u64,
+    /// Size of each category of the report's breakdown
+    pub categories: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeTrendBuild {
+    pub build_id: String,
+    pub version: String,
+    pub build_number: u32,
+    pub profile: Option<String>,
+    pub timestamp: u64,
+    pub total_size: u64,
+    pub js_bundle_size: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTrend {
+    pub name: String,
+    /// One size per build, in the order of `SizeTrend::builds`
+    pub sizes: Vec<u64>,
+}
+
+/// How the artifacts of one platform and format grew, oldest build first
+#[derive(Debug, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeTrend {
+    pub platform: String,
+    /// APK, AAB or IPA. Each format gets its own trend, their sizes can't be compared.
+    pub file_type: String,
+    pub builds: Vec<SizeTrendBuild>,
+    pub categories: Vec<CategoryTrend>,
+}
+
+/// Replace the stored size report of a build
+pub fn save_build_size(conn: &Connection, build_id: &str, report: &AppSizeReport) -> Result<(), String> {
+    let json = serde_json::to_string(report).map_err(|e| e.to_string())?;
+    // The size and its categories are saved together or not at all
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT OR REPLACE INTO build_sizes (build_id, file_type, total_size, js_bundle_size, report) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![build_id, report.file_type, report.total_size, report.js_bundle_size, json],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.execute("DELETE FROM build_size_categories WHERE build_id = ?1", params![build_id])
+        .map_err(|e| e.to_string())?;
+    for category in &report.breakdown {
+        tx.execute(
+            "INSERT INTO build_size_categories (build_id, category, size) VALUES (?1, ?2, ?3)",
+            params![build_id, category.name, category.size],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+pub fn load_build_size(conn: &Connection, build_id: &str) -> Result<Option<AppSizeReport>, String> {
+    let json: Option<String> = conn
+        .query_row("SELECT report FROM build_sizes WHERE build_id = ?1", params![build_id], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    json.map(|json| serde_json::from_str(&json).map_err(|e| format!("Invalid size report: {}", e)))
+        .transpose()
+}
+
+/// Stored size report of the last successful build of this project/platform/profile with an
+/// artifact of the same format
+pub fn previous_build_size(
+    conn: &Connection,
+    project_id: &str,
+    platform: &str,
+    profile: Option<&str>,
+    file_type: &str,
+) -> Result<Option<AppSizeReport>, String> {
+    let build_id: Option<String> = conn
+        .query_row(
+            "SELECT h.id FROM build_sizes s INNER JOIN build_history h ON h.id = s.build_id
+             WHERE h.project_id = ?1 AND h.platform = ?2 AND h.profile IS ?3 AND h.status = 'success' AND s.file_type = ?4
+             ORDER BY h.timestamp DESC LIMIT 1",
+            params![project_id, platform, profile, file_type],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match build_id {
+        Some(build_id) => load_build_size(conn, &build_id),
+        None => Ok(None),
+    }
+}
+
+/// Sizes of the project's successful builds, oldest first, of one platform or all of them
+pub fn load_build_sizes(conn: &Connection, project_id: &str, platform: Option<&str>) -> Result<Vec<BuildSize>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT h.id, h.platform, h.version, h.build_number, h.profile, h.timestamp, s.file_type, s.total_size, s.js_bundle_size
+             FROM build_sizes s INNER JOIN build_history h ON h.id = s.build_id
+             WHERE h.project_id = ?1 AND (?2 IS NULL OR h.platform = ?2) AND h.status = 'success'
+             ORDER BY h.timestamp ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let mut builds = stmt
+        .query_map(params![project_id, platform], |row| {
+            Ok(BuildSize {
+                build_id: row.get(0)?,
+                platform: row.get(1)?,
+                version: row.get(2)?,
+                build_number: row.get(3)?,
+                profile: row.get(4)?,
+                timestamp: row.get(5)?,
+                file_type: row.get(6)?,
+                total_size: row.get(7)?,
+                js_bundle_size: row.get(8)?,
+                categories: Vec::new(),
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare("SELECT category, size FROM build_size_categories WHERE build_id = ?1")
+        .map_err(|e| e.to_string())?;
+    for build in &mut builds {
+        build.categories = stmt
+            .query_map(params![build.build_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(builds)
+}
+
+/// Group build sizes into one trend per platform and artifact format. Categories no build of
+/// a trend has are left out.
+pub fn size_trends(builds: Vec<BuildSize>) -> Vec<SizeTrend> {
+    let mut groups: Vec<(String, String, Vec<BuildSize>)> = Vec::new();
+    for build in builds {
+        match groups.iter_mut().find(|(platform, file_type, _)| *platform == build.platform && *file_type == build.file_type) {
+            Some((_, _, group)) => group.push(build),
+            None => groups.push((build.platform.clone(), build.file_type.clone(), vec![build])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(platform, file_type, builds)| {
+            let size_of = |build: &BuildSize, category: &str| {
+                build.categories.iter().find(|(name, _)| name == category).map_or(0, |(_, size)| *size)
+            };
+            let categories = CATEGORIES
+                .iter()
+                .map(|name| CategoryTrend { name: name.to_string(), sizes: builds.iter().map(|b| size_of(b, name)).collect() })
+                .filter(|trend| trend.sizes.iter().any(|size| *size > 0))
+                .collect();
+            let builds = builds
+                .into_iter()
+                .map(|b| SizeTrendBuild {
+                    build_id: b.build_id,
+                    version: b.version,
+                    build_number: b.build_number,
+                    profile: b.profile,
+                    timestamp: b.timestamp,
+                    total_size: b.total_size,
+                    js_bundle_size: b.js_bundle_size,
+                })
+                .collect();
+            SizeTrend { platform, file_type, builds, categories }
+        })
+        .collect()
+}
+
+#[command]
+pub async fn get_size_history(
+    state: State<'_, DbState>,
+    project_id: String,
+    platform: Option<String>,
+) -> Result<Vec<SizeTrend>, String> {
+    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(size_trends(load_build_sizes(&conn, &project_id, platform.as_deref())?))
+}
+
+#[command]
+pub async fn get_build_size_report(state: State<'_, DbState>, build_id: String) -> Result<Option<AppSizeReport>, String> {
+    let conn = state.0.lock().map_err(|e| e.to_string())?;
+    load_build_size(&conn, &build_id)
+}
@@ -0,0 +1,93 @@
+use crate::commands::pipeline::{StepReport, StepStatus};
+use crate::models::project::{BuildTiming, PhaseTiming};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Build the timing record of a finished build from its step reports.
+/// Steps that never started (skipped up front or after a failure) have no timing.
+pub fn timing_from_reports(started_at: u64, finished_at: u64, reports: &[StepReport]) -> BuildTiming {
+    let phases = reports
+        .iter()
+        .filter_map(|report| {
+            let started_at = report.started_at?;
+            let duration_ms = report.duration_ms.unwrap_or(0);
+            Some(PhaseTiming {
+                phase: report.name.as_str().to_string(),
+                status: match report.status {
+                    StepStatus::Success => "success",
+                    StepStatus::Skipped => "skipped",
+                    _ => "failed",
+                }
+                .to_string(),
+                started_at,
+                finished_at: started_at + duration_ms,
+                duration_ms,
+            })
+        })
+        .collect();
+
+    BuildTiming {
+        started_at,
+        finished_at,
+        duration_ms: finished_at.saturating_sub(started_at),
+        phases,
+    }
+}
+
+/// Replace the stored timing of a build
+pub fn save_build_timing(conn: &Connection, build_id: &str, timing: &BuildTiming) -> Result<(), String> {
+    // The build and its phases are saved together or not at all
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT OR REPLACE INTO build_timings (build_id, started_at, finished_at, duration_ms) VALUES (?1, ?2, ?3, ?4)",
+        params![build_id, timing.started_at, timing.finished_at, timing.duration_ms],
+    )
+    .map_err(|e| e.to_string())?;
+
+    tx.execute("DELETE FROM build_phase_timings WHERE build_id = ?1", params![build_id])
+        .map_err(|e| e.to_string())?;
+    for phase in &timing.phases {
+        tx.execute(
+            "INSERT INTO build_phase_timings (build_id, phase, status, started_at, finished_at, duration_ms)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![build_id, phase.phase, phase.status, phase.started_at, phase.finished_at, phase.duration_ms],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+pub fn load_build_timing(conn: &Connection, build_id: &str) -> Result<Option<BuildTiming>, String> {
+    let build = conn
+        .query_row(
+            "SELECT started_at, finished_at, duration_ms FROM build_timings WHERE build_id = ?1",
+            params![build_id],
+            |row| Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?, row.get::<_, u64>(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let Some((started_at, finished_at, duration_ms)) = build else {
+        return Ok(None);
+    };
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT phase, status, started_at, finished_at, duration_ms
+             FROM build_phase_timings WHERE build_id = ?1 ORDER BY started_at, id",
+        )
+        .map_err(|e| e.to_string())?;
+    let phases = stmt
+        .query_map(params![build_id], |row| {
+            Ok(PhaseTiming {
+                phase: row.get(0)?,
+                status: row.get(1)?,
+                started_at: row.get(2)?,
+                finished_at: row.get(3)?,
+                duration_ms: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(BuildTiming { started_at, finished_at, duration_ms, phases }))
+}
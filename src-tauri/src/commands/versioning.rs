@@ -0,0 +1,204 @@
+use crate::commands::project::write_project_versions;
+use crate::models::project::{BuildNumberStrategy, Project, VersionBump, VersionBumpSettings};
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A line of a build file or app config changed to write new versions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChange {
+    pub file: PathBuf,
+    /// 1-based
+    pub line: usize,
+    pub old: String,
+    pub new: String,
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {} → {}", self.file.display(), self.line, self.old, self.new)
+    }
+}
+
+/// Bump a `major.minor.patch` version. Missing components count as 0 and anything after
+/// the numbers (`-beta.1`, `+build`) is dropped.
+pub fn bump_version(version: &str, bump: VersionBump) -> Result<String, String> {
+    if bump == VersionBump::None {
+        return Ok(version.to_string());
+    }
+
+    let core = version.trim().split(['-', '+']).next().unwrap_or_default();
+    let mut parts = [0u32; 3];
+    for (i, part) in core.split('.').enumerate() {
+        if i >= 3 {
+            return Err(format!("Version {} has more than three components", version));
+        }
+        parts[i] = part
+            .parse()
+            .map_err(|_| format!("Version {} is not a major.minor.patch version", version))?;
+    }
+
+    let [major, minor, patch] = parts;
+    Ok(match bump {
+        VersionBump::Major => format!("{}.0.0", major + 1),
+        VersionBump::Minor => format!("{}.{}.0", major, minor + 1),
+        VersionBump::Patch => format!("{}.{}.{}", major, minor, patch + 1),
+        VersionBump::None => unreachable!(),
+    })
+}
+
+/// Next `YYMMDDNN` build number: the counter continues if `current` was already made today,
+/// otherwise it restarts at 01
+pub fn date_build_number(current: u32, today: NaiveDate) -> Result<u32, String> {
+    let prefix = (today.year() as u32 % 100) * 10000 + today.month() * 100 + today.day();
+    let first = prefix * 100 + 1;
+    if current < first {
+        return Ok(first);
+    }
+    if current / 100 == prefix && current % 100 < 99 {
+        return Ok(current + 1);
+    }
+    Err(format!("Build number {} cannot be replaced by a date-based number for {}", current, today))
+}
+
+/// Apply the bump strategies to `project` for a build of `platform`.
+/// The version only changes for `platform`; a shared build number is set on both platforms.
+/// Returns whether anything changed.
+pub fn apply_bump(project: &mut Project, platform: &str, settings: &VersionBumpSettings, today: NaiveDate) -> Result<bool, String> {
+    let before = (
+        project.ios.version.clone(),
+        project.ios.build_number,
+        project.android.version.clone(),
+        project.android.version_code,
+    );
+
+    let (version, build_number) = if platform == "ios" {
+        (&mut project.ios.version, &mut project.ios.build_number)
+    } else {
+        (&mut project.android.version, &mut project.android.version_code)
+    };
+    *version = bump_version(version, settings.version)?;
+
+    match settings.build_number {
+        BuildNumberStrategy::None => {}
+        BuildNumberStrategy::Increment => *build_number += 1,
+        BuildNumberStrategy::Date => *build_number = date_build_number(*build_number, today)?,
+        BuildNumberStrategy::Shared => {
+            let next = project.ios.build_number.max(project.android.version_code) + 1;
+            project.ios.build_number = next;
+            project.android.version_code = next;
+        }
+    }
+
+    let after = (
+        project.ios.version.clone(),
+        project.ios.build_number,
+        project.android.version.clone(),
+        project.android.version_code,
+    );
+    Ok(before != after)
+}
+
+/// The versions a bump replaced and the ones it wrote, so a failed build can undo its own
+/// bump without touching what another build or the user changed in the meantime
+pub struct VersionSnapshot {
+    before: Project,
+    after: Project,
+}
+
+impl VersionSnapshot {
+    pub fn new(before: &Project, after: &Project) -> Self {
+        VersionSnapshot { before: before.clone(), after: after.clone() }
+    }
+
+    /// The project as it was before the bump
+    pub fn project(&self) -> &Project {
+        &self.before
+    }
+
+    /// Put back the values this bump changed in `current`, unless they changed again since.
+    /// Returns whether anything was put back.
+    pub fn revert(&self, current: &mut Project) -> bool {
+        let (before, after) = (&self.before, &self.after);
+        let mut reverted = false;
+        if before.ios.version != after.ios.version && current.ios.version == after.ios.version {
+            current.ios.version = before.ios.version.clone();
+            reverted = true;
+        }
+        if before.ios.build_number != after.ios.build_number && current.ios.build_number == after.ios.build_number {
+            current.ios.build_number = before.ios.build_number;
+            reverted = true;
+        }
+        if before.android.version != after.android.version && current.android.version == after.android.version {
+            current.android.version = before.android.version.clone();
+            reverted = true;
+        }
+        if before.android.version_code != after.android.version_code
+            && current.android.version_code == after.android.version_code
+        {
+            current.android.version_code = before.android.version_code;
+            reverted = true;
+        }
+        reverted
+    }
+
+    /// Revert the stored versions and write them back to the version files. Only the version
+    /// lines are rewritten, so other edits to those files are kept.
+    pub fn restore(&self, conn: &Connection) -> Result<(), String> {
+        let mut current = self.after.clone();
+        load_project_versions(conn, &mut current)?;
+        if !self.revert(&mut current) {
+            return Ok(());
+        }
+        write_project_versions(&current)?;
+        save_project_versions(conn, &current)
+    }
+}
+
+/// Store the project's versions and build numbers without touching the rest of its row
+pub fn save_project_versions(conn: &Connection, project: &Project) -> Result<(), String> {
+    conn.execute(
+        "UPDATE projects SET version_ios = ?1, build_number_ios = ?2, version_android = ?3, build_number_android = ?4
+         WHERE id = ?5",
+        params![
+            project.ios.version,
+            project.ios.build_number,
+            project.android.version,
+            project.android.version_code,
+            project.id,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Refresh the project's versions and build numbers from its stored row, which may be newer
+/// than the copy a queued build was started with. Leaves `project` as is when it is not stored.
+pub fn load_project_versions(conn: &Connection, project: &mut Project) -> Result<(), String> {
+    let stored = conn
+        .query_row(
+            "SELECT version_ios, build_number_ios, version_android, build_number_android FROM projects WHERE id = ?1",
+            params![project.id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<u32>>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<u32>>(3)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    if let Some((ios_version, ios_build, android_version, android_code)) = stored {
+        project.ios.version = ios_version.unwrap_or(project.ios.version.clone());
+        project.ios.build_number = ios_build.unwrap_or(project.ios.build_number);
+        project.android.version = android_version.unwrap_or(project.android.version.clone());
+        project.android.version_code = android_code.unwrap_or(project.android.version_code);
+    }
+    Ok(())
+}
@@ -17,20 +17,32 @@ use commands::doctor::*;
 use commands::emulator::*;
 use commands::notification::*;
 use commands::keystore::*;
+use commands::keystore_inspector::inspect_keystore;
+use commands::signing::*;
+use commands::bundletool::{build_apks, estimate_download_sizes, install_apks};
+use commands::provisioning::{check_provisioning_profiles, list_provisioning_profiles};
 use commands::maintenance::*;
 use commands::dependencies::*;
+use commands::queue::*;
+use commands::release_notes::generate_release_notes;
 use crate::commands::analyzer::analyze_app_size;
+use crate::commands::artifact_diff::{compare_artifacts, export_artifact_diff};
+use crate::commands::size_history::{get_build_size_report, get_size_history};
 use models::database::init_db;
 use std::sync::{Mutex, Arc};
 use std::collections::HashMap;
 use std::process::Child;
 use rusqlite::Connection;
+use models::queue::BuildQueueJob;
 
 pub struct DbState(pub Mutex<Connection>);
 
 // Store active build processes for cancellation
 pub struct BuildProcessState(pub Arc<Mutex<HashMap<String, Arc<Mutex<Option<Child>>>>>>);
 
+// Build queue jobs currently being executed, keyed by job id
+pub struct BuildQueueState(pub Arc<Mutex<HashMap<String, BuildQueueJob>>>);
+
 // Store active recording processes
 pub struct RecordingProcess {
     pub child: Child,
@@ -91,6 +103,12 @@ pub fn run() {
             app.manage(DbState(Mutex::new(conn)));
             app.manage(BuildProcessState(Arc::new(Mutex::new(HashMap::new()))));
             app.manage(RecordingState(Arc::new(Mutex::new(HashMap::new()))));
+            app.manage(BuildQueueState(Arc::new(Mutex::new(HashMap::new()))));
+
+            // Resume builds that were still queued when the app last exited
+            if let Err(e) = restore_build_queue(app.handle()) {
+                println!("Failed to restore build queue: {}", e);
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -144,6 +162,15 @@ pub fn run() {
             test_notification,
             generate_keystore,
             show_in_folder,
+            inspect_keystore,
+            save_signing_passwords,
+            has_signing_passwords,
+            delete_signing_passwords,
+            list_provisioning_profiles,
+            check_provisioning_profiles,
+            build_apks,
+            estimate_download_sizes,
+            install_apks,
             deep_clean_project,
             open_xcode,
             open_android_studio,
@@ -155,7 +182,19 @@ pub fn run() {
             install_dependencies,
             add_dependency,
             remove_dependency,
-            analyze_app_size
+            analyze_app_size,
+            compare_artifacts,
+            export_artifact_diff,
+            get_size_history,
+            get_build_size_report,
+            enqueue_build,
+            list_build_queue,
+            cancel_queued_build,
+            move_queued_build,
+            clear_finished_builds,
+            get_build_queue_settings,
+            update_build_queue_settings,
+            generate_release_notes
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
@@ -49,6 +49,20 @@ pub fn init_db(app_handle: &AppHandle) -> Result<Connection> {
     let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_build_command TEXT", []);
     let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_firebase_app_id TEXT", []);
     let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_distribution_groups TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN release_settings TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_play_track TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_play_locales TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_play_release_status TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN ios_beta_groups TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_testers TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN ios_firebase_app_id TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN ios_distribution_groups TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN ios_testers TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN android_signing TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN ios_export_options TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN prebuild_settings TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN build_profiles TEXT", []);
+    let _ = conn.execute("ALTER TABLE projects ADD COLUMN size_budgets TEXT", []);
 
     // Create credentials table
     conn.execute(
@@ -89,6 +103,106 @@ pub fn init_db(app_handle: &AppHandle) -> Result<Connection> {
     let _ = conn.execute("ALTER TABLE build_history ADD COLUMN format TEXT", []);
     let _ = conn.execute("ALTER TABLE build_history ADD COLUMN artifact_path TEXT", []);
     let _ = conn.execute("ALTER TABLE build_history ADD COLUMN log_file_path TEXT", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN git_commit TEXT", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN git_branch TEXT", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN git_tag TEXT", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN git_dirty INTEGER", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN git_uncommitted_files TEXT", []);
+    let _ = conn.execute("ALTER TABLE build_history ADD COLUMN profile TEXT", []);
+
+    // Create build_diagnostics table (parsed from the build log, keyed by build_history id)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_diagnostics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            step TEXT,
+            severity TEXT NOT NULL,
+            category TEXT NOT NULL,
+            file TEXT,
+            line_number INTEGER,
+            column_number INTEGER,
+            message TEXT NOT NULL,
+            remediation TEXT
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_build_diagnostics_build_id ON build_diagnostics(build_id)", [])?;
+
+    // Create build timing tables (one row per build, one row per pipeline phase)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_timings (
+            build_id TEXT PRIMARY KEY,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_phase_timings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            phase TEXT NOT NULL,
+            status TEXT NOT NULL,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_build_phase_timings_build_id ON build_phase_timings(build_id)", [])?;
+
+    // Create build size tables (the artifact's size report, one row per breakdown category)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_sizes (
+            build_id TEXT PRIMARY KEY,
+            file_type TEXT NOT NULL,
+            total_size INTEGER NOT NULL,
+            js_bundle_size INTEGER NOT NULL,
+            report TEXT NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_size_categories (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            category TEXT NOT NULL,
+            size INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_build_size_categories_build_id ON build_size_categories(build_id)", [])?;
+
+    // Create build_queue table (jobs survive app restarts)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_queue (
+            id TEXT PRIMARY KEY,
+            project_id TEXT NOT NULL,
+            platform TEXT NOT NULL,
+            project TEXT NOT NULL,
+            options TEXT,
+            status TEXT NOT NULL,
+            position INTEGER NOT NULL,
+            enqueued_at INTEGER NOT NULL,
+            started_at INTEGER,
+            finished_at INTEGER,
+            error TEXT,
+            FOREIGN KEY(project_id) REFERENCES projects(id)
+        )",
+        [],
+    )?;
+
+    // Create build_queue_settings table (single row)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS build_queue_settings (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            max_concurrent INTEGER NOT NULL,
+            max_ios INTEGER NOT NULL,
+            max_android INTEGER NOT NULL
+        )",
+        [],
+    )?;
 
     Ok(conn)
 }
@@ -1,3 +1,4 @@
 pub mod project;
 pub mod database;
 pub mod credential;
+pub mod queue;
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -9,6 +10,61 @@ pub struct IosConfig {
     pub export_method: Option<String>,
     pub api_key: Option<String>,
     pub api_issuer: Option<String>,
+    /// Comma-separated TestFlight groups an uploaded build is added to
+    #[serde(default)]
+    pub beta_groups: Option<String>,
+    /// Firebase App ID of the iOS app, for App Distribution
+    #[serde(default)]
+    pub firebase_app_id: Option<String>,
+    /// Comma-separated App Distribution group aliases
+    #[serde(default)]
+    pub distribution_groups: Option<String>,
+    /// Comma-separated App Distribution tester emails
+    #[serde(default)]
+    pub testers: Option<String>,
+    /// What goes into the generated ExportOptions.plist besides the method and team
+    #[serde(default)]
+    pub export_options: Option<IosExportOptions>,
+}
+
+/// ExportOptions.plist keys, see `xcodebuild -help`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IosExportOptions {
+    /// Profile name or UUID for each target's bundle ID, extensions included. Signing is
+    /// manual when any are set.
+    #[serde(default)]
+    pub provisioning_profiles: BTreeMap<String, String>,
+    /// Certificate name, SHA-1 or selector such as "Apple Distribution"
+    #[serde(default)]
+    pub signing_certificate: Option<String>,
+    #[serde(default = "default_upload_symbols")]
+    pub upload_symbols: bool,
+    #[serde(default)]
+    pub compile_bitcode: bool,
+    /// "<none>", "<thin-for-all-variants>" or a device model such as "iPhone14,2"
+    #[serde(default)]
+    pub thinning: Option<String>,
+    /// Let App Store Connect bump the build number on upload; left to Xcode when unset
+    #[serde(default)]
+    pub manage_app_version_and_build_number: Option<bool>,
+}
+
+fn default_upload_symbols() -> bool {
+    true
+}
+
+impl Default for IosExportOptions {
+    fn default() -> Self {
+        Self {
+            provisioning_profiles: BTreeMap::new(),
+            signing_certificate: None,
+            upload_symbols: default_upload_symbols(),
+            compile_bitcode: false,
+            thinning: None,
+            manage_app_version_and_build_number: None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -25,6 +81,31 @@ pub struct IosPlatform {
 pub struct AndroidConfig {
     pub firebase_app_id: Option<String>,
     pub distribution_groups: Option<String>,
+    /// Comma-separated App Distribution tester emails
+    #[serde(default)]
+    pub testers: Option<String>,
+    /// Google Play track: internal, alpha, beta or production
+    #[serde(default)]
+    pub play_track: Option<String>,
+    /// Comma-separated locales the release notes are published under, e.g. "en-US, fr-FR"
+    #[serde(default)]
+    pub play_locales: Option<String>,
+    /// "completed", or "draft" for apps that were never published
+    #[serde(default)]
+    pub play_release_status: Option<String>,
+    /// Keystore release builds are signed with, instead of the Gradle signingConfig
+    #[serde(default)]
+    pub signing: Option<AndroidSigningConfig>,
+}
+
+/// A keystore attached to a project. The passwords are kept in the keychain under `id`,
+/// see `signing::save_signing_passwords`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AndroidSigningConfig {
+    pub id: String,
+    pub keystore_path: String,
+    pub key_alias: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -74,6 +155,58 @@ pub struct NotificationConfig {
     pub telegram: Option<TelegramConfig>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseSettings {
+    /// Refuse release builds while the project has uncommitted changes
+    #[serde(default)]
+    pub require_clean_tree: bool,
+    /// Create a git tag like `ios/1.4.0+57` once a build is uploaded or distributed
+    #[serde(default)]
+    pub tag_after_upload: bool,
+    /// Generate release notes from the commits since the last build when none are given
+    #[serde(default)]
+    pub auto_release_notes: bool,
+    /// See `release_notes::render` for the placeholders
+    #[serde(default)]
+    pub release_notes_template: Option<String>,
+    /// Bump the version and build number when a build starts
+    #[serde(default)]
+    pub version_bump: Option<VersionBumpSettings>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum VersionBump {
+    #[default]
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildNumberStrategy {
+    #[default]
+    None,
+    /// Current build number + 1
+    Increment,
+    /// `YYMMDDNN`: today's date followed by a two-digit counter
+    Date,
+    /// One counter for both platforms: the highest of the two + 1, written to both
+    Shared,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionBumpSettings {
+    #[serde(default)]
+    pub version: VersionBump,
+    #[serde(default)]
+    pub build_number: BuildNumberStrategy,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Project {
@@ -85,6 +218,94 @@ pub struct Project {
     pub credentials: ProjectCredentials,
     #[serde(default)]
     pub notifications: Option<NotificationConfig>,
+    #[serde(default)]
+    pub release: Option<ReleaseSettings>,
+    #[serde(default)]
+    pub prebuild: Option<PrebuildSettings>,
+    /// Variants of the app (dev, staging, prod...) a build can be made for
+    #[serde(default)]
+    pub profiles: Vec<BuildProfile>,
+    #[serde(default)]
+    pub size_budgets: Option<SizeBudgets>,
+}
+
+/// A named variant of the project. Each field that is set replaces the project's own value
+/// for builds of this profile.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildProfile {
+    pub name: String,
+    /// Appended to both bundle IDs, e.g. ".staging". The scheme or flavor is what actually
+    /// builds the app under that ID; the suffixed ID is used to find the app in the stores.
+    #[serde(default)]
+    pub bundle_id_suffix: Option<String>,
+    #[serde(default)]
+    pub ios_scheme: Option<String>,
+    #[serde(default)]
+    pub ios_configuration: Option<String>,
+    /// Gradle product flavor, e.g. "staging" builds `bundleStagingRelease`
+    #[serde(default)]
+    pub android_flavor: Option<String>,
+    /// Replaces the Android build command (and the flavor's task)
+    #[serde(default)]
+    pub android_build_command: Option<String>,
+    /// Env file relative to the project, passed to every build command as `ENVFILE`
+    /// (react-native-config)
+    #[serde(default)]
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub ios_firebase_app_id: Option<String>,
+    #[serde(default)]
+    pub android_firebase_app_id: Option<String>,
+    #[serde(default)]
+    pub ios_credential_id: Option<String>,
+    #[serde(default)]
+    pub android_credential_id: Option<String>,
+    #[serde(default)]
+    pub notifications: Option<NotificationConfig>,
+}
+
+/// Continuous Native Generation: `expo prebuild` generates `ios/` and `android/` from the Expo
+/// config before each build, and versions are written to the config instead of native files
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PrebuildSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Delete the native folders before generating them (`--clean`)
+    #[serde(default)]
+    pub clean: bool,
+    /// Generate into a copy of the project in the temporary directory, leaving the repository untouched
+    #[serde(default)]
+    pub isolated: bool,
+}
+
+/// Limits on the size of the artifact, checked after each build. Sizes are in bytes; a
+/// budget left empty is not checked.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeBudgets {
+    #[serde(default)]
+    pub total: Option<u64>,
+    #[serde(default)]
+    pub native_libraries: Option<u64>,
+    #[serde(default)]
+    pub js_bundle: Option<u64>,
+    #[serde(default)]
+    pub assets: Option<u64>,
+    /// How much the total size may grow since the previous successful build
+    #[serde(default)]
+    pub max_increase: Option<u64>,
+    /// Fail the build when a budget is exceeded, instead of only warning
+    #[serde(default)]
+    pub fail_build: bool,
+}
+
+impl Project {
+    /// The prebuild settings when prebuild is turned on
+    pub fn prebuild_settings(&self) -> Option<&PrebuildSettings> {
+        self.prebuild.as_ref().filter(|p| p.enabled)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -104,4 +325,55 @@ pub struct BuildHistory {
     pub format: Option<String>,
     pub artifact_path: Option<String>,
     pub log_file_path: Option<String>,
+    /// Build profile the build was made with
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub git: Option<GitProvenance>,
+    #[serde(default)]
+    pub diagnostics: Vec<BuildDiagnostic>, // Loaded from build_diagnostics, not saved with the row
+    #[serde(default)]
+    pub timing: Option<BuildTiming>, // Loaded from build_timings, not saved with the row
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildTiming {
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub duration_ms: u64,
+    pub phases: Vec<PhaseTiming>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub phase: String, // Pipeline step: "prepare", "archive", "export", "sign", "upload", "distribute", "notify"
+    pub status: String, // "success", "failed" or "skipped"
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitProvenance {
+    pub commit: String,
+    pub branch: Option<String>, // None on a detached HEAD
+    pub tag: Option<String>,    // Tag pointing exactly at the commit
+    pub dirty: bool,
+    pub uncommitted_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDiagnostic {
+    pub step: Option<String>,
+    pub severity: String, // "error" or "warning"
+    pub category: String, // "gradle", "kotlin", "java", "cocoapods", "compiler", "codesign", "metro", "xcodebuild"
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub message: String,
+    pub remediation: Option<String>,
 }
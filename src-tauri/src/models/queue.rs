@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use crate::models::project::Project;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildQueueJob {
+    pub id: String,
+    pub project_id: String,
+    pub platform: String, // "ios" or "android"
+    pub project: Project, // Snapshot of the project at enqueue time
+    pub options: Option<serde_json::Value>,
+    pub status: String, // "pending", "running", "success", "failed", "cancelled"
+    pub position: i64,
+    pub enqueued_at: u64,
+    pub started_at: Option<u64>,
+    pub finished_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildQueueSettings {
+    pub max_concurrent: u32,
+    pub max_ios: u32,
+    pub max_android: u32,
+}
+
+impl Default for BuildQueueSettings {
+    fn default() -> Self {
+        // xcodebuild and gradle both saturate the machine, so run one of each by default
+        BuildQueueSettings {
+            max_concurrent: 2,
+            max_ios: 1,
+            max_android: 1,
+        }
+    }
+}
+
+impl BuildQueueSettings {
+    pub fn limit_for(&self, platform: &str) -> u32 {
+        match platform {
+            "ios" => self.max_ios,
+            "android" => self.max_android,
+            _ => self.max_concurrent,
+        }
+    }
+}
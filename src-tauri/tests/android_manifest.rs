@@ -0,0 +1,88 @@
+use app_builder_lib::commands::android_manifest::{
+    manifest_info, parse_binary_xml, parse_proto_xml, read_manifest, AndroidManifestInfo, ExportedComponent,
+};
+use std::io::{Cursor, Write};
+use zip::ZipArchive;
+
+const BINARY_XML: &[u8] = include_bytes!("fixtures/AndroidManifest.axml");
+const BINARY_XML_UTF8: &[u8] = include_bytes!("fixtures/AndroidManifest-utf8.axml");
+const PROTO_XML: &[u8] = include_bytes!("fixtures/AndroidManifest.pb");
+
+fn archive(name: &str, contents: &[u8]) -> ZipArchive<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    writer.start_file(name, zip::write::SimpleFileOptions::default()).unwrap();
+    writer.write_all(contents).unwrap();
+    ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+}
+
+fn assert_demo_manifest(info: &AndroidManifestInfo) {
+    assert_eq!(info.package, "com.example.demo");
+    assert_eq!(info.version_code, Some(42));
+    assert_eq!(info.version_name.as_deref(), Some("1.4.0"));
+    assert_eq!((info.min_sdk, info.target_sdk, info.compile_sdk), (Some(24), Some(34), Some(34)));
+    assert_eq!(info.permissions, vec!["android.permission.INTERNET", "android.permission.CAMERA"]);
+    assert_eq!(
+        info.exported_components,
+        vec![
+            ExportedComponent { kind: "activity".into(), name: ".MainActivity".into(), implicit: false },
+            ExportedComponent { kind: "receiver".into(), name: ".PushReceiver".into(), implicit: true },
+        ]
+    );
+    assert!(info.debuggable);
+    assert!(!info.allow_backup);
+}
+
+#[test]
+fn binary_xml_manifests_are_decoded() {
+    for bytes in [BINARY_XML, BINARY_XML_UTF8] {
+        let root = parse_binary_xml(bytes).unwrap();
+        assert_eq!(root.children.len(), 4);
+        assert_demo_manifest(&manifest_info(&root).unwrap());
+    }
+}
+
+#[test]
+fn protobuf_manifests_are_decoded() {
+    // debuggable only has a compiled value in the fixture
+    let root = parse_proto_xml(PROTO_XML).unwrap();
+    assert_demo_manifest(&manifest_info(&root).unwrap());
+}
+
+#[test]
+fn obfuscated_attribute_names_are_resolved_by_resource_id() {
+    let mut bytes = BINARY_XML_UTF8.to_vec();
+    let at = bytes.windows(11).position(|w| w == b"versionCode").unwrap();
+    bytes[at..at + 11].copy_from_slice(b"a0b1c2d3e4f");
+
+    let root = parse_binary_xml(&bytes).unwrap();
+    assert!(!root.attributes.contains_key("a0b1c2d3e4f"));
+    assert_eq!(manifest_info(&root).unwrap().version_code, Some(42));
+}
+
+#[test]
+fn manifests_are_read_from_apks_and_app_bundles() {
+    let apk = read_manifest(&mut archive("AndroidManifest.xml", BINARY_XML), "apk").unwrap();
+    assert_demo_manifest(&apk);
+    let aab = read_manifest(&mut archive("base/manifest/AndroidManifest.xml", PROTO_XML), "aab").unwrap();
+    assert_demo_manifest(&aab);
+
+    assert_eq!(
+        read_manifest(&mut archive("AndroidManifest.xml", BINARY_XML), "aab").unwrap_err(),
+        "base/manifest/AndroidManifest.xml not found"
+    );
+    assert_eq!(
+        read_manifest(&mut archive("AndroidManifest.xml", b"<manifest/>"), "apk").unwrap_err(),
+        "Not a binary XML file"
+    );
+}
+
+#[test]
+fn missing_flags_use_android_defaults() {
+    let mut root = parse_binary_xml(BINARY_XML).unwrap();
+    let application = root.children.iter_mut().find(|c| c.name == "application").unwrap();
+    application.attributes.clear();
+
+    let info = manifest_info(&root).unwrap();
+    assert!(!info.debuggable);
+    assert!(info.allow_backup);
+}
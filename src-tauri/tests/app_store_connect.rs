@@ -0,0 +1,333 @@
+mod common;
+
+use app_builder_lib::commands::app_store_connect::{ApiKey, AppStoreConnectClient, BuildUpload};
+use mockito::{Matcher, Server, ServerGuard};
+use common::TempFile;
+use serde_json::json;
+use std::time::Duration;
+
+const APP_ID: &str = "1234567890";
+
+fn api_key() -> ApiKey {
+    ApiKey {
+        key_id: "TEST1234".to_string(),
+        issuer_id: "69a6de70-03db-47e3-e053-5b8c7c11a4d1".to_string(),
+        private_key: include_str!("fixtures/AuthKey_TEST1234.p8").to_string(),
+    }
+}
+
+fn client(server: &ServerGuard) -> AppStoreConnectClient {
+    AppStoreConnectClient::new(api_key(), &server.url())
+}
+
+fn ipa(name: &str, contents: &[u8]) -> TempFile {
+    TempFile::new(&format!("app-store-connect-{}.ipa", name), contents)
+}
+
+fn build_upload() -> BuildUpload {
+    BuildUpload {
+        id: "upload-1".to_string(),
+        app_id: APP_ID.to_string(),
+        version: "1.4.0".to_string(),
+        build_number: "57".to_string(),
+    }
+}
+
+#[test]
+fn tokens_are_es256_and_name_the_key() {
+    let token = api_key().token().unwrap();
+    let header = jsonwebtoken::decode_header(&token).unwrap();
+
+    assert_eq!(header.alg, jsonwebtoken::Algorithm::ES256);
+    assert_eq!(header.kid.as_deref(), Some("TEST1234"));
+}
+
+#[tokio::test]
+async fn upload_sends_every_part_then_marks_the_file_uploaded() {
+    let mut server = Server::new_async().await;
+    let bearer = Matcher::Regex("^Bearer [A-Za-z0-9_-]+\\.[A-Za-z0-9_-]+\\.[A-Za-z0-9_-]+$".into());
+
+    let upload = server
+        .mock("POST", "/v1/buildUploads")
+        .match_header("authorization", bearer.clone())
+        .match_body(Matcher::PartialJson(json!({
+            "data": {
+                "attributes": { "cfBundleShortVersionString": "1.4.0", "cfBundleVersion": "57", "platform": "IOS" },
+                "relationships": { "app": { "data": { "type": "apps", "id": APP_ID } } }
+            }
+        })))
+        .with_status(201)
+        .with_body(r#"{"data": {"type": "buildUploads", "id": "upload-1"}}"#)
+        .create_async()
+        .await;
+    let reserve = server
+        .mock("POST", "/v1/buildUploadFiles")
+        .match_body(Matcher::PartialJson(json!({
+            "data": { "attributes": { "fileName": format!("{}-app-store-connect-parts.ipa", std::process::id()), "fileSize": 10 } }
+        })))
+        .with_status(201)
+        .with_body(
+            json!({
+                "data": {
+                    "type": "buildUploadFiles",
+                    "id": "file-1",
+                    "attributes": {
+                        "uploadOperations": [
+                            { "method": "PUT", "url": format!("{}/parts/1", server.url()), "offset": 0, "length": 6,
+                              "requestHeaders": [{ "name": "Content-Type", "value": "application/octet-stream" }] },
+                            { "method": "PUT", "url": format!("{}/parts/2", server.url()), "offset": 6, "length": 4,
+                              "requestHeaders": [{ "name": "Content-Type", "value": "application/octet-stream" }] }
+                        ]
+                    }
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    // Pre-signed part URLs must not carry the API token
+    let first_part = server
+        .mock("PUT", "/parts/1")
+        .match_header("authorization", Matcher::Missing)
+        .match_body("012345")
+        .create_async()
+        .await;
+    let second_part = server.mock("PUT", "/parts/2").match_body("6789").create_async().await;
+    let complete = server
+        .mock("PATCH", "/v1/buildUploadFiles/file-1")
+        .match_body(Matcher::PartialJson(json!({ "data": { "id": "file-1", "attributes": { "uploaded": true } } })))
+        .with_body(r#"{"data": {"type": "buildUploadFiles", "id": "file-1"}}"#)
+        .create_async()
+        .await;
+
+    let path = ipa("parts", b"0123456789");
+    let mut progress = Vec::new();
+    let uploaded = client(&server)
+        .upload_build(APP_ID, &path, "1.4.0", "57", |sent, total| progress.push((sent, total)))
+        .await
+        .unwrap();
+
+    assert_eq!(uploaded.id, "upload-1");
+    assert_eq!(progress, vec![(0, 10), (6, 10), (10, 10)]);
+    upload.assert_async().await;
+    reserve.assert_async().await;
+    first_part.assert_async().await;
+    second_part.assert_async().await;
+    complete.assert_async().await;
+}
+
+#[tokio::test]
+async fn processing_is_polled_until_the_build_is_valid() {
+    let mut server = Server::new_async().await;
+    let builds_query = Matcher::AllOf(vec![
+        Matcher::UrlEncoded("filter[app]".into(), APP_ID.into()),
+        Matcher::UrlEncoded("filter[version]".into(), "57".into()),
+        Matcher::UrlEncoded("filter[preReleaseVersion.version]".into(), "1.4.0".into()),
+    ]);
+
+    // Nothing yet, then processing, then valid
+    server
+        .mock("GET", "/v1/builds")
+        .match_query(builds_query.clone())
+        .with_body(r#"{"data": []}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/buildUploads/upload-1")
+        .with_body(r#"{"data": {"id": "upload-1", "attributes": {"state": {"state": "PROCESSING", "errors": []}}}}"#)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/builds")
+        .match_query(builds_query.clone())
+        .with_body(r#"{"data": [{"id": "build-1", "attributes": {"processingState": "PROCESSING"}}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/builds")
+        .match_query(builds_query)
+        .with_body(r#"{"data": [{"id": "build-1", "attributes": {"processingState": "VALID"}}]}"#)
+        .create_async()
+        .await;
+
+    let mut states = Vec::new();
+    let build = client(&server)
+        .wait_for_processing(&build_upload(), Duration::from_millis(10), Duration::from_secs(10), |state| {
+            states.push(state.to_string())
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(build.id, "build-1");
+    assert_eq!(states, vec!["PROCESSING"]);
+}
+
+#[tokio::test]
+async fn a_rejected_upload_reports_apples_errors() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/builds")
+        .match_query(Matcher::Any)
+        .with_body(r#"{"data": []}"#)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/v1/buildUploads/upload-1")
+        .with_body(
+            r#"{"data": {"id": "upload-1", "attributes": {"state": {"state": "FAILED", "errors": [
+                {"code": "90062", "description": "The value for key CFBundleShortVersionString must be higher than 1.4.0."}
+            ]}}}}"#,
+        )
+        .create_async()
+        .await;
+
+    let error = client(&server)
+        .wait_for_processing(&build_upload(), Duration::from_millis(10), Duration::from_secs(10), |_| {})
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("must be higher than 1.4.0"), "{}", error);
+}
+
+#[tokio::test]
+async fn waiting_gives_up_after_the_timeout() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/builds")
+        .match_query(Matcher::Any)
+        .with_body(r#"{"data": [{"id": "build-1", "attributes": {"processingState": "PROCESSING"}}]}"#)
+        .create_async()
+        .await;
+
+    let build = client(&server)
+        .wait_for_processing(&build_upload(), Duration::from_millis(10), Duration::from_millis(50), |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(build, None);
+}
+
+#[tokio::test]
+async fn what_to_test_updates_the_existing_localization() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/builds/build-1/betaBuildLocalizations")
+        .with_body(r#"{"data": [{"id": "loc-1", "attributes": {"locale": "en-US", "whatsNew": null}}]}"#)
+        .create_async()
+        .await;
+    let update = server
+        .mock("PATCH", "/v1/betaBuildLocalizations/loc-1")
+        .match_body(Matcher::PartialJson(json!({ "data": { "attributes": { "whatsNew": "Try the new checkout" } } })))
+        .with_body(r#"{"data": {"id": "loc-1"}}"#)
+        .create_async()
+        .await;
+    let create = server.mock("POST", "/v1/betaBuildLocalizations").expect(0).create_async().await;
+
+    client(&server).set_what_to_test("build-1", "en-US", "  Try the new checkout\n").await.unwrap();
+
+    update.assert_async().await;
+    create.assert_async().await;
+}
+
+#[tokio::test]
+async fn what_to_test_creates_a_missing_localization() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/builds/build-1/betaBuildLocalizations")
+        .with_body(r#"{"data": []}"#)
+        .create_async()
+        .await;
+    let create = server
+        .mock("POST", "/v1/betaBuildLocalizations")
+        .match_body(Matcher::Json(json!({
+            "data": {
+                "type": "betaBuildLocalizations",
+                "attributes": { "locale": "en-US", "whatsNew": "Try the new checkout" },
+                "relationships": { "build": { "data": { "type": "builds", "id": "build-1" } } }
+            }
+        })))
+        .with_status(201)
+        .with_body(r#"{"data": {"id": "loc-2"}}"#)
+        .create_async()
+        .await;
+
+    client(&server).set_what_to_test("build-1", "en-US", "Try the new checkout").await.unwrap();
+
+    create.assert_async().await;
+}
+
+#[tokio::test]
+async fn builds_are_added_to_the_named_beta_groups() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/betaGroups")
+        .match_query(Matcher::UrlEncoded("filter[app]".into(), APP_ID.into()))
+        .with_body(
+            r#"{"data": [
+                {"id": "group-1", "attributes": {"name": "QA"}},
+                {"id": "group-2", "attributes": {"name": "Design"}},
+                {"id": "group-3", "attributes": {"name": "Friends"}}
+            ]}"#,
+        )
+        .create_async()
+        .await;
+    let add = server
+        .mock("POST", "/v1/builds/build-1/relationships/betaGroups")
+        .match_body(Matcher::Json(json!({
+            "data": [{ "type": "betaGroups", "id": "group-1" }, { "type": "betaGroups", "id": "group-3" }]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    client(&server)
+        .add_to_beta_groups(APP_ID, "build-1", &["QA".to_string(), "Friends".to_string()])
+        .await
+        .unwrap();
+
+    add.assert_async().await;
+}
+
+#[tokio::test]
+async fn unknown_beta_groups_fail_without_changing_anything() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/betaGroups")
+        .match_query(Matcher::Any)
+        .with_body(r#"{"data": [{"id": "group-1", "attributes": {"name": "QA"}}]}"#)
+        .create_async()
+        .await;
+    let add = server
+        .mock("POST", "/v1/builds/build-1/relationships/betaGroups")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let error = client(&server)
+        .add_to_beta_groups(APP_ID, "build-1", &["QA".to_string(), "Externals".to_string()])
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("TestFlight group not found: Externals"), "{}", error);
+    add.assert_async().await;
+}
+
+#[tokio::test]
+async fn api_errors_carry_apples_detail() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("GET", "/v1/apps")
+        .match_query(Matcher::Any)
+        .with_status(401)
+        .with_body(r#"{"errors": [{"status": "401", "code": "NOT_AUTHORIZED", "title": "Authentication credentials are missing or invalid.", "detail": "Provide a properly configured and signed bearer token."}]}"#)
+        .create_async()
+        .await;
+
+    let error = client(&server).find_app("com.example.app").await.unwrap_err();
+
+    assert!(error.contains("Looking up the app failed (401"), "{}", error);
+    assert!(error.contains("properly configured and signed bearer token"), "{}", error);
+}
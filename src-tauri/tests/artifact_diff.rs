@@ -0,0 +1,152 @@
+mod common;
+
+use app_builder_lib::commands::artifact_diff::{compare_artifact_files, diff_markdown, FileChange, MetadataChange};
+use common::TempFile;
+
+const MANIFEST: &[u8] = include_bytes!("fixtures/AndroidManifest-utf8.axml");
+const THIN: &[u8] = include_bytes!("fixtures/Demo.macho");
+const FAT: &[u8] = include_bytes!("fixtures/DemoFat.macho");
+
+const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key><string>com.example.app</string>
+    <key>CFBundleExecutable</key><string>Demo</string>
+    <key>CFBundleShortVersionString</key><string>2.1.0</string>
+    <key>CFBundleVersion</key><string>BUILD</string>
+</dict>
+</plist>"#;
+
+fn artifact(name: &str, entries: &[(&str, &[u8])]) -> TempFile {
+    TempFile::zip(&format!("artifact-diff-{}", name), entries)
+}
+
+/// The fixture manifest with one string swapped for another of the same length
+fn manifest_with(from: &str, to: &str) -> Vec<u8> {
+    let mut bytes = MANIFEST.to_vec();
+    let at = bytes.windows(from.len()).position(|w| w == from.as_bytes()).unwrap();
+    bytes[at..at + from.len()].copy_from_slice(to.as_bytes());
+    bytes
+}
+
+/// Builds 57 and 58 of an APK, named after the test so parallel tests don't share files
+fn apks(test: &str) -> (TempFile, TempFile) {
+    let base = artifact(
+        &format!("{}-57.apk", test),
+        &[
+            ("AndroidManifest.xml", MANIFEST),
+            ("classes.dex", &[1; 1000]),
+            ("res/layout/main.xml", &[2; 300]),
+            ("lib/arm64-v8a/libapp.so", &[3; 500]),
+            ("lib/arm64-v8a/libold.so", &[4; 200]),
+        ],
+    );
+    let mut manifest = manifest_with("android.permission.CAMERA", "android.permission.REBOOT");
+    let at = manifest.windows(5).position(|w| w == b"1.4.0").unwrap();
+    manifest[at..at + 5].copy_from_slice(b"1.5.0");
+    let target = artifact(
+        &format!("{}-58.apk", test),
+        &[
+            ("AndroidManifest.xml", &manifest),
+            ("classes.dex", &[1; 1600]),
+            ("res/layout/main.xml", &[2; 300]),
+            ("lib/arm64-v8a/libapp.so", &[5; 500]),
+            ("lib/x86_64/libnew.so", &[6; 700]),
+        ],
+    );
+    (base, target)
+}
+
+fn change(path: &str, change: &str, base_size: u64, target_size: u64) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        change: change.to_string(),
+        base_size,
+        target_size,
+        delta: target_size as i64 - base_size as i64,
+    }
+}
+
+#[test]
+fn apk_changes_are_grouped_by_category() {
+    let (base, target) = apks("categories");
+    let diff = compare_artifact_files(&base, &target).unwrap();
+
+    let categories: Vec<_> = diff.categories.iter().map(|c| (c.name.as_str(), c.delta)).collect();
+    assert_eq!(categories, vec![("Code (DEX/JS)", 600), ("Resources", 0), ("Native Libraries", 500), ("Others", 0)]);
+    assert_eq!(diff.categories[0].files, vec![change("classes.dex", "changed", 1000, 1600)]);
+    // Same size, different contents
+    assert!(diff.categories[2].files.contains(&change("lib/arm64-v8a/libapp.so", "changed", 500, 500)));
+    assert!(diff.categories[1].files.is_empty());
+    // The manifest's strings were swapped in place
+    assert_eq!(diff.categories[3].files, vec![change("AndroidManifest.xml", "changed", MANIFEST.len() as u64, MANIFEST.len() as u64)]);
+
+    assert_eq!(
+        diff.native_libraries,
+        vec![
+            change("lib/x86_64/libnew.so", "added", 0, 700),
+            change("lib/arm64-v8a/libold.so", "removed", 200, 0),
+            change("lib/arm64-v8a/libapp.so", "changed", 500, 500),
+        ]
+    );
+    assert_eq!(
+        diff.metadata_changes,
+        vec![MetadataChange { field: "Version name".into(), base: Some("1.4.0".into()), target: Some("1.5.0".into()) }]
+    );
+    assert_eq!(diff.permissions_added, vec!["android.permission.REBOOT"]);
+    assert_eq!(diff.permissions_removed, vec!["android.permission.CAMERA"]);
+}
+
+#[test]
+fn ipa_binaries_report_architecture_changes() {
+    let base_plist = INFO_PLIST.replace("BUILD", "57");
+    let target_plist = INFO_PLIST.replace("BUILD", "58");
+    let base = artifact(
+        "57.ipa",
+        &[
+            ("Payload/Demo.app/Info.plist", base_plist.as_bytes()),
+            ("Payload/Demo.app/Demo", THIN),
+            ("Payload/Demo.app/Frameworks/React.framework/React", FAT),
+        ],
+    );
+    let target = artifact(
+        "58.ipa",
+        &[
+            ("Payload/Demo.app/Info.plist", target_plist.as_bytes()),
+            ("Payload/Demo.app/Demo", THIN),
+            ("Payload/Demo.app/Frameworks/React.framework/React", THIN),
+        ],
+    );
+    let diff = compare_artifact_files(&base, &target).unwrap();
+
+    assert_eq!(
+        diff.metadata_changes,
+        vec![
+            MetadataChange { field: "Build number".into(), base: Some("57".into()), target: Some("58".into()) },
+            MetadataChange { field: "React architectures".into(), base: Some("arm64, x86_64".into()), target: Some("arm64".into()) },
+        ]
+    );
+    let react = "Payload/Demo.app/Frameworks/React.framework/React";
+    assert_eq!(diff.native_libraries, vec![change(react, "changed", FAT.len() as u64, THIN.len() as u64)]);
+    assert!(diff.permissions_added.is_empty());
+}
+
+#[test]
+fn different_artifact_types_are_not_compared() {
+    let (apk, _) = apks("types");
+    let ipa = artifact("other.ipa", &[("Payload/readme.txt", b"empty")]);
+    assert_eq!(compare_artifact_files(&apk, &ipa).unwrap_err(), "Can't compare an APK with an IPA");
+}
+
+#[test]
+fn diffs_export_as_markdown() {
+    let (base, target) = apks("markdown");
+    let markdown = diff_markdown(&compare_artifact_files(&base, &target).unwrap());
+
+    assert!(markdown.starts_with("# APK comparison\n"));
+    assert!(markdown.contains("| Code (DEX/JS) | 1000 B | 1.56 KB | +600 B |"));
+    assert!(markdown.contains("- Version name: 1.4.0 → 1.5.0"));
+    assert!(markdown.contains("- Permission added: `android.permission.REBOOT`"));
+    assert!(markdown.contains("- removed `lib/arm64-v8a/libold.so` (-200 B)"));
+    assert!(markdown.contains("| `classes.dex` | changed | +600 B |"));
+}
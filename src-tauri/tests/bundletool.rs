@@ -0,0 +1,103 @@
+use app_builder_lib::commands::bundletool::{apks_path, build_apks_args, extract_universal_apk, parse_size_csv, ApkSigning};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const BUNDLE: &str = "/builds/app-release.aab";
+
+#[test]
+fn apk_sets_are_written_next_to_the_bundle() {
+    let aab = Path::new(BUNDLE);
+
+    assert_eq!(apks_path(aab, "default", None, None), PathBuf::from("/builds/app-release.apks"));
+    assert_eq!(apks_path(aab, "universal", None, None), PathBuf::from("/builds/app-release.universal.apks"));
+    assert_eq!(apks_path(aab, "device", Some("emulator-5554"), None), PathBuf::from("/builds/app-release.emulator-5554.apks"));
+    assert_eq!(apks_path(aab, "device", Some("192.168.1.20:5555"), None), PathBuf::from("/builds/app-release.192.168.1.20_5555.apks"));
+}
+
+#[test]
+fn apk_sets_signed_with_a_project_key_are_kept_apart() {
+    let aab = Path::new(BUNDLE);
+
+    assert_eq!(apks_path(aab, "default", None, Some("upload")), PathBuf::from("/builds/app-release.key-upload.apks"));
+    assert_eq!(apks_path(aab, "universal", None, Some("upload")), PathBuf::from("/builds/app-release.universal.key-upload.apks"));
+    assert_ne!(apks_path(aab, "default", None, Some("universal")), apks_path(aab, "universal", None, None));
+}
+
+#[test]
+fn build_apks_arguments_follow_the_mode() {
+    let aab = Path::new(BUNDLE);
+    let output = Path::new("/builds/out.apks");
+
+    let universal = build_apks_args(aab, output, "universal", None, None).unwrap();
+    assert_eq!(
+        universal,
+        vec!["build-apks", "--bundle=/builds/app-release.aab", "--output=/builds/out.apks", "--overwrite", "--mode=universal"]
+    );
+
+    let device = build_apks_args(aab, output, "device", Some("emulator-5554"), None).unwrap();
+    assert!(device.ends_with(&["--connected-device".to_string(), "--device-id=emulator-5554".to_string()]), "{:?}", device);
+
+    assert_eq!(build_apks_args(aab, output, "device", None, None).unwrap_err(), "Pick a running device to build its APKs");
+    assert_eq!(build_apks_args(aab, output, "instant", None, None).unwrap_err(), "Unknown APK mode: instant");
+}
+
+#[test]
+fn keystore_passwords_are_passed_as_files() {
+    let signing = ApkSigning {
+        keystore_path: "/keys/upload.jks".to_string(),
+        key_alias: "upload".to_string(),
+        store_password_file: PathBuf::from("/tmp/store-pass"),
+        key_password_file: PathBuf::from("/tmp/key-pass"),
+    };
+
+    let args = build_apks_args(Path::new(BUNDLE), Path::new("/builds/out.apks"), "default", None, Some(&signing)).unwrap();
+
+    assert!(!args.iter().any(|a| a.starts_with("--mode")), "{:?}", args);
+    assert!(args.ends_with(&[
+        "--ks=/keys/upload.jks".to_string(),
+        "--ks-key-alias=upload".to_string(),
+        "--ks-pass=file:/tmp/store-pass".to_string(),
+        "--key-pass=file:/tmp/key-pass".to_string(),
+    ]));
+}
+
+#[test]
+fn download_sizes_are_parsed_per_configuration() {
+    let output = "SDK,ABI,SCREEN_DENSITY,MIN,MAX\n\
+                  21-,arm64-v8a,XXHDPI,4521873,4603411\n\
+                  21-,x86_64,MDPI,4820004,4820004\n";
+
+    let sizes = parse_size_csv(output).unwrap();
+
+    assert_eq!(sizes.len(), 2);
+    assert_eq!(sizes[0].dimensions["ABI"], "arm64-v8a");
+    assert_eq!(sizes[0].dimensions["SCREEN_DENSITY"], "XXHDPI");
+    assert_eq!(sizes[0].dimensions["SDK"], "21-");
+    assert_eq!((sizes[0].min, sizes[0].max), (4521873, 4603411));
+
+    let device = parse_size_csv("MIN,MAX\n3910442,3910442\n").unwrap();
+    assert!(device[0].dimensions.is_empty());
+    assert_eq!(device[0].min, 3910442);
+
+    assert!(parse_size_csv("Error: no APKs\n").is_err());
+}
+
+#[test]
+fn universal_apks_are_extracted_from_the_set() {
+    let dir = std::env::temp_dir().join(format!("bundletool-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let apks = dir.join("app-release.universal.apks");
+
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&apks).unwrap());
+    writer.start_file("toc.pb", zip::write::SimpleFileOptions::default()).unwrap();
+    writer.write_all(b"toc").unwrap();
+    writer.start_file("universal.apk", zip::write::SimpleFileOptions::default()).unwrap();
+    writer.write_all(b"apk contents").unwrap();
+    writer.finish().unwrap();
+
+    let apk = dir.join("app-release.universal.apk");
+    extract_universal_apk(&apks, &apk).unwrap();
+    assert_eq!(std::fs::read(&apk).unwrap(), b"apk contents");
+
+    assert_eq!(extract_universal_apk(&apk, &dir.join("x.apk")).unwrap_err().split(':').next(), Some("Invalid APK set"));
+}
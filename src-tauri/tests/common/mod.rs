@@ -0,0 +1,42 @@
+// Each test crate includes this module and uses only part of it
+#![allow(dead_code)]
+
+use std::io::{Cursor, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A file in the temp directory, deleted when dropped
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+    /// `name` is the file name, unique among the tests running in parallel
+    pub fn new(name: &str, contents: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        TempFile(path)
+    }
+
+    /// A zip archive (APK, AAB or IPA) with the given entries
+    pub fn zip(name: &str, entries: &[(&str, &[u8])]) -> Self {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in entries {
+            writer.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+            writer.write_all(contents).unwrap();
+        }
+        TempFile::new(name, &writer.finish().unwrap().into_inner())
+    }
+}
+
+impl Deref for TempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -0,0 +1,192 @@
+use app_builder_lib::commands::diagnostics::{load_build_diagnostics, save_build_diagnostics, DiagnosticParser};
+use app_builder_lib::models::project::BuildDiagnostic;
+use rusqlite::Connection;
+
+fn parse(line: &str) -> BuildDiagnostic {
+    DiagnosticParser::new().parse_line(line, Some("archive")).unwrap_or_else(|| panic!("no diagnostic for {:?}", line))
+}
+
+fn remediation(line: &str) -> String {
+    parse(line).remediation.unwrap_or_default()
+}
+
+/// `build_diagnostics` as created by `init_db`, with a check that lets a test make an
+/// insert fail
+fn database() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute(
+        "CREATE TABLE build_diagnostics (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            step TEXT,
+            severity TEXT NOT NULL CHECK (severity IN ('error', 'warning')),
+            category TEXT NOT NULL,
+            file TEXT,
+            line_number INTEGER,
+            column_number INTEGER,
+            message TEXT NOT NULL,
+            remediation TEXT
+        )",
+        [],
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn every_rule_recognises_its_sample_line() {
+    let samples = [
+        ("e: file:///work/app/src/main/java/com/demo/MainActivity.kt:12:5 Unresolved reference: bar", "kotlin", "error"),
+        ("w: /work/app/build.gradle.kts: (3, 1): 'jcenter(): RepositoryHandler' is deprecated", "kotlin", "warning"),
+        ("/work/app/src/main/java/com/demo/MainApplication.java:42: error: cannot find symbol", "java", "error"),
+        ("/Users/me/Demo/ios/Demo/AppDelegate.swift:3:8: error: No such module 'React'", "compiler", "error"),
+        ("Unable to resolve module react-native-svg from /work/src/App.tsx: react-native-svg could not be found", "metro", "error"),
+        ("SyntaxError: /work/src/App.tsx: Unexpected token (12:4)", "metro", "error"),
+        ("error: No profiles for 'com.example.demo' were found: Xcode couldn't find any iOS App Development provisioning profiles", "codesign", "error"),
+        ("error: No signing certificate \"iOS Distribution\" found: No \"iOS Distribution\" signing certificate matching team ID", "codesign", "error"),
+        ("error: Provisioning profile \"Demo AppStore\" doesn't include signing certificate \"Apple Distribution: Example\".", "codesign", "error"),
+        ("/Users/me/Library/Developer/Xcode/DerivedData/Demo.app: errSecInternalComponent", "codesign", "error"),
+        ("Code Signing Error: No account for team \"ABCDE12345\".", "codesign", "error"),
+        ("[!] CocoaPods could not find compatible versions for pod \"React-Core\":", "cocoapods", "error"),
+        ("error: The sandbox is not in sync with the Podfile.lock. Run 'pod install' or update your CocoaPods installation.", "cocoapods", "error"),
+        ("xcodebuild: error: The project named \"Demo\" does not contain a scheme named \"Demo-Prod\".", "xcodebuild", "error"),
+        ("Execution failed for task ':app:mergeReleaseResources'.", "gradle", "error"),
+        ("Could not resolve all files for configuration ':app:releaseRuntimeClasspath'.", "gradle", "error"),
+        ("Could not find com.facebook.react:react-native:0.72.0.", "gradle", "error"),
+        ("SDK location not found. Define a valid SDK location with an ANDROID_HOME environment variable.", "gradle", "error"),
+        ("java.lang.OutOfMemoryError: Java heap space", "gradle", "error"),
+        ("Unsupported class file major version 65", "gradle", "error"),
+        ("Keystore file '/work/android/app/release.keystore' not found for signing config 'release'.", "codesign", "error"),
+    ];
+    for (line, category, severity) in samples {
+        let diagnostic = parse(line);
+        assert_eq!((diagnostic.category.as_str(), diagnostic.severity.as_str()), (category, severity), "{}", line);
+        assert_eq!(diagnostic.step.as_deref(), Some("archive"));
+    }
+}
+
+#[test]
+fn kotlin_locations_are_read_in_both_formats() {
+    let current = parse("e: file:///work/app/src/main/java/com/demo/MainActivity.kt:12:5 Unresolved reference: bar");
+    assert_eq!(current.file.as_deref(), Some("/work/app/src/main/java/com/demo/MainActivity.kt"));
+    assert_eq!((current.line, current.column), (Some(12), Some(5)));
+    assert_eq!(current.message, "Unresolved reference: bar");
+
+    let older = parse("e: /work/app/Foo.kt: (7, 21): Type mismatch: inferred type is String? but String was expected");
+    assert_eq!(older.file.as_deref(), Some("/work/app/Foo.kt"));
+    assert_eq!((older.line, older.column), (Some(7), Some(21)));
+    assert_eq!(older.message, "Type mismatch: inferred type is String? but String was expected");
+}
+
+#[test]
+fn java_and_compiler_locations_are_read() {
+    let java = parse("/work/app/src/main/java/com/demo/MainApplication.java:42: warning: [deprecation] getReactNativeHost() has been deprecated");
+    assert_eq!((java.file.as_deref(), java.line, java.column), (Some("/work/app/src/main/java/com/demo/MainApplication.java"), Some(42), None));
+    assert_eq!(java.severity, "warning");
+
+    let clang = parse("/Users/me/Demo/ios/Demo/AppDelegate.mm:10:9: fatal error: 'React/RCTBridge.h' file not found");
+    assert_eq!((clang.line, clang.column), (Some(10), Some(9)));
+    assert_eq!(clang.severity, "error");
+    assert_eq!(clang.message, "'React/RCTBridge.h' file not found");
+}
+
+#[test]
+fn missing_modules_suggest_pod_install() {
+    assert!(remediation("/Users/me/Demo/ios/Demo/AppDelegate.swift:3:8: error: No such module 'React'").contains("pod install"));
+    assert!(remediation("/Users/me/Demo/ios/Demo/AppDelegate.mm:10:9: fatal error: 'React/RCTBridge.h' file not found").contains("pod install"));
+    assert_eq!(parse("/Users/me/Demo/ios/Demo/AppDelegate.swift:8:3: warning: variable 'x' was never used").remediation, None);
+}
+
+#[test]
+fn metro_remediation_depends_on_the_module() {
+    let package = parse("Unable to resolve module react-native-svg from /work/src/App.tsx: react-native-svg could not be found");
+    assert_eq!(package.file.as_deref(), Some("/work/src/App.tsx"));
+    assert!(package.remediation.unwrap().contains("npm install react-native-svg"));
+    assert!(remediation("Unable to resolve module ./Header from /work/src/App.tsx").contains("Fix the import path"));
+
+    let syntax = parse("SyntaxError: /work/src/App.tsx: Unexpected token (12:4)");
+    assert_eq!((syntax.file.as_deref(), syntax.line, syntax.column), (Some("/work/src/App.tsx"), Some(12), Some(4)));
+    assert_eq!(syntax.message, "Unexpected token");
+}
+
+#[test]
+fn codesign_remediations_name_what_is_missing() {
+    assert!(remediation("error: No profile for 'com.example.demo' was found").contains("provisioning profile for com.example.demo"));
+    assert!(remediation("error: No signing certificate \"iOS Distribution\" found").contains("\"iOS Distribution\" certificate"));
+    assert!(remediation("error: Provisioning profile \"Demo AppStore\" doesn't include signing certificate \"X\".").contains("Regenerate \"Demo AppStore\""));
+    assert!(remediation("Demo.app: errSecInternalComponent").contains("security unlock-keychain"));
+    assert_eq!(parse("error: Code Signing Error: No account for team \"ABCDE12345\".").message, "No account for team \"ABCDE12345\".");
+    assert!(remediation("Keystore file '/work/release.keystore' not found for signing config 'release'.").contains("storeFile"));
+}
+
+#[test]
+fn cocoapods_remediations_depend_on_the_message() {
+    assert!(remediation("[!] CocoaPods could not find compatible versions for pod \"React-Core\":").contains("--repo-update"));
+    assert!(remediation("[!] Unable to find a specification for `RNFoo`").contains("--repo-update"));
+    let sync = parse("error: The sandbox is not in sync with the Podfile.lock. Run 'pod install' or update your CocoaPods installation.");
+    assert!(sync.message.starts_with("The sandbox is not in sync"));
+    assert_eq!(sync.remediation.as_deref(), Some("Run `pod install` in the ios directory."));
+    assert_eq!(parse("[!] Something else went wrong").remediation, None);
+}
+
+#[test]
+fn gradle_remediations_name_the_task_and_dependency() {
+    assert!(remediation("Execution failed for task ':app:mergeReleaseResources'.").contains("./gradlew :app:mergeReleaseResources --stacktrace"));
+    assert!(remediation("Could not find com.facebook.react:react-native:0.72.0.").starts_with("com.facebook.react:react-native:0.72.0 is not"));
+    assert!(remediation("Could not resolve all dependencies for configuration ':app:debugCompileClasspath'.").contains("--refresh-dependencies"));
+    assert!(remediation("SDK location not found.").contains("sdk.dir"));
+    assert!(remediation("Expiring Daemon because JVM heap space is exhausted").contains("org.gradle.jvmargs"));
+    assert!(remediation("java.lang.OutOfMemoryError: Metaspace").contains("org.gradle.jvmargs"));
+    assert!(remediation("Unsupported class file major version 65").contains("JDK 17"));
+    assert!(remediation("xcodebuild: error: Unable to find a destination").contains("xcodebuild -list"));
+}
+
+#[test]
+fn the_line_after_what_went_wrong_is_reported() {
+    let mut parser = DiagnosticParser::new();
+    assert!(parser.parse_line("* What went wrong:", Some("archive")).is_none());
+    let diagnostic = parser.parse_line("A problem occurred evaluating project ':app'.", Some("archive")).unwrap();
+    assert_eq!((diagnostic.category.as_str(), diagnostic.message.as_str()), ("gradle", "A problem occurred evaluating project ':app'."));
+    // Only the first line
+    assert!(parser.parse_line("Plugin with id 'com.android.application' not found.", Some("archive")).is_none());
+}
+
+#[test]
+fn stderr_markers_and_gradle_prefixes_are_stripped() {
+    let diagnostic = parse("⚠️ > Execution failed for task ':app:bundleReleaseJsAndAssets'.");
+    assert_eq!(diagnostic.message, "Execution failed for task ':app:bundleReleaseJsAndAssets'.");
+}
+
+#[test]
+fn unrelated_lines_and_duplicates_are_skipped() {
+    let mut parser = DiagnosticParser::new();
+    assert!(parser.parse_line("BUILD SUCCESSFUL in 42s", None).is_none());
+    assert!(parser.parse_line("", None).is_none());
+    let line = "Could not find com.facebook.react:react-native:0.72.0.";
+    assert!(parser.parse_line(line, None).is_some());
+    assert!(parser.parse_line(line, None).is_none());
+}
+
+#[test]
+fn saving_replaces_the_diagnostics_of_a_build() {
+    let conn = database();
+    let first = vec![parse("SDK location not found."), parse("Unsupported class file major version 65")];
+    save_build_diagnostics(&conn, "b1", &first).unwrap();
+    save_build_diagnostics(&conn, "b1", &first[1..]).unwrap();
+
+    let saved = load_build_diagnostics(&conn, "b1").unwrap();
+    assert_eq!(saved.iter().map(|d| d.message.as_str()).collect::<Vec<_>>(), ["Unsupported class file major version 65"]);
+}
+
+#[test]
+fn failed_saves_keep_the_previous_diagnostics() {
+    let conn = database();
+    save_build_diagnostics(&conn, "b1", &[parse("SDK location not found.")]).unwrap();
+
+    let mut invalid = parse("Unsupported class file major version 65");
+    invalid.severity = "fatal".to_string();
+    assert!(save_build_diagnostics(&conn, "b1", &[parse("java.lang.OutOfMemoryError: Java heap space"), invalid]).is_err());
+
+    let saved = load_build_diagnostics(&conn, "b1").unwrap();
+    assert_eq!(saved.iter().map(|d| d.message.as_str()).collect::<Vec<_>>(), ["SDK location not found."]);
+}
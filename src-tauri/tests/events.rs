@@ -0,0 +1,135 @@
+use app_builder_lib::commands::events::{BuildEvent, BuildEventBus, BuildEventEnvelope, BuildEventSink, BuildStatus, LogLevel};
+use app_builder_lib::commands::pipeline::{StepName, StepReport, StepStatus};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Keeps every event in memory, for the test to inspect once the build is done
+#[derive(Clone, Default)]
+struct MemorySink(Arc<Mutex<Vec<BuildEventEnvelope>>>);
+
+impl MemorySink {
+    fn events(&self) -> Vec<BuildEventEnvelope> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl BuildEventSink for MemorySink {
+    fn handle(&mut self, envelope: &BuildEventEnvelope) -> Result<(), String> {
+        self.0.lock().unwrap().push(envelope.clone());
+        Ok(())
+    }
+}
+
+/// Fails on every event, like a window that was closed mid-build
+struct FailingSink;
+
+impl BuildEventSink for FailingSink {
+    fn handle(&mut self, _: &BuildEventEnvelope) -> Result<(), String> {
+        Err("window closed".to_string())
+    }
+}
+
+fn report(name: StepName, status: StepStatus, bus: &BuildEventBus) -> StepReport {
+    StepReport { name, status, started_at: None, duration_ms: None, log_start: 0, log_end: bus.line_count(), message: None }
+}
+
+/// Emits what `BuildPipeline::run` and `finish` emit for a build whose steps each log a line,
+/// the export step producing the artifact
+fn run_stub_pipeline(bus: &mut BuildEventBus, steps: &[StepName]) {
+    for step in steps {
+        bus.set_step(Some(*step));
+        bus.step(&report(*step, StepStatus::Running, bus));
+        bus.log(&format!("{} ran", step.label()));
+        if *step == StepName::Export {
+            bus.artifact(Path::new("/tmp/app-release.aab"));
+        }
+        bus.step(&report(*step, StepStatus::Success, bus));
+    }
+    bus.set_step(None);
+    bus.status(BuildStatus::Success);
+}
+
+fn kind(event: &BuildEvent) -> String {
+    match event {
+        BuildEvent::Step(report) => format!("step {} {:?}", report.name.as_str(), report.status),
+        BuildEvent::Log { step, message, .. } => format!("log {} {}", step.map(|s| s.as_str()).unwrap_or("-"), message),
+        BuildEvent::Artifact { path } => format!("artifact {}", path),
+        BuildEvent::Status { status } => format!("status {}", status.as_str()),
+        other => format!("{:?}", other),
+    }
+}
+
+#[test]
+fn a_build_emits_steps_logs_the_artifact_and_the_status_in_order() {
+    let sink = MemorySink::default();
+    let mut bus = BuildEventBus::new("demo", "android", Some("b1".to_string()));
+    bus.subscribe(Box::new(sink.clone()));
+
+    run_stub_pipeline(&mut bus, &[StepName::Prepare, StepName::Export]);
+
+    let events = sink.events();
+    let kinds: Vec<_> = events.iter().map(|e| kind(&e.event)).collect();
+    assert_eq!(
+        kinds,
+        [
+            "step prepare Running",
+            "log prepare Prepare ran",
+            "step prepare Success",
+            "step export Running",
+            "log export Export ran",
+            "artifact /tmp/app-release.aab",
+            "step export Success",
+            "status success",
+        ]
+    );
+    let sequences: Vec<_> = events.iter().map(|e| e.sequence).collect();
+    assert_eq!(sequences, (1..=8).collect::<Vec<u64>>());
+    assert!(events.iter().all(|e| e.project_id == "demo" && e.platform == "android" && e.build_id.as_deref() == Some("b1")));
+}
+
+#[test]
+fn a_failing_sink_does_not_stop_the_others() {
+    let before = MemorySink::default();
+    let after = MemorySink::default();
+    let mut bus = BuildEventBus::new("demo", "ios", None);
+    bus.subscribe(Box::new(before.clone()));
+    bus.subscribe(Box::new(FailingSink));
+    bus.subscribe(Box::new(after.clone()));
+
+    run_stub_pipeline(&mut bus, &[StepName::Archive, StepName::Export]);
+
+    assert_eq!(before.events().len(), 8);
+    assert_eq!(after.events().len(), 8);
+    // Reported once, not for every event
+    assert_eq!(bus.sink_errors(), ["window closed"]);
+}
+
+#[test]
+fn log_lines_are_written_to_the_log_file_and_counted() {
+    let dir = std::env::temp_dir().join(format!("events-log-{}", std::process::id()));
+    let mut bus = BuildEventBus::new("demo", "android", None);
+    bus.open_log_file(&dir).unwrap();
+
+    bus.log_at(LogLevel::Debug, "> Task :app:bundleRelease");
+    bus.warn("first\nsecond");
+    bus.progress(0.5, "Half way");
+
+    assert_eq!(bus.line_count(), 3);
+    let content = std::fs::read_to_string(bus.log_path().unwrap()).unwrap();
+    assert_eq!(content, "> Task :app:bundleRelease\nfirst\nsecond\n");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn envelopes_are_flattened_for_the_frontend() {
+    let sink = MemorySink::default();
+    let mut bus = BuildEventBus::new("demo", "ios", Some("b1".to_string()));
+    bus.subscribe(Box::new(sink.clone()));
+    bus.version("1.4.0", 57);
+
+    let json = serde_json::to_value(&sink.events()[0]).unwrap();
+    assert_eq!(json["type"], "version");
+    assert_eq!(json["buildNumber"], 57);
+    assert_eq!(json["projectId"], "demo");
+    assert_eq!(json["sequence"], 1);
+}
@@ -0,0 +1,111 @@
+use app_builder_lib::commands::expo::{expo_config_file, prebuild_command, prepare_workspace, update_expo_versions};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_project(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("expo-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn prebuild_command_sets_platform_and_clean() {
+    assert_eq!(prebuild_command("ios", false), "CI=1 npx expo prebuild --platform ios 2>&1");
+    assert_eq!(prebuild_command("android", true), "CI=1 npx expo prebuild --platform android --clean 2>&1");
+}
+
+#[test]
+fn app_json_versions_are_updated_in_place() {
+    let dir = temp_project("app-json");
+    let app_json = r#"{
+  "expo": {
+    "name": "Demo",
+    "version": "1.2.0",
+    "ios": {
+      "bundleIdentifier": "com.example.demo",
+      "buildNumber": "7"
+    },
+    "android": {
+      "package": "com.example.demo",
+      "versionCode": 7
+    }
+  }
+}
+"#;
+    fs::write(dir.join("app.json"), app_json).unwrap();
+
+    let update = update_expo_versions(&dir, Some("1.3.0"), Some(8), Some(9)).unwrap();
+
+    assert!(update.missing.is_empty(), "{:?}", update.missing);
+    let changes: Vec<_> = update.changes.iter().map(|c| (c.line, c.old.as_str(), c.new.as_str())).collect();
+    assert_eq!(
+        changes,
+        vec![
+            (4, r#""version": "1.2.0","#, r#""version": "1.3.0","#),
+            (7, r#""buildNumber": "7""#, r#""buildNumber": "8""#),
+            (11, r#""versionCode": 7"#, r#""versionCode": 9"#),
+        ]
+    );
+    let written = fs::read_to_string(dir.join("app.json")).unwrap();
+    assert_eq!(written, app_json.replace("1.2.0", "1.3.0").replace("\"7\"", "\"8\"").replace(": 7\n", ": 9\n"));
+
+    let unchanged = update_expo_versions(&dir, Some("1.3.0"), None, None).unwrap();
+    assert!(unchanged.changes.is_empty());
+}
+
+#[test]
+fn line_endings_are_kept() {
+    let dir = temp_project("crlf");
+    let app_json = "{\r\n  \"expo\": {\r\n    \"version\": \"1.2.0\",\r\n    \"ios\": { \"buildNumber\": \"7\" }\r\n  }\r\n}";
+    fs::write(dir.join("app.json"), app_json).unwrap();
+
+    update_expo_versions(&dir, None, Some(8), None).unwrap();
+
+    let written = fs::read_to_string(dir.join("app.json")).unwrap();
+    assert_eq!(written, app_json.replace("\"7\"", "\"8\""));
+}
+
+#[test]
+fn dynamic_configs_only_get_literal_values_updated() {
+    let dir = temp_project("app-config");
+    fs::write(dir.join("app.json"), r#"{ "name": "not-expo" }"#).unwrap();
+    fs::write(
+        dir.join("app.config.ts"),
+        "export default {\n  version: process.env.APP_VERSION,\n  ios: { buildNumber: '3' },\n  android: {\n    versionCode: 3,\n  },\n};\n",
+    )
+    .unwrap();
+    assert_eq!(expo_config_file(&dir), Some(dir.join("app.config.ts")));
+
+    let update = update_expo_versions(&dir, Some("2.0.0"), Some(4), Some(4)).unwrap();
+
+    assert_eq!(update.missing, vec!["version"]);
+    let written = fs::read_to_string(dir.join("app.config.ts")).unwrap();
+    assert!(written.contains("version: process.env.APP_VERSION,"), "{}", written);
+    assert!(written.contains("ios: { buildNumber: '4' },"), "{}", written);
+    assert!(written.contains("    versionCode: 4,\n"), "{}", written);
+}
+
+#[test]
+fn workspaces_leave_out_native_folders_and_link_node_modules() {
+    let dir = temp_project("workspace");
+    let project = dir.join("project");
+    for folder in ["ios/Demo", "android/app", "node_modules/expo", "src/screens", ".git"] {
+        fs::create_dir_all(project.join(folder)).unwrap();
+    }
+    fs::write(project.join("app.json"), r#"{ "expo": {} }"#).unwrap();
+    fs::write(project.join("src/screens/Home.tsx"), "export {}").unwrap();
+    fs::write(project.join("node_modules/expo/package.json"), "{}").unwrap();
+
+    let workspace = dir.join("workspace");
+    fs::create_dir_all(workspace.join("ios")).unwrap();
+    prepare_workspace(&project, &workspace).unwrap();
+
+    assert!(workspace.join("app.json").is_file());
+    assert_eq!(fs::read_to_string(workspace.join("src/screens/Home.tsx")).unwrap(), "export {}");
+    assert!(fs::symlink_metadata(workspace.join("node_modules")).unwrap().file_type().is_symlink());
+    assert!(workspace.join("node_modules/expo/package.json").is_file());
+    for excluded in ["ios", "android", ".git"] {
+        assert!(!workspace.join(excluded).exists(), "{} was copied", excluded);
+    }
+}
@@ -0,0 +1,68 @@
+use app_builder_lib::commands::export_options::{export_options_plist, write_export_options};
+use app_builder_lib::models::project::{IosConfig, IosExportOptions};
+use plist::Value;
+
+fn keys(plist: &plist::Dictionary) -> Vec<&str> {
+    let mut keys: Vec<&str> = plist.keys().map(|k| k.as_str()).collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn defaults_keep_automatic_signing() {
+    let plist = export_options_plist("app-store", "AB12CD34EF", &IosExportOptions::default());
+
+    assert_eq!(keys(&plist), vec!["compileBitcode", "method", "signingStyle", "stripSwiftSymbols", "teamID", "uploadSymbols"]);
+    assert_eq!(plist["method"], Value::String("app-store".to_string()));
+    assert_eq!(plist["teamID"], Value::String("AB12CD34EF".to_string()));
+    assert_eq!(plist["signingStyle"], Value::String("automatic".to_string()));
+    assert_eq!(plist["uploadSymbols"], Value::Boolean(true));
+    assert_eq!(plist["compileBitcode"], Value::Boolean(false));
+}
+
+#[test]
+fn mapped_profiles_switch_to_manual_signing() {
+    let options: IosExportOptions = serde_json::from_value(serde_json::json!({
+        "provisioningProfiles": {
+            "com.example.app": "Example App Store",
+            "com.example.app.NotificationService": "Example Notification Service Store"
+        },
+        "signingCertificate": "Apple Distribution",
+        "uploadSymbols": false,
+        "thinning": "<none>",
+        "manageAppVersionAndBuildNumber": false
+    }))
+    .unwrap();
+
+    let path = std::env::temp_dir().join(format!("ExportOptions-{}.plist", std::process::id()));
+    write_export_options(&path, "app-store-connect", "AB12CD34EF", &options).unwrap();
+    let plist = Value::from_file(&path).unwrap().into_dictionary().unwrap();
+
+    assert_eq!(plist["signingStyle"], Value::String("manual".to_string()));
+    let profiles = plist["provisioningProfiles"].as_dictionary().unwrap();
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(
+        profiles["com.example.app.NotificationService"],
+        Value::String("Example Notification Service Store".to_string())
+    );
+    assert_eq!(plist["signingCertificate"], Value::String("Apple Distribution".to_string()));
+    assert_eq!(plist["uploadSymbols"], Value::Boolean(false));
+    assert_eq!(plist["thinning"], Value::String("<none>".to_string()));
+    assert_eq!(plist["manageAppVersionAndBuildNumber"], Value::Boolean(false));
+}
+
+#[test]
+fn projects_saved_before_export_options_still_load() {
+    let config: IosConfig = serde_json::from_value(serde_json::json!({
+        "scheme": "Example",
+        "configuration": "Release",
+        "teamId": "AB12CD34EF",
+        "exportMethod": "ad-hoc"
+    }))
+    .unwrap();
+    assert!(config.export_options.is_none());
+
+    let options: IosExportOptions = serde_json::from_str("{}").unwrap();
+    assert!(options.upload_symbols);
+    assert!(options.provisioning_profiles.is_empty());
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use app_builder_lib::commands::firebase_distribution::{project_number, AppDistributionClient};
+use app_builder_lib::commands::google_auth::ServiceAccountKey;
+use mockito::{Matcher, Server, ServerGuard};
+use common::TempFile;
+use serde_json::json;
+use std::time::Duration;
+
+const APP_ID: &str = "1:1234567890:android:0a1b2c3d4e5f67890";
+const RELEASE: &str = "projects/1234567890/apps/1:1234567890:android:0a1b2c3d4e5f67890/releases/rel-1";
+
+async fn client(server: &mut ServerGuard) -> AppDistributionClient {
+    server
+        .mock("POST", "/token")
+        .match_body(Matcher::Regex("assertion=".into()))
+        .with_body(r#"{"access_token": "test-token", "expires_in": 3599, "token_type": "Bearer"}"#)
+        .create_async()
+        .await;
+    let json = include_str!("fixtures/service_account.json").replace("TOKEN_URI", &format!("{}/token", server.url()));
+    let key = ServiceAccountKey::from_json(&json).unwrap();
+    AppDistributionClient::authenticate(&key, &server.url()).await.unwrap()
+}
+
+fn artifact(name: &str) -> TempFile {
+    TempFile::new(&format!("firebase-{}.apk", name), b"PK\x03\x04 not really an apk")
+}
+
+#[test]
+fn project_numbers_come_from_the_app_id() {
+    assert_eq!(project_number(APP_ID), Ok("1234567890"));
+    assert_eq!(project_number("1:987:ios:abc"), Ok("987"));
+    assert!(project_number("com.example.app").is_err());
+}
+
+#[tokio::test]
+async fn upload_sends_the_binary_and_returns_the_operation() {
+    let mut server = Server::new_async().await;
+    let client = client(&mut server).await;
+    let upload = server
+        .mock("POST", format!("/upload/v1/projects/1234567890/apps/{}/releases:upload", APP_ID).as_str())
+        .match_header("authorization", "Bearer test-token")
+        .match_header("x-goog-upload-protocol", "raw")
+        .match_header("x-goog-upload-file-name", format!("{}-firebase-upload.apk", std::process::id()).as_str())
+        .match_body(Matcher::Regex("not really an apk".into()))
+        .with_body(r#"{"name": "projects/1234567890/apps/app/releases/-/operations/op-1"}"#)
+        .create_async()
+        .await;
+
+    let operation = client.upload(APP_ID, &artifact("upload")).await.unwrap();
+
+    assert_eq!(operation, "projects/1234567890/apps/app/releases/-/operations/op-1");
+    upload.assert_async().await;
+}
+
+#[tokio::test]
+async fn the_operation_is_polled_until_the_release_exists() {
+    let mut server = Server::new_async().await;
+    let client = client(&mut server).await;
+    let path = "/v1/projects/1234567890/apps/app/releases/-/operations/op-1";
+    let pending = server.mock("GET", path).with_body(r#"{"name": "op-1"}"#).expect(2).create_async().await;
+    server
+        .mock("GET", path)
+        .with_body(
+            json!({
+                "name": "op-1",
+                "done": true,
+                "response": {
+                    "result": "RELEASE_CREATED",
+                    "release": { "name": RELEASE, "displayVersion": "1.4.0", "buildVersion": "57" }
+                }
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let uploaded = client
+        .wait_for_release(&path[4..], Duration::from_millis(10), Duration::from_secs(10))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(uploaded.result, "RELEASE_CREATED");
+    assert_eq!(uploaded.release.name, RELEASE);
+    assert_eq!(uploaded.release.display_version, "1.4.0");
+    pending.assert_async().await;
+}
+
+#[tokio::test]
+async fn a_failed_operation_reports_firebases_message() {
+    let mut server = Server::new_async().await;
+    let client = client(&mut server).await;
+    server
+        .mock("GET", "/v1/operations/op-2")
+        .with_body(r#"{"name": "op-2", "done": true, "error": {"code": 3, "message": "APK is not signed"}}"#)
+        .create_async()
+        .await;
+
+    let error = client
+        .wait_for_release("operations/op-2", Duration::from_millis(10), Duration::from_secs(10))
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("APK is not signed"), "{}", error);
+}
+
+#[tokio::test]
+async fn release_notes_are_patched_then_the_release_is_distributed() {
+    let mut server = Server::new_async().await;
+    let client = client(&mut server).await;
+    let notes = server
+        .mock("PATCH", format!("/v1/{}", RELEASE).as_str())
+        .match_query(Matcher::UrlEncoded("updateMask".into(), "release_notes.text".into()))
+        .match_body(Matcher::Json(json!({ "name": RELEASE, "releaseNotes": { "text": "Bug fixes" } })))
+        .with_body(json!({ "name": RELEASE }).to_string())
+        .create_async()
+        .await;
+    let distribute = server
+        .mock("POST", format!("/v1/{}:distribute", RELEASE).as_str())
+        .match_body(Matcher::Json(json!({
+            "testerEmails": ["qa@example.com"],
+            "groupAliases": ["qa-team", "trusted-testers"]
+        })))
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    client.set_release_notes(RELEASE, " Bug fixes\n").await.unwrap();
+    client
+        .distribute(RELEASE, &["qa@example.com".to_string()], &["qa-team".to_string(), "trusted-testers".to_string()])
+        .await
+        .unwrap();
+
+    notes.assert_async().await;
+    distribute.assert_async().await;
+}
+
+#[tokio::test]
+async fn unknown_groups_are_reported() {
+    let mut server = Server::new_async().await;
+    let client = client(&mut server).await;
+    server
+        .mock("POST", format!("/v1/{}:distribute", RELEASE).as_str())
+        .with_status(404)
+        .with_body(r#"{"error": {"code": 404, "message": "Group not found: beta-crew", "status": "NOT_FOUND"}}"#)
+        .create_async()
+        .await;
+
+    let error = client.distribute(RELEASE, &[], &["beta-crew".to_string()]).await.unwrap_err();
+
+    assert!(error.contains("Distributing the release failed (404"), "{}", error);
+    assert!(error.contains("Group not found: beta-crew"), "{}", error);
+}
@@ -0,0 +1,48 @@
+use app_builder_lib::commands::git::{create_tag, git, release_tag_name};
+use std::fs;
+use std::path::PathBuf;
+
+/// A repository with two empty commits, returned with their hashes (oldest first)
+fn repository(name: &str) -> (PathBuf, String, String) {
+    let dir = std::env::temp_dir().join(format!("git-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    git(&dir, &["init", "--quiet"]).unwrap();
+    git(&dir, &["config", "user.name", "Release Bot"]).unwrap();
+    git(&dir, &["config", "user.email", "release@example.com"]).unwrap();
+    let mut commits = Vec::new();
+    for message in ["first", "second"] {
+        git(&dir, &["commit", "--quiet", "--allow-empty", "-m", message]).unwrap();
+        commits.push(git(&dir, &["rev-parse", "HEAD"]).unwrap());
+    }
+    let second = commits.pop().unwrap();
+    (dir, commits.pop().unwrap(), second)
+}
+
+#[test]
+fn tag_names_include_the_profile() {
+    assert_eq!(release_tag_name("ios", None, "1.4.0", 57), "ios/1.4.0+57");
+    assert_eq!(release_tag_name("android", Some("staging"), "1.4.0", 57), "android-staging/1.4.0+57");
+}
+
+#[test]
+fn tagging_the_same_commit_twice_keeps_the_tag() {
+    let (dir, _, head) = repository("same-commit");
+
+    assert!(create_tag(&dir, "ios/1.4.0+57", &head, "Demo 1.4.0 (57) ios").unwrap());
+    assert!(!create_tag(&dir, "ios/1.4.0+57", &head, "Demo 1.4.0 (57) ios").unwrap());
+    assert_eq!(git(&dir, &["rev-parse", "ios/1.4.0+57^{commit}"]).unwrap(), head);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn tags_on_another_commit_are_not_moved() {
+    let (dir, first, head) = repository("other-commit");
+    create_tag(&dir, "ios/1.4.0+57", &first, "Demo 1.4.0 (57) ios").unwrap();
+
+    let error = create_tag(&dir, "ios/1.4.0+57", &head, "Demo 1.4.0 (57) ios").unwrap_err();
+
+    assert_eq!(error, format!("tag ios/1.4.0+57 already exists on commit {}", &first[..10]));
+    assert_eq!(git(&dir, &["rev-parse", "ios/1.4.0+57^{commit}"]).unwrap(), first);
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,179 @@
+mod common;
+
+use app_builder_lib::commands::google_auth::ServiceAccountKey;
+use app_builder_lib::commands::google_play::{release_notes_for, PlayClient, PlayRelease};
+use mockito::{Matcher, Server, ServerGuard};
+use common::TempFile;
+use serde_json::json;
+
+const PACKAGE: &str = "com.example.app";
+
+/// The fixture service account, pointed at the mock server's token endpoint
+fn service_account(server: &ServerGuard) -> ServiceAccountKey {
+    let json = include_str!("fixtures/service_account.json").replace("TOKEN_URI", &format!("{}/token", server.url()));
+    ServiceAccountKey::from_json(&json).unwrap()
+}
+
+fn bundle(name: &str) -> TempFile {
+    TempFile::new(&format!("google-play-{}.aab", name), b"PK\x03\x04 not really a bundle")
+}
+
+async fn authenticated_client(server: &mut ServerGuard) -> PlayClient {
+    server
+        .mock("POST", "/token")
+        .match_body(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("grant_type".into(), "urn:ietf:params:oauth:grant-type:jwt-bearer".into()),
+            Matcher::Regex("assertion=[A-Za-z0-9_-]+\\.[A-Za-z0-9_-]+\\.[A-Za-z0-9_-]+".into()),
+        ]))
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"access_token": "test-token", "expires_in": 3599, "token_type": "Bearer"}"#)
+        .create_async()
+        .await;
+    PlayClient::authenticate(&service_account(server), &server.url()).await.unwrap()
+}
+
+fn release<'a>(bundle_path: &'a std::path::Path, track: &'a str) -> PlayRelease<'a> {
+    PlayRelease {
+        package_name: PACKAGE,
+        bundle_path,
+        track,
+        status: "completed",
+        release_notes: release_notes_for(&["en-US".to_string(), "fr-FR".to_string()], "Bug fixes"),
+    }
+}
+
+#[tokio::test]
+async fn publish_uploads_the_bundle_assigns_the_track_and_commits() {
+    let mut server = Server::new_async().await;
+    let client = authenticated_client(&mut server).await;
+    let edits = format!("/androidpublisher/v3/applications/{}/edits", PACKAGE);
+
+    let insert = server
+        .mock("POST", edits.as_str())
+        .match_header("authorization", "Bearer test-token")
+        .with_body(r#"{"id": "edit-1", "expiryTimeSeconds": "1700000000"}"#)
+        .create_async()
+        .await;
+    let upload = server
+        .mock("POST", format!("/upload{}/edit-1/bundles", edits).as_str())
+        .match_query(Matcher::UrlEncoded("uploadType".into(), "media".into()))
+        .match_header("authorization", "Bearer test-token")
+        .match_header("content-type", "application/octet-stream")
+        .match_body(Matcher::Regex("not really a bundle".into()))
+        .with_body(r#"{"versionCode": 42, "sha256": "abc"}"#)
+        .create_async()
+        .await;
+    let track = server
+        .mock("PUT", format!("{}/edit-1/tracks/beta", edits).as_str())
+        .match_body(Matcher::Json(json!({
+            "track": "beta",
+            "releases": [{
+                "versionCodes": ["42"],
+                "status": "completed",
+                "releaseNotes": [
+                    {"language": "en-US", "text": "Bug fixes"},
+                    {"language": "fr-FR", "text": "Bug fixes"}
+                ]
+            }]
+        })))
+        .with_body(r#"{"track": "beta"}"#)
+        .create_async()
+        .await;
+    let commit = server
+        .mock("POST", format!("{}/edit-1:commit", edits).as_str())
+        .with_body(r#"{"id": "edit-1"}"#)
+        .create_async()
+        .await;
+    let delete = server.mock("DELETE", Matcher::Any).expect(0).create_async().await;
+
+    let bundle_path = bundle("publish");
+    let version_code = client.publish(&release(&bundle_path, "beta")).await.unwrap();
+
+    assert_eq!(version_code, 42);
+    insert.assert_async().await;
+    upload.assert_async().await;
+    track.assert_async().await;
+    commit.assert_async().await;
+    delete.assert_async().await;
+}
+
+#[tokio::test]
+async fn a_failed_upload_deletes_the_edit_and_reports_googles_message() {
+    let mut server = Server::new_async().await;
+    let client = authenticated_client(&mut server).await;
+    let edits = format!("/androidpublisher/v3/applications/{}/edits", PACKAGE);
+
+    server
+        .mock("POST", edits.as_str())
+        .with_body(r#"{"id": "edit-2"}"#)
+        .create_async()
+        .await;
+    server
+        .mock("POST", format!("/upload{}/edit-2/bundles", edits).as_str())
+        .match_query(Matcher::Any)
+        .with_status(403)
+        .with_body(r#"{"error": {"code": 403, "message": "APK specifies a version code that has already been used.", "status": "PERMISSION_DENIED"}}"#)
+        .create_async()
+        .await;
+    let commit = server.mock("POST", format!("{}/edit-2:commit", edits).as_str()).expect(0).create_async().await;
+    let delete = server
+        .mock("DELETE", format!("{}/edit-2", edits).as_str())
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let bundle_path = bundle("failed-upload");
+    let error = client.publish(&release(&bundle_path, "internal")).await.unwrap_err();
+
+    assert!(error.contains("Uploading the bundle failed (403"), "{}", error);
+    assert!(error.contains("version code that has already been used"), "{}", error);
+    commit.assert_async().await;
+    delete.assert_async().await;
+}
+
+#[tokio::test]
+async fn authentication_errors_carry_the_oauth_description() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/token")
+        .with_status(400)
+        .with_body(r#"{"error": "invalid_grant", "error_description": "Invalid JWT Signature."}"#)
+        .create_async()
+        .await;
+
+    let error = PlayClient::authenticate(&service_account(&server), &server.url()).await.err().unwrap();
+
+    assert!(error.contains("Invalid JWT Signature."), "{}", error);
+}
+
+#[tokio::test]
+async fn unknown_tracks_are_rejected_before_any_request() {
+    let mut server = Server::new_async().await;
+    let client = authenticated_client(&mut server).await;
+    let any = server.mock("POST", Matcher::Regex("/androidpublisher/.*".into())).expect(0).create_async().await;
+
+    let bundle_path = bundle("unknown-track");
+    let error = client.publish(&release(&bundle_path, "staging")).await.unwrap_err();
+
+    assert!(error.contains("Unknown track staging"), "{}", error);
+    any.assert_async().await;
+}
+
+#[test]
+fn release_notes_are_split_per_locale_when_tagged() {
+    let locales = vec!["en-US".to_string()];
+    let notes = release_notes_for(&locales, "<en-US>\nNew widgets\n</en-US>\n<de-DE>Neue Widgets</de-DE>");
+
+    let pairs: Vec<(&str, &str)> = notes.iter().map(|n| (n.language.as_str(), n.text.as_str())).collect();
+    assert_eq!(pairs, vec![("en-US", "New widgets"), ("de-DE", "Neue Widgets")]);
+}
+
+#[test]
+fn plain_release_notes_go_to_every_locale_truncated_to_500_characters() {
+    let locales = vec!["en-US".to_string(), "ja-JP".to_string()];
+    let notes = release_notes_for(&locales, &"x".repeat(600));
+
+    assert_eq!(notes.len(), 2);
+    assert!(notes.iter().all(|n| n.text.chars().count() == 500));
+    assert!(release_notes_for(&locales, "   ").is_empty());
+}
@@ -0,0 +1,161 @@
+use app_builder_lib::commands::gradle::{read_android_versions, update_android_versions};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An `android` folder with the given files, relative to it
+fn android_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gradle-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn read(dir: &Path, path: &str) -> String {
+    fs::read_to_string(dir.join(path)).unwrap()
+}
+
+#[test]
+fn kotlin_dsl_literals_are_updated_in_place() {
+    let build_file = r#"android {
+    namespace = "com.example.demo"
+    defaultConfig {
+        applicationId = "com.example.demo"
+        versionCode = 12 // bumped by CI
+        versionName = "1.2.0"
+    }
+}
+"#;
+    let dir = android_dir("kts", &[("app/build.gradle.kts", build_file)]);
+
+    let changes = update_android_versions(&dir, "1.3.0", 13).unwrap();
+
+    assert_eq!(read(&dir, "app/build.gradle.kts"), build_file.replace("= 12", "= 13").replace("1.2.0", "1.3.0"));
+    let lines: Vec<_> = changes.iter().map(|c| (c.line, c.old.as_str(), c.new.as_str())).collect();
+    assert_eq!(
+        lines,
+        [
+            (5, "versionCode = 12 // bumped by CI", "versionCode = 13 // bumped by CI"),
+            (6, "versionName = \"1.2.0\"", "versionName = \"1.3.0\""),
+        ]
+    );
+    assert_eq!(read_android_versions(&dir), (Some("1.3.0".to_string()), Some(13)));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn groovy_values_outside_default_config_are_left_alone() {
+    let build_file = "android {\r\n    defaultConfig {\r\n        versionCode 12\r\n        versionName '1.2.0'\r\n    }\r\n    productFlavors {\r\n        legacy {\r\n            versionCode 99\r\n        }\r\n    }\r\n}\r\n";
+    let dir = android_dir("groovy", &[("app/build.gradle", build_file)]);
+
+    update_android_versions(&dir, "1.3.0", 13).unwrap();
+
+    // Quotes and CRLF line endings are kept
+    assert_eq!(
+        read(&dir, "app/build.gradle"),
+        build_file.replace("versionCode 12", "versionCode 13").replace("'1.2.0'", "'1.3.0'")
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn gradle_properties_are_followed() {
+    let build_file = r#"android {
+    defaultConfig {
+        versionCode (project.findProperty("VERSION_CODE") as String).toInteger()
+        versionName VERSION_NAME
+    }
+}
+"#;
+    let properties = "org.gradle.jvmargs=-Xmx2048m\nVERSION_CODE=12\nVERSION_NAME=1.2.0\n";
+    let dir = android_dir("properties", &[("app/build.gradle", build_file), ("gradle.properties", properties)]);
+
+    assert_eq!(read_android_versions(&dir), (Some("1.2.0".to_string()), Some(12)));
+    let changes = update_android_versions(&dir, "1.3.0", 13).unwrap();
+
+    assert_eq!(read(&dir, "app/build.gradle"), build_file);
+    assert_eq!(read(&dir, "gradle.properties"), "org.gradle.jvmargs=-Xmx2048m\nVERSION_CODE=13\nVERSION_NAME=1.3.0\n");
+    assert!(changes.iter().all(|c| c.file == dir.join("gradle.properties")));
+    assert_eq!(changes.iter().map(|c| c.line).collect::<Vec<_>>(), [2, 3]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn version_catalog_entries_are_followed() {
+    let build_file = r#"android {
+    defaultConfig {
+        versionCode = libs.versions.app.versionCode.get().toInt()
+        versionName = libs.versions.app.version.name.get()
+    }
+}
+"#;
+    let catalog = r#"[versions]
+kotlin = "1.9.24"
+app-versionCode = "12"
+app_version_name = "1.2.0"
+
+[libraries]
+app-versionCode = { module = "com.example:unrelated", version = "1.0" }
+"#;
+    let dir = android_dir("catalog", &[("app/build.gradle.kts", build_file), ("gradle/libs.versions.toml", catalog)]);
+
+    assert_eq!(read_android_versions(&dir), (Some("1.2.0".to_string()), Some(12)));
+    update_android_versions(&dir, "1.3.0", 13).unwrap();
+
+    assert_eq!(read(&dir, "app/build.gradle.kts"), build_file);
+    assert_eq!(read(&dir, "gradle/libs.versions.toml"), catalog.replace("\"12\"", "\"13\"").replace("\"1.2.0\"", "\"1.3.0\""));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unchanged_values_report_no_changes() {
+    let build_file = "android {\n    defaultConfig {\n        versionCode 12\n        versionName \"1.2.0\"\n    }\n}\n";
+    let dir = android_dir("unchanged", &[("app/build.gradle", build_file)]);
+
+    assert!(update_android_versions(&dir, "1.2.0", 12).unwrap().is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn projects_without_version_lines_are_skipped() {
+    let dir = android_dir("missing", &[("app/build.gradle", "android {\n    defaultConfig {\n        minSdkVersion 24\n    }\n}\n")]);
+    assert!(update_android_versions(&dir, "1.3.0", 13).unwrap().is_empty());
+    assert_eq!(read_android_versions(&dir), (None, None));
+
+    let empty = android_dir("no-build-file", &[("settings.gradle", "include ':app'\n")]);
+    assert!(update_android_versions(&empty, "1.3.0", 13).unwrap().is_empty());
+
+    fs::remove_dir_all(dir).unwrap();
+    fs::remove_dir_all(empty).unwrap();
+}
+
+#[test]
+fn computed_versions_are_rejected() {
+    let build_file = "android {\n    defaultConfig {\n        versionCode major * 10000 + minor\n        versionName \"${major}.${minor}\"\n    }\n}\n";
+    let dir = android_dir("computed", &[("app/build.gradle", build_file)]);
+
+    let error = update_android_versions(&dir, "1.3.0", 13).unwrap_err();
+
+    assert!(error.ends_with("app/build.gradle:3: cannot update versionCode = major * 10000 + minor, it is not a literal, a gradle.properties property or a version catalog entry"), "{}", error);
+    assert_eq!(read(&dir, "app/build.gradle"), build_file);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn missing_properties_and_catalog_entries_are_rejected() {
+    let build_file = "android {\n    defaultConfig {\n        versionName VERSION_NAME\n    }\n}\n";
+    let dir = android_dir("missing-property", &[("app/build.gradle", build_file), ("gradle.properties", "VERSION_CODE=12\n")]);
+    assert!(update_android_versions(&dir, "1.3.0", 13).unwrap_err().contains("cannot update versionName = VERSION_NAME"));
+
+    let build_file = "android {\n    defaultConfig {\n        versionCode = libs.versions.appCode.get().toInt()\n    }\n}\n";
+    let catalog = "[versions]\nkotlin = \"1.9.24\"\n\n[plugins]\nappCode = \"12\"\n";
+    let catalog_dir = android_dir("missing-entry", &[("app/build.gradle.kts", build_file), ("gradle/libs.versions.toml", catalog)]);
+    assert!(update_android_versions(&catalog_dir, "1.3.0", 13).unwrap_err().contains("cannot update versionCode"));
+    assert_eq!(read(&catalog_dir, "gradle/libs.versions.toml"), catalog);
+
+    fs::remove_dir_all(dir).unwrap();
+    fs::remove_dir_all(catalog_dir).unwrap();
+}
@@ -0,0 +1,114 @@
+use app_builder_lib::commands::ipa_metadata::{parse_macho, read_ipa, SegmentSize};
+use chrono::{DateTime, Utc};
+use std::io::{Cursor, Write};
+use zip::ZipArchive;
+
+const THIN: &[u8] = include_bytes!("fixtures/Demo.macho");
+const FAT: &[u8] = include_bytes!("fixtures/DemoFat.macho");
+const PROFILE: &[u8] = include_bytes!("fixtures/development.mobileprovision");
+
+const INFO_PLIST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleIdentifier</key><string>com.example.app</string>
+    <key>CFBundleName</key><string>Demo</string>
+    <key>CFBundleExecutable</key><string>Demo</string>
+    <key>CFBundleShortVersionString</key><string>2.1.0</string>
+    <key>CFBundleVersion</key><string>57</string>
+    <key>MinimumOSVersion</key><string>15.0</string>
+    <key>CFBundleSupportedPlatforms</key><array><string>iPhoneOS</string></array>
+</dict>
+</plist>"#;
+
+fn ipa(entries: &[(&str, &[u8])]) -> ZipArchive<Cursor<Vec<u8>>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in entries {
+        writer.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(contents).unwrap();
+    }
+    ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+}
+
+fn at(date: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(date).unwrap().with_timezone(&Utc)
+}
+
+#[test]
+fn thin_binaries_list_their_slice() {
+    let (slices, libraries) = parse_macho(THIN).unwrap();
+
+    assert_eq!(slices.len(), 1);
+    let slice = &slices[0];
+    assert_eq!(slice.arch, "arm64");
+    assert_eq!(slice.platform.as_deref(), Some("ios"));
+    assert_eq!(slice.minimum_os.as_deref(), Some("15.0"));
+    assert_eq!(slice.encrypted, Some(false));
+    assert!(!slice.simulator && !slice.debug_symbols);
+    assert_eq!(
+        slice.segments.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+        vec!["__PAGEZERO", "__TEXT", "__DATA", "__LINKEDIT"]
+    );
+    assert_eq!(slice.segments[1], SegmentSize { name: "__TEXT".into(), file_size: 0x800, vm_size: 0x4000 });
+    assert_eq!(
+        libraries,
+        vec![
+            "/System/Library/Frameworks/UIKit.framework/UIKit",
+            "/System/Library/Frameworks/Foundation.framework/Foundation",
+            "@rpath/React.framework/React",
+        ]
+    );
+}
+
+#[test]
+fn simulator_slices_and_debug_symbols_are_flagged() {
+    let (slices, libraries) = parse_macho(FAT).unwrap();
+
+    assert_eq!(slices.iter().map(|s| s.arch.as_str()).collect::<Vec<_>>(), vec!["arm64", "x86_64"]);
+    assert!(!slices[0].simulator && slices[0].debug_symbols);
+    assert_eq!(slices[1].platform.as_deref(), Some("iossimulator"));
+    assert!(slices[1].simulator && !slices[1].debug_symbols);
+    // Libraries linked by both slices are listed once
+    assert_eq!(libraries.len(), 3);
+}
+
+#[test]
+fn ipas_are_read_from_the_app_bundle() {
+    let mut archive = ipa(&[
+        ("Payload/Demo.app/Info.plist", INFO_PLIST.as_bytes()),
+        ("Payload/Demo.app/Demo", THIN),
+        ("Payload/Demo.app/embedded.mobileprovision", PROFILE),
+        ("Payload/Demo.app/Frameworks/React.framework/React", FAT),
+        ("Payload/Demo.app/Frameworks/React.framework/Info.plist", INFO_PLIST.as_bytes()),
+        ("Payload/Demo.app/Demo.app.dSYM/Contents/Resources/DWARF/Demo", THIN),
+        ("Payload/Demo.app/Demo.app.dSYM/Contents/Info.plist", INFO_PLIST.as_bytes()),
+        ("Payload/Demo.app/_CodeSignature/CodeResources", b"<plist/>"),
+        ("Payload/Demo.app/PkgInfo", b"APPL????"),
+        ("Payload/Demo.app/main.jsbundle", b"__d(function(){})"),
+    ]);
+    let info = read_ipa(&mut archive, at("2026-10-18T00:00:00Z")).unwrap();
+
+    assert_eq!(info.bundle_id, "com.example.app");
+    assert_eq!(info.name.as_deref(), Some("Demo"));
+    assert_eq!((info.version.as_deref(), info.build_number.as_deref()), (Some("2.1.0"), Some("57")));
+    assert_eq!(info.minimum_os.as_deref(), Some("15.0"));
+    assert_eq!(info.supported_platforms, vec!["iPhoneOS"]);
+
+    let profile = info.profile.unwrap();
+    assert_eq!((profile.name.as_str(), profile.profile_type.as_str()), ("Example App Development", "development"));
+    assert!(!profile.expired);
+
+    let binaries: Vec<_> = info.binaries.iter().map(|b| (b.path.as_str(), b.kind.as_str())).collect();
+    assert_eq!(
+        binaries,
+        vec![("Payload/Demo.app/Demo", "app"), ("Payload/Demo.app/Frameworks/React.framework/React", "framework")]
+    );
+    assert_eq!(info.binaries[1].size, FAT.len() as u64);
+    assert_eq!(info.dsym_paths, vec!["Payload/Demo.app/Demo.app.dSYM"]);
+}
+
+#[test]
+fn ipas_without_an_app_are_rejected() {
+    let mut archive = ipa(&[("Payload/readme.txt", b"empty")]);
+    assert_eq!(read_ipa(&mut archive, Utc::now()).unwrap_err(), "Payload/*.app/Info.plist not found");
+}
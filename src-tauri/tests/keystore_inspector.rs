@@ -0,0 +1,69 @@
+use app_builder_lib::commands::keystore_inspector::{inspect, DEFAULT_EXPIRY_WARNING_DAYS};
+use chrono::{DateTime, Utc};
+
+const JKS: &[u8] = include_bytes!("fixtures/upload.jks");
+const PKCS12: &[u8] = include_bytes!("fixtures/release.p12");
+
+fn at(date: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(date).unwrap().with_timezone(&Utc)
+}
+
+#[test]
+fn jks_entries_list_their_certificates() {
+    let info = inspect(JKS, "android", at("2026-11-01T00:00:00Z"), DEFAULT_EXPIRY_WARNING_DAYS).unwrap();
+
+    assert_eq!(info.format, "JKS");
+    let aliases: Vec<(&str, &str)> = info.entries.iter().map(|e| (e.alias.as_str(), e.kind.as_str())).collect();
+    assert_eq!(aliases, vec![("partner", "trustedCertificate"), ("upload", "privateKey")]);
+
+    let upload = &info.entries[1];
+    assert!(upload.created.as_deref().unwrap().starts_with("2026-10-18"));
+    let cert = &upload.certificates[0];
+    assert_eq!(cert.subject, "CN=Upload Key, O=Example");
+    assert_eq!(cert.signature_algorithm, "SHA256withRSA");
+    assert_eq!(cert.public_key, "RSA 2048-bit");
+    assert_eq!(cert.sha1, "68:23:B1:76:C9:7B:2F:AF:9C:0E:F9:BC:13:C1:0B:6C:D1:0D:3A:24");
+    assert_eq!(
+        cert.sha256,
+        "59:D4:4E:C6:B1:DE:39:37:A5:FC:04:77:9B:FB:31:1F:D0:63:73:A7:05:3A:55:A0:CA:8B:2A:B2:A2:6F:E8:96"
+    );
+    assert_eq!(cert.valid_until, "2054-03-05T03:53:31+00:00");
+    assert!(info.warnings.is_empty(), "{:?}", info.warnings);
+}
+
+#[test]
+fn pkcs12_entries_list_their_certificates() {
+    let info = inspect(PKCS12, "release-pass", at("2026-10-20T00:00:00Z"), 30).unwrap();
+
+    assert_eq!(info.format, "PKCS12");
+    assert_eq!(info.entries.len(), 1);
+    let release = &info.entries[0];
+    assert_eq!(release.alias, "release");
+    assert_eq!(release.kind, "privateKey");
+    assert_eq!(release.created, None);
+    let cert = &release.certificates[0];
+    assert_eq!(cert.signature_algorithm, "SHA256withECDSA");
+    assert_eq!(cert.public_key, "EC 256-bit");
+    assert_eq!(cert.sha1, "77:10:F1:EC:D8:D0:00:9A:F4:9E:15:F0:87:0F:C6:34:67:EF:A9:B5");
+    assert_eq!(cert.days_until_expiry, 58);
+    assert!(info.warnings.is_empty(), "{:?}", info.warnings);
+}
+
+#[test]
+fn certificates_close_to_expiry_are_reported() {
+    let info = inspect(PKCS12, "release-pass", at("2026-12-01T00:00:00Z"), 30).unwrap();
+    assert_eq!(info.warnings, vec!["The certificate of release expires on 2026-12-17T03:53:33+00:00, in 16 days"]);
+
+    let info = inspect(PKCS12, "release-pass", at("2027-01-01T00:00:00Z"), 30).unwrap();
+    assert_eq!(info.entries[0].certificates[0].days_until_expiry, -15);
+    assert_eq!(info.warnings, vec!["The certificate of release expired on 2026-12-17T03:53:33+00:00"]);
+}
+
+#[test]
+fn wrong_passwords_are_rejected() {
+    let now = at("2026-11-01T00:00:00Z");
+
+    assert_eq!(inspect(JKS, "wrong", now, 30).unwrap_err(), "Keystore password is incorrect");
+    assert_eq!(inspect(PKCS12, "wrong", now, 30).unwrap_err(), "Keystore password is incorrect");
+    assert_eq!(inspect(b"not a keystore", "android", now, 30).unwrap_err(), "Not a JKS or PKCS12 keystore");
+}
@@ -0,0 +1,186 @@
+use app_builder_lib::commands::pbxproj::{source_relative, BuildConfiguration, XcodeProject};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+const PBXPROJ: &str = include_str!("fixtures/Demo.pbxproj");
+
+/// An `ios` folder holding `Shop.xcodeproj` with the fixture, an empty Pods project and the
+/// Info.plist files of the targets
+fn ios_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pbxproj-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (path, content) in [
+        ("Shop.xcodeproj/project.pbxproj", PBXPROJ),
+        ("Pods.xcodeproj/project.pbxproj", "{}"),
+        ("Demo/Info.plist", ""),
+        ("NotificationService/Info.plist", ""),
+        ("DemoTests/Info.plist", ""),
+    ] {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn configuration(settings: &[(&str, &str)]) -> BuildConfiguration {
+    let settings: HashMap<String, String> = settings.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    BuildConfiguration { name: "Release".to_string(), settings }
+}
+
+#[test]
+fn every_native_target_is_read() {
+    let dir = ios_dir("targets");
+    let project = XcodeProject::open(&XcodeProject::find(&dir).unwrap()).unwrap();
+
+    let targets = project.targets();
+    let summary: Vec<_> = targets.iter().map(|t| (t.name.as_str(), t.is_versioned(), t.configurations.len())).collect();
+    assert_eq!(summary, [("Demo", true, 2), ("NotificationService", true, 2), ("DemoTests", false, 1)]);
+    assert_eq!(project.app_target().unwrap().name, "Demo");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn the_pods_project_is_skipped() {
+    let dir = ios_dir("find");
+    assert_eq!(XcodeProject::find(&dir), Some(dir.join("Shop.xcodeproj/project.pbxproj")));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn target_settings_override_project_settings() {
+    let dir = ios_dir("settings");
+    let project = XcodeProject::open(&dir.join("Shop.xcodeproj/project.pbxproj")).unwrap();
+    let app = project.app_target().unwrap();
+    let release = app.configuration("Release").unwrap();
+
+    // Inherited from the project's Release configuration, quotes removed
+    assert_eq!(release.settings["IPHONEOS_DEPLOYMENT_TARGET"], "15.1");
+    assert_eq!(release.settings["SWIFT_ACTIVE_COMPILATION_CONDITIONS"], "");
+    assert_eq!(release.settings["PRODUCT_NAME"], "Demo App");
+    assert_eq!(release.setting("PRODUCT_BUNDLE_IDENTIFIER").as_deref(), Some("com.example.demo"));
+    assert_eq!(app.configuration("Debug").unwrap().setting("PRODUCT_BUNDLE_IDENTIFIER").as_deref(), Some("com.example.demo.debug"));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn missing_configurations_fall_back_to_the_first() {
+    let dir = ios_dir("configurations");
+    let project = XcodeProject::open(&dir.join("Shop.xcodeproj/project.pbxproj")).unwrap();
+    let targets = project.targets();
+
+    assert_eq!(targets[0].configuration("Staging").unwrap().name, "Debug");
+    assert_eq!(targets[2].configuration("Release").unwrap().name, "Debug");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn setting_references_are_expanded() {
+    let config = configuration(&[
+        ("PRODUCT_NAME", "Demo"),
+        ("BUNDLE_ID_PREFIX", "com.example"),
+        ("BASE_ID", "$(BUNDLE_ID_PREFIX).${PRODUCT_NAME}"),
+        ("LOOP_A", "$(LOOP_B)"),
+        ("LOOP_B", "$(LOOP_A)"),
+    ]);
+
+    assert_eq!(config.resolve("$(BASE_ID).widget").as_deref(), Some("com.example.Demo.widget"));
+    // Modifiers are dropped, the value is used as is
+    assert_eq!(config.resolve("$(BUNDLE_ID_PREFIX).$(PRODUCT_NAME:rfc1034identifier)").as_deref(), Some("com.example.Demo"));
+    assert_eq!(config.resolve("plain").as_deref(), Some("plain"));
+    assert_eq!(config.resolve("$(TARGET_NAME)"), None);
+    assert_eq!(config.resolve("$(LOOP_A)"), None);
+    assert_eq!(config.setting("MISSING"), None);
+}
+
+#[test]
+fn unresolved_references_in_the_project_give_none() {
+    let dir = ios_dir("unresolved");
+    let project = XcodeProject::open(&dir.join("Shop.xcodeproj/project.pbxproj")).unwrap();
+    let debug = project.targets().remove(1).configurations.remove(0);
+
+    // PRODUCT_NAME is `$(TARGET_NAME)`, which only xcodebuild defines
+    assert_eq!(debug.setting("PRODUCT_BUNDLE_IDENTIFIER"), None);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn info_plists_of_versioned_targets_are_found() {
+    let dir = ios_dir("plists");
+    let project = XcodeProject::open(&dir.join("Shop.xcodeproj/project.pbxproj")).unwrap();
+
+    assert_eq!(project.info_plists(), [dir.join("Demo/Info.plist"), dir.join("NotificationService/Info.plist")]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn source_root_prefixes_are_stripped() {
+    for path in ["$(SRCROOT)/Demo/Info.plist", "${SRCROOT}/Demo/Info.plist", "$(PROJECT_DIR)/Demo/Info.plist", "${PROJECT_DIR}/Demo/Info.plist"] {
+        assert_eq!(source_relative(path), "Demo/Info.plist");
+    }
+    assert_eq!(source_relative("Demo/Info.plist"), "Demo/Info.plist");
+    assert_eq!(source_relative("$(BUILT_PRODUCTS_DIR)/Info.plist"), "$(BUILT_PRODUCTS_DIR)/Info.plist");
+}
+
+#[test]
+fn versions_are_set_on_every_versioned_target() {
+    let dir = ios_dir("set-versions");
+    let path = dir.join("Shop.xcodeproj/project.pbxproj");
+    let mut project = XcodeProject::open(&path).unwrap();
+
+    let changed = project.set_versions("1.3.0", "8").unwrap();
+    project.save().unwrap();
+
+    assert_eq!(changed, ["Demo (Debug)", "Demo (Release)", "NotificationService (Debug)", "NotificationService (Release)"]);
+    let content = fs::read_to_string(&path).unwrap();
+    // Missing settings are added on their own lines, with the dictionary's indentation
+    let inserted = "\t\t\t\tCURRENT_PROJECT_VERSION = 8;\n\t\t\t\tMARKETING_VERSION = 1.3.0;\n\t\t\t};\n\t\t\tname = Release;";
+    assert!(content.contains(inserted));
+    // Everything else is left as it was
+    let restored = content
+        .replace(inserted, "\t\t\t};\n\t\t\tname = Release;")
+        .replace("= 1.3.0;", "= 1.2.0;")
+        .replace("= 8;", "= 7;");
+    assert_eq!(restored, PBXPROJ);
+
+    let reopened = XcodeProject::open(&path).unwrap();
+    for target in reopened.targets() {
+        for configuration in &target.configurations {
+            let versions = (configuration.settings.get("MARKETING_VERSION"), configuration.settings.get("CURRENT_PROJECT_VERSION"));
+            if target.is_versioned() {
+                assert_eq!(versions, (Some(&"1.3.0".to_string()), Some(&"8".to_string())), "{}", target.name);
+            } else {
+                assert_eq!(versions, (None, None));
+            }
+        }
+    }
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn versions_that_need_quotes_are_quoted() {
+    let dir = ios_dir("quoted");
+    let path = dir.join("Shop.xcodeproj/project.pbxproj");
+    let mut project = XcodeProject::open(&path).unwrap();
+
+    project.set_versions("2.0.0-beta 1", "8").unwrap();
+    project.save().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    assert_eq!(content.matches("MARKETING_VERSION = \"2.0.0-beta 1\";").count(), 4);
+    let reopened = XcodeProject::open(&path).unwrap();
+    assert_eq!(reopened.app_target().unwrap().configuration("Release").unwrap().settings["MARKETING_VERSION"], "2.0.0-beta 1");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unchanged_versions_report_no_changes() {
+    let dir = ios_dir("unchanged");
+    let mut project = XcodeProject::open(&dir.join("Shop.xcodeproj/project.pbxproj")).unwrap();
+
+    // Only the configuration without versions gets them
+    assert_eq!(project.set_versions("1.2.0", "7").unwrap(), ["NotificationService (Release)"]);
+    assert!(project.set_versions("1.2.0", "7").unwrap().is_empty());
+    fs::remove_dir_all(dir).unwrap();
+}
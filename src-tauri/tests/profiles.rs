@@ -0,0 +1,110 @@
+use app_builder_lib::commands::profiles::{
+    android_output_path, apply_profile, find_profile, gradle_task, profile_bundle_id, profile_env,
+};
+use app_builder_lib::models::project::Project;
+use std::path::{Path, PathBuf};
+
+fn project() -> Project {
+    serde_json::from_value(serde_json::json!({
+        "id": "p1",
+        "name": "Demo",
+        "path": "/work/demo",
+        "ios": { "bundleId": "com.example.demo", "version": "1.0.0", "buildNumber": 4, "config": null },
+        "android": {
+            "bundleId": "com.example.demo",
+            "version": "1.0.0",
+            "versionCode": 4,
+            "buildCommand": "./gradlew bundleRelease",
+            "config": { "firebaseAppId": "1:android:prod", "distributionGroups": "qa" }
+        },
+        "credentials": { "iosId": "ios-prod", "androidId": "android-prod" },
+        "notifications": { "slack": { "webhookUrl": "https://hooks.example/prod", "enabled": true } },
+        "profiles": [
+            {
+                "name": "staging",
+                "bundleIdSuffix": ".staging",
+                "iosScheme": "Demo Staging",
+                "androidFlavor": "staging",
+                "envFile": ".env.staging",
+                "iosFirebaseAppId": "1:ios:staging",
+                "androidFirebaseAppId": "1:android:staging",
+                "androidCredentialId": "android-staging",
+                "notifications": { "discord": { "webhookUrl": "https://discord.example/qa", "enabled": true } }
+            },
+            { "name": "prod" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn profiles_are_found_by_name() {
+    let project = project();
+
+    assert_eq!(find_profile(&project, "staging").unwrap().bundle_id_suffix.as_deref(), Some(".staging"));
+    assert_eq!(find_profile(&project, "dev").unwrap_err(), "Project Demo has no build profile named dev");
+}
+
+#[test]
+fn applied_profiles_replace_only_what_they_set() {
+    let mut staging = project();
+    let profile = find_profile(&staging, "staging").unwrap();
+    apply_profile(&mut staging, &profile);
+
+    let ios = staging.ios.config.as_ref().unwrap();
+    assert_eq!(ios.scheme, "Demo Staging");
+    assert_eq!(ios.configuration, "Release");
+    assert_eq!(ios.firebase_app_id.as_deref(), Some("1:ios:staging"));
+    let android = staging.android.config.as_ref().unwrap();
+    assert_eq!(android.firebase_app_id.as_deref(), Some("1:android:staging"));
+    assert_eq!(android.distribution_groups.as_deref(), Some("qa"));
+    assert_eq!(staging.credentials.ios_id.as_deref(), Some("ios-prod"));
+    assert_eq!(staging.credentials.android_id.as_deref(), Some("android-staging"));
+    let notifications = staging.notifications.as_ref().unwrap();
+    assert!(notifications.slack.is_none() && notifications.discord.is_some());
+    // Stored files keep the project's own IDs and command
+    assert_eq!(staging.ios.bundle_id, "com.example.demo");
+    assert_eq!(staging.android.build_command.as_deref(), Some("./gradlew bundleRelease"));
+
+    let mut prod = project();
+    let profile = find_profile(&prod, "prod").unwrap();
+    apply_profile(&mut prod, &profile);
+    assert!(prod.ios.config.is_none());
+    assert_eq!(prod.credentials.android_id.as_deref(), Some("android-prod"));
+}
+
+#[test]
+fn bundle_ids_and_env_come_from_the_profile() {
+    let project = project();
+    let staging = find_profile(&project, "staging").unwrap();
+    let prod = find_profile(&project, "prod").unwrap();
+
+    assert_eq!(profile_bundle_id("com.example.demo", Some(&staging)), "com.example.demo.staging");
+    assert_eq!(profile_bundle_id("com.example.demo", Some(&prod)), "com.example.demo");
+    assert_eq!(profile_bundle_id("com.example.demo", None), "com.example.demo");
+
+    assert_eq!(profile_env(Some(&staging)), vec![("ENVFILE".to_string(), ".env.staging".to_string())]);
+    assert!(profile_env(Some(&prod)).is_empty());
+}
+
+#[test]
+fn flavors_pick_the_gradle_task_and_output() {
+    assert_eq!(gradle_task("aab", None), "bundleRelease");
+    assert_eq!(gradle_task("apk", None), "assembleRelease");
+    assert_eq!(gradle_task("aab", Some("staging")), "bundleStagingRelease");
+    assert_eq!(gradle_task("apk", Some("dev")), "assembleDevRelease");
+
+    let android = Path::new("/work/demo/android");
+    assert_eq!(
+        android_output_path(android, "aab", Some("staging")),
+        PathBuf::from("/work/demo/android/app/build/outputs/bundle/stagingRelease/app-staging-release.aab")
+    );
+    assert_eq!(
+        android_output_path(android, "apk", Some("staging")),
+        PathBuf::from("/work/demo/android/app/build/outputs/apk/staging/release/app-staging-release.apk")
+    );
+    assert_eq!(
+        android_output_path(android, "apk", None),
+        PathBuf::from("/work/demo/android/app/build/outputs/apk/release/app-release.apk")
+    );
+}
@@ -0,0 +1,95 @@
+use app_builder_lib::commands::provisioning::{match_profile, parse_profile, parse_signing_identities, ProvisioningProfile, SigningIdentity};
+use chrono::{DateTime, Utc};
+
+const PROFILE: &[u8] = include_bytes!("fixtures/development.mobileprovision");
+const CERTIFICATE_SHA1: &str = "69AB62E34618CF7873142CC18692C4AB97955BA2";
+
+fn at(date: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(date).unwrap().with_timezone(&Utc)
+}
+
+fn identities() -> Vec<SigningIdentity> {
+    vec![SigningIdentity { sha1: CERTIFICATE_SHA1.to_string(), name: "Apple Development: Jane Doe (XY98ZW76VU)".to_string() }]
+}
+
+fn development_profile() -> ProvisioningProfile {
+    parse_profile(PROFILE, &identities(), at("2026-10-18T00:00:00Z")).unwrap()
+}
+
+#[test]
+fn profiles_are_decoded_from_the_cms_envelope() {
+    let profile = development_profile();
+
+    assert_eq!(profile.name, "Example App Development");
+    assert_eq!(profile.uuid, "6f1c2e9a-3b4d-4e5f-8a7b-9c0d1e2f3a4b");
+    assert_eq!(profile.team_id, "AB12CD34EF");
+    assert_eq!(profile.team_name, "Example Inc");
+    assert_eq!(profile.bundle_id, "com.example.app");
+    assert_eq!(profile.profile_type, "development");
+    assert_eq!(profile.platforms, vec!["iOS"]);
+    assert_eq!(profile.expires, "2027-03-02T10:00:00+00:00");
+    assert!(!profile.expired);
+    assert_eq!(profile.device_count, 2);
+    assert_eq!(profile.entitlements["aps-environment"], "development");
+    assert_eq!(profile.entitlements["get-task-allow"], true);
+
+    assert_eq!(profile.certificates.len(), 1);
+    assert_eq!(profile.certificates[0].certificate.sha1.replace(':', ""), CERTIFICATE_SHA1);
+    assert!(profile.certificates[0].installed);
+}
+
+#[test]
+fn garbage_is_not_a_profile() {
+    assert!(parse_profile(b"<?xml version=\"1.0\"?><plist/>", &[], Utc::now()).is_err());
+}
+
+#[test]
+fn matching_profiles_are_picked_for_the_export_method() {
+    let profile = development_profile();
+
+    let check = match_profile(std::slice::from_ref(&profile), "com.example.app", "debugging", Some("AB12CD34EF"));
+    assert_eq!(check.profile.map(|p| p.uuid), Some(profile.uuid.clone()));
+    assert!(check.problems.is_empty(), "{:?}", check.problems);
+
+    let wildcard = ProvisioningProfile { bundle_id: "com.example.*".to_string(), uuid: "wildcard".to_string(), ..profile.clone() };
+    let check = match_profile(&[wildcard.clone(), profile.clone()], "com.example.app", "development", None);
+    assert_eq!(check.profile.map(|p| p.uuid), Some(profile.uuid.clone()));
+    let check = match_profile(&[wildcard], "com.example.other", "development", None);
+    assert_eq!(check.profile.map(|p| p.uuid), Some("wildcard".to_string()));
+}
+
+#[test]
+fn mismatched_profiles_explain_why() {
+    let profile = development_profile();
+
+    let check = match_profile(std::slice::from_ref(&profile), "com.example.other", "development", None);
+    assert_eq!(check.problems, vec!["No provisioning profile covers com.example.other"]);
+
+    let check = match_profile(std::slice::from_ref(&profile), "com.example.app", "app-store-connect", Some("ZZ99ZZ99ZZ"));
+    assert!(check.profile.is_none());
+    assert_eq!(
+        check.problems,
+        vec!["\"Example App Development\" is a development profile, app-store-connect export needs app-store, belongs to team AB12CD34EF, not ZZ99ZZ99ZZ"]
+    );
+
+    let expired = parse_profile(PROFILE, &[], at("2027-04-01T00:00:00Z")).unwrap();
+    let check = match_profile(&[expired], "com.example.app", "development", None);
+    assert_eq!(
+        check.problems,
+        vec!["\"Example App Development\" expired on 2027-03-02T10:00:00+00:00, none of its certificates is installed in the keychain"]
+    );
+}
+
+#[test]
+fn signing_identities_are_read_from_security_output() {
+    let output = "  1) 69AB62E34618CF7873142CC18692C4AB97955BA2 \"Apple Development: Jane Doe (XY98ZW76VU)\"\n  \
+                  2) 0123456789abcdef0123456789abcdef01234567 \"Apple Distribution: Example Inc (AB12CD34EF)\"\n     \
+                  2 valid identities found\n";
+
+    let identities = parse_signing_identities(output);
+
+    assert_eq!(identities.len(), 2);
+    assert_eq!(identities[0], self::identities()[0]);
+    assert_eq!(identities[1].sha1, "0123456789ABCDEF0123456789ABCDEF01234567");
+    assert_eq!(identities[1].name, "Apple Distribution: Example Inc (AB12CD34EF)");
+}
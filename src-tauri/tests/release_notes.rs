@@ -0,0 +1,64 @@
+use app_builder_lib::commands::release_notes::{render, Commit, DEFAULT_TEMPLATE};
+
+fn commits(subjects: &[&str]) -> Vec<Commit> {
+    subjects.iter().map(|s| Commit::parse(s)).collect()
+}
+
+#[test]
+fn conventional_subjects_are_parsed() {
+    let commit = Commit::parse("Feat(auth)!: drop the legacy login");
+    assert_eq!(commit.kind.as_deref(), Some("feat"));
+    assert_eq!(commit.scope.as_deref(), Some("auth"));
+    assert!(commit.breaking);
+    assert_eq!(commit.description, "drop the legacy login");
+
+    let plain = Commit::parse("Merge the login screen: part 2");
+    assert_eq!((plain.kind, plain.scope, plain.breaking), (None, None, false));
+    assert_eq!(plain.description, "Merge the login screen: part 2");
+}
+
+#[test]
+fn changes_are_grouped_in_a_fixed_order() {
+    let commits = commits(&[
+        "chore: bump dependencies",
+        "fix(ios): crash on launch",
+        "Update README",
+        "feat: dark mode",
+        "perf: faster image cache",
+        "ci: cache gradle",
+        "feat(android)!: require Android 8",
+    ]);
+
+    let notes = render(DEFAULT_TEMPLATE, &commits, "android", "1.4.0", 57);
+
+    assert_eq!(
+        notes,
+        "✨ Features\n- dark mode\n- android: require Android 8 (breaking)\n\n\
+         🐛 Bug Fixes\n- ios: crash on launch\n\n\
+         ⚡ Performance\n- faster image cache\n\n\
+         🧹 Chores\n- bump dependencies\n- cache gradle\n\n\
+         📝 Other Changes\n- Update README"
+    );
+}
+
+#[test]
+fn empty_groups_are_left_out() {
+    let notes = render("{{features}}\n\n\n{{fixes}}\n{{other}}", &commits(&["fix: typo"]), "ios", "1.4.0", 57);
+    assert_eq!(notes, "🐛 Bug Fixes\n- typo");
+}
+
+#[test]
+fn placeholders_are_substituted() {
+    let template = "{{platform}} {{version}} ({{build}})\n\n{{commits}}\n\n{{unknown}}";
+    let notes = render(template, &commits(&["feat: dark mode", "Update README"]), "ios", "1.4.0", 57);
+    assert_eq!(notes, "iOS 1.4.0 (57)\n\n- dark mode\n- Update README\n\n{{unknown}}");
+}
+
+#[test]
+fn placeholders_in_commit_subjects_are_not_expanded() {
+    let commits = commits(&["docs: explain {{version}} in the template", "fix: escape {{fixes}}"]);
+
+    let notes = render("{{version}}\n{{changes}}", &commits, "android", "1.4.0", 57);
+
+    assert_eq!(notes, "1.4.0\n🐛 Bug Fixes\n- escape {{fixes}}\n\n🧹 Chores\n- explain {{version}} in the template");
+}
@@ -0,0 +1,94 @@
+use app_builder_lib::commands::signing::{fingerprint, injected_signing_args, signer_certificate};
+use app_builder_lib::models::project::AndroidSigningConfig;
+use std::io::Write;
+use std::path::PathBuf;
+
+const CERTIFICATE: &[u8] = include_bytes!("fixtures/signer.der");
+const SHA256: &str = "3D:02:FF:8C:F4:21:BD:97:A2:DE:E9:B0:5E:BF:D0:7F:B8:1E:AB:9F:7A:CE:EB:99:1C:45:4B:C9:E8:50:6A:B0";
+
+fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, contents) in entries {
+        writer.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(contents).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+fn artifact(name: &str, bytes: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("signing-{}-{}.apk", name, std::process::id()));
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn length_prefixed(value: &[u8]) -> Vec<u8> {
+    let mut bytes = (value.len() as u32).to_le_bytes().to_vec();
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+/// Insert an APK Signing Block holding one v2 signer before the central directory
+fn with_v2_signing_block(mut apk: Vec<u8>) -> Vec<u8> {
+    let signed_data = [length_prefixed(&[]), length_prefixed(&length_prefixed(CERTIFICATE))].concat();
+    let signer = length_prefixed(&[length_prefixed(&signed_data), length_prefixed(&[]), length_prefixed(&[])].concat());
+    let value = [0x7109_871au32.to_le_bytes().to_vec(), length_prefixed(&signer)].concat();
+    let pair = [(value.len() as u64).to_le_bytes().to_vec(), value].concat();
+    let size = (pair.len() + 8 + 16) as u64;
+    let block = [size.to_le_bytes().to_vec(), pair, size.to_le_bytes().to_vec(), b"APK Sig Block 42".to_vec()].concat();
+
+    let eocd = apk.len() - 22;
+    let central_directory = u32::from_le_bytes(apk[eocd + 16..eocd + 20].try_into().unwrap()) as usize;
+    apk[eocd + 16..eocd + 20].copy_from_slice(&((central_directory + block.len()) as u32).to_le_bytes());
+    apk.splice(central_directory..central_directory, block);
+    apk
+}
+
+#[test]
+fn fingerprints_match_keytool() {
+    assert_eq!(fingerprint(CERTIFICATE), SHA256);
+}
+
+#[test]
+fn bundles_report_the_jar_signing_certificate() {
+    let bundle = zip(&[
+        ("base/manifest/AndroidManifest.xml", b"manifest"),
+        ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\r\n"),
+        ("META-INF/UPLOAD.RSA", include_bytes!("fixtures/signer.p7b")),
+    ]);
+
+    let signer = signer_certificate(&artifact("bundle", &bundle)).unwrap().unwrap();
+
+    assert_eq!(signer.scheme, "JAR signing");
+    assert_eq!(signer.sha256, SHA256);
+}
+
+#[test]
+fn apks_report_the_v2_signing_certificate() {
+    let apk = with_v2_signing_block(zip(&[("AndroidManifest.xml", b"manifest"), ("classes.dex", b"dex")]));
+
+    let signer = signer_certificate(&artifact("v2", &apk)).unwrap().unwrap();
+
+    assert_eq!(signer.scheme, "APK Signature Scheme v2");
+    assert_eq!(signer.sha256, SHA256);
+}
+
+#[test]
+fn unsigned_artifacts_have_no_signer() {
+    let apk = zip(&[("AndroidManifest.xml", b"manifest"), ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\r\n")]);
+
+    assert_eq!(signer_certificate(&artifact("unsigned", &apk)).unwrap(), None);
+}
+
+#[test]
+fn passwords_are_left_to_the_shell_environment() {
+    let args = injected_signing_args(&AndroidSigningConfig {
+        id: "signing-1".to_string(),
+        keystore_path: "/Users/me/Keys/it's-release.jks".to_string(),
+        key_alias: "upload".to_string(),
+    });
+
+    assert!(args.contains(r"-Pandroid.injected.signing.store.file='/Users/me/Keys/it'\''s-release.jks'"), "{}", args);
+    assert!(args.contains("-Pandroid.injected.signing.store.password=\"$APP_BUILDER_STORE_PASSWORD\""), "{}", args);
+    assert!(args.contains("-Pandroid.injected.signing.key.alias='upload'"), "{}", args);
+    assert!(args.contains("-Pandroid.injected.signing.key.password=\"$APP_BUILDER_KEY_PASSWORD\""), "{}", args);
+}
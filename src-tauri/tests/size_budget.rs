@@ -0,0 +1,59 @@
+mod common;
+
+use app_builder_lib::commands::analyzer::analyze_artifact;
+use app_builder_lib::commands::size_budget::check_budgets;
+use app_builder_lib::models::project::SizeBudgets;
+use common::TempFile;
+
+/// An APK with `code` bytes of DEX, a 3000 byte JS bundle, 2000 bytes of assets and 4000 of native libraries
+fn release(name: &str, code: usize) -> TempFile {
+    TempFile::zip(
+        &format!("size-budget-{}.apk", name),
+        &[
+            ("classes.dex", &vec![1; code]),
+            ("assets/index.android.bundle", &[2; 3000]),
+            ("assets/fonts/Inter.ttf", &[3; 2000]),
+            ("lib/arm64-v8a/libhermes.so", &[4; 4000]),
+        ],
+    )
+}
+
+#[test]
+fn js_bundles_are_measured() {
+    let report = analyze_artifact(&release("bundle", 1000)).unwrap();
+    assert_eq!(report.js_bundle_size, 3000);
+}
+
+#[test]
+fn every_exceeded_budget_is_reported() {
+    let report = analyze_artifact(&release("exceeded", 1000)).unwrap();
+    let budgets = SizeBudgets {
+        total: Some(report.total_size),
+        native_libraries: Some(3000),
+        js_bundle: Some(2048),
+        // The bundle counts as an asset as well
+        assets: Some(6000),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        check_budgets(&report, &budgets, None),
+        vec!["Native libraries is 3.91 KB, over the 2.93 KB budget", "JS bundle is 2.93 KB, over the 2.00 KB budget"]
+    );
+    assert!(check_budgets(&report, &SizeBudgets::default(), None).is_empty());
+}
+
+#[test]
+fn growth_is_limited_against_the_previous_build() {
+    let previous = analyze_artifact(&release("previous", 1000)).unwrap();
+    let current = analyze_artifact(&release("current", 3000)).unwrap();
+    let growth = current.total_size - previous.total_size;
+    let budgets = |limit| SizeBudgets { max_increase: Some(limit), ..Default::default() };
+
+    assert!(check_budgets(&current, &budgets(growth), Some(&previous)).is_empty());
+    let violations = check_budgets(&current, &budgets(growth - 1), Some(&previous));
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].starts_with("Total size grew by "), "{}", violations[0]);
+    // Without a previous build there is nothing to compare with
+    assert!(check_budgets(&current, &budgets(0), None).is_empty());
+}
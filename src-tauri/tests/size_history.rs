@@ -0,0 +1,111 @@
+use app_builder_lib::commands::analyzer::{AppSizeReport, SizeBreakdown};
+use app_builder_lib::commands::size_history::{load_build_size, save_build_size, size_trends, BuildSize, CategoryTrend};
+use rusqlite::Connection;
+
+fn build(id: &str, platform: &str, file_type: &str, timestamp: u64, categories: &[(&str, u64)]) -> BuildSize {
+    BuildSize {
+        build_id: id.to_string(),
+        platform: platform.to_string(),
+        version: "1.0.0".to_string(),
+        build_number: timestamp as u32,
+        profile: None,
+        timestamp,
+        file_type: file_type.to_string(),
+        total_size: categories.iter().map(|(_, size)| size).sum(),
+        js_bundle_size: 0,
+        categories: categories.iter().map(|(name, size)| (name.to_string(), *size)).collect(),
+    }
+}
+
+/// The size tables as created by `init_db`, with a check that lets a test make an insert fail
+fn database() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE build_sizes (
+            build_id TEXT PRIMARY KEY,
+            file_type TEXT NOT NULL,
+            total_size INTEGER NOT NULL,
+            js_bundle_size INTEGER NOT NULL,
+            report TEXT NOT NULL
+        );
+        CREATE TABLE build_size_categories (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            category TEXT NOT NULL,
+            size INTEGER NOT NULL CHECK (size > 0)
+        );",
+    )
+    .unwrap();
+    conn
+}
+
+fn report(categories: &[(&str, u64)]) -> AppSizeReport {
+    AppSizeReport {
+        total_size: categories.iter().map(|(_, size)| size).sum(),
+        breakdown: categories.iter().map(|(name, size)| SizeBreakdown { name: name.to_string(), size: *size, percentage: 0.0 }).collect(),
+        file_type: "AAB".to_string(),
+        supports_16k_page_size: None,
+        large_files: Vec::new(),
+        js_bundle_size: 0,
+        manifest: None,
+        manifest_error: None,
+        ipa: None,
+        ipa_error: None,
+    }
+}
+
+fn category_sizes(conn: &Connection) -> Vec<(String, u64)> {
+    let mut stmt = conn.prepare("SELECT category, size FROM build_size_categories ORDER BY id").unwrap();
+    stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap().map(Result::unwrap).collect()
+}
+
+#[test]
+fn saving_replaces_the_size_of_a_build() {
+    let conn = database();
+    save_build_size(&conn, "b1", &report(&[("Assets", 40), ("Code (DEX/JS)", 100)])).unwrap();
+    save_build_size(&conn, "b1", &report(&[("Code (DEX/JS)", 150)])).unwrap();
+
+    assert_eq!(load_build_size(&conn, "b1").unwrap().unwrap().total_size, 150);
+    assert_eq!(category_sizes(&conn), [("Code (DEX/JS)".to_string(), 150)]);
+}
+
+#[test]
+fn failed_saves_keep_the_previous_size() {
+    let conn = database();
+    save_build_size(&conn, "b1", &report(&[("Code (DEX/JS)", 100)])).unwrap();
+
+    assert!(save_build_size(&conn, "b1", &report(&[("Code (DEX/JS)", 150), ("Assets", 0)])).is_err());
+
+    assert_eq!(load_build_size(&conn, "b1").unwrap().unwrap().total_size, 100);
+    assert_eq!(category_sizes(&conn), [("Code (DEX/JS)".to_string(), 100)]);
+}
+
+#[test]
+fn trends_are_split_by_platform_and_format() {
+    let trends = size_trends(vec![
+        build("a1", "android", "AAB", 1, &[("Code (DEX/JS)", 100)]),
+        build("i1", "ios", "IPA", 2, &[("Code (DEX/JS)", 300)]),
+        build("a2", "android", "APK", 3, &[("Code (DEX/JS)", 150)]),
+        build("a3", "android", "AAB", 4, &[("Code (DEX/JS)", 120)]),
+    ]);
+
+    let keys: Vec<_> = trends.iter().map(|t| (t.platform.as_str(), t.file_type.as_str())).collect();
+    assert_eq!(keys, vec![("android", "AAB"), ("ios", "IPA"), ("android", "APK")]);
+    let builds: Vec<_> = trends[0].builds.iter().map(|b| (b.build_id.as_str(), b.total_size)).collect();
+    assert_eq!(builds, vec![("a1", 100), ("a3", 120)]);
+}
+
+#[test]
+fn category_sizes_line_up_with_the_builds() {
+    let trends = size_trends(vec![
+        build("a1", "android", "AAB", 1, &[("Native Libraries", 500), ("Code (DEX/JS)", 100)]),
+        build("a2", "android", "AAB", 2, &[("Code (DEX/JS)", 150), ("Assets", 40)]),
+    ]);
+
+    let trend = |name: &str, sizes: &[u64]| CategoryTrend { name: name.to_string(), sizes: sizes.to_vec() };
+    // In the order of the report's breakdown; Resources and Others are empty in every build
+    assert_eq!(
+        trends[0].categories,
+        vec![trend("Code (DEX/JS)", &[100, 150]), trend("Assets", &[0, 40]), trend("Native Libraries", &[500, 0])]
+    );
+}
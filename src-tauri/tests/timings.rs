@@ -0,0 +1,68 @@
+use app_builder_lib::commands::timings::{load_build_timing, save_build_timing};
+use app_builder_lib::models::project::{BuildTiming, PhaseTiming};
+use rusqlite::Connection;
+
+/// The timing tables as created by `init_db`, with a check that lets a test make an insert fail
+fn database() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE build_timings (
+            build_id TEXT PRIMARY KEY,
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL
+        );
+        CREATE TABLE build_phase_timings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            build_id TEXT NOT NULL,
+            phase TEXT NOT NULL,
+            status TEXT NOT NULL CHECK (status IN ('success', 'failed', 'skipped')),
+            started_at INTEGER NOT NULL,
+            finished_at INTEGER NOT NULL,
+            duration_ms INTEGER NOT NULL
+        );",
+    )
+    .unwrap();
+    conn
+}
+
+fn timing(finished_at: u64, phases: &[(&str, &str)]) -> BuildTiming {
+    BuildTiming {
+        started_at: 0,
+        finished_at,
+        duration_ms: finished_at,
+        phases: phases
+            .iter()
+            .map(|(phase, status)| PhaseTiming {
+                phase: phase.to_string(),
+                status: status.to_string(),
+                started_at: 0,
+                finished_at,
+                duration_ms: finished_at,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn saving_replaces_the_timing_of_a_build() {
+    let conn = database();
+    save_build_timing(&conn, "b1", &timing(100, &[("prepare", "success"), ("archive", "failed")])).unwrap();
+    save_build_timing(&conn, "b1", &timing(200, &[("prepare", "success")])).unwrap();
+
+    let saved = load_build_timing(&conn, "b1").unwrap().unwrap();
+    assert_eq!(saved.duration_ms, 200);
+    assert_eq!(saved.phases.iter().map(|p| p.phase.as_str()).collect::<Vec<_>>(), ["prepare"]);
+}
+
+#[test]
+fn failed_saves_keep_the_previous_timing() {
+    let conn = database();
+    save_build_timing(&conn, "b1", &timing(100, &[("prepare", "success")])).unwrap();
+
+    assert!(save_build_timing(&conn, "b1", &timing(200, &[("prepare", "success"), ("archive", "running")])).is_err());
+
+    let saved = load_build_timing(&conn, "b1").unwrap().unwrap();
+    assert_eq!(saved.duration_ms, 100);
+    assert_eq!(saved.phases.len(), 1);
+}
@@ -0,0 +1,108 @@
+use app_builder_lib::commands::versioning::{apply_bump, bump_version, date_build_number, VersionSnapshot};
+use app_builder_lib::models::project::{BuildNumberStrategy, Project, VersionBump, VersionBumpSettings};
+use chrono::NaiveDate;
+
+fn project() -> Project {
+    serde_json::from_value(serde_json::json!({
+        "id": "p1",
+        "name": "Demo",
+        "path": "/work/demo",
+        "ios": { "bundleId": "com.example.demo", "version": "1.2.3", "buildNumber": 7, "config": null },
+        "android": { "bundleId": "com.example.demo", "version": "1.2.0", "versionCode": 12, "config": null },
+        "credentials": { "iosId": null, "androidId": null }
+    }))
+    .unwrap()
+}
+
+fn day(date: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap()
+}
+
+fn settings(version: VersionBump, build_number: BuildNumberStrategy) -> VersionBumpSettings {
+    VersionBumpSettings { version, build_number }
+}
+
+#[test]
+fn versions_bump_each_component() {
+    assert_eq!(bump_version("1.2.3", VersionBump::Major).unwrap(), "2.0.0");
+    assert_eq!(bump_version("1.2.3", VersionBump::Minor).unwrap(), "1.3.0");
+    assert_eq!(bump_version("1.2.3", VersionBump::Patch).unwrap(), "1.2.4");
+    assert_eq!(bump_version("1.2.3-beta.1", VersionBump::None).unwrap(), "1.2.3-beta.1");
+}
+
+#[test]
+fn short_and_prerelease_versions_are_normalized() {
+    assert_eq!(bump_version("2", VersionBump::Patch).unwrap(), "2.0.1");
+    assert_eq!(bump_version("1.4", VersionBump::Minor).unwrap(), "1.5.0");
+    assert_eq!(bump_version(" 1.2.3-beta.1+42 ", VersionBump::Patch).unwrap(), "1.2.4");
+}
+
+#[test]
+fn invalid_versions_are_rejected() {
+    assert_eq!(bump_version("1.2.3.4", VersionBump::Patch).unwrap_err(), "Version 1.2.3.4 has more than three components");
+    assert_eq!(bump_version("v1.2", VersionBump::Patch).unwrap_err(), "Version v1.2 is not a major.minor.patch version");
+}
+
+#[test]
+fn date_build_numbers_count_up_within_a_day() {
+    let today = day("2026-10-18");
+    assert_eq!(date_build_number(57, today).unwrap(), 26101801);
+    assert_eq!(date_build_number(26101801, today).unwrap(), 26101802);
+    // A number from an earlier day restarts the counter
+    assert_eq!(date_build_number(26101705, today).unwrap(), 26101801);
+}
+
+#[test]
+fn date_build_numbers_never_go_down() {
+    let today = day("2026-10-18");
+    assert!(date_build_number(26101899, today).is_err());
+    assert!(date_build_number(26101901, today).is_err());
+}
+
+#[test]
+fn bumps_only_touch_the_platform_being_built() {
+    let mut project = project();
+    let changed = apply_bump(&mut project, "ios", &settings(VersionBump::Minor, BuildNumberStrategy::Increment), day("2026-10-18")).unwrap();
+
+    assert!(changed);
+    assert_eq!((project.ios.version.as_str(), project.ios.build_number), ("1.3.0", 8));
+    assert_eq!((project.android.version.as_str(), project.android.version_code), ("1.2.0", 12));
+}
+
+#[test]
+fn shared_build_numbers_are_set_on_both_platforms() {
+    let mut project = project();
+    apply_bump(&mut project, "ios", &settings(VersionBump::None, BuildNumberStrategy::Shared), day("2026-10-18")).unwrap();
+    assert_eq!((project.ios.build_number, project.android.version_code), (13, 13));
+
+    let mut project = self::project();
+    apply_bump(&mut project, "android", &settings(VersionBump::None, BuildNumberStrategy::Date), day("2026-10-18")).unwrap();
+    assert_eq!((project.ios.build_number, project.android.version_code), (7, 26101801));
+}
+
+#[test]
+fn bumps_that_change_nothing_are_reported() {
+    let mut project = project();
+    let changed = apply_bump(&mut project, "android", &VersionBumpSettings::default(), day("2026-10-18")).unwrap();
+    assert!(!changed);
+}
+
+#[test]
+fn rollbacks_keep_values_changed_by_another_build() {
+    let before = project();
+    let mut after = project();
+    apply_bump(&mut after, "ios", &settings(VersionBump::Patch, BuildNumberStrategy::Shared), day("2026-10-18")).unwrap();
+    let snapshot = VersionSnapshot::new(&before, &after);
+
+    // An Android build running alongside bumped the shared build number again
+    let mut current = after.clone();
+    apply_bump(&mut current, "android", &settings(VersionBump::None, BuildNumberStrategy::Shared), day("2026-10-18")).unwrap();
+    assert!(snapshot.revert(&mut current));
+    assert_eq!((current.ios.version.as_str(), current.ios.build_number), ("1.2.3", 14));
+    assert_eq!(current.android.version_code, 14);
+
+    // Nothing else touched the versions: everything goes back
+    let mut current = after.clone();
+    assert!(snapshot.revert(&mut current));
+    assert_eq!((current.ios.build_number, current.android.version_code), (7, 12));
+}
@@ -1,5 +1,5 @@
 import React from 'react';
-import { KeyIcon } from '../Icons';
+import { FolderIcon, KeyIcon } from '../Icons';
 import { inputStyle, labelStyle, sectionStyle } from './AddProject.styles';
 import { Credential } from '../../types/credential';
 
@@ -13,8 +13,29 @@ interface AndroidBuildSettingsProps {
   setFirebaseAppId: (val: string) => void;
   distributionGroups: string;
   setDistributionGroups: (val: string) => void;
+  testers: string;
+  setTesters: (val: string) => void;
+  playTrack: PlayTrack;
+  setPlayTrack: (val: PlayTrack) => void;
+  playLocales: string;
+  setPlayLocales: (val: string) => void;
+  playReleaseStatus: PlayReleaseStatus;
+  setPlayReleaseStatus: (val: PlayReleaseStatus) => void;
+  keystorePath: string;
+  setKeystorePath: (val: string) => void;
+  keyAlias: string;
+  setKeyAlias: (val: string) => void;
+  storePassword: string;
+  setStorePassword: (val: string) => void;
+  keyPassword: string;
+  setKeyPassword: (val: string) => void;
+  hasSigningPasswords: boolean;
+  onBrowseKeystore: () => void;
 }
 
+type PlayTrack = 'internal' | 'alpha' | 'beta' | 'production';
+type PlayReleaseStatus = 'completed' | 'draft';
+
 export const AndroidBuildSettings: React.FC<AndroidBuildSettingsProps> = ({
   selectedCredentialId,
   setSelectedCredentialId,
@@ -25,6 +46,24 @@ export const AndroidBuildSettings: React.FC<AndroidBuildSettingsProps> = ({
   setFirebaseAppId,
   distributionGroups,
   setDistributionGroups,
+  testers,
+  setTesters,
+  playTrack,
+  setPlayTrack,
+  playLocales,
+  setPlayLocales,
+  playReleaseStatus,
+  setPlayReleaseStatus,
+  keystorePath,
+  setKeystorePath,
+  keyAlias,
+  setKeyAlias,
+  storePassword,
+  setStorePassword,
+  keyPassword,
+  setKeyPassword,
+  hasSigningPasswords,
+  onBrowseKeystore,
 }) => {
   return (
     <div style={{ marginBottom: 'var(--spacing-md)' }}>
@@ -145,6 +184,209 @@ export const AndroidBuildSettings: React.FC<AndroidBuildSettingsProps> = ({
               onChange={(e) => setDistributionGroups(e.target.value)}
             />
           </div>
+
+          <div style={{ marginTop: 'var(--spacing-sm)' }}>
+            <label style={labelStyle}>
+              Testers
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (comma-separated emails)
+              </span>
+            </label>
+            <input
+              type="text"
+              style={inputStyle}
+              placeholder="qa@example.com, pm@example.com"
+              value={testers}
+              onChange={(e) => setTesters(e.target.value)}
+            />
+          </div>
+        </div>
+
+        {/* Google Play Section */}
+        <div
+          style={{
+            marginTop: 'var(--spacing-md)',
+            paddingTop: 'var(--spacing-md)',
+            borderTop: '1px solid var(--color-border)',
+          }}
+        >
+          <p
+            style={{
+              fontSize: '13px',
+              fontWeight: 600,
+              marginBottom: 'var(--spacing-sm)',
+              color: 'var(--color-text)',
+            }}
+          >
+            ▶️ Google Play
+          </p>
+
+          <div style={{ display: 'flex', gap: 'var(--spacing-sm)', marginBottom: 'var(--spacing-sm)' }}>
+            <div style={{ flex: 1 }}>
+              <label style={labelStyle}>Track</label>
+              <select
+                style={{ ...inputStyle, cursor: 'pointer' }}
+                value={playTrack}
+                onChange={(e) => setPlayTrack(e.target.value as PlayTrack)}
+              >
+                <option value="internal">Internal testing</option>
+                <option value="alpha">Closed testing (alpha)</option>
+                <option value="beta">Open testing (beta)</option>
+                <option value="production">Production</option>
+              </select>
+            </div>
+            <div style={{ flex: 1 }}>
+              <label style={labelStyle}>Release Status</label>
+              <select
+                style={{ ...inputStyle, cursor: 'pointer' }}
+                value={playReleaseStatus}
+                onChange={(e) => setPlayReleaseStatus(e.target.value as PlayReleaseStatus)}
+              >
+                <option value="completed">Completed</option>
+                <option value="draft">Draft (app not yet published)</option>
+              </select>
+            </div>
+          </div>
+
+          <div>
+            <label style={labelStyle}>
+              Release Note Locales
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (comma-separated, defaults to en-US)
+              </span>
+            </label>
+            <input
+              type="text"
+              style={inputStyle}
+              placeholder="en-US, fr-FR"
+              value={playLocales}
+              onChange={(e) => setPlayLocales(e.target.value)}
+            />
+          </div>
+        </div>
+
+        {/* Release Signing Section */}
+        <div
+          style={{
+            marginTop: 'var(--spacing-md)',
+            paddingTop: 'var(--spacing-md)',
+            borderTop: '1px solid var(--color-border)',
+          }}
+        >
+          <p
+            style={{
+              fontSize: '13px',
+              fontWeight: 600,
+              marginBottom: 'var(--spacing-sm)',
+              color: 'var(--color-text)',
+            }}
+          >
+            🔏 Release Signing
+          </p>
+
+          <div style={{ marginBottom: 'var(--spacing-sm)' }}>
+            <label style={labelStyle}>
+              Keystore
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (optional, overrides the signingConfig in build.gradle)
+              </span>
+            </label>
+            <div style={{ display: 'flex', gap: 'var(--spacing-sm)' }}>
+              <input
+                type="text"
+                style={{
+                  ...inputStyle,
+                  overflow: 'hidden',
+                  textOverflow: 'ellipsis',
+                }}
+                placeholder="/Users/me/keys/release.jks"
+                value={keystorePath}
+                onChange={(e) => setKeystorePath(e.target.value)}
+              />
+              <button
+                type="button"
+                className="btn btn-secondary"
+                onClick={onBrowseKeystore}
+                style={{ flexShrink: 0, gap: '6px' }}
+              >
+                <FolderIcon size={16} />
+                <span>Browse</span>
+              </button>
+            </div>
+          </div>
+
+          {keystorePath && (
+            <>
+              <div style={{ marginBottom: 'var(--spacing-sm)' }}>
+                <label style={labelStyle}>Key Alias</label>
+                <input
+                  type="text"
+                  style={inputStyle}
+                  placeholder="upload"
+                  value={keyAlias}
+                  onChange={(e) => setKeyAlias(e.target.value)}
+                  required
+                />
+              </div>
+
+              <div style={{ display: 'flex', gap: 'var(--spacing-sm)' }}>
+                <div style={{ flex: 1 }}>
+                  <label style={labelStyle}>Store Password</label>
+                  <input
+                    type="password"
+                    style={inputStyle}
+                    placeholder={hasSigningPasswords ? 'Saved in Keychain' : ''}
+                    value={storePassword}
+                    onChange={(e) => setStorePassword(e.target.value)}
+                    required={!hasSigningPasswords}
+                  />
+                </div>
+                <div style={{ flex: 1 }}>
+                  <label style={labelStyle}>
+                    Key Password
+                    <span
+                      style={{
+                        color: 'var(--color-text-secondary)',
+                        fontWeight: 400,
+                        marginLeft: '4px',
+                        fontSize: '12px',
+                      }}
+                    >
+                      (if different)
+                    </span>
+                  </label>
+                  <input
+                    type="password"
+                    style={inputStyle}
+                    placeholder={hasSigningPasswords ? 'Saved in Keychain' : ''}
+                    value={keyPassword}
+                    onChange={(e) => setKeyPassword(e.target.value)}
+                  />
+                </div>
+              </div>
+            </>
+          )}
         </div>
 
         {credentials.length === 0 && (
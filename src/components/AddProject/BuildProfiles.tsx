@@ -0,0 +1,212 @@
+import React from 'react';
+import { inputStyle, labelStyle, sectionStyle } from './AddProject.styles';
+import { PlusIcon, TrashIcon } from '../Icons';
+import { BuildProfile } from '../../types/project';
+import { Credential } from '../../types/credential';
+
+interface BuildProfilesProps {
+  profiles: BuildProfile[];
+  setProfiles: (val: BuildProfile[]) => void;
+  iosCredentials: Credential[];
+  androidCredentials: Credential[];
+  telegramBotToken: string;
+}
+
+type TextField = Exclude<keyof BuildProfile, 'name' | 'notifications'>;
+
+const TEXT_FIELDS: { key: TextField; label: string; placeholder: string }[] = [
+  { key: 'bundleIdSuffix', label: 'Bundle ID Suffix', placeholder: '.staging' },
+  { key: 'envFile', label: 'Env File', placeholder: '.env.staging' },
+  { key: 'iosScheme', label: 'iOS Scheme', placeholder: 'MyApp Staging' },
+  { key: 'iosConfiguration', label: 'iOS Configuration', placeholder: 'Release.Staging' },
+  { key: 'androidFlavor', label: 'Gradle Flavor', placeholder: 'staging' },
+  {
+    key: 'androidBuildCommand',
+    label: 'Android Build Command',
+    placeholder: './gradlew bundleStagingRelease',
+  },
+  { key: 'iosFirebaseAppId', label: 'iOS Firebase App ID', placeholder: '1:1234567890:ios:abc123' },
+  {
+    key: 'androidFirebaseAppId',
+    label: 'Android Firebase App ID',
+    placeholder: '1:1234567890:android:abc123',
+  },
+];
+
+// Once a profile has a target of its own, the project's targets no longer apply to it
+const notificationPlaceholder = (profile: BuildProfile) =>
+  profile.notifications ? 'Not notified' : 'Project default';
+
+export const BuildProfiles: React.FC<BuildProfilesProps> = ({
+  profiles,
+  setProfiles,
+  iosCredentials,
+  androidCredentials,
+  telegramBotToken,
+}) => {
+  const update = (index: number, changes: Partial<BuildProfile>) =>
+    setProfiles(profiles.map((p, i) => (i === index ? { ...p, ...changes } : p)));
+
+  const addProfile = () => {
+    const used = new Set(profiles.map((p) => p.name));
+    const name = ['dev', 'staging', 'prod'].find((n) => !used.has(n)) || '';
+    setProfiles([...profiles, { name }]);
+  };
+
+  // Leaving every webhook empty keeps the project's notification targets
+  const setNotification = (
+    index: number,
+    target: 'slack' | 'discord' | 'telegram',
+    value: string,
+  ) => {
+    const current = profiles[index].notifications || {};
+    const next = { ...current };
+    if (!value) {
+      delete next[target];
+    } else if (target === 'telegram') {
+      next.telegram = { botToken: telegramBotToken, chatId: value, enabled: true };
+    } else {
+      next[target] = { webhookUrl: value, enabled: true };
+    }
+    update(index, { notifications: Object.keys(next).length > 0 ? next : undefined });
+  };
+
+  return (
+    <div style={{ marginBottom: 'var(--spacing-md)' }}>
+      <p
+        style={{
+          fontSize: '13px',
+          fontWeight: 600,
+          marginBottom: 'var(--spacing-sm)',
+          color: 'var(--color-text)',
+        }}
+      >
+        🎛️ Build Profiles
+      </p>
+      <div
+        style={{ ...sectionStyle, display: 'flex', flexDirection: 'column', gap: 'var(--spacing-md)' }}
+      >
+        <span style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>
+          Variants such as dev, staging and prod. Empty fields use the project's settings.
+        </span>
+        {profiles.map((profile, index) => (
+          <div
+            key={index}
+            style={{
+              borderTop: index > 0 ? '1px solid var(--color-border)' : 'none',
+              paddingTop: index > 0 ? 'var(--spacing-md)' : 0,
+            }}
+          >
+            <div style={{ display: 'flex', gap: 'var(--spacing-sm)', alignItems: 'center' }}>
+              <input
+                style={{ ...inputStyle, fontWeight: 600 }}
+                placeholder="Profile name"
+                value={profile.name}
+                onChange={(e) => update(index, { name: e.target.value })}
+              />
+              <button
+                type="button"
+                className="btn btn-ghost"
+                title="Remove profile"
+                onClick={() => setProfiles(profiles.filter((_, i) => i !== index))}
+                style={{ color: 'var(--color-error)' }}
+              >
+                <TrashIcon size={16} />
+              </button>
+            </div>
+            <div
+              style={{
+                display: 'grid',
+                gridTemplateColumns: '1fr 1fr',
+                columnGap: 'var(--spacing-md)',
+              }}
+            >
+              {TEXT_FIELDS.map(({ key, label, placeholder }) => (
+                <div key={key}>
+                  <label style={labelStyle}>{label}</label>
+                  <input
+                    style={inputStyle}
+                    placeholder={placeholder}
+                    value={profile[key] || ''}
+                    onChange={(e) => update(index, { [key]: e.target.value || undefined })}
+                  />
+                </div>
+              ))}
+              <div>
+                <label style={labelStyle}>iOS Credential</label>
+                <select
+                  style={{ ...inputStyle, cursor: 'pointer' }}
+                  value={profile.iosCredentialId || ''}
+                  onChange={(e) => update(index, { iosCredentialId: e.target.value || undefined })}
+                >
+                  <option value="">Project default</option>
+                  {iosCredentials.map((c) => (
+                    <option key={c.id} value={c.id}>
+                      {c.name}
+                    </option>
+                  ))}
+                </select>
+              </div>
+              <div>
+                <label style={labelStyle}>Android Credential</label>
+                <select
+                  style={{ ...inputStyle, cursor: 'pointer' }}
+                  value={profile.androidCredentialId || ''}
+                  onChange={(e) =>
+                    update(index, { androidCredentialId: e.target.value || undefined })
+                  }
+                >
+                  <option value="">Project default</option>
+                  {androidCredentials.map((c) => (
+                    <option key={c.id} value={c.id}>
+                      {c.name}
+                    </option>
+                  ))}
+                </select>
+              </div>
+              <div>
+                <label style={labelStyle}>Slack Webhook</label>
+                <input
+                  style={inputStyle}
+                  placeholder={notificationPlaceholder(profile)}
+                  value={profile.notifications?.slack?.webhookUrl || ''}
+                  onChange={(e) => setNotification(index, 'slack', e.target.value)}
+                />
+              </div>
+              <div>
+                <label style={labelStyle}>Discord Webhook</label>
+                <input
+                  style={inputStyle}
+                  placeholder={notificationPlaceholder(profile)}
+                  value={profile.notifications?.discord?.webhookUrl || ''}
+                  onChange={(e) => setNotification(index, 'discord', e.target.value)}
+                />
+              </div>
+              <div>
+                <label style={labelStyle}>Telegram Chat ID</label>
+                <input
+                  style={inputStyle}
+                  placeholder={
+                    telegramBotToken ? notificationPlaceholder(profile) : 'Set a bot token first'
+                  }
+                  disabled={!telegramBotToken}
+                  value={profile.notifications?.telegram?.chatId || ''}
+                  onChange={(e) => setNotification(index, 'telegram', e.target.value)}
+                />
+              </div>
+            </div>
+          </div>
+        ))}
+        <button
+          type="button"
+          className="btn btn-secondary"
+          onClick={addProfile}
+          style={{ alignSelf: 'flex-start' }}
+        >
+          <PlusIcon size={14} />
+          <span>Add Profile</span>
+        </button>
+      </div>
+    </div>
+  );
+};
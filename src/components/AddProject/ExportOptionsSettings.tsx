@@ -0,0 +1,186 @@
+import React from 'react';
+import { PlusIcon, TrashIcon } from '../Icons';
+import { inputStyle, labelStyle } from './AddProject.styles';
+import { IosExportOptions } from '../../types/project';
+
+interface ExportOptionsSettingsProps {
+  options: IosExportOptions;
+  setOptions: (val: IosExportOptions) => void;
+}
+
+const Toggle = ({
+  label,
+  checked,
+  onChange,
+}: {
+  label: string;
+  checked: boolean;
+  onChange: (checked: boolean) => void;
+}) => (
+  <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+    <span style={{ fontSize: '13px', fontWeight: 600, color: 'var(--color-text)' }}>{label}</span>
+    <label className="switch">
+      <input type="checkbox" checked={checked} onChange={(e) => onChange(e.target.checked)} />
+      <span className="slider round"></span>
+    </label>
+  </div>
+);
+
+export const ExportOptionsSettings: React.FC<ExportOptionsSettingsProps> = ({
+  options,
+  setOptions,
+}) => {
+  const profiles = Object.entries(options.provisioningProfiles || {});
+
+  // Rebuilding the object keeps the rows in place while a bundle ID is being typed
+  const setProfiles = (entries: [string, string][]) =>
+    setOptions({ ...options, provisioningProfiles: Object.fromEntries(entries) });
+
+  const updateProfile = (index: number, bundleId: string, profile: string) =>
+    setProfiles(profiles.map((entry, i) => (i === index ? [bundleId, profile] : entry)));
+
+  return (
+    <div
+      style={{
+        marginTop: 'var(--spacing-md)',
+        paddingTop: 'var(--spacing-md)',
+        borderTop: '1px solid var(--color-border)',
+      }}
+    >
+      <p
+        style={{
+          fontSize: '13px',
+          fontWeight: 600,
+          marginBottom: 'var(--spacing-sm)',
+          color: 'var(--color-text)',
+        }}
+      >
+        📦 Export Options
+      </p>
+
+      <label style={{ ...labelStyle, marginTop: 0 }}>
+        Provisioning Profiles
+        <span
+          style={{
+            color: 'var(--color-text-secondary)',
+            fontWeight: 400,
+            marginLeft: '4px',
+            fontSize: '12px',
+          }}
+        >
+          (one per target, switches to manual signing)
+        </span>
+      </label>
+      {profiles.map(([bundleId, profile], index) => (
+        <div key={index} style={{ display: 'flex', gap: '8px', marginBottom: '8px' }}>
+          <input
+            style={inputStyle}
+            value={bundleId}
+            onChange={(e) => updateProfile(index, e.target.value, profile)}
+            placeholder="com.example.app.NotificationService"
+          />
+          <input
+            style={inputStyle}
+            value={profile}
+            onChange={(e) => updateProfile(index, bundleId, e.target.value)}
+            placeholder="Profile name or UUID"
+          />
+          <button
+            type="button"
+            className="btn btn-secondary"
+            onClick={() => setProfiles(profiles.filter((_, i) => i !== index))}
+            style={{ flexShrink: 0 }}
+          >
+            <TrashIcon size={14} />
+          </button>
+        </div>
+      ))}
+      <button
+        type="button"
+        className="btn btn-secondary"
+        onClick={() => setProfiles([...profiles, ['', '']])}
+        disabled={profiles.some(([bundleId]) => !bundleId)}
+        style={{ padding: '4px 12px', fontSize: '12px' }}
+      >
+        <PlusIcon size={12} />
+        <span>Add Target</span>
+      </button>
+
+      <div
+        style={{
+          display: 'grid',
+          gridTemplateColumns: '1fr 1fr',
+          gap: 'var(--spacing-md)',
+        }}
+      >
+        <div>
+          <label style={labelStyle}>Signing Certificate</label>
+          <input
+            style={inputStyle}
+            value={options.signingCertificate || ''}
+            onChange={(e) =>
+              setOptions({ ...options, signingCertificate: e.target.value || undefined })
+            }
+            placeholder="Apple Distribution"
+          />
+        </div>
+        <div>
+          <label style={labelStyle}>App Thinning</label>
+          <select
+            style={{ ...inputStyle, cursor: 'pointer' }}
+            value={options.thinning || ''}
+            onChange={(e) => setOptions({ ...options, thinning: e.target.value || undefined })}
+          >
+            <option value="">Default</option>
+            <option value="<none>">None</option>
+            <option value="<thin-for-all-variants>">All Variants</option>
+          </select>
+        </div>
+      </div>
+
+      <div
+        style={{
+          display: 'flex',
+          flexDirection: 'column',
+          gap: 'var(--spacing-sm)',
+          marginTop: 'var(--spacing-md)',
+        }}
+      >
+        <Toggle
+          label="Upload Symbols"
+          checked={options.uploadSymbols ?? true}
+          onChange={(uploadSymbols) => setOptions({ ...options, uploadSymbols })}
+        />
+        <Toggle
+          label="Compile Bitcode"
+          checked={options.compileBitcode ?? false}
+          onChange={(compileBitcode) => setOptions({ ...options, compileBitcode })}
+        />
+        <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+          <span style={{ fontSize: '13px', fontWeight: 600, color: 'var(--color-text)' }}>
+            Let App Store Connect Manage Version and Build Number
+          </span>
+          <select
+            style={{ ...inputStyle, width: 'auto', cursor: 'pointer' }}
+            value={
+              options.manageAppVersionAndBuildNumber === undefined
+                ? ''
+                : String(options.manageAppVersionAndBuildNumber)
+            }
+            onChange={(e) =>
+              setOptions({
+                ...options,
+                manageAppVersionAndBuildNumber:
+                  e.target.value === '' ? undefined : e.target.value === 'true',
+              })
+            }
+          >
+            <option value="">Xcode Default</option>
+            <option value="true">Yes</option>
+            <option value="false">No</option>
+          </select>
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -2,30 +2,57 @@ import React from 'react';
 import { KeyIcon } from '../Icons';
 import { inputStyle, labelStyle, sectionStyle } from './AddProject.styles';
 import { Credential } from '../../types/credential';
+import { IosExportOptions } from '../../types/project';
+import { ExportOptionsSettings } from './ExportOptionsSettings';
+import { ProvisioningCheck } from './ProvisioningCheck';
 
 interface IosBuildSettingsProps {
+  bundleId: string;
   scheme: string;
   setScheme: (val: string) => void;
   configuration: string;
   setConfiguration: (val: string) => void;
   exportMethod: 'development' | 'ad-hoc' | 'app-store' | 'enterprise';
   setExportMethod: (val: any) => void;
+  exportOptions: IosExportOptions;
+  setExportOptions: (val: IosExportOptions) => void;
+  betaGroups: string;
+  setBetaGroups: (val: string) => void;
+  firebaseAppId: string;
+  setFirebaseAppId: (val: string) => void;
+  distributionGroups: string;
+  setDistributionGroups: (val: string) => void;
+  testers: string;
+  setTesters: (val: string) => void;
   selectedCredentialId: string;
   setSelectedCredentialId: (val: string) => void;
   credentials: Credential[];
 }
 
 export const IosBuildSettings: React.FC<IosBuildSettingsProps> = ({
+  bundleId,
   scheme,
   setScheme,
   configuration,
   setConfiguration,
   exportMethod,
   setExportMethod,
+  exportOptions,
+  setExportOptions,
+  betaGroups,
+  setBetaGroups,
+  firebaseAppId,
+  setFirebaseAppId,
+  distributionGroups,
+  setDistributionGroups,
+  testers,
+  setTesters,
   selectedCredentialId,
   setSelectedCredentialId,
   credentials,
 }) => {
+  const teamId = credentials.find((c) => c.id === selectedCredentialId)?.ios?.teamId;
+
   return (
     <div style={{ marginBottom: 'var(--spacing-md)' }}>
       <div style={sectionStyle}>
@@ -106,6 +133,122 @@ export const IosBuildSettings: React.FC<IosBuildSettingsProps> = ({
           </div>
         </div>
 
+        <ExportOptionsSettings options={exportOptions} setOptions={setExportOptions} />
+
+        <div style={{ marginTop: 'var(--spacing-md)' }}>
+          <label style={labelStyle}>
+            TestFlight Groups
+            <span
+              style={{
+                color: 'var(--color-text-secondary)',
+                fontWeight: 400,
+                marginLeft: '4px',
+                fontSize: '12px',
+              }}
+            >
+              (comma-separated, added after App Store upload)
+            </span>
+          </label>
+          <input
+            type="text"
+            style={inputStyle}
+            placeholder="QA, Internal Testers"
+            value={betaGroups}
+            onChange={(e) => setBetaGroups(e.target.value)}
+          />
+        </div>
+
+        {/* Firebase App Distribution Section */}
+        <div
+          style={{
+            marginTop: 'var(--spacing-md)',
+            paddingTop: 'var(--spacing-md)',
+            borderTop: '1px solid var(--color-border)',
+          }}
+        >
+          <p
+            style={{
+              fontSize: '13px',
+              fontWeight: 600,
+              marginBottom: 'var(--spacing-sm)',
+              color: 'var(--color-text)',
+            }}
+          >
+            🔥 Firebase App Distribution
+          </p>
+
+          <div style={{ marginBottom: 'var(--spacing-sm)' }}>
+            <label style={labelStyle}>
+              Firebase App ID
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (uses the Android credential's service account)
+              </span>
+            </label>
+            <input
+              type="text"
+              style={inputStyle}
+              placeholder="1:1234567890:ios:0a1b2c3d4e5f67890"
+              value={firebaseAppId}
+              onChange={(e) => setFirebaseAppId(e.target.value)}
+            />
+          </div>
+
+          <div>
+            <label style={labelStyle}>
+              Distribution Groups
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (comma-separated, e.g., qa-team, testers)
+              </span>
+            </label>
+            <input
+              type="text"
+              style={inputStyle}
+              placeholder="qa-team, trusted-testers"
+              value={distributionGroups}
+              onChange={(e) => setDistributionGroups(e.target.value)}
+            />
+          </div>
+
+          <div style={{ marginTop: 'var(--spacing-sm)' }}>
+            <label style={labelStyle}>
+              Testers
+              <span
+                style={{
+                  color: 'var(--color-text-secondary)',
+                  fontWeight: 400,
+                  marginLeft: '4px',
+                  fontSize: '12px',
+                }}
+              >
+                (comma-separated emails)
+              </span>
+            </label>
+            <input
+              type="text"
+              style={inputStyle}
+              placeholder="qa@example.com, pm@example.com"
+              value={testers}
+              onChange={(e) => setTesters(e.target.value)}
+            />
+          </div>
+        </div>
+
+        <ProvisioningCheck bundleId={bundleId} exportMethod={exportMethod} teamId={teamId} />
+
         {credentials.length === 0 && (
           <p
             style={{
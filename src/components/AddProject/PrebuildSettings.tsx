@@ -0,0 +1,50 @@
+import React from 'react';
+import { sectionStyle } from './AddProject.styles';
+import { ToggleRow } from './ReleaseSettings';
+import { PrebuildSettings as PrebuildSettingsValue } from '../../types/project';
+
+interface PrebuildSettingsProps {
+  settings: PrebuildSettingsValue;
+  setSettings: (val: PrebuildSettingsValue) => void;
+}
+
+export const PrebuildSettings: React.FC<PrebuildSettingsProps> = ({ settings, setSettings }) => (
+  <div style={{ marginBottom: 'var(--spacing-md)' }}>
+    <p
+      style={{
+        fontSize: '13px',
+        fontWeight: 600,
+        marginBottom: 'var(--spacing-sm)',
+        color: 'var(--color-text)',
+      }}
+    >
+      🏗️ Expo Prebuild
+    </p>
+    <div
+      style={{ ...sectionStyle, display: 'flex', flexDirection: 'column', gap: 'var(--spacing-md)' }}
+    >
+      <ToggleRow
+        label="Run expo prebuild before building"
+        description="Generate ios/ and android/ from the Expo config; versions are written to app.json"
+        checked={settings.enabled}
+        onChange={(checked) => setSettings({ ...settings, enabled: checked })}
+      />
+      {settings.enabled && (
+        <>
+          <ToggleRow
+            label="Clean native folders"
+            description="Delete ios/ and android/ before generating them (--clean)"
+            checked={!!settings.clean}
+            onChange={(checked) => setSettings({ ...settings, clean: checked })}
+          />
+          <ToggleRow
+            label="Generate in a temporary workspace"
+            description="Prebuild a copy of the project so the repository stays untouched"
+            checked={!!settings.isolated}
+            onChange={(checked) => setSettings({ ...settings, isolated: checked })}
+          />
+        </>
+      )}
+    </div>
+  </div>
+);
@@ -0,0 +1,137 @@
+import React, { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { AlertCircleIcon, CheckCircleIcon, LoaderIcon, ShieldCheckIcon } from '../Icons';
+import { ProvisioningCheck as ProvisioningCheckResult } from '../../types/provisioning';
+
+interface ProvisioningCheckProps {
+  bundleId: string;
+  exportMethod: string;
+  teamId?: string;
+}
+
+const formatDate = (date: string) => new Date(date).toLocaleDateString();
+
+export const ProvisioningCheck: React.FC<ProvisioningCheckProps> = ({
+  bundleId,
+  exportMethod,
+  teamId,
+}) => {
+  const [isChecking, setIsChecking] = useState(false);
+  const [result, setResult] = useState<ProvisioningCheckResult | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const handleCheck = async () => {
+    setIsChecking(true);
+    setError(null);
+    setResult(null);
+    try {
+      setResult(
+        await invoke<ProvisioningCheckResult>('check_provisioning_profiles', {
+          bundleId,
+          exportMethod,
+          teamId: teamId || null,
+        }),
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsChecking(false);
+    }
+  };
+
+  const profile = result?.profile;
+
+  return (
+    <div
+      style={{
+        marginTop: 'var(--spacing-md)',
+        paddingTop: 'var(--spacing-md)',
+        borderTop: '1px solid var(--color-border)',
+      }}
+    >
+      <div style={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between' }}>
+        <p style={{ fontSize: '13px', fontWeight: 600, color: 'var(--color-text)' }}>
+          📜 Provisioning Profile
+        </p>
+        <button
+          type="button"
+          className="btn btn-secondary"
+          onClick={handleCheck}
+          disabled={isChecking || !bundleId}
+          style={{ padding: '4px 12px', fontSize: '12px' }}
+        >
+          {isChecking ? (
+            <LoaderIcon size={12} className="animate-spin" />
+          ) : (
+            <ShieldCheckIcon size={12} />
+          )}
+          <span>Check Profiles</span>
+        </button>
+      </div>
+
+      {error && (
+        <p style={{ color: 'var(--color-error)', fontSize: '12px', marginTop: '8px' }}>{error}</p>
+      )}
+
+      {profile && (
+        <div
+          style={{
+            marginTop: '8px',
+            fontSize: '12px',
+            color: 'var(--color-text-secondary)',
+            lineHeight: 1.6,
+          }}
+        >
+          <div
+            style={{
+              display: 'flex',
+              alignItems: 'center',
+              gap: '6px',
+              color: 'var(--color-success)',
+              fontWeight: 600,
+            }}
+          >
+            <CheckCircleIcon size={14} />
+            {profile.name}
+          </div>
+          <div>
+            {profile.bundleId} · {profile.profileType} · {profile.teamName} ({profile.teamId})
+          </div>
+          <div>
+            Expires {formatDate(profile.expires)}
+            {profile.deviceCount > 0 && ` · ${profile.deviceCount} device(s)`}
+          </div>
+          {profile.certificates.map((certificate) => (
+            <div key={certificate.sha1}>
+              {certificate.installed ? '🔑' : '⚠️'} {certificate.subject} (until{' '}
+              {formatDate(certificate.validUntil)})
+            </div>
+          ))}
+          <div style={{ color: 'var(--color-text-tertiary)' }}>
+            Entitlements: {Object.keys(profile.entitlements || {}).join(', ')}
+          </div>
+        </div>
+      )}
+
+      {result && !profile && (
+        <div style={{ marginTop: '8px', display: 'flex', flexDirection: 'column', gap: '4px' }}>
+          {result.problems.map((problem) => (
+            <div
+              key={problem}
+              style={{
+                display: 'flex',
+                gap: '6px',
+                alignItems: 'flex-start',
+                fontSize: '12px',
+                color: 'var(--color-warning)',
+              }}
+            >
+              <AlertCircleIcon size={14} style={{ flexShrink: 0, marginTop: '2px' }} />
+              {problem}
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,159 @@
+import React from 'react';
+import { inputStyle, labelStyle, sectionStyle } from './AddProject.styles';
+import {
+  BuildNumberStrategy,
+  ReleaseSettings as ReleaseSettingsValue,
+  VersionBump,
+  VersionBumpSettings,
+} from '../../types/project';
+
+interface ReleaseSettingsProps {
+  settings: ReleaseSettingsValue;
+  setSettings: (val: ReleaseSettingsValue) => void;
+}
+
+export const ToggleRow = ({
+  label,
+  description,
+  checked,
+  onChange,
+}: {
+  label: string;
+  description: string;
+  checked: boolean;
+  onChange: (checked: boolean) => void;
+}) => (
+  <div
+    style={{
+      display: 'flex',
+      alignItems: 'center',
+      justifyContent: 'space-between',
+      gap: 'var(--spacing-md)',
+    }}
+  >
+    <div>
+      <label style={{ ...labelStyle, marginTop: 0, marginBottom: '2px' }}>{label}</label>
+      <span style={{ fontSize: '12px', color: 'var(--color-text-secondary)' }}>{description}</span>
+    </div>
+    <label className="switch">
+      <input type="checkbox" checked={checked} onChange={(e) => onChange(e.target.checked)} />
+      <span className="slider round"></span>
+    </label>
+  </div>
+);
+
+export const ReleaseSettings: React.FC<ReleaseSettingsProps> = ({ settings, setSettings }) => {
+  const versionBump: VersionBumpSettings = settings.versionBump || {
+    version: 'none',
+    buildNumber: 'none',
+  };
+  const setVersionBump = (value: VersionBumpSettings) =>
+    setSettings({
+      ...settings,
+      versionBump: value.version === 'none' && value.buildNumber === 'none' ? undefined : value,
+    });
+
+  return (
+    <div style={{ marginBottom: 'var(--spacing-md)' }}>
+      <p
+        style={{
+          fontSize: '13px',
+          fontWeight: 600,
+          marginBottom: 'var(--spacing-sm)',
+          color: 'var(--color-text)',
+        }}
+      >
+        🔖 Release & Source Control
+      </p>
+      <div
+        style={{ ...sectionStyle, display: 'flex', flexDirection: 'column', gap: 'var(--spacing-md)' }}
+      >
+        <ToggleRow
+          label="Require a clean working tree"
+          description="Refuse release builds while the project has uncommitted changes"
+          checked={!!settings.requireCleanTree}
+          onChange={(checked) => setSettings({ ...settings, requireCleanTree: checked })}
+        />
+        <ToggleRow
+          label="Tag published builds"
+          description="Create a git tag like ios/1.4.0+57 after a successful upload"
+          checked={!!settings.tagAfterUpload}
+          onChange={(checked) => setSettings({ ...settings, tagAfterUpload: checked })}
+        />
+        <ToggleRow
+          label="Generate release notes"
+          description="Build notes from the commits since the last successful build when none are typed"
+          checked={!!settings.autoReleaseNotes}
+          onChange={(checked) => setSettings({ ...settings, autoReleaseNotes: checked })}
+        />
+        <div
+          style={{
+            display: 'grid',
+            gridTemplateColumns: '1fr 1fr',
+            gap: 'var(--spacing-md)',
+          }}
+        >
+          <div>
+            <label style={{ ...labelStyle, marginTop: 0 }}>Bump Version</label>
+            <select
+              style={{ ...inputStyle, cursor: 'pointer' }}
+              value={versionBump.version}
+              onChange={(e) =>
+                setVersionBump({ ...versionBump, version: e.target.value as VersionBump })
+              }
+            >
+              <option value="none">Never</option>
+              <option value="patch">Patch (1.4.0 → 1.4.1)</option>
+              <option value="minor">Minor (1.4.0 → 1.5.0)</option>
+              <option value="major">Major (1.4.0 → 2.0.0)</option>
+            </select>
+          </div>
+          <div>
+            <label style={{ ...labelStyle, marginTop: 0 }}>Bump Build Number</label>
+            <select
+              style={{ ...inputStyle, cursor: 'pointer' }}
+              value={versionBump.buildNumber}
+              onChange={(e) =>
+                setVersionBump({
+                  ...versionBump,
+                  buildNumber: e.target.value as BuildNumberStrategy,
+                })
+              }
+            >
+              <option value="none">Never</option>
+              <option value="increment">Increment (+1)</option>
+              <option value="date">Date (YYMMDDNN)</option>
+              <option value="shared">Shared across iOS & Android</option>
+            </select>
+          </div>
+        </div>
+        <span style={{ fontSize: '12px', color: 'var(--color-text-secondary)', marginTop: '-8px' }}>
+          Applied when a build starts and rolled back if the build fails or is cancelled
+        </span>
+        <div>
+          <label style={{ ...labelStyle, marginTop: 0 }}>
+            Release Notes Template
+            <span
+              style={{
+                color: 'var(--color-text-secondary)',
+                fontWeight: 400,
+                marginLeft: '4px',
+                fontSize: '12px',
+              }}
+            >
+              {'(optional: {{version}}, {{build}}, {{platform}}, {{changes}}, {{features}}, {{fixes}}, {{chores}}, {{commits}})'}
+            </span>
+          </label>
+          <textarea
+            style={{ ...inputStyle, minHeight: '70px', resize: 'vertical', fontFamily: 'inherit' }}
+            placeholder="{{changes}}"
+            value={settings.releaseNotesTemplate || ''}
+            onChange={(e) =>
+              setSettings({ ...settings, releaseNotesTemplate: e.target.value || undefined })
+            }
+          />
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,80 @@
+import React from 'react';
+import { inputStyle, labelStyle, sectionStyle } from './AddProject.styles';
+import { ToggleRow } from './ReleaseSettings';
+import { SizeBudgets as SizeBudgetsValue } from '../../types/project';
+
+interface SizeBudgetsProps {
+  budgets: SizeBudgetsValue;
+  setBudgets: (val: SizeBudgetsValue) => void;
+}
+
+const MB = 1024 * 1024;
+
+type BudgetKey = 'total' | 'nativeLibraries' | 'jsBundle' | 'assets' | 'maxIncrease';
+
+const FIELDS: { key: BudgetKey; label: string }[] = [
+  { key: 'total', label: 'Total size (MB)' },
+  { key: 'nativeLibraries', label: 'Native libraries (MB)' },
+  { key: 'jsBundle', label: 'JS bundle (MB)' },
+  { key: 'assets', label: 'Assets (MB)' },
+  { key: 'maxIncrease', label: 'Growth per build (MB)' },
+];
+
+export const SizeBudgets: React.FC<SizeBudgetsProps> = ({ budgets, setBudgets }) => {
+  const toMegabytes = (bytes?: number) =>
+    bytes === undefined ? '' : String(Math.round((bytes / MB) * 100) / 100);
+  const setBudget = (key: BudgetKey, value: string) =>
+    setBudgets({
+      ...budgets,
+      [key]: value.trim() === '' ? undefined : Math.round(parseFloat(value) * MB),
+    });
+
+  return (
+    <div style={{ marginBottom: 'var(--spacing-md)' }}>
+      <p
+        style={{
+          fontSize: '13px',
+          fontWeight: 600,
+          marginBottom: 'var(--spacing-sm)',
+          color: 'var(--color-text)',
+        }}
+      >
+        📏 Size Budgets
+      </p>
+      <div style={sectionStyle}>
+        <p style={{ fontSize: '12px', color: 'var(--color-text-secondary)', margin: 0 }}>
+          Checked against the artifact after each build. Leave a budget empty to skip it.
+        </p>
+        <div
+          style={{
+            display: 'grid',
+            gridTemplateColumns: '1fr 1fr',
+            gap: '0 var(--spacing-md)',
+            marginBottom: 'var(--spacing-md)',
+          }}
+        >
+          {FIELDS.map(({ key, label }) => (
+            <div key={key}>
+              <label style={labelStyle}>{label}</label>
+              <input
+                style={inputStyle}
+                type="number"
+                min={0}
+                step="0.1"
+                placeholder="No limit"
+                value={toMegabytes(budgets[key])}
+                onChange={(e) => setBudget(key, e.target.value)}
+              />
+            </div>
+          ))}
+        </div>
+        <ToggleRow
+          label="Fail the build when a budget is exceeded"
+          description="Otherwise the build only warns. Notification channels are told either way."
+          checked={!!budgets.failBuild}
+          onChange={(checked) => setBudgets({ ...budgets, failBuild: checked })}
+        />
+      </div>
+    </div>
+  );
+};
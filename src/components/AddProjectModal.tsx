@@ -15,6 +15,10 @@ import { PlatformConfigSection } from './AddProject/PlatformConfigSection';
 import { IosBuildSettings } from './AddProject/IosBuildSettings';
 import { AndroidBuildSettings } from './AddProject/AndroidBuildSettings';
 import { NotificationSettings } from './AddProject/NotificationSettings';
+import { ReleaseSettings } from './AddProject/ReleaseSettings';
+import { PrebuildSettings } from './AddProject/PrebuildSettings';
+import { SizeBudgets } from './AddProject/SizeBudgets';
+import { BuildProfiles } from './AddProject/BuildProfiles';
 import {
   sidebarStyle,
   sidebarItemStyle,
@@ -190,12 +194,23 @@ export const AddProjectModal: React.FC<AddProjectModalProps> = ({
               {activeTab === 'configs' && (
                 <div style={{ display: 'flex', flexDirection: 'column', gap: 'var(--spacing-lg)' }}>
                   <IosBuildSettings
+                    bundleId={states.iosBundle}
                     scheme={states.iosScheme}
                     setScheme={states.setIosScheme}
                     configuration={states.iosConfiguration}
                     setConfiguration={states.setIosConfiguration}
                     exportMethod={states.iosExportMethod}
                     setExportMethod={states.setIosExportMethod}
+                    exportOptions={states.iosExportOptions}
+                    setExportOptions={states.setIosExportOptions}
+                    betaGroups={states.betaGroups}
+                    setBetaGroups={states.setBetaGroups}
+                    firebaseAppId={states.iosFirebaseAppId}
+                    setFirebaseAppId={states.setIosFirebaseAppId}
+                    distributionGroups={states.iosDistributionGroups}
+                    setDistributionGroups={states.setIosDistributionGroups}
+                    testers={states.iosTesters}
+                    setTesters={states.setIosTesters}
                     // These are now handled in separate Credentials tab, but kept for compatibility
                     selectedCredentialId={states.selectedIosCredentialId}
                     setSelectedCredentialId={states.setSelectedIosCredentialId}
@@ -213,7 +228,45 @@ export const AddProjectModal: React.FC<AddProjectModalProps> = ({
                     setFirebaseAppId={states.setFirebaseAppId}
                     distributionGroups={states.distributionGroups}
                     setDistributionGroups={states.setDistributionGroups}
+                    testers={states.testers}
+                    setTesters={states.setTesters}
+                    playTrack={states.playTrack}
+                    setPlayTrack={states.setPlayTrack}
+                    playLocales={states.playLocales}
+                    setPlayLocales={states.setPlayLocales}
+                    playReleaseStatus={states.playReleaseStatus}
+                    setPlayReleaseStatus={states.setPlayReleaseStatus}
+                    keystorePath={states.keystorePath}
+                    setKeystorePath={states.setKeystorePath}
+                    keyAlias={states.keyAlias}
+                    setKeyAlias={states.setKeyAlias}
+                    storePassword={states.storePassword}
+                    setStorePassword={states.setStorePassword}
+                    keyPassword={states.keyPassword}
+                    setKeyPassword={states.setKeyPassword}
+                    hasSigningPasswords={states.hasSigningPasswords}
+                    onBrowseKeystore={handlers.handleBrowseKeystore}
+                  />
+
+                  <BuildProfiles
+                    profiles={states.profiles}
+                    setProfiles={states.setProfiles}
+                    iosCredentials={states.iosCredentials}
+                    androidCredentials={states.androidCredentials}
+                    telegramBotToken={states.telegramBotToken}
+                  />
+
+                  <PrebuildSettings
+                    settings={states.prebuildSettings}
+                    setSettings={states.setPrebuildSettings}
+                  />
+
+                  <ReleaseSettings
+                    settings={states.releaseSettings}
+                    setSettings={states.setReleaseSettings}
                   />
+
+                  <SizeBudgets budgets={states.sizeBudgets} setBudgets={states.setSizeBudgets} />
                 </div>
               )}
 
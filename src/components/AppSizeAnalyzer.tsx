@@ -16,14 +16,108 @@ interface SizeBreakdown {
   percentage: number;
 }
 
+interface ExportedComponent {
+  kind: string;
+  name: string;
+  implicit: boolean;
+}
+
+interface AndroidManifestInfo {
+  package: string;
+  versionCode?: number;
+  versionName?: string;
+  minSdk?: number;
+  targetSdk?: number;
+  compileSdk?: number;
+  permissions: string[];
+  exportedComponents: ExportedComponent[];
+  debuggable: boolean;
+  allowBackup: boolean;
+}
+
+interface MachOSlice {
+  arch: string;
+  platform?: string;
+  minimumOs?: string;
+  encrypted?: boolean;
+  segments: { name: string; fileSize: number; vmSize: number }[];
+  simulator: boolean;
+  debugSymbols: boolean;
+}
+
+interface MachOBinary {
+  path: string;
+  kind: string;
+  size: number;
+  slices: MachOSlice[];
+  linkedLibraries: string[];
+}
+
+interface EmbeddedProfile {
+  name: string;
+  teamId: string;
+  profileType: string;
+  expires: string;
+  expired: boolean;
+}
+
+interface IpaInfo {
+  bundleId: string;
+  name?: string;
+  version?: string;
+  buildNumber?: string;
+  minimumOs?: string;
+  supportedPlatforms: string[];
+  profile?: EmbeddedProfile;
+  binaries: MachOBinary[];
+  dsymPaths: string[];
+}
+
 interface AppSizeReport {
   totalSize: number;
   breakdown: SizeBreakdown[];
   fileType: string;
   supports16kPageSize?: boolean;
   largeFiles?: { path: string; size: number }[];
+  jsBundleSize?: number;
+  manifest?: AndroidManifestInfo;
+  manifestError?: string;
+  ipa?: IpaInfo;
+  ipaError?: string;
 }
 
+const fileName = (path: string) => path.split('/').pop() || path;
+
+const DetailRow: React.FC<{ label: string; children: React.ReactNode }> = ({
+  label,
+  children,
+}) => (
+  <div
+    style={{
+      display: 'flex',
+      justifyContent: 'space-between',
+      gap: '16px',
+      padding: '10px 20px',
+      borderBottom: '1px solid rgba(255,255,255,0.03)',
+      fontSize: '13px',
+    }}
+  >
+    <span style={{ color: 'var(--color-text-secondary)', flexShrink: 0 }}>{label}</span>
+    <span style={{ fontWeight: 600, textAlign: 'right', wordBreak: 'break-all' }}>{children}</span>
+  </div>
+);
+
+const DetailFlag: React.FC<{ ok: boolean; text: string }> = ({ ok, text }) => (
+  <div style={{ display: 'flex', alignItems: 'center', gap: '8px', fontSize: '13px' }}>
+    {ok ? (
+      <CheckCircleIcon size={14} style={{ color: 'var(--color-success)' }} />
+    ) : (
+      <AlertCircleIcon size={14} style={{ color: 'var(--color-warning)' }} />
+    )}
+    <span style={{ color: 'var(--color-text-secondary)' }}>{text}</span>
+  </div>
+);
+
 interface AppSizeAnalyzerProps {
   artifactPath: string;
   onClose: () => void;
@@ -238,6 +332,17 @@ export const AppSizeAnalyzer: React.FC<AppSizeAnalyzerProps> = ({
                     Properly compressed and signed
                   </span>
                 </div>
+                {!!report.jsBundleSize && (
+                  <div
+                    style={{
+                      fontSize: '13px',
+                      color: 'var(--color-text-secondary)',
+                      marginTop: '8px',
+                    }}
+                  >
+                    JS bundle: {formatSize(report.jsBundleSize)}
+                  </div>
+                )}
                 {report.supports16kPageSize !== undefined && (
                   <div
                     style={{
@@ -262,6 +367,237 @@ export const AppSizeAnalyzer: React.FC<AppSizeAnalyzerProps> = ({
                 )}
               </div>
 
+              {/* Manifest */}
+              {(report.manifest || report.manifestError) && (
+                <div style={{ marginBottom: '32px' }}>
+                  <h4
+                    style={{
+                      fontSize: '16px',
+                      fontWeight: 700,
+                      marginBottom: '16px',
+                      display: 'flex',
+                      alignItems: 'center',
+                      gap: '8px',
+                    }}
+                  >
+                    <FileIcon size={16} /> Manifest
+                  </h4>
+                  {report.manifest ? (
+                    <>
+                      <div
+                        style={{
+                          background: 'rgba(255,255,255,0.02)',
+                          borderRadius: '16px',
+                          border: '1px solid rgba(255,255,255,0.03)',
+                          overflow: 'hidden',
+                          marginBottom: '12px',
+                        }}
+                      >
+                        <DetailRow label="Package">{report.manifest.package}</DetailRow>
+                        <DetailRow label="Version">
+                          {report.manifest.versionName ?? '—'} (
+                          {report.manifest.versionCode ?? '—'})
+                        </DetailRow>
+                        <DetailRow label="SDK (min / target / compile)">
+                          {[
+                            report.manifest.minSdk,
+                            report.manifest.targetSdk,
+                            report.manifest.compileSdk,
+                          ]
+                            .map((sdk) => sdk ?? '—')
+                            .join(' / ')}
+                        </DetailRow>
+                        <DetailRow label={`Permissions (${report.manifest.permissions.length})`}>
+                          {report.manifest.permissions.length > 0
+                            ? report.manifest.permissions.map((p) => (
+                                <div key={p}>{p.replace('android.permission.', '')}</div>
+                              ))
+                            : 'None'}
+                        </DetailRow>
+                        <DetailRow
+                          label={`Exported Components (${
+                            report.manifest.exportedComponents.length
+                          })`}
+                        >
+                          {report.manifest.exportedComponents.length > 0
+                            ? report.manifest.exportedComponents.map((c) => (
+                                <div
+                                  key={`${c.kind}:${c.name}`}
+                                  style={{ color: c.implicit ? 'var(--color-warning)' : undefined }}
+                                  title={
+                                    c.implicit
+                                      ? 'Has an intent filter but no android:exported'
+                                      : undefined
+                                  }
+                                >
+                                  {c.name} <span style={{ opacity: 0.6 }}>{c.kind}</span>
+                                </div>
+                              ))
+                            : 'None'}
+                        </DetailRow>
+                      </div>
+                      <div style={{ display: 'grid', gap: '8px' }}>
+                        <DetailFlag
+                          ok={!report.manifest.debuggable}
+                          text={
+                            report.manifest.debuggable
+                              ? 'android:debuggable is true'
+                              : 'Not debuggable'
+                          }
+                        />
+                        <DetailFlag
+                          ok={!report.manifest.allowBackup}
+                          text={
+                            report.manifest.allowBackup
+                              ? 'App data can be backed up (allowBackup)'
+                              : 'Backups disabled'
+                          }
+                        />
+                        {report.manifest.exportedComponents.some((c) => c.implicit) && (
+                          <DetailFlag
+                            ok={false}
+                            text="Implicitly exported components fail to install on Android 12+"
+                          />
+                        )}
+                      </div>
+                    </>
+                  ) : (
+                    <DetailFlag
+                      ok={false}
+                      text={`Manifest not decoded: ${report.manifestError}`}
+                    />
+                  )}
+                </div>
+              )}
+
+              {/* IPA */}
+              {(report.ipa || report.ipaError) && (
+                <div style={{ marginBottom: '32px' }}>
+                  <h4
+                    style={{
+                      fontSize: '16px',
+                      fontWeight: 700,
+                      marginBottom: '16px',
+                      display: 'flex',
+                      alignItems: 'center',
+                      gap: '8px',
+                    }}
+                  >
+                    <FileIcon size={16} /> App Bundle
+                  </h4>
+                  {report.ipa ? (
+                    <>
+                      <div
+                        style={{
+                          background: 'rgba(255,255,255,0.02)',
+                          borderRadius: '16px',
+                          border: '1px solid rgba(255,255,255,0.03)',
+                          overflow: 'hidden',
+                          marginBottom: '12px',
+                        }}
+                      >
+                        <DetailRow label="Bundle ID">{report.ipa.bundleId}</DetailRow>
+                        <DetailRow label="Version">
+                          {report.ipa.version ?? '—'} ({report.ipa.buildNumber ?? '—'})
+                        </DetailRow>
+                        <DetailRow label="Minimum OS">{report.ipa.minimumOs ?? '—'}</DetailRow>
+                        <DetailRow label="Signing Profile">
+                          {report.ipa.profile
+                            ? `${report.ipa.profile.name} (${report.ipa.profile.profileType})`
+                            : 'None'}
+                        </DetailRow>
+                      </div>
+                      <div style={{ display: 'grid', gap: '8px', marginBottom: '12px' }}>
+                        {report.ipa.binaries
+                          .filter((b) => b.slices.some((s) => s.simulator))
+                          .map((b) => (
+                            <DetailFlag
+                              key={`sim-${b.path}`}
+                              ok={false}
+                              text={`${fileName(b.path)} contains simulator slices (${b.slices
+                                .filter((s) => s.simulator)
+                                .map((s) => s.arch)
+                                .join(', ')})`}
+                            />
+                          ))}
+                        {report.ipa.binaries
+                          .filter((b) => b.slices.some((s) => s.debugSymbols))
+                          .map((b) => (
+                            <DetailFlag
+                              key={`dbg-${b.path}`}
+                              ok={false}
+                              text={`${fileName(b.path)} is not stripped of debug symbols`}
+                            />
+                          ))}
+                        {report.ipa.dsymPaths.map((path) => (
+                          <DetailFlag
+                            key={path}
+                            ok={false}
+                            text={`${fileName(path)} is shipped inside the app`}
+                          />
+                        ))}
+                        {report.ipa.profile?.expired && (
+                          <DetailFlag ok={false} text="The embedded provisioning profile expired" />
+                        )}
+                      </div>
+                      <div style={{ display: 'grid', gap: '12px' }}>
+                        {report.ipa.binaries.map((binary) => (
+                          <div
+                            key={binary.path}
+                            style={{
+                              padding: '16px 20px',
+                              background: 'rgba(255,255,255,0.02)',
+                              borderRadius: '16px',
+                              border: '1px solid rgba(255,255,255,0.03)',
+                              fontSize: '12px',
+                              color: 'var(--color-text-secondary)',
+                              display: 'grid',
+                              gap: '6px',
+                            }}
+                          >
+                            <div
+                              style={{
+                                display: 'flex',
+                                justifyContent: 'space-between',
+                                fontSize: '14px',
+                                color: 'var(--color-text)',
+                              }}
+                            >
+                              <span style={{ fontWeight: 600 }} title={binary.path}>
+                                {fileName(binary.path)}{' '}
+                                <span style={{ opacity: 0.6, fontWeight: 400 }}>{binary.kind}</span>
+                              </span>
+                              <span style={{ fontWeight: 700 }}>{formatSize(binary.size)}</span>
+                            </div>
+                            {binary.slices.map((slice, i) => (
+                              <div key={i}>
+                                <strong>{slice.arch}</strong>
+                                {slice.platform && ` · ${slice.platform}`}
+                                {slice.minimumOs && ` ${slice.minimumOs}+`}
+                                {slice.encrypted !== undefined &&
+                                  ` · ${slice.encrypted ? 'encrypted' : 'not encrypted'}`}
+                                {' · '}
+                                {slice.segments
+                                  .filter((s) => s.fileSize > 0)
+                                  .map((s) => `${s.name} ${formatSize(s.fileSize)}`)
+                                  .join(', ')}
+                              </div>
+                            ))}
+                            {binary.linkedLibraries.length > 0 && (
+                              <div title={binary.linkedLibraries.join('\n')}>
+                                Links {binary.linkedLibraries.map(fileName).join(', ')}
+                              </div>
+                            )}
+                          </div>
+                        ))}
+                      </div>
+                    </>
+                  ) : (
+                    <DetailFlag ok={false} text={`App bundle not decoded: ${report.ipaError}`} />
+                  )}
+                </div>
+              )}
+
               {/* Composition Bar */}
               <h4
                 style={{
@@ -0,0 +1,277 @@
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { save } from '@tauri-apps/plugin-dialog';
+import { CloseIcon, DownloadIcon, GitCompareIcon, LoaderIcon } from './Icons';
+import { ArtifactDiff, FileChange } from '../types/artifactDiff';
+import { BuildHistory } from '../types/project';
+
+interface ArtifactComparisonProps {
+  baseBuild: BuildHistory;
+  targetBuild: BuildHistory;
+  appName: string;
+  onClose: () => void;
+}
+
+const formatSize = (bytes: number) => {
+  if (bytes === 0) return '0 B';
+  const k = 1024;
+  const sizes = ['B', 'KB', 'MB', 'GB'];
+  const i = Math.floor(Math.log(bytes) / Math.log(k));
+  return parseFloat((bytes / Math.pow(k, i)).toFixed(2)) + ' ' + sizes[i];
+};
+
+const buildLabel = (build: BuildHistory) => `${build.version} (${build.buildNumber})`;
+
+const formatDelta = (delta: number) =>
+  `${delta > 0 ? '+' : delta < 0 ? '-' : '±'}${formatSize(Math.abs(delta))}`;
+
+const deltaColor = (delta: number) =>
+  delta > 0
+    ? 'var(--color-warning)'
+    : delta < 0
+      ? 'var(--color-success)'
+      : 'var(--color-text-tertiary)';
+
+const sectionStyle: React.CSSProperties = {
+  background: 'rgba(255,255,255,0.03)',
+  borderRadius: '20px',
+  padding: '20px',
+  border: '1px solid rgba(255,255,255,0.05)',
+  marginBottom: '16px',
+};
+
+const FileChangeRow: React.FC<{ change: FileChange }> = ({ change }) => (
+  <div
+    style={{
+      display: 'flex',
+      justifyContent: 'space-between',
+      gap: '12px',
+      fontSize: '12px',
+      padding: '4px 0',
+    }}
+  >
+    <span
+      style={{
+        color: 'var(--color-text-secondary)',
+        whiteSpace: 'nowrap',
+        overflow: 'hidden',
+        textOverflow: 'ellipsis',
+      }}
+      title={change.path}
+    >
+      <span style={{ opacity: 0.6 }}>{change.change}</span> {change.path}
+    </span>
+    <span style={{ fontWeight: 600, color: deltaColor(change.delta), flexShrink: 0 }}>
+      {formatDelta(change.delta)}
+    </span>
+  </div>
+);
+
+export const ArtifactComparison: React.FC<ArtifactComparisonProps> = ({
+  baseBuild,
+  targetBuild,
+  appName,
+  onClose,
+}) => {
+  const [diff, setDiff] = useState<ArtifactDiff | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<ArtifactDiff>('compare_artifacts', {
+      baseBuildId: baseBuild.id,
+      targetBuildId: targetBuild.id,
+    })
+      .then(setDiff)
+      .catch((err) => setError(String(err)));
+  }, [baseBuild.id, targetBuild.id]);
+
+  const handleExport = async (format: 'json' | 'markdown') => {
+    if (!diff) return;
+    const extension = format === 'json' ? 'json' : 'md';
+    const savePath = await save({
+      defaultPath: `compare_${baseBuild.buildNumber}_${targetBuild.buildNumber}.${extension}`,
+      filters: [{ name: format === 'json' ? 'JSON' : 'Markdown', extensions: [extension] }],
+    });
+    if (!savePath) return;
+    try {
+      const path = await invoke<string>('export_artifact_diff', { diff, format, savePath });
+      setMessage(`Saved to ${path}`);
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  return (
+    <div
+      className="modal-overlay"
+      style={{ zIndex: 3000, backdropFilter: 'blur(20px)', background: 'rgba(0,0,0,0.75)' }}
+      onClick={onClose}
+    >
+      <div
+        className="card"
+        style={{
+          width: '90%',
+          maxWidth: '720px',
+          maxHeight: '85vh',
+          overflow: 'hidden',
+          display: 'flex',
+          flexDirection: 'column',
+          borderRadius: '32px',
+          boxShadow: '0 40px 100px rgba(0,0,0,0.6)',
+          border: '1px solid rgba(255,255,255,0.1)',
+          background: 'linear-gradient(135deg, #1A1D23 0%, #0D0F12 100%)',
+        }}
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div
+          style={{
+            padding: '24px 32px',
+            borderBottom: '1px solid rgba(255,255,255,0.05)',
+            display: 'flex',
+            justifyContent: 'space-between',
+            alignItems: 'center',
+            background: 'rgba(255,255,255,0.02)',
+          }}
+        >
+          <div style={{ display: 'flex', alignItems: 'center', gap: '16px' }}>
+            <div
+              style={{
+                width: '48px',
+                height: '48px',
+                borderRadius: '14px',
+                background: 'rgba(0, 122, 255, 0.1)',
+                display: 'flex',
+                alignItems: 'center',
+                justifyContent: 'center',
+                color: 'var(--color-primary)',
+              }}
+            >
+              <GitCompareIcon size={24} />
+            </div>
+            <div>
+              <h3 style={{ margin: 0, fontSize: '20px', fontWeight: 800 }}>Compare Builds</h3>
+              <p style={{ margin: 0, fontSize: '13px', color: 'var(--color-text-secondary)' }}>
+                {appName} • {buildLabel(baseBuild)} → {buildLabel(targetBuild)}
+              </p>
+            </div>
+          </div>
+          <button
+            className="btn btn-ghost"
+            onClick={onClose}
+            style={{ width: '40px', height: '40px', borderRadius: '50%', padding: 0 }}
+          >
+            <CloseIcon size={20} />
+          </button>
+        </div>
+
+        <div style={{ flex: 1, overflowY: 'auto', padding: '32px' }}>
+          {!diff && !error && (
+            <div style={{ padding: '60px 0', textAlign: 'center' }}>
+              <LoaderIcon
+                className="animate-spin"
+                size={48}
+                style={{ color: 'var(--color-primary)' }}
+              />
+            </div>
+          )}
+          {diff && (
+            <>
+              <div style={{ ...sectionStyle, textAlign: 'center' }}>
+                <div style={{ fontSize: '13px', color: 'var(--color-text-secondary)' }}>
+                  {formatSize(diff.base.totalSize)} → {formatSize(diff.target.totalSize)}
+                </div>
+                <div
+                  style={{ fontSize: '36px', fontWeight: 900, color: deltaColor(diff.sizeDelta) }}
+                >
+                  {formatDelta(diff.sizeDelta)}
+                </div>
+                <div style={{ display: 'flex', gap: '8px', justifyContent: 'center' }}>
+                  <button className="btn btn-secondary" onClick={() => handleExport('markdown')}>
+                    <DownloadIcon size={14} />
+                    <span>Markdown</span>
+                  </button>
+                  <button className="btn btn-secondary" onClick={() => handleExport('json')}>
+                    <DownloadIcon size={14} />
+                    <span>JSON</span>
+                  </button>
+                </div>
+              </div>
+
+              {(diff.metadataChanges.length > 0 ||
+                diff.permissionsAdded.length > 0 ||
+                diff.permissionsRemoved.length > 0) && (
+                <div style={sectionStyle}>
+                  <h4 style={{ fontSize: '15px', fontWeight: 700, marginBottom: '12px' }}>
+                    {diff.target.fileType === 'IPA' ? 'Info.plist' : 'Manifest'}
+                  </h4>
+                  <div style={{ display: 'grid', gap: '6px', fontSize: '12px' }}>
+                    {diff.metadataChanges.map((change) => (
+                      <div key={change.field} style={{ color: 'var(--color-text-secondary)' }}>
+                        <strong>{change.field}:</strong> {change.base ?? '—'} →{' '}
+                        {change.target ?? '—'}
+                      </div>
+                    ))}
+                    {diff.permissionsAdded.map((permission) => (
+                      <div key={permission} style={{ color: 'var(--color-warning)' }}>
+                        + {permission}
+                      </div>
+                    ))}
+                    {diff.permissionsRemoved.map((permission) => (
+                      <div key={permission} style={{ color: 'var(--color-success)' }}>
+                        − {permission}
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
+
+              {diff.nativeLibraries.length > 0 && (
+                <div style={sectionStyle}>
+                  <h4 style={{ fontSize: '15px', fontWeight: 700, marginBottom: '12px' }}>
+                    Native Libraries
+                  </h4>
+                  {diff.nativeLibraries.map((change) => (
+                    <FileChangeRow key={change.path} change={change} />
+                  ))}
+                </div>
+              )}
+
+              {diff.categories.map((category) => (
+                <div key={category.name} style={sectionStyle}>
+                  <div
+                    style={{
+                      display: 'flex',
+                      justifyContent: 'space-between',
+                      marginBottom: category.files.length > 0 ? '12px' : 0,
+                    }}
+                  >
+                    <h4 style={{ fontSize: '15px', fontWeight: 700, margin: 0 }}>
+                      {category.name}
+                    </h4>
+                    <span style={{ fontSize: '13px', fontWeight: 700 }}>
+                      {formatSize(category.baseSize)} → {formatSize(category.targetSize)}{' '}
+                      <span style={{ color: deltaColor(category.delta) }}>
+                        {formatDelta(category.delta)}
+                      </span>
+                    </span>
+                  </div>
+                  {category.files.slice(0, 50).map((change) => (
+                    <FileChangeRow key={change.path} change={change} />
+                  ))}
+                  {category.files.length > 50 && (
+                    <div style={{ fontSize: '12px', color: 'var(--color-text-tertiary)' }}>
+                      and {category.files.length - 50} more, see the export
+                    </div>
+                  )}
+                </div>
+              ))}
+            </>
+          )}
+          {message && <p style={{ color: 'var(--color-success)', fontSize: '13px' }}>{message}</p>}
+          {error && <p style={{ color: 'var(--color-error)', fontSize: '13px' }}>{error}</p>}
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,293 @@
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { CloseIcon, DownloadIcon, LoaderIcon, PackageIcon, SmartphoneIcon } from './Icons';
+import { ApkMode, ApkSet, DownloadSizeReport } from '../types/bundletool';
+import { Emulator } from '../hooks/useEmulators';
+
+interface BundletoolPanelProps {
+  artifactPath: string;
+  projectId: string;
+  appName: string;
+  onClose: () => void;
+}
+
+const formatSize = (bytes: number) => {
+  if (bytes === 0) return '0 B';
+  const k = 1024;
+  const sizes = ['B', 'KB', 'MB', 'GB'];
+  const i = Math.floor(Math.log(bytes) / Math.log(k));
+  return parseFloat((bytes / Math.pow(k, i)).toFixed(2)) + ' ' + sizes[i];
+};
+
+const sectionStyle: React.CSSProperties = {
+  background: 'rgba(255,255,255,0.03)',
+  borderRadius: '20px',
+  padding: '20px',
+  border: '1px solid rgba(255,255,255,0.05)',
+  marginBottom: '16px',
+};
+
+export const BundletoolPanel: React.FC<BundletoolPanelProps> = ({
+  artifactPath,
+  projectId,
+  appName,
+  onClose,
+}) => {
+  const [emulators, setEmulators] = useState<Emulator[]>([]);
+  const [deviceId, setDeviceId] = useState('');
+  const [busy, setBusy] = useState<string | null>(null);
+  const [apkSet, setApkSet] = useState<ApkSet | null>(null);
+  const [sizes, setSizes] = useState<DownloadSizeReport | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<Emulator[]>('list_emulators')
+      .then((all) => {
+        const running = all.filter((e) => e.platform === 'android' && e.state === 'Booted');
+        setEmulators(running);
+        if (running.length > 0) setDeviceId(running[0].id);
+      })
+      .catch((err) => console.error('Failed to list emulators', err));
+  }, []);
+
+  const run = async (label: string, action: () => Promise<void>) => {
+    setBusy(label);
+    setError(null);
+    setMessage(null);
+    try {
+      await action();
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleBuild = (mode: ApkMode) =>
+    run(mode, async () => {
+      setApkSet(
+        await invoke<ApkSet>('build_apks', {
+          artifactPath,
+          projectId,
+          mode,
+          deviceId: mode === 'device' ? deviceId : null,
+        }),
+      );
+    });
+
+  const handleEstimate = (forDevice: boolean) =>
+    run('sizes', async () => {
+      setSizes(
+        await invoke<DownloadSizeReport>('estimate_download_sizes', {
+          artifactPath,
+          projectId,
+          deviceId: forDevice ? deviceId : null,
+        }),
+      );
+    });
+
+  const handleInstall = () =>
+    run('install', async () => {
+      setMessage(await invoke<string>('install_apks', { artifactPath, projectId, deviceId }));
+    });
+
+  const spinner = (label: string, icon: React.ReactNode) =>
+    busy === label ? <LoaderIcon size={14} className="animate-spin" /> : icon;
+
+  const dimensionNames = sizes?.sizes[0] ? Object.keys(sizes.sizes[0].dimensions) : [];
+
+  return (
+    <div
+      className="modal-overlay"
+      style={{ zIndex: 3000, backdropFilter: 'blur(20px)', background: 'rgba(0,0,0,0.75)' }}
+      onClick={onClose}
+    >
+      <div
+        className="card"
+        style={{
+          width: '90%',
+          maxWidth: '640px',
+          maxHeight: '85vh',
+          overflow: 'hidden',
+          display: 'flex',
+          flexDirection: 'column',
+          borderRadius: '32px',
+          boxShadow: '0 40px 100px rgba(0,0,0,0.6)',
+          border: '1px solid rgba(255,255,255,0.1)',
+          background: 'linear-gradient(135deg, #1A1D23 0%, #0D0F12 100%)',
+        }}
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div
+          style={{
+            padding: '24px 32px',
+            borderBottom: '1px solid rgba(255,255,255,0.05)',
+            display: 'flex',
+            justifyContent: 'space-between',
+            alignItems: 'center',
+            background: 'rgba(255,255,255,0.02)',
+          }}
+        >
+          <div style={{ display: 'flex', alignItems: 'center', gap: '16px' }}>
+            <div
+              style={{
+                width: '48px',
+                height: '48px',
+                borderRadius: '14px',
+                background: 'rgba(52, 199, 89, 0.1)',
+                display: 'flex',
+                alignItems: 'center',
+                justifyContent: 'center',
+                color: 'var(--color-success)',
+              }}
+            >
+              <PackageIcon size={24} />
+            </div>
+            <div>
+              <h3 style={{ margin: 0, fontSize: '20px', fontWeight: 800 }}>App Bundle Tools</h3>
+              <p style={{ margin: 0, fontSize: '13px', color: 'var(--color-text-secondary)' }}>
+                {appName} • APKs, download sizes and installs via bundletool
+              </p>
+            </div>
+          </div>
+          <button
+            className="btn btn-ghost"
+            onClick={onClose}
+            style={{ width: '40px', height: '40px', borderRadius: '50%', padding: 0 }}
+          >
+            <CloseIcon size={20} />
+          </button>
+        </div>
+
+        <div style={{ flex: 1, overflowY: 'auto', padding: '32px' }}>
+          <div style={sectionStyle}>
+            <label className="input-label">Device</label>
+            <select
+              className="input"
+              value={deviceId}
+              onChange={(e) => setDeviceId(e.target.value)}
+              style={{ cursor: 'pointer' }}
+            >
+              {emulators.length === 0 && <option value="">No running Android emulator</option>}
+              {emulators.map((emulator) => (
+                <option key={emulator.id} value={emulator.id}>
+                  {emulator.name} ({emulator.id})
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <div style={sectionStyle}>
+            <h4 style={{ fontSize: '15px', fontWeight: 700, marginBottom: '12px' }}>Build APKs</h4>
+            <div style={{ display: 'flex', gap: '8px', flexWrap: 'wrap' }}>
+              <button
+                className="btn btn-secondary"
+                disabled={!!busy}
+                onClick={() => handleBuild('universal')}
+              >
+                {spinner('universal', <DownloadIcon size={14} />)}
+                <span>Universal APK</span>
+              </button>
+              <button
+                className="btn btn-secondary"
+                disabled={!!busy}
+                onClick={() => handleBuild('default')}
+              >
+                {spinner('default', <PackageIcon size={14} />)}
+                <span>APK Set (All Devices)</span>
+              </button>
+              <button
+                className="btn btn-secondary"
+                disabled={!!busy || !deviceId}
+                onClick={() => handleBuild('device')}
+              >
+                {spinner('device', <SmartphoneIcon size={14} />)}
+                <span>APK Set (This Device)</span>
+              </button>
+            </div>
+            {apkSet && (
+              <div
+                style={{
+                  marginTop: '12px',
+                  fontSize: '12px',
+                  color: 'var(--color-text-secondary)',
+                  lineHeight: 1.6,
+                  wordBreak: 'break-all',
+                }}
+              >
+                <div>
+                  {apkSet.universalApk || apkSet.apksPath} ({formatSize(apkSet.size)})
+                </div>
+                <div>Signed with {apkSet.signedWith}</div>
+              </div>
+            )}
+          </div>
+
+          <div style={sectionStyle}>
+            <h4 style={{ fontSize: '15px', fontWeight: 700, marginBottom: '12px' }}>
+              Download Size
+            </h4>
+            <div style={{ display: 'flex', gap: '8px' }}>
+              <button
+                className="btn btn-secondary"
+                disabled={!!busy}
+                onClick={() => handleEstimate(false)}
+              >
+                {spinner('sizes', <PackageIcon size={14} />)}
+                <span>Per Configuration</span>
+              </button>
+              <button
+                className="btn btn-secondary"
+                disabled={!!busy || !deviceId}
+                onClick={() => handleEstimate(true)}
+              >
+                <SmartphoneIcon size={14} />
+                <span>For This Device</span>
+              </button>
+            </div>
+            {sizes && (
+              <table style={{ width: '100%', marginTop: '12px', fontSize: '12px' }}>
+                <thead>
+                  <tr style={{ color: 'var(--color-text-tertiary)', textAlign: 'left' }}>
+                    {dimensionNames.map((name) => (
+                      <th key={name}>{name}</th>
+                    ))}
+                    <th style={{ textAlign: 'right' }}>Min</th>
+                    <th style={{ textAlign: 'right' }}>Max</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {sizes.sizes.map((size, index) => (
+                    <tr key={index} style={{ color: 'var(--color-text-secondary)' }}>
+                      {dimensionNames.map((name) => (
+                        <td key={name}>{size.dimensions[name]}</td>
+                      ))}
+                      <td style={{ textAlign: 'right' }}>{formatSize(size.min)}</td>
+                      <td style={{ textAlign: 'right' }}>{formatSize(size.max)}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            )}
+          </div>
+
+          <div style={sectionStyle}>
+            <h4 style={{ fontSize: '15px', fontWeight: 700, marginBottom: '12px' }}>Install</h4>
+            <button
+              className="btn btn-primary"
+              disabled={!!busy || !deviceId}
+              onClick={handleInstall}
+            >
+              {spinner('install', <SmartphoneIcon size={14} />)}
+              <span>Install on Device</span>
+            </button>
+          </div>
+
+          {message && <p style={{ color: 'var(--color-success)', fontSize: '13px' }}>{message}</p>}
+          {error && <p style={{ color: 'var(--color-error)', fontSize: '13px' }}>{error}</p>}
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,295 @@
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
+import { AlertCircleIcon, CheckIcon, FolderIcon, LoaderIcon, SearchIcon } from './Icons';
+import { CertificateInfo, KeystoreInfo } from '../types/keystore';
+
+interface KeystoreInspectorProps {
+  initialPath?: string | null;
+}
+
+const inputStyle: React.CSSProperties = {
+  background: 'var(--color-sidebar)',
+  borderRadius: '12px',
+};
+
+const formatDate = (date: string) => new Date(date).toLocaleDateString();
+
+const Fingerprint: React.FC<{ label: string; value: string }> = ({ label, value }) => {
+  const [copied, setCopied] = useState(false);
+
+  const handleCopy = async () => {
+    await navigator.clipboard.writeText(value);
+    setCopied(true);
+    setTimeout(() => setCopied(false), 1500);
+  };
+
+  return (
+    <div style={{ display: 'flex', alignItems: 'flex-start', gap: '8px', marginTop: '6px' }}>
+      <span
+        style={{
+          fontSize: '11px',
+          fontWeight: 700,
+          color: 'var(--color-text-tertiary)',
+          width: '52px',
+        }}
+      >
+        {label}
+      </span>
+      <code
+        style={{
+          flex: 1,
+          fontSize: '11px',
+          fontFamily: '"SF Mono", monospace',
+          wordBreak: 'break-all',
+          color: 'var(--color-text-secondary)',
+        }}
+      >
+        {value}
+      </code>
+      <button
+        type="button"
+        className="btn btn-secondary"
+        onClick={handleCopy}
+        style={{ padding: '2px 8px', fontSize: '11px', flexShrink: 0 }}
+      >
+        {copied ? <CheckIcon size={12} /> : 'Copy'}
+      </button>
+    </div>
+  );
+};
+
+const CertificateDetails: React.FC<{ certificate: CertificateInfo; warningDays: number }> = ({
+  certificate,
+  warningDays,
+}) => {
+  const expiryColor =
+    certificate.daysUntilExpiry < 0
+      ? 'var(--color-error)'
+      : certificate.daysUntilExpiry <= warningDays
+        ? 'var(--color-warning)'
+        : 'var(--color-text-secondary)';
+
+  return (
+    <div style={{ fontSize: '12px', color: 'var(--color-text-secondary)', lineHeight: 1.6 }}>
+      <div>{certificate.subject}</div>
+      <div style={{ color: expiryColor }}>
+        Valid {formatDate(certificate.validFrom)} – {formatDate(certificate.validUntil)}
+        {certificate.daysUntilExpiry < 0
+          ? ' (expired)'
+          : ` (${certificate.daysUntilExpiry} days left)`}
+      </div>
+      <div>
+        {certificate.publicKey} · {certificate.signatureAlgorithm}
+      </div>
+      <Fingerprint label="SHA-1" value={certificate.sha1} />
+      <Fingerprint label="SHA-256" value={certificate.sha256} />
+    </div>
+  );
+};
+
+export const KeystoreInspector: React.FC<KeystoreInspectorProps> = ({ initialPath }) => {
+  const [path, setPath] = useState(initialPath || '');
+  const [password, setPassword] = useState('');
+  const [warningDays, setWarningDays] = useState(90);
+  const [isInspecting, setIsInspecting] = useState(false);
+  const [info, setInfo] = useState<KeystoreInfo | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (initialPath) setPath(initialPath);
+  }, [initialPath]);
+
+  const handleBrowse = async () => {
+    const selected = await open({
+      multiple: false,
+      title: 'Select Keystore',
+      filters: [{ name: 'Keystore', extensions: ['jks', 'keystore', 'p12', 'pfx'] }],
+    });
+    if (selected && typeof selected === 'string') setPath(selected);
+  };
+
+  const handleInspect = async () => {
+    setIsInspecting(true);
+    setError(null);
+    setInfo(null);
+    try {
+      setInfo(
+        await invoke<KeystoreInfo>('inspect_keystore', {
+          path,
+          password,
+          expiryWarningDays: warningDays,
+        }),
+      );
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setIsInspecting(false);
+    }
+  };
+
+  return (
+    <section
+      className="card"
+      style={{ padding: '24px', borderRadius: '24px', border: '1px solid var(--color-border)' }}
+    >
+      <div style={{ display: 'flex', alignItems: 'center', gap: '12px', marginBottom: '24px' }}>
+        <div
+          style={{
+            width: '40px',
+            height: '40px',
+            borderRadius: '12px',
+            background: 'rgba(175, 82, 222, 0.1)',
+            display: 'flex',
+            alignItems: 'center',
+            justifyContent: 'center',
+          }}
+        >
+          <SearchIcon size={20} style={{ color: '#af52de' }} />
+        </div>
+        <div>
+          <h3 style={{ fontSize: '18px', fontWeight: 800 }}>Inspect Keystore</h3>
+          <p style={{ fontSize: '12px', color: 'var(--color-text-tertiary)' }}>
+            Aliases, expiry and the fingerprints Firebase and Google Sign-In ask for.
+          </p>
+        </div>
+      </div>
+
+      <div className="form-group" style={{ marginBottom: '20px' }}>
+        <label className="input-label">Keystore (JKS or PKCS12)</label>
+        <div style={{ display: 'flex', gap: '8px' }}>
+          <input
+            type="text"
+            value={path}
+            onChange={(e) => setPath(e.target.value)}
+            className="input"
+            placeholder="/Users/me/keys/upload.jks"
+            style={inputStyle}
+          />
+          <button
+            type="button"
+            className="btn btn-secondary"
+            onClick={handleBrowse}
+            style={{ flexShrink: 0, borderRadius: '12px' }}
+          >
+            <FolderIcon size={16} />
+          </button>
+        </div>
+      </div>
+
+      <div
+        style={{
+          display: 'grid',
+          gridTemplateColumns: '1fr 1fr',
+          gap: '20px',
+          marginBottom: '20px',
+        }}
+      >
+        <div className="form-group">
+          <label className="input-label">Store Password</label>
+          <input
+            type="password"
+            value={password}
+            onChange={(e) => setPassword(e.target.value)}
+            className="input"
+            placeholder="••••••••••••"
+            style={inputStyle}
+          />
+        </div>
+        <div className="form-group">
+          <label className="input-label">Warn Within (Days)</label>
+          <input
+            type="number"
+            min={0}
+            value={warningDays}
+            onChange={(e) => setWarningDays(parseInt(e.target.value) || 0)}
+            className="input"
+            style={inputStyle}
+          />
+        </div>
+      </div>
+
+      <button
+        className="btn btn-secondary"
+        onClick={handleInspect}
+        disabled={isInspecting || !path}
+        style={{ width: '100%', height: '44px', borderRadius: '14px', fontWeight: 700 }}
+      >
+        {isInspecting ? (
+          <LoaderIcon size={16} className="animate-spin" />
+        ) : (
+          <SearchIcon size={16} />
+        )}
+        <span>Inspect</span>
+      </button>
+
+      {error && (
+        <p style={{ color: 'var(--color-error)', fontSize: '13px', marginTop: '16px' }}>{error}</p>
+      )}
+
+      {info && (
+        <div style={{ marginTop: '20px', display: 'flex', flexDirection: 'column', gap: '12px' }}>
+          {info.warnings.map((warning) => (
+            <div
+              key={warning}
+              style={{
+                display: 'flex',
+                gap: '8px',
+                alignItems: 'center',
+                fontSize: '13px',
+                color: 'var(--color-warning)',
+                background: 'rgba(255, 149, 0, 0.08)',
+                padding: '10px 12px',
+                borderRadius: '12px',
+              }}
+            >
+              <AlertCircleIcon size={16} />
+              {warning}
+            </div>
+          ))}
+
+          {info.entries.map((entry) => (
+            <div
+              key={entry.alias}
+              style={{
+                background: 'var(--color-sidebar)',
+                border: '1px solid var(--color-border)',
+                borderRadius: '16px',
+                padding: '16px',
+              }}
+            >
+              <div
+                style={{ display: 'flex', justifyContent: 'space-between', marginBottom: '8px' }}
+              >
+                <span style={{ fontWeight: 800, fontSize: '14px' }}>{entry.alias}</span>
+                <span style={{ fontSize: '11px', color: 'var(--color-text-tertiary)' }}>
+                  {info.format} ·{' '}
+                  {entry.kind === 'privateKey' ? 'Private key' : 'Trusted certificate'}
+                  {entry.created && ` · added ${formatDate(entry.created)}`}
+                </span>
+              </div>
+              {entry.certificates.slice(0, 1).map((certificate) => (
+                <CertificateDetails
+                  key={certificate.sha256}
+                  certificate={certificate}
+                  warningDays={warningDays}
+                />
+              ))}
+              {entry.certificates.length > 1 && (
+                <p
+                  style={{
+                    fontSize: '11px',
+                    color: 'var(--color-text-tertiary)',
+                    marginTop: '8px',
+                  }}
+                >
+                  + {entry.certificates.length - 1} certificate(s) in the chain
+                </p>
+              )}
+            </div>
+          ))}
+        </div>
+      )}
+    </section>
+  );
+};
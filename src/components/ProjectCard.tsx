@@ -1,4 +1,5 @@
 import React, { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import {
   AppleIcon,
   AndroidIcon,
@@ -25,6 +26,8 @@ interface ProjectCardProps {
       releaseNote?: string;
       androidFormat?: 'apk' | 'aab';
       sendToAppDistribution?: boolean;
+      uploadToPlayStore?: boolean;
+      profile?: string;
     },
   ) => void;
   onSelect: () => void;
@@ -73,6 +76,16 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
     return localStorage.getItem(`send_to_app_distribution_${project.id}`) === 'true';
   });
 
+  const [uploadToPlayStore, setUploadToPlayStore] = useState(() => {
+    return localStorage.getItem(`upload_to_playstore_${project.id}`) === 'true';
+  });
+
+  const [profileName, setProfileName] = useState(() => {
+    return localStorage.getItem(`build_profile_${project.id}`) || '';
+  });
+  // The stored name may belong to a profile that was since renamed or removed
+  const profile = (project.profiles || []).find((p) => p.name === profileName);
+
   const [isHovered, setIsHovered] = useState(false);
 
   // Per-platform building state
@@ -96,13 +109,40 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
     localStorage.setItem(`upload_to_appstore_${project.id}`, String(checked));
   };
 
+  const handlePlayStoreUploadChange = (checked: boolean) => {
+    setUploadToPlayStore(checked);
+    localStorage.setItem(`upload_to_playstore_${project.id}`, String(checked));
+  };
+
+  const handleProfileChange = (name: string) => {
+    setProfileName(name);
+    localStorage.setItem(`build_profile_${project.id}`, name);
+  };
+
   const handleReleaseNoteChange = (value: string) => {
     setReleaseNote(value);
     localStorage.setItem(`release_note_${project.id}`, value);
   };
 
   const hasIosCredentials = !!project.ios.config?.apiKey && !!project.ios.config?.apiIssuer;
-  const hasReleaseNote = !!releaseNote.trim();
+  // Google Play only accepts App Bundles
+  const androidCredentialId = profile?.androidCredentialId || project.credentials.androidId;
+  const canUploadToPlayStore = !!androidCredentialId && androidFormat === 'aab';
+  const handleGenerateReleaseNote = async (platform: 'ios' | 'android') => {
+    try {
+      const notes = await invoke<string>('generate_release_notes', {
+        project,
+        platform,
+        profile: profile?.name,
+      });
+      if (notes) handleReleaseNoteChange(notes);
+    } catch (e) {
+      console.error('Failed to generate release notes', e);
+    }
+  };
+
+  // With automatic release notes the build generates them when the field is left empty
+  const hasReleaseNote = !!releaseNote.trim() || !!project.release?.autoReleaseNotes;
   const canBuildIos = hasReleaseNote && !isBuildingIos;
   const canBuildAndroid = hasReleaseNote && !isBuildingAndroid;
 
@@ -309,6 +349,36 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
         </div>
       </div>
 
+      {/* Build Profile Selector */}
+      {(project.profiles || []).length > 0 && (
+        <div style={{ marginBottom: 'var(--spacing-md)' }} onClick={(e) => e.stopPropagation()}>
+          <label
+            style={{
+              display: 'block',
+              fontSize: '12px',
+              marginBottom: '6px',
+              fontWeight: 600,
+              color: 'var(--color-text-secondary)',
+            }}
+          >
+            Build Profile
+          </label>
+          <select
+            className="input"
+            value={profile?.name || ''}
+            onChange={(e) => handleProfileChange(e.target.value)}
+            style={{ fontSize: '13px', cursor: 'pointer' }}
+          >
+            <option value="">Default (project settings)</option>
+            {(project.profiles || []).map((p) => (
+              <option key={p.name} value={p.name}>
+                {p.name}
+              </option>
+            ))}
+          </select>
+        </div>
+      )}
+
       {/* Release Note Input */}
       <div style={{ marginBottom: 'var(--spacing-md)' }} onClick={(e) => e.stopPropagation()}>
         <label
@@ -320,12 +390,39 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
             color: 'var(--color-text-secondary)',
           }}
         >
-          Release Note <span style={{ color: 'var(--color-error)' }}>*</span>
+          Release Note{' '}
+          {!project.release?.autoReleaseNotes && (
+            <span style={{ color: 'var(--color-error)' }}>*</span>
+          )}
+          <span style={{ float: 'right', display: 'flex', gap: '8px', fontWeight: 500 }}>
+            <button
+              type="button"
+              className="btn btn-ghost"
+              style={{ padding: '0 4px', fontSize: '11px' }}
+              title="Generate from git history since the last iOS build"
+              onClick={() => handleGenerateReleaseNote('ios')}
+            >
+              ✨ iOS
+            </button>
+            <button
+              type="button"
+              className="btn btn-ghost"
+              style={{ padding: '0 4px', fontSize: '11px' }}
+              title="Generate from git history since the last Android build"
+              onClick={() => handleGenerateReleaseNote('android')}
+            >
+              ✨ Android
+            </button>
+          </span>
         </label>
         <textarea
           value={releaseNote}
           onChange={(e) => handleReleaseNoteChange(e.target.value)}
-          placeholder="Enter release notes for this build..."
+          placeholder={
+            project.release?.autoReleaseNotes
+              ? 'Leave empty to generate release notes from git history...'
+              : 'Enter release notes for this build...'
+          }
           className="input"
           style={{
             minHeight: '70px',
@@ -381,7 +478,7 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
           </span>
         </label>
 
-        {/* Send to App Distribution (Android) */}
+        {/* Send to Firebase App Distribution */}
         <label
           style={{
             flex: 1,
@@ -409,6 +506,41 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
           />
           <span style={{ fontWeight: 500 }}>App Distribution</span>
         </label>
+
+        {/* Upload to Google Play (Android) */}
+        <label
+          style={{
+            flex: 1,
+            display: 'flex',
+            alignItems: 'center',
+            gap: 'var(--spacing-sm)',
+            fontSize: '13px',
+            cursor: canUploadToPlayStore ? 'pointer' : 'not-allowed',
+            userSelect: 'none',
+            color: canUploadToPlayStore ? 'var(--color-text)' : 'var(--color-text-secondary)',
+            padding: 'var(--spacing-sm)',
+            borderRadius: 'var(--radius-sm)',
+          }}
+        >
+          <input
+            type="checkbox"
+            checked={uploadToPlayStore && canUploadToPlayStore}
+            onChange={(e) => handlePlayStoreUploadChange(e.target.checked)}
+            disabled={!canUploadToPlayStore}
+            style={{
+              cursor: canUploadToPlayStore ? 'pointer' : 'not-allowed',
+              width: '16px',
+              height: '16px',
+              accentColor: 'var(--color-success)',
+            }}
+          />
+          <span style={{ fontWeight: 500 }}>
+            Google Play{' '}
+            {!androidCredentialId && (
+              <span style={{ fontSize: '10px', opacity: 0.7 }}>(No credentials)</span>
+            )}
+          </span>
+        </label>
       </div>
 
       {/* Build Buttons */}
@@ -418,7 +550,12 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
           onClick={(e) => {
             e.stopPropagation();
             if (canBuildIos) {
-              onBuild('ios', { uploadToAppStore, releaseNote: releaseNote.trim() });
+              onBuild('ios', {
+                uploadToAppStore,
+                releaseNote: releaseNote.trim(),
+                sendToAppDistribution,
+                profile: profile?.name,
+              });
             }
           }}
           disabled={!canBuildIos}
@@ -455,6 +592,8 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
                 releaseNote: releaseNote.trim(),
                 androidFormat,
                 sendToAppDistribution,
+                uploadToPlayStore: uploadToPlayStore && canUploadToPlayStore,
+                profile: profile?.name,
               });
             }
           }}
@@ -0,0 +1,251 @@
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { CloseIcon, LoaderIcon, TrendingUpIcon } from './Icons';
+import { SizeTrend, SizeTrendBuild } from '../types/sizeHistory';
+
+interface SizeHistoryProps {
+  projectId: string;
+  appName: string;
+  onClose: () => void;
+}
+
+const formatSize = (bytes: number) => {
+  if (bytes === 0) return '0 B';
+  const k = 1024;
+  const sizes = ['B', 'KB', 'MB', 'GB'];
+  const i = Math.floor(Math.log(bytes) / Math.log(k));
+  return parseFloat((bytes / Math.pow(k, i)).toFixed(2)) + ' ' + sizes[i];
+};
+
+const buildLabel = (build: SizeTrendBuild) => `${build.version} (${build.buildNumber})`;
+
+const CHART_WIDTH = 640;
+const CHART_HEIGHT = 200;
+const SERIES_COLORS = ['#007AFF', '#34C759', '#FF9500', '#AF52DE', '#FF2D55', '#8E8E93'];
+
+const sectionStyle: React.CSSProperties = {
+  background: 'rgba(255,255,255,0.03)',
+  borderRadius: '20px',
+  padding: '20px',
+  border: '1px solid rgba(255,255,255,0.05)',
+  marginBottom: '16px',
+};
+
+// One line per series, builds spread evenly from left to right
+const TrendChart: React.FC<{ trend: SizeTrend }> = ({ trend }) => {
+  const series = [
+    { name: 'Total', sizes: trend.builds.map((b) => b.totalSize) },
+    ...trend.categories,
+  ];
+  const max = Math.max(...trend.builds.map((b) => b.totalSize), 1);
+  const x = (index: number) =>
+    trend.builds.length === 1 ? CHART_WIDTH / 2 : (index / (trend.builds.length - 1)) * CHART_WIDTH;
+  const y = (size: number) => CHART_HEIGHT - (size / max) * (CHART_HEIGHT - 10);
+
+  return (
+    <>
+      <svg
+        viewBox={`-6 0 ${CHART_WIDTH + 12} ${CHART_HEIGHT + 6}`}
+        style={{ width: '100%', height: 'auto', overflow: 'visible' }}
+      >
+        <line
+          x1={0}
+          y1={CHART_HEIGHT}
+          x2={CHART_WIDTH}
+          y2={CHART_HEIGHT}
+          stroke="rgba(255,255,255,0.1)"
+        />
+        {series.map((s, i) => (
+          <g key={s.name} stroke={SERIES_COLORS[i % SERIES_COLORS.length]}>
+            <polyline
+              fill="none"
+              strokeWidth={i === 0 ? 3 : 2}
+              points={s.sizes.map((size, index) => `${x(index)},${y(size)}`).join(' ')}
+            />
+            {s.sizes.map((size, index) => (
+              <circle
+                key={index}
+                cx={x(index)}
+                cy={y(size)}
+                r={3}
+                fill={SERIES_COLORS[i % SERIES_COLORS.length]}
+              >
+                <title>
+                  {buildLabel(trend.builds[index])} • {s.name}: {formatSize(size)}
+                </title>
+              </circle>
+            ))}
+          </g>
+        ))}
+      </svg>
+      <div
+        style={{
+          display: 'flex',
+          justifyContent: 'space-between',
+          fontSize: '11px',
+          color: 'var(--color-text-tertiary)',
+          marginTop: '6px',
+        }}
+      >
+        <span>{buildLabel(trend.builds[0])}</span>
+        {trend.builds.length > 1 && (
+          <span>{buildLabel(trend.builds[trend.builds.length - 1])}</span>
+        )}
+      </div>
+      <div style={{ display: 'flex', flexWrap: 'wrap', gap: '12px', marginTop: '12px' }}>
+        {series.map((s, i) => (
+          <span
+            key={s.name}
+            style={{
+              display: 'flex',
+              alignItems: 'center',
+              gap: '6px',
+              fontSize: '12px',
+              color: 'var(--color-text-secondary)',
+            }}
+          >
+            <span
+              style={{
+                width: '10px',
+                height: '10px',
+                borderRadius: '50%',
+                background: SERIES_COLORS[i % SERIES_COLORS.length],
+              }}
+            />
+            {s.name}: {formatSize(s.sizes[s.sizes.length - 1])}
+          </span>
+        ))}
+      </div>
+    </>
+  );
+};
+
+export const SizeHistory: React.FC<SizeHistoryProps> = ({ projectId, appName, onClose }) => {
+  const [trends, setTrends] = useState<SizeTrend[] | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<SizeTrend[]>('get_size_history', { projectId })
+      .then(setTrends)
+      .catch((err) => setError(String(err)));
+  }, [projectId]);
+
+  return (
+    <div
+      className="modal-overlay"
+      style={{ zIndex: 3000, backdropFilter: 'blur(20px)', background: 'rgba(0,0,0,0.75)' }}
+      onClick={onClose}
+    >
+      <div
+        className="card"
+        style={{
+          width: '90%',
+          maxWidth: '760px',
+          maxHeight: '85vh',
+          overflow: 'hidden',
+          display: 'flex',
+          flexDirection: 'column',
+          borderRadius: '32px',
+          boxShadow: '0 40px 100px rgba(0,0,0,0.6)',
+          border: '1px solid rgba(255,255,255,0.1)',
+          background: 'linear-gradient(135deg, #1A1D23 0%, #0D0F12 100%)',
+        }}
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div
+          style={{
+            padding: '24px 32px',
+            borderBottom: '1px solid rgba(255,255,255,0.05)',
+            display: 'flex',
+            justifyContent: 'space-between',
+            alignItems: 'center',
+            background: 'rgba(255,255,255,0.02)',
+          }}
+        >
+          <div style={{ display: 'flex', alignItems: 'center', gap: '16px' }}>
+            <div
+              style={{
+                width: '48px',
+                height: '48px',
+                borderRadius: '14px',
+                background: 'rgba(0, 122, 255, 0.1)',
+                display: 'flex',
+                alignItems: 'center',
+                justifyContent: 'center',
+                color: 'var(--color-primary)',
+              }}
+            >
+              <TrendingUpIcon size={24} />
+            </div>
+            <div>
+              <h3 style={{ margin: 0, fontSize: '20px', fontWeight: 800 }}>Size History</h3>
+              <p style={{ margin: 0, fontSize: '13px', color: 'var(--color-text-secondary)' }}>
+                {appName} • successful builds over time
+              </p>
+            </div>
+          </div>
+          <button
+            className="btn btn-ghost"
+            onClick={onClose}
+            style={{ width: '40px', height: '40px', borderRadius: '50%', padding: 0 }}
+          >
+            <CloseIcon size={20} />
+          </button>
+        </div>
+
+        <div style={{ flex: 1, overflowY: 'auto', padding: '32px' }}>
+          {!trends && !error && (
+            <div style={{ padding: '60px 0', textAlign: 'center' }}>
+              <LoaderIcon
+                className="animate-spin"
+                size={48}
+                style={{ color: 'var(--color-primary)' }}
+              />
+            </div>
+          )}
+          {trends && trends.length === 0 && (
+            <p style={{ color: 'var(--color-text-secondary)', fontSize: '13px' }}>
+              No sizes recorded yet. They are saved with each successful build from now on.
+            </p>
+          )}
+          {trends?.map((trend) => {
+            const first = trend.builds[0];
+            const last = trend.builds[trend.builds.length - 1];
+            const growth = last.totalSize - first.totalSize;
+            return (
+              <div key={`${trend.platform}-${trend.fileType}`} style={sectionStyle}>
+                <div
+                  style={{
+                    display: 'flex',
+                    justifyContent: 'space-between',
+                    alignItems: 'baseline',
+                    marginBottom: '16px',
+                  }}
+                >
+                  <h4 style={{ fontSize: '15px', fontWeight: 700, margin: 0 }}>
+                    {trend.platform === 'ios' ? 'iOS' : 'Android'} • {trend.fileType}
+                  </h4>
+                  <span style={{ fontSize: '13px', color: 'var(--color-text-secondary)' }}>
+                    {trend.builds.length} build(s) •{' '}
+                    <span
+                      style={{
+                        fontWeight: 700,
+                        color: growth > 0 ? 'var(--color-warning)' : 'var(--color-success)',
+                      }}
+                    >
+                      {growth >= 0 ? '+' : '-'}
+                      {formatSize(Math.abs(growth))}
+                    </span>{' '}
+                    since {buildLabel(first)}
+                  </span>
+                </div>
+                <TrendChart trend={trend} />
+              </div>
+            );
+          })}
+          {error && <p style={{ color: 'var(--color-error)', fontSize: '13px' }}>{error}</p>}
+        </div>
+      </div>
+    </div>
+  );
+};
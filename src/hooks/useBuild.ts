@@ -2,7 +2,9 @@ import { useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { useBuildStore, buildKey } from '../stores/buildStore';
-import { Project, BuildHistory } from '../types/project';
+import { useProjectStore } from '../stores/projectStore';
+import { Project, BuildHistory, BuildStepReport } from '../types/project';
+import { BuildEventEnvelope } from '../types/buildEvent';
 
 export const useBuild = () => {
   const { updateBuild, startBuild: startBuildStore, addToHistory, clearActive } = useBuildStore();
@@ -16,6 +18,8 @@ export const useBuild = () => {
         releaseNote?: string;
         androidFormat?: 'apk' | 'aab';
         sendToAppDistribution?: boolean;
+        uploadToPlayStore?: boolean;
+        profile?: string; // Name of one of the project's build profiles
       },
     ) => {
       const key = buildKey(project.id, platform);
@@ -31,51 +35,18 @@ export const useBuild = () => {
         logs: `Starting ${platform} build for ${project.name}...\n`,
         releaseNote: options?.releaseNote || '',
         format: platform === 'android' ? options?.androidFormat : undefined,
+        profile: options?.profile,
       };
 
       startBuildStore(key, initialBuild);
 
-      // Listen for events, filtering by BOTH projectId AND platform
-      const unlistenLogs = await listen<any>('build-log', (event) => {
-        const data = typeof event.payload === 'string' ? JSON.parse(event.payload) : event.payload;
-        if (data.projectId !== project.id || data.platform !== platform) return;
-
-        updateBuild(key, (prev) => ({
-          ...prev,
-          logs: prev.logs + (data.payload || '') + '\n',
-        }));
-      });
-
-      const unlistenLogFile = await listen<any>('build-log-file', (event) => {
-        const data = typeof event.payload === 'string' ? JSON.parse(event.payload) : event.payload;
-        if (data.projectId !== project.id || data.platform !== platform) return;
-
-        updateBuild(key, (prev) => ({
-          ...prev,
-          logFilePath: data.payload,
-        }));
-      });
-
-      const unlistenArtifactPath = await listen<any>('build-artifact-path', (event) => {
-        const data = typeof event.payload === 'string' ? JSON.parse(event.payload) : event.payload;
-        if (data.projectId !== project.id || data.platform !== platform) return;
-
-        updateBuild(key, (prev) => ({
-          ...prev,
-          artifactPath: data.payload,
-        }));
-      });
-
-      const unlistenStatus = await listen<any>('build-status', async (event) => {
-        const data = typeof event.payload === 'string' ? JSON.parse(event.payload) : event.payload;
-        if (data.projectId !== project.id || data.platform !== platform) return;
-
+      const finishBuild = async (status: 'success' | 'failed') => {
         const currentBuild = useBuildStore.getState().activeBuilds[key];
         if (!currentBuild) return;
 
         const finalBuild: BuildHistory = {
           ...currentBuild,
-          status: data.status,
+          status,
           timestamp: Date.now(),
         };
 
@@ -88,14 +59,84 @@ export const useBuild = () => {
           console.error('Failed to save build history', e);
         }
 
-        unlistenLogs();
-        unlistenLogFile();
-        unlistenArtifactPath();
-        unlistenStatus();
+        unlisten();
+
+        // The build may have bumped (or rolled back) the stored versions
+        if (project.release?.versionBump) {
+          useProjectStore.getState().fetchProjects();
+        }
+      };
+
+      const unlisten = await listen<BuildEventEnvelope>('build-event', (event) => {
+        const data = event.payload;
+        if (data.projectId !== project.id || data.platform !== platform) return;
+        if (data.buildId && data.buildId !== buildId) return;
+
+        switch (data.type) {
+          case 'log':
+            updateBuild(key, (prev) => ({ ...prev, logs: prev.logs + data.message + '\n' }));
+            break;
+          case 'logFile':
+            updateBuild(key, (prev) => ({ ...prev, logFilePath: data.path }));
+            break;
+          case 'artifact':
+            updateBuild(key, (prev) => ({ ...prev, artifactPath: data.path }));
+            break;
+          case 'provenance':
+            updateBuild(key, (prev) => ({
+              ...prev,
+              git: {
+                commit: data.commit,
+                branch: data.branch,
+                tag: data.tag,
+                dirty: data.dirty,
+                uncommittedFiles: data.uncommittedFiles,
+              },
+            }));
+            break;
+          case 'version':
+            updateBuild(key, (prev) => ({
+              ...prev,
+              version: data.version,
+              buildNumber: data.buildNumber,
+            }));
+            break;
+          case 'releaseNotes':
+            updateBuild(key, (prev) => ({ ...prev, releaseNote: data.notes }));
+            break;
+          case 'progress':
+            updateBuild(key, (prev) => ({ ...prev, progress: data.fraction }));
+            break;
+          case 'step': {
+            const report: BuildStepReport = {
+              name: data.name,
+              status: data.status,
+              startedAt: data.startedAt,
+              durationMs: data.durationMs,
+              logStart: data.logStart,
+              logEnd: data.logEnd,
+              message: data.message,
+            };
+            updateBuild(key, (prev) => {
+              const steps = prev.steps || [];
+              const exists = steps.some((s) => s.name === report.name);
+              return {
+                ...prev,
+                steps: exists
+                  ? steps.map((s) => (s.name === report.name ? report : s))
+                  : [...steps, report],
+              };
+            });
+            break;
+          }
+          case 'status':
+            finishBuild(data.status === 'success' ? 'success' : 'failed');
+            break;
+        }
       });
 
       try {
-        await invoke('build_project', { project, platform, options });
+        await invoke('build_project', { project, platform, options: { ...options, buildId } });
       } catch (e) {
         console.error('Build command failed', e);
         const currentBuild = useBuildStore.getState().activeBuilds[key];
@@ -114,10 +155,7 @@ export const useBuild = () => {
             console.error('Failed to save build history', e);
           }
         }
-        unlistenLogs();
-        unlistenLogFile();
-        unlistenArtifactPath();
-        unlistenStatus();
+        unlisten();
       }
     },
     [updateBuild, addToHistory, clearActive],
@@ -0,0 +1,72 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Project } from '../types/project';
+import { BuildQueueEvent, BuildQueueJob, BuildQueueSettings } from '../types/queue';
+
+export const useBuildQueue = () => {
+  const [jobs, setJobs] = useState<BuildQueueJob[]>([]);
+  const [settings, setSettings] = useState<BuildQueueSettings | null>(null);
+
+  const loadQueue = useCallback(async () => {
+    try {
+      const result = await invoke<BuildQueueJob[]>('list_build_queue', { includeFinished: false });
+      setJobs(result);
+    } catch (e) {
+      console.error('Failed to load build queue', e);
+    }
+  }, []);
+
+  const loadSettings = useCallback(async () => {
+    try {
+      setSettings(await invoke<BuildQueueSettings>('get_build_queue_settings'));
+    } catch (e) {
+      console.error('Failed to load build queue settings', e);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadQueue();
+    loadSettings();
+
+    // Any queue transition (enqueue, start, finish, reorder, cancel) refreshes the list
+    const unlisten = listen<BuildQueueEvent>('build-queue', () => {
+      loadQueue();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [loadQueue, loadSettings]);
+
+  const enqueueBuild = useCallback(
+    async (
+      project: Project,
+      platform: 'ios' | 'android',
+      options?: {
+        uploadToAppStore?: boolean;
+        releaseNote?: string;
+        androidFormat?: 'apk' | 'aab';
+        sendToAppDistribution?: boolean;
+        uploadToPlayStore?: boolean;
+      },
+    ) => invoke<BuildQueueJob>('enqueue_build', { project, platform, options }),
+    [],
+  );
+
+  const cancelJob = useCallback(
+    async (jobId: string) => invoke('cancel_queued_build', { jobId }),
+    [],
+  );
+
+  const moveJob = useCallback(
+    async (jobId: string, index: number) => invoke('move_queued_build', { jobId, index }),
+    [],
+  );
+
+  const updateSettings = useCallback(async (next: BuildQueueSettings) => {
+    await invoke('update_build_queue_settings', { settings: next });
+    setSettings(next);
+  }, []);
+
+  return { jobs, settings, enqueueBuild, cancelJob, moveJob, updateSettings, reload: loadQueue };
+};
@@ -1,7 +1,15 @@
 import { useState, useEffect } from 'react';
 import { open } from '@tauri-apps/plugin-dialog';
 import { invoke } from '@tauri-apps/api/core';
-import { Project } from '../types/project';
+import {
+  AndroidSigningConfig,
+  BuildProfile,
+  IosExportOptions,
+  PrebuildSettings,
+  Project,
+  ReleaseSettings,
+  SizeBudgets,
+} from '../types/project';
 import { useCredentials } from './useCredentials';
 
 interface AppJsonInfo {
@@ -43,6 +51,20 @@ export const useProjectForm = (
   const [distributionGroups, setDistributionGroups] = useState(
     initialData?.android?.config?.distributionGroups || '',
   );
+  const [testers, setTesters] = useState(initialData?.android?.config?.testers || '');
+  const [playTrack, setPlayTrack] = useState(initialData?.android?.config?.playTrack || 'internal');
+  const [playLocales, setPlayLocales] = useState(initialData?.android?.config?.playLocales || '');
+  const [playReleaseStatus, setPlayReleaseStatus] = useState(
+    initialData?.android?.config?.playReleaseStatus || 'completed',
+  );
+  const [keystorePath, setKeystorePath] = useState(
+    initialData?.android?.config?.signing?.keystorePath || '',
+  );
+  const [keyAlias, setKeyAlias] = useState(initialData?.android?.config?.signing?.keyAlias || '');
+  // Passwords go to the keychain on save, they are never loaded back into the form
+  const [storePassword, setStorePassword] = useState('');
+  const [keyPassword, setKeyPassword] = useState('');
+  const [hasSigningPasswords, setHasSigningPasswords] = useState(false);
   const [iosScheme, setIosScheme] = useState(initialData?.ios?.config?.scheme || '');
   const [iosConfiguration, setIosConfiguration] = useState(
     initialData?.ios?.config?.configuration || 'Release',
@@ -51,6 +73,18 @@ export const useProjectForm = (
     'development' | 'ad-hoc' | 'app-store' | 'enterprise'
   >(initialData?.ios?.config?.exportMethod || 'development');
 
+  const [iosExportOptions, setIosExportOptions] = useState<IosExportOptions>(
+    initialData?.ios?.config?.exportOptions || {},
+  );
+  const [betaGroups, setBetaGroups] = useState(initialData?.ios?.config?.betaGroups || '');
+  const [iosFirebaseAppId, setIosFirebaseAppId] = useState(
+    initialData?.ios?.config?.firebaseAppId || '',
+  );
+  const [iosDistributionGroups, setIosDistributionGroups] = useState(
+    initialData?.ios?.config?.distributionGroups || '',
+  );
+  const [iosTesters, setIosTesters] = useState(initialData?.ios?.config?.testers || '');
+
   const [selectedIosId, setSelectedIosId] = useState(initialData?.credentials?.iosId || '');
   const [selectedAndroidId, setSelectedAndroidId] = useState(
     initialData?.credentials?.androidId || '',
@@ -81,6 +115,15 @@ export const useProjectForm = (
     initialData?.notifications?.telegram?.enabled || false,
   );
 
+  const [releaseSettings, setReleaseSettings] = useState<ReleaseSettings>(
+    initialData?.release || {},
+  );
+  const [prebuildSettings, setPrebuildSettings] = useState<PrebuildSettings>(
+    initialData?.prebuild || { enabled: false },
+  );
+  const [profiles, setProfiles] = useState<BuildProfile[]>(initialData?.profiles || []);
+  const [sizeBudgets, setSizeBudgets] = useState<SizeBudgets>(initialData?.sizeBudgets || {});
+
   const iosCredentials = credentials.filter((c) => c.platform === 'ios');
   const androidCredentials = credentials.filter((c) => c.platform === 'android');
 
@@ -98,9 +141,22 @@ export const useProjectForm = (
       setAndroidBuildCommand(initialData?.android?.buildCommand || '');
       setFirebaseAppId(initialData?.android?.config?.firebaseAppId || '');
       setDistributionGroups(initialData?.android?.config?.distributionGroups || '');
+      setTesters(initialData?.android?.config?.testers || '');
+      setPlayTrack(initialData?.android?.config?.playTrack || 'internal');
+      setPlayLocales(initialData?.android?.config?.playLocales || '');
+      setPlayReleaseStatus(initialData?.android?.config?.playReleaseStatus || 'completed');
+      setKeystorePath(initialData?.android?.config?.signing?.keystorePath || '');
+      setKeyAlias(initialData?.android?.config?.signing?.keyAlias || '');
+      setStorePassword('');
+      setKeyPassword('');
       setIosScheme(initialData?.ios?.config?.scheme || '');
       setIosConfiguration(initialData?.ios?.config?.configuration || 'Release');
       setIosExportMethod(initialData?.ios?.config?.exportMethod || 'development');
+      setIosExportOptions(initialData?.ios?.config?.exportOptions || {});
+      setBetaGroups(initialData?.ios?.config?.betaGroups || '');
+      setIosFirebaseAppId(initialData?.ios?.config?.firebaseAppId || '');
+      setIosDistributionGroups(initialData?.ios?.config?.distributionGroups || '');
+      setIosTesters(initialData?.ios?.config?.testers || '');
       setSelectedIosId(initialData?.credentials?.iosId || '');
       setSelectedAndroidId(initialData?.credentials?.androidId || '');
 
@@ -111,7 +167,22 @@ export const useProjectForm = (
       setTelegramBotToken(initialData?.notifications?.telegram?.botToken || '');
       setTelegramChatId(initialData?.notifications?.telegram?.chatId || '');
       setTelegramEnabled(initialData?.notifications?.telegram?.enabled || false);
+      setReleaseSettings(initialData?.release || {});
+      setPrebuildSettings(initialData?.prebuild || { enabled: false });
+      setProfiles(initialData?.profiles || []);
+      setSizeBudgets(initialData?.sizeBudgets || {});
+    }
+  }, [isOpen, initialData]);
+
+  useEffect(() => {
+    const signingId = initialData?.android?.config?.signing?.id;
+    if (!isOpen || !signingId) {
+      setHasSigningPasswords(false);
+      return;
     }
+    invoke<boolean>('has_signing_passwords', { signingId })
+      .then(setHasSigningPasswords)
+      .catch(() => setHasSigningPasswords(false));
   }, [isOpen, initialData]);
 
   const handleBrowse = async () => {
@@ -157,9 +228,51 @@ export const useProjectForm = (
     }
   };
 
-  const handleSubmit = (e: React.FormEvent) => {
+  const handleBrowseKeystore = async () => {
+    try {
+      const selected = await open({
+        multiple: false,
+        title: 'Select Release Keystore',
+        filters: [{ name: 'Keystore', extensions: ['jks', 'keystore', 'p12', 'pfx'] }],
+      });
+      if (selected && typeof selected === 'string') {
+        setKeystorePath(selected);
+      }
+    } catch (e) {
+      console.error('Failed to open dialog', e);
+    }
+  };
+
+  // Keep the keystore passwords in the keychain, forgetting them when the keystore is removed
+  const saveSigning = async () => {
+    const previousId = initialData?.android?.config?.signing?.id;
+    if (!keystorePath) {
+      if (previousId) await invoke('delete_signing_passwords', { signingId: previousId });
+      return undefined;
+    }
+    const id = previousId || Math.random().toString(36).substr(2, 9);
+    if (storePassword) {
+      await invoke('save_signing_passwords', {
+        signingId: id,
+        storePassword,
+        keyPassword: keyPassword || null,
+      });
+    }
+    return { id, keystorePath, keyAlias };
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
+    let signing: AndroidSigningConfig | undefined;
+    try {
+      signing = await saveSigning();
+    } catch (error) {
+      console.error('Failed to save keystore passwords:', error);
+      alert(`Failed to save keystore passwords: ${error}`);
+      return;
+    }
+
     const iosCred = iosCredentials.find((c) => c.id === selectedIosId);
 
     // Create the project object for saving
@@ -177,6 +290,11 @@ export const useProjectForm = (
           exportMethod: iosExportMethod,
           apiKey: iosCred?.ios?.apiKeyId || initialData?.ios?.config?.apiKey,
           apiIssuer: iosCred?.ios?.apiIssuerId || initialData?.ios?.config?.apiIssuer,
+          betaGroups: betaGroups || undefined,
+          firebaseAppId: iosFirebaseAppId || undefined,
+          distributionGroups: iosDistributionGroups || undefined,
+          testers: iosTesters || undefined,
+          exportOptions: iosExportOptions,
         },
       },
       android: {
@@ -187,6 +305,11 @@ export const useProjectForm = (
         config: {
           firebaseAppId: firebaseAppId || undefined,
           distributionGroups: distributionGroups || undefined,
+          testers: testers || undefined,
+          playTrack,
+          playLocales: playLocales || undefined,
+          playReleaseStatus,
+          signing,
         },
       },
       credentials: {
@@ -208,6 +331,10 @@ export const useProjectForm = (
           enabled: telegramEnabled,
         },
       },
+      release: releaseSettings,
+      prebuild: prebuildSettings,
+      profiles: profiles.filter((p) => p.name.trim()),
+      sizeBudgets,
     };
 
     console.log('Submitting Project Data:', projectData);
@@ -239,12 +366,39 @@ export const useProjectForm = (
       setFirebaseAppId,
       distributionGroups,
       setDistributionGroups,
+      testers,
+      setTesters,
+      playTrack,
+      setPlayTrack,
+      playLocales,
+      setPlayLocales,
+      playReleaseStatus,
+      setPlayReleaseStatus,
+      keystorePath,
+      setKeystorePath,
+      keyAlias,
+      setKeyAlias,
+      storePassword,
+      setStorePassword,
+      keyPassword,
+      setKeyPassword,
+      hasSigningPasswords,
       iosScheme,
       setIosScheme,
       iosConfiguration,
       setIosConfiguration,
       iosExportMethod,
       setIosExportMethod,
+      iosExportOptions,
+      setIosExportOptions,
+      betaGroups,
+      setBetaGroups,
+      iosFirebaseAppId,
+      setIosFirebaseAppId,
+      iosDistributionGroups,
+      setIosDistributionGroups,
+      iosTesters,
+      setIosTesters,
       selectedIosCredentialId: selectedIosId,
       setSelectedIosCredentialId: setSelectedIosId,
       selectedAndroidCredentialId: selectedAndroidId,
@@ -265,9 +419,18 @@ export const useProjectForm = (
       setTelegramChatId,
       telegramEnabled,
       setTelegramEnabled,
+      releaseSettings,
+      setReleaseSettings,
+      prebuildSettings,
+      setPrebuildSettings,
+      profiles,
+      setProfiles,
+      sizeBudgets,
+      setSizeBudgets,
     },
     handlers: {
       handleBrowse,
+      handleBrowseKeystore,
       handleSubmit,
     },
   };
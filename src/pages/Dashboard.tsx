@@ -4,6 +4,7 @@ import {
   SearchIcon,
   FolderIcon,
   AlertCircleIcon,
+  CheckCircleIcon,
   TrashIcon,
   EraserIcon,
   TerminalIcon,
@@ -21,8 +22,17 @@ import { invoke } from '@tauri-apps/api/core';
 
 export const Dashboard: React.FC = () => {
   const navigate = useNavigate();
-  const { projects, addProject, updateProject, deleteProject, fetchProjects, error, clearError } =
-    useProjectStore();
+  const {
+    projects,
+    addProject,
+    updateProject,
+    deleteProject,
+    fetchProjects,
+    error,
+    clearError,
+    versionChanges,
+    clearVersionChanges,
+  } = useProjectStore();
   const [searchTerm, setSearchTerm] = useState('');
   const [isModalOpen, setIsModalOpen] = useState(false);
   const [isCreateModalOpen, setIsCreateModalOpen] = useState(false);
@@ -91,7 +101,7 @@ export const Dashboard: React.FC = () => {
   const handleBuild = async (
     projectId: string,
     platform: 'ios' | 'android',
-    options?: { uploadToAppStore?: boolean },
+    options?: { uploadToAppStore?: boolean; profile?: string },
   ) => {
     const project = projects.find((p) => p.id === projectId);
     if (project) {
@@ -333,6 +343,54 @@ export const Dashboard: React.FC = () => {
         </div>
       )}
 
+      {/* Updated build files */}
+      {versionChanges.length > 0 && (
+        <div
+          style={{
+            padding: '16px 20px',
+            marginBottom: '24px',
+            background: 'rgba(52, 199, 89, 0.08)',
+            border: '1px solid rgba(52, 199, 89, 0.2)',
+            borderRadius: '16px',
+            display: 'flex',
+            justifyContent: 'space-between',
+            alignItems: 'flex-start',
+            flexShrink: 0,
+            animation: 'fadeIn 0.3s ease',
+          }}
+        >
+          <div style={{ display: 'flex', alignItems: 'flex-start', gap: '12px', minWidth: 0 }}>
+            <CheckCircleIcon size={20} style={{ color: 'var(--color-success)', flexShrink: 0 }} />
+            <div style={{ minWidth: 0 }}>
+              <div style={{ fontWeight: 500, marginBottom: '6px' }}>
+                Updated {versionChanges.length} line{versionChanges.length === 1 ? '' : 's'} with
+                the new versions
+              </div>
+              {versionChanges.map((change) => (
+                <div
+                  key={`${change.file}:${change.line}`}
+                  style={{
+                    fontFamily: 'monospace',
+                    fontSize: '12px',
+                    color: 'var(--color-text-secondary)',
+                    overflowWrap: 'anywhere',
+                  }}
+                >
+                  {change.file}:{change.line}: {change.old} → {change.new}
+                </div>
+              ))}
+            </div>
+          </div>
+          <button
+            onClick={clearVersionChanges}
+            className="btn btn-ghost"
+            style={{ fontWeight: 600 }}
+          >
+            Dismiss
+          </button>
+        </div>
+      )}
+
       {/* Scrollable Project Content */}
       <div
         style={{
@@ -11,6 +11,7 @@ import {
   ShieldCheckIcon,
   InfoIcon,
 } from '../components/Icons';
+import { KeystoreInspector } from '../components/KeystoreInspector';
 
 export const KeystoreGenerator: React.FC = () => {
   const [alias, setAlias] = useState('upload');
@@ -439,6 +440,8 @@ export const KeystoreGenerator: React.FC = () => {
             )}
             <span>{isGenerating ? 'Synthesizing RSA Entropy...' : 'Generate Secure Keystore'}</span>
           </button>
+
+          <KeystoreInspector initialPath={result ? outputPath : null} />
         </div>
 
         {/* Right Column - Status & Output */}
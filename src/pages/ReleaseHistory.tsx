@@ -14,9 +14,21 @@ import {
   AndroidIcon,
   TerminalIcon,
   ActivityIcon,
+  PackageIcon,
+  GitCompareIcon,
+  TrendingUpIcon,
 } from '../components/Icons';
 import { BuildHistory } from '../types/project';
 import { AppSizeAnalyzer } from '../components/AppSizeAnalyzer';
+import { BundletoolPanel } from '../components/BundletoolPanel';
+import { ArtifactComparison } from '../components/ArtifactComparison';
+import { SizeHistory } from '../components/SizeHistory';
+
+const formatDuration = (ms: number) => {
+  const seconds = Math.round(ms / 1000);
+  if (seconds < 60) return `${seconds}s`;
+  return `${Math.floor(seconds / 60)}m ${seconds % 60}s`;
+};
 
 const LogModal = ({ build, onClose }: { build: BuildHistory; onClose: () => void }) => {
   const handleOpenExternal = () => {
@@ -78,6 +90,23 @@ const LogModal = ({ build, onClose }: { build: BuildHistory; onClose: () => void
               <span style={{ fontSize: '12px', color: '#888' }}>
                 v{build.version} ({build.buildNumber}) •{' '}
                 {new Date(build.timestamp).toLocaleString()}
+                {build.git && (
+                  <>
+                    {' '}
+                    • {build.git.commit.slice(0, 10)}
+                    {build.git.branch && ` on ${build.git.branch}`}
+                    {build.git.tag && ` (${build.git.tag})`}
+                    {build.git.dirty && (
+                      <span
+                        style={{ color: '#FFB020' }}
+                        title={build.git.uncommittedFiles.join('\n')}
+                      >
+                        {' '}
+                        • {build.git.uncommittedFiles.length} uncommitted
+                      </span>
+                    )}
+                  </>
+                )}
               </span>
             </div>
           </div>
@@ -112,6 +141,40 @@ const LogModal = ({ build, onClose }: { build: BuildHistory; onClose: () => void
             background: '#0D0F12',
           }}
         >
+          {build.diagnostics && build.diagnostics.length > 0 && (
+            <div
+              style={{
+                marginBottom: '20px',
+                padding: '16px',
+                borderRadius: '12px',
+                background: '#15181E',
+                border: '1px solid #2A1E22',
+                fontFamily: 'inherit',
+              }}
+            >
+              <div style={{ fontWeight: 700, color: '#FF6B6B', marginBottom: '8px' }}>
+                {build.diagnostics.length} problem{build.diagnostics.length > 1 ? 's' : ''} found
+              </div>
+              {build.diagnostics.map((d, i) => (
+                <div key={i} style={{ marginBottom: '8px' }}>
+                  <span style={{ color: d.severity === 'error' ? '#FF6B6B' : '#FFB020' }}>
+                    [{d.category}]
+                  </span>{' '}
+                  {d.message}
+                  {d.file && (
+                    <span style={{ color: '#888' }}>
+                      {' '}
+                      — {d.file}
+                      {d.line ? `:${d.line}` : ''}
+                    </span>
+                  )}
+                  {d.remediation && (
+                    <div style={{ color: '#8AB4F8', paddingLeft: '16px' }}>💡 {d.remediation}</div>
+                  )}
+                </div>
+              ))}
+            </div>
+          )}
           {build.logs ? (
             build.logs.split('\n').map((line, i) => (
               <div
@@ -162,6 +225,28 @@ export const ReleaseHistory: React.FC = () => {
   const { fetchProjects } = useProjectStore();
   const [viewingBuild, setViewingBuild] = useState<BuildHistory | null>(null);
   const [analyzingBuild, setAnalyzingBuild] = useState<BuildHistory | null>(null);
+  const [bundleBuild, setBundleBuild] = useState<BuildHistory | null>(null);
+  // The first build picked for a comparison, then the pair being compared
+  const [compareBase, setCompareBase] = useState<BuildHistory | null>(null);
+  const [comparing, setComparing] = useState<[BuildHistory, BuildHistory] | null>(null);
+  const [showSizeHistory, setShowSizeHistory] = useState(false);
+
+  const handleCompare = (build: BuildHistory) => {
+    if (!compareBase) {
+      setCompareBase(build);
+    } else if (compareBase.id === build.id) {
+      setCompareBase(null);
+    } else {
+      setComparing([compareBase, build]);
+      setCompareBase(null);
+    }
+  };
+
+  const compareTitle = (build: BuildHistory) => {
+    if (!compareBase) return 'Compare with Another Build';
+    if (compareBase.id === build.id) return 'Cancel Comparison';
+    return `Compare with ${compareBase.version} (${compareBase.buildNumber})`;
+  };
 
   useEffect(() => {
     fetchHistory();
@@ -265,6 +350,17 @@ export const ReleaseHistory: React.FC = () => {
             </select>
           </div>
 
+          {selectedProjectId && (
+            <button
+              className="btn btn-secondary"
+              title="Artifact size over time"
+              onClick={() => setShowSizeHistory(true)}
+            >
+              <TrendingUpIcon size={16} />
+              <span>Size History</span>
+            </button>
+          )}
+
           <div
             style={{
               background: 'var(--color-sidebar)',
@@ -467,6 +563,23 @@ export const ReleaseHistory: React.FC = () => {
                                   {build.format.toUpperCase()}
                                 </span>
                               )}
+                              {build.profile && (
+                                <span
+                                  title="Build profile"
+                                  style={{
+                                    marginLeft: '8px',
+                                    padding: '2px 6px',
+                                    borderRadius: '4px',
+                                    background: 'rgba(0,122,255,0.12)',
+                                    color: 'var(--color-primary)',
+                                    fontSize: '10px',
+                                    fontWeight: 800,
+                                    textTransform: 'none',
+                                  }}
+                                >
+                                  {build.profile}
+                                </span>
+                              )}
                             </span>
                           </div>
                         </td>
@@ -517,6 +630,16 @@ export const ReleaseHistory: React.FC = () => {
                             hour: '2-digit',
                             minute: '2-digit',
                           })}
+                          {build.timing && (
+                            <div
+                              style={{ fontSize: '12px', marginTop: '4px' }}
+                              title={build.timing.phases
+                                .map((p) => `${p.phase}: ${formatDuration(p.durationMs)}`)
+                                .join('\n')}
+                            >
+                              ⏱ {formatDuration(build.timing.durationMs)}
+                            </div>
+                          )}
                         </td>
                         <td
                           style={{
@@ -545,6 +668,44 @@ export const ReleaseHistory: React.FC = () => {
                                   <ActivityIcon size={16} />
                                 </button>
                               )}
+                              {build.artifactPath && (
+                                <button
+                                  className="btn btn-ghost"
+                                  onClick={() => handleCompare(build)}
+                                  title={compareTitle(build)}
+                                  style={{
+                                    width: '32px',
+                                    height: '32px',
+                                    padding: 0,
+                                    color:
+                                      compareBase?.id === build.id
+                                        ? 'white'
+                                        : 'var(--color-primary)',
+                                    background:
+                                      compareBase?.id === build.id
+                                        ? 'var(--color-primary)'
+                                        : 'rgba(0, 122, 255, 0.05)',
+                                  }}
+                                >
+                                  <GitCompareIcon size={16} />
+                                </button>
+                              )}
+                              {build.artifactPath?.endsWith('.aab') && (
+                                <button
+                                  className="btn btn-ghost"
+                                  onClick={() => setBundleBuild(build)}
+                                  title="Build APKs and Install"
+                                  style={{
+                                    width: '32px',
+                                    height: '32px',
+                                    padding: 0,
+                                    color: 'var(--color-success)',
+                                    background: 'rgba(52, 199, 89, 0.05)',
+                                  }}
+                                >
+                                  <PackageIcon size={16} />
+                                </button>
+                              )}
                               <button
                                 className="btn btn-ghost"
                                 onClick={() => {
@@ -635,6 +796,32 @@ export const ReleaseHistory: React.FC = () => {
         />
       )}
 
+      {comparing && (
+        <ArtifactComparison
+          baseBuild={comparing[0]}
+          targetBuild={comparing[1]}
+          appName={projects.find((p) => p.id === comparing[1].projectId)?.name || 'Unknown'}
+          onClose={() => setComparing(null)}
+        />
+      )}
+
+      {showSizeHistory && selectedProjectId && (
+        <SizeHistory
+          projectId={selectedProjectId}
+          appName={projects.find((p) => p.id === selectedProjectId)?.name || 'Unknown'}
+          onClose={() => setShowSizeHistory(false)}
+        />
+      )}
+
+      {bundleBuild && bundleBuild.artifactPath && (
+        <BundletoolPanel
+          artifactPath={bundleBuild.artifactPath}
+          projectId={bundleBuild.projectId}
+          appName={projects.find((p) => p.id === bundleBuild.projectId)?.name || 'Unknown'}
+          onClose={() => setBundleBuild(null)}
+        />
+      )}
+
       <style>{`
         .table-row-hover:hover td {
           background: var(--color-border) !important;
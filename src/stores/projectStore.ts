@@ -1,6 +1,6 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
-import { Project } from '../types/project';
+import { FileChange, Project } from '../types/project';
 import { useBuildStore } from './buildStore';
 
 interface ProjectState {
@@ -8,12 +8,15 @@ interface ProjectState {
   selectedProjectId: string | null;
   isLoading: boolean;
   error: string | null;
+  /** Build file lines the last save updated with the project's versions */
+  versionChanges: FileChange[];
   fetchProjects: () => Promise<void>;
   addProject: (project: Project) => Promise<void>;
   updateProject: (id: string, updates: Partial<Project>) => Promise<void>;
   deleteProject: (id: string) => Promise<void>;
   selectProject: (id: string | null) => void;
   clearError: () => void;
+  clearVersionChanges: () => void;
 }
 
 export const useProjectStore = create<ProjectState>((set, get) => ({
@@ -21,7 +24,9 @@ export const useProjectStore = create<ProjectState>((set, get) => ({
   selectedProjectId: null,
   isLoading: false,
   error: null,
+  versionChanges: [],
   clearError: () => set({ error: null }),
+  clearVersionChanges: () => set({ versionChanges: [] }),
 
   fetchProjects: async () => {
     set({ isLoading: true });
@@ -35,8 +40,8 @@ export const useProjectStore = create<ProjectState>((set, get) => ({
 
   addProject: async (project) => {
     try {
-      await invoke('save_project', { project });
-      set((state) => ({ projects: [...state.projects, project] }));
+      const versionChanges = await invoke<FileChange[]>('save_project', { project });
+      set((state) => ({ projects: [...state.projects, project], versionChanges }));
     } catch (e) {
       set({ error: (e as Error).toString() });
     }
@@ -48,9 +53,12 @@ export const useProjectStore = create<ProjectState>((set, get) => ({
 
     const updatedProject = { ...project, ...updates };
     try {
-      await invoke('save_project', { project: updatedProject });
+      const versionChanges = await invoke<FileChange[]>('save_project', {
+        project: updatedProject,
+      });
       set((state) => ({
         projects: state.projects.map((p) => (p.id === id ? updatedProject : p)),
+        versionChanges,
       }));
     } catch (e) {
       set({ error: (e as Error).toString() });
@@ -0,0 +1,39 @@
+export interface ArtifactSummary {
+  path: string;
+  fileType: string;
+  totalSize: number;
+  label?: string; // "1.2.0 (58)" when compared by build
+}
+
+export interface FileChange {
+  path: string;
+  change: 'added' | 'removed' | 'changed';
+  baseSize: number;
+  targetSize: number;
+  delta: number;
+}
+
+export interface CategoryDiff {
+  name: string;
+  baseSize: number;
+  targetSize: number;
+  delta: number;
+  files: FileChange[]; // Largest changes first
+}
+
+export interface MetadataChange {
+  field: string;
+  base?: string;
+  target?: string;
+}
+
+export interface ArtifactDiff {
+  base: ArtifactSummary;
+  target: ArtifactSummary;
+  sizeDelta: number;
+  categories: CategoryDiff[];
+  metadataChanges: MetadataChange[];
+  permissionsAdded: string[];
+  permissionsRemoved: string[];
+  nativeLibraries: FileChange[];
+}
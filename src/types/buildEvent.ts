@@ -0,0 +1,25 @@
+import { BuildStepName, BuildStepReport, GitProvenance } from './project';
+
+export type BuildLogLevel = 'info' | 'warning' | 'error';
+
+export type BuildEventStatus = 'success' | 'failed' | 'cancelled';
+
+export type BuildEvent =
+  | { type: 'log'; level: BuildLogLevel; step?: BuildStepName; message: string }
+  | ({ type: 'step' } & BuildStepReport)
+  | { type: 'progress'; step?: BuildStepName; fraction: number; message: string }
+  | { type: 'artifact'; path: string }
+  | ({ type: 'provenance' } & GitProvenance)
+  | { type: 'version'; version: string; buildNumber: number }
+  | { type: 'releaseNotes'; notes: string }
+  | { type: 'logFile'; path: string }
+  | { type: 'status'; status: BuildEventStatus };
+
+// Payload of the `build-event` channel
+export type BuildEventEnvelope = BuildEvent & {
+  projectId: string;
+  platform: 'ios' | 'android';
+  buildId?: string;
+  sequence: number;
+  timestamp: number;
+};
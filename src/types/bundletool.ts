@@ -0,0 +1,20 @@
+// universal: one APK with every split, default: splits for every device, device: one device's splits
+export type ApkMode = 'universal' | 'default' | 'device';
+
+export interface ApkSet {
+  apksPath: string;
+  universalApk?: string; // Extracted next to the bundle in universal mode
+  signedWith: string; // Key alias, or "debug keystore"
+  size: number;
+}
+
+export interface DownloadSize {
+  dimensions: Record<string, string>; // e.g. { ABI: 'arm64-v8a', SDK: '24-' }, empty for one device
+  min: number;
+  max: number;
+}
+
+export interface DownloadSizeReport {
+  deviceId?: string;
+  sizes: DownloadSize[];
+}
@@ -0,0 +1,25 @@
+export interface CertificateInfo {
+  subject: string;
+  issuer: string;
+  serialNumber: string;
+  validFrom: string;
+  validUntil: string;
+  daysUntilExpiry: number; // Negative once expired
+  signatureAlgorithm: string; // e.g. SHA256withRSA
+  publicKey: string; // e.g. RSA 2048-bit
+  sha1: string;
+  sha256: string;
+}
+
+export interface KeystoreEntryInfo {
+  alias: string;
+  kind: 'privateKey' | 'trustedCertificate';
+  created?: string; // Only JKS/JCEKS stores record it
+  certificates: CertificateInfo[]; // Signing certificate first
+}
+
+export interface KeystoreInfo {
+  format: 'JKS' | 'JCEKS' | 'PKCS12';
+  entries: KeystoreEntryInfo[];
+  warnings: string[];
+}
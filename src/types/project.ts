@@ -1,3 +1,71 @@
+export type VersionBump = 'none' | 'patch' | 'minor' | 'major';
+
+// increment: +1, date: YYMMDDNN, shared: one counter for both platforms
+export type BuildNumberStrategy = 'none' | 'increment' | 'date' | 'shared';
+
+export interface VersionBumpSettings {
+  version: VersionBump;
+  buildNumber: BuildNumberStrategy;
+}
+
+export interface ReleaseSettings {
+  requireCleanTree?: boolean; // Refuse release builds from a dirty working tree
+  tagAfterUpload?: boolean; // Tag the commit (e.g. ios/1.4.0+57) once the build is published
+  autoReleaseNotes?: boolean; // Generate release notes from git history when none are typed
+  releaseNotesTemplate?: string; // e.g. "{{platform}} {{version}} ({{build}})\n\n{{changes}}"
+  versionBump?: VersionBumpSettings; // Applied when a build starts, rolled back if it fails
+}
+
+// Continuous Native Generation: `expo prebuild` generates ios/ and android/ before each build
+export interface PrebuildSettings {
+  enabled: boolean;
+  clean?: boolean; // Delete the native folders first (--clean)
+  isolated?: boolean; // Generate into a temporary copy of the project, leaving the repo untouched
+}
+
+// Limits on the artifact's size, checked after each build. Sizes in bytes, unset ones are skipped
+export interface SizeBudgets {
+  total?: number;
+  nativeLibraries?: number;
+  jsBundle?: number;
+  assets?: number;
+  maxIncrease?: number; // Growth allowed since the previous successful build
+  failBuild?: boolean; // Fail the build instead of only warning
+}
+
+// A variant of the app (dev, staging, prod...); every field that is set replaces the project's
+export interface BuildProfile {
+  name: string;
+  bundleIdSuffix?: string; // e.g. ".staging", appended to both bundle IDs
+  iosScheme?: string;
+  iosConfiguration?: string;
+  androidFlavor?: string; // Gradle product flavor, e.g. "staging" builds bundleStagingRelease
+  androidBuildCommand?: string; // Replaces the build command and the flavor's task
+  envFile?: string; // Relative to the project, passed to the build as ENVFILE
+  iosFirebaseAppId?: string;
+  androidFirebaseAppId?: string;
+  iosCredentialId?: string;
+  androidCredentialId?: string;
+  notifications?: Project['notifications'];
+}
+
+// Passwords are kept in the keychain under `id`, never in the project
+export interface AndroidSigningConfig {
+  id: string;
+  keystorePath: string;
+  keyAlias: string;
+}
+
+// ExportOptions.plist keys beyond the method and team, see `xcodebuild -help`
+export interface IosExportOptions {
+  provisioningProfiles?: Record<string, string>; // Bundle ID → profile name or UUID; signs manually
+  signingCertificate?: string; // e.g. "Apple Distribution" or a certificate SHA-1
+  uploadSymbols?: boolean; // Defaults to true
+  compileBitcode?: boolean;
+  thinning?: string; // "<none>", "<thin-for-all-variants>" or a device model
+  manageAppVersionAndBuildNumber?: boolean; // Left to Xcode when unset
+}
+
 export interface Project {
   id: string;
   name: string;
@@ -13,6 +81,11 @@ export interface Project {
       exportMethod?: 'development' | 'ad-hoc' | 'app-store' | 'enterprise';
       apiKey?: string;
       apiIssuer?: string;
+      betaGroups?: string; // Comma-separated TestFlight groups uploaded builds are added to
+      firebaseAppId?: string; // Firebase App ID for App Distribution (e.g., 1:1234567890:ios:0a1b2c3d4e5f67890)
+      distributionGroups?: string; // Comma-separated tester group aliases
+      testers?: string; // Comma-separated tester emails
+      exportOptions?: IosExportOptions;
     };
   };
   android: {
@@ -23,6 +96,11 @@ export interface Project {
     config?: {
       firebaseAppId?: string; // Firebase App ID for App Distribution (e.g., 1:1234567890:android:0a1b2c3d4e5f67890)
       distributionGroups?: string; // Comma-separated tester groups (e.g., "qa-team, trusted-testers")
+      testers?: string; // Comma-separated tester emails
+      playTrack?: 'internal' | 'alpha' | 'beta' | 'production'; // Google Play track, defaults to internal
+      playLocales?: string; // Comma-separated release note locales (e.g., "en-US, fr-FR")
+      playReleaseStatus?: 'completed' | 'draft'; // draft for apps that were never published
+      signing?: AndroidSigningConfig; // Keystore release builds are signed with
     };
   };
   credentials: {
@@ -44,9 +122,76 @@ export interface Project {
       enabled: boolean;
     };
   };
+  release?: ReleaseSettings;
+  prebuild?: PrebuildSettings;
+  profiles?: BuildProfile[];
+  sizeBudgets?: SizeBudgets;
   lastBuild?: BuildHistory;
 }
 
+/** A line of a build file or app config updated by `save_project` */
+export interface FileChange {
+  file: string;
+  /** 1-based */
+  line: number;
+  old: string;
+  new: string;
+}
+
+export type BuildStepName =
+  | 'prepare'
+  | 'archive'
+  | 'export'
+  | 'sign'
+  | 'measure'
+  | 'upload'
+  | 'distribute'
+  | 'notify';
+
+export interface BuildStepReport {
+  name: BuildStepName;
+  status: 'running' | 'success' | 'failed' | 'skipped';
+  startedAt?: number;
+  durationMs?: number;
+  logStart: number; // first log file line written by the step
+  logEnd: number; // one past the last log file line
+  message?: string;
+}
+
+export interface BuildDiagnostic {
+  step?: BuildStepName;
+  severity: 'error' | 'warning';
+  category: 'gradle' | 'kotlin' | 'java' | 'cocoapods' | 'compiler' | 'codesign' | 'metro' | 'xcodebuild';
+  file?: string;
+  line?: number;
+  column?: number;
+  message: string;
+  remediation?: string;
+}
+
+export interface PhaseTiming {
+  phase: BuildStepName;
+  status: 'success' | 'failed' | 'skipped';
+  startedAt: number;
+  finishedAt: number;
+  durationMs: number;
+}
+
+export interface BuildTiming {
+  startedAt: number;
+  finishedAt: number;
+  durationMs: number;
+  phases: PhaseTiming[];
+}
+
+export interface GitProvenance {
+  commit: string;
+  branch?: string;
+  tag?: string;
+  dirty: boolean;
+  uncommittedFiles: string[];
+}
+
 export interface BuildHistory {
   id: string;
   projectId: string;
@@ -60,4 +205,10 @@ export interface BuildHistory {
   releaseNote: string;
   format?: string;
   artifactPath?: string;
+  profile?: string; // Build profile the build was made with
+  steps?: BuildStepReport[]; // Live pipeline progress of an active build
+  progress?: number; // 0–1, live progress of an active build
+  git?: GitProvenance; // Source control state the build was made from
+  diagnostics?: BuildDiagnostic[]; // Problems parsed from the build output
+  timing?: BuildTiming; // Start/end of the build and duration of each phase
 }
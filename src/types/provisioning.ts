@@ -0,0 +1,28 @@
+import { CertificateInfo } from './keystore';
+
+export interface ProfileCertificate extends CertificateInfo {
+  installed: boolean; // The keychain holds its private key
+}
+
+export interface ProvisioningProfile {
+  path: string;
+  name: string;
+  uuid: string;
+  teamId: string;
+  teamName: string;
+  appIdName: string;
+  bundleId: string; // May end with a * wildcard
+  profileType: 'development' | 'ad-hoc' | 'app-store' | 'enterprise';
+  platforms: string[];
+  created: string;
+  expires: string;
+  expired: boolean;
+  deviceCount: number;
+  entitlements: Record<string, unknown>;
+  certificates: ProfileCertificate[];
+}
+
+export interface ProvisioningCheck {
+  profile?: ProvisioningProfile; // Absent when nothing fits
+  problems: string[];
+}
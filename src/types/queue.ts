@@ -0,0 +1,32 @@
+import { Project } from './project';
+
+export type BuildQueueStatus = 'pending' | 'running' | 'success' | 'failed' | 'cancelled';
+
+export interface BuildQueueJob {
+  id: string;
+  projectId: string;
+  platform: 'ios' | 'android';
+  project: Project;
+  options?: Record<string, unknown>;
+  status: BuildQueueStatus;
+  position: number;
+  enqueuedAt: number;
+  startedAt?: number;
+  finishedAt?: number;
+  error?: string;
+}
+
+export interface BuildQueueSettings {
+  maxConcurrent: number;
+  maxIos: number;
+  maxAndroid: number;
+}
+
+// Payload of the `build-queue` event
+export interface BuildQueueEvent {
+  jobId: string;
+  projectId: string;
+  platform: 'ios' | 'android';
+  status: BuildQueueStatus;
+  position: number;
+}
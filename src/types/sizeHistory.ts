@@ -0,0 +1,22 @@
+export interface SizeTrendBuild {
+  buildId: string;
+  version: string;
+  buildNumber: number;
+  profile?: string;
+  timestamp: number;
+  totalSize: number;
+  jsBundleSize: number;
+}
+
+export interface CategoryTrend {
+  name: string;
+  sizes: number[]; // One per build, in the order of SizeTrend.builds
+}
+
+// Size of a project's successful builds over time, for one platform and artifact format
+export interface SizeTrend {
+  platform: 'ios' | 'android';
+  fileType: string; // APK, AAB or IPA
+  builds: SizeTrendBuild[];
+  categories: CategoryTrend[];
+}